@@ -85,7 +85,7 @@ impl lemna::Component for App {
                         (" Yeah!", None, 8.0)
                     ),
                 )
-                .tool_tip("Wait, don't!\nWhy not? Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.".to_string())
+                .tooltip("Wait, don't!\nWhy not? Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.")
                 .on_click(Box::new(|| msg!(HelloEvent::Button {
                     name: "jk, I'm just another button!".to_string()
                 }))),
@@ -286,6 +286,14 @@ impl Component for EventReactor {
         println!("Double clicked on {} with {:?}", &self.name, event.input.0);
     }
 
+    fn on_context_menu(&mut self, event: &mut Event<event::ContextMenu>) {
+        println!(
+            "Context menu requested on {} at {:?}",
+            &self.name,
+            event.logical_position()
+        );
+    }
+
     fn on_mouse_enter(&mut self, _event: &mut Event<event::MouseEnter>) {
         println!("Entered {}", &self.name);
     }
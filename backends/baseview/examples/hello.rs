@@ -31,6 +31,14 @@ impl Component for App {
                 }
                 .radius(5.0),
                 [size: [100], margin: rect!(5)]
+            ))
+            .push(node!(
+                widgets::Button::new(txt!("Save")).tooltip("Save the project (Ctrl+S)"),
+                [margin: [5]]
+            ))
+            .push(node!(
+                widgets::Toggle::new(true).tooltip("Autosave"),
+                [margin: [5]]
             )),
         )
     }
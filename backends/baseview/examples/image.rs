@@ -50,11 +50,37 @@ impl lemna::Component for App {
             // Five of these images will force two textures to be allocated
             .push(node!(widgets::Canvas::new()
                 .set(&IMAGE.0[..IMAGE.1], IMAGE.2)
-                .scale(0.02))),
+                .scale(0.02)))
+            .push(avatars()),
         )
     }
 }
 
+/// A row of circular avatars, clipped from the square image.
+fn avatars() -> Node {
+    let mut row = node!(
+        widgets::Div::new(),
+        lay![size_pct: [100.0, Auto],
+             axis_alignment: Center,
+             margin: [10.0],
+        ]
+    );
+    for (i, size) in [80.0, 60.0, 40.0].iter().enumerate() {
+        row = row.push(
+            node!(
+                widgets::Div::new(),
+                lay![size: [*size], margin: [5.0]],
+                i as u64
+            )
+            .clip_path(widgets::ClipPath::ellipse())
+            .push(node!(widgets::Canvas::new()
+                .set(&IMAGE.0[..IMAGE.1], IMAGE.2)
+                .scale(size / IMAGE.2.width as f32))),
+        );
+    }
+    row
+}
+
 fn main() {
     println!("hello");
     lemna_baseview::Window::open_blocking::<App>(
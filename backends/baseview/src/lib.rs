@@ -2,9 +2,10 @@ use std::any::Any;
 use std::cell::UnsafeCell;
 use std::sync::{Arc, RwLock};
 
-use arboard::{self, Clipboard};
+use arboard::{self, Clipboard, ImageData};
 use baseview::MouseCursor;
-use lemna::{Component, Data, PixelSize, UI};
+use lemna::{Component, Cursor, Data, DataKind, PixelSize, UI};
+use log::warn;
 use raw_window_handle::{
     HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
 };
@@ -17,6 +18,10 @@ pub type Message = Box<dyn Any + Send>;
 #[derive(Debug)]
 pub enum ParentMessage {
     Resize,
+    /// Set the logical size of the window, e.g. when the host has resized it. An [`Input::Resize`] is sent once the window has been resized.
+    SetSize(PixelSize),
+    /// Redraw the app on the next frame, with [`UI#request_redraw`][lemna::UI#method.request_redraw]. Threads that don't have the channel can do the same with a [`UiWaker`][lemna::UiWaker], which marks the app dirty right away.
+    Dirty,
     AppMessage(Message),
 }
 
@@ -33,13 +38,21 @@ pub struct Window {
     scale_factor: f32,
     baseview_window: Option<&'static baseview::Window<'static>>,
     drop_target_valid: Arc<RwLock<bool>>,
+    min_size: RwLock<Option<PixelSize>>,
+    max_size: RwLock<Option<PixelSize>>,
+    // Set by `request_resize`, and applied on the next frame
+    requested_size: RwLock<Option<PixelSize>>,
+    resize_handler: Option<Box<dyn Fn(PixelSize) -> bool + Send + Sync>>,
+    resized_handler: Option<Box<dyn Fn(PixelSize) + Send + Sync>>,
+    // The last cursor set, so that setting the same one again is free
+    cursor: RwLock<Cursor>,
 }
 
 unsafe impl Send for Window {}
 unsafe impl Sync for Window {}
 
 impl Window {
-    /// Open as a child of another window. `options.resizable` will not do anything.
+    /// Open as a child of another window. `options.resizable` will not do anything, since the host decides whether the window can be resized, but its size constraints are still enforced.
     pub fn open_parented<P, A, B>(
         parent: &P,
         mut options: WindowOptions,
@@ -52,32 +65,34 @@ impl Window {
         B: Fn(&mut UI<Window, A>) + 'static + Send,
     {
         let drop_target_valid = Arc::new(RwLock::new(true));
-        let drop_target_valid2 = drop_target_valid.clone();
+        let open_options = options.open_options(true, drop_target_valid.clone());
+        let size = options.initial_size();
         baseview::Window::open_parented(
             parent,
-            baseview::WindowOpenOptions {
-                title: options.title,
-                size: baseview::Size::new(options.width.into(), options.height.into()),
-                scale: options.scale_policy,
-                resizable: false,
-                drop_target_valid: Some(Box::new(move || -> bool {
-                    *drop_target_valid2.read().unwrap()
-                })),
-            },
+            open_options,
             move |window: &mut baseview::Window<'_>| -> BaseViewUI<A> {
                 let scale_factor = match options.scale_policy {
                     baseview::WindowScalePolicy::ScaleFactor(scale) => scale,
                     baseview::WindowScalePolicy::SystemScaleFactor => 1.0, // Assume for now until scale event
                 } as f32;
-                let mut ui = UI::new(Self {
-                    handle: window.raw_window_handle(),
-                    display_handle: window.raw_display_handle(),
-                    size: (options.width, options.height),
-                    scale_factor,
-                    scale_policy: options.scale_policy,
-                    baseview_window: None,
-                    drop_target_valid,
-                });
+                let mut ui = UI::with_render_options(
+                    Self {
+                        handle: window.raw_window_handle(),
+                        display_handle: window.raw_display_handle(),
+                        size: (size.width, size.height),
+                        scale_factor,
+                        scale_policy: options.scale_policy,
+                        baseview_window: None,
+                        drop_target_valid,
+                        min_size: RwLock::new(options.min_size),
+                        max_size: RwLock::new(options.max_size),
+                        requested_size: RwLock::new(None),
+                        resize_handler: None,
+                        resized_handler: None,
+                        cursor: Default::default(),
+                    },
+                    options.render_options,
+                );
                 for (name, data) in options.fonts.drain(..) {
                     ui.add_font(name, data);
                 }
@@ -93,36 +108,69 @@ impl Window {
         )
     }
 
+    /// Ask `handler` before performing a resize requested with [`lemna::Window#method.request_resize`]. It is called with the new logical size, and the resize only happens if it returns true. This is used to let a plugin host veto resizes.
+    pub fn on_resize_request(&mut self, handler: Box<dyn Fn(PixelSize) -> bool + Send + Sync>) {
+        self.resize_handler = Some(handler);
+    }
+
+    /// Set a function that is called with the new logical size whenever the window has been resized, whether by the app, the user, or the host of a parented window.
+    pub fn on_resized(&mut self, handler: Box<dyn Fn(PixelSize) + Send + Sync>) {
+        self.resized_handler = Some(handler);
+    }
+
+    fn clamp_size(&self, size: PixelSize) -> PixelSize {
+        let mut size = size;
+        if let Some(min) = *self.min_size.read().unwrap() {
+            size.width = size.width.max(min.width);
+            size.height = size.height.max(min.height);
+        }
+        if let Some(max) = *self.max_size.read().unwrap() {
+            size.width = size.width.min(max.width);
+            size.height = size.height.min(max.height);
+        }
+        size
+    }
+
+    /// Resize to fit the current size constraints, if needed.
+    fn constrain_size(&self) {
+        let size = lemna::Window::logical_size(self);
+        if self.clamp_size(size) != size {
+            lemna::Window::request_resize(self, size);
+        }
+    }
+
     pub fn open_blocking<A>(mut options: WindowOptions)
     where
         A: 'static + Component + Default + Send + Sync,
     {
         let drop_target_valid = Arc::new(RwLock::new(true));
-        let drop_target_valid2 = drop_target_valid.clone();
+        let open_options = options.open_options(false, drop_target_valid.clone());
+        let size = options.initial_size();
         baseview::Window::open_blocking(
-            baseview::WindowOpenOptions {
-                title: options.title,
-                size: baseview::Size::new(options.width.into(), options.height.into()),
-                scale: options.scale_policy,
-                resizable: options.resizable,
-                drop_target_valid: Some(Box::new(move || -> bool {
-                    *drop_target_valid2.read().unwrap()
-                })),
-            },
+            open_options,
             move |window: &mut baseview::Window<'_>| -> BaseViewUI<A> {
                 let scale_factor = match options.scale_policy {
                     baseview::WindowScalePolicy::ScaleFactor(scale) => scale,
                     baseview::WindowScalePolicy::SystemScaleFactor => 1.0, // Assume for now until scale event
                 } as f32;
-                let mut ui = UI::new(Self {
-                    handle: window.raw_window_handle(),
-                    display_handle: window.raw_display_handle(),
-                    size: (options.width, options.height),
-                    scale_factor,
-                    scale_policy: options.scale_policy,
-                    baseview_window: None,
-                    drop_target_valid,
-                });
+                let mut ui = UI::with_render_options(
+                    Self {
+                        handle: window.raw_window_handle(),
+                        display_handle: window.raw_display_handle(),
+                        size: (size.width, size.height),
+                        scale_factor,
+                        scale_policy: options.scale_policy,
+                        baseview_window: None,
+                        drop_target_valid,
+                        min_size: RwLock::new(options.min_size),
+                        max_size: RwLock::new(options.max_size),
+                        requested_size: RwLock::new(None),
+                        resize_handler: None,
+                        resized_handler: None,
+                        cursor: Default::default(),
+                    },
+                    options.render_options,
+                );
                 for (name, data) in options.fonts.drain(..) {
                     ui.add_font(name, data);
                 }
@@ -152,6 +200,7 @@ unsafe impl HasRawDisplayHandle for Window {
     }
 }
 
+use lemna::event::ModifiersHeld;
 use lemna::input::{Button, Drag, Input, Key, Motion, MouseButton};
 impl<A: 'static + Component + Default + Send + Sync> baseview::WindowHandler for BaseViewUI<A> {
     fn on_frame(&mut self, window: &mut baseview::Window) {
@@ -165,9 +214,27 @@ impl<A: 'static + Component + Default + Send + Sync> baseview::WindowHandler for
                         let size = self.ui.window.read().unwrap().size;
                         window.resize(baseview::Size::new(size.0.into(), size.1.into()));
                     }
+                    ParentMessage::Dirty => self.ui.request_redraw(),
+                    ParentMessage::SetSize(size) => {
+                        self.ui.window.write().unwrap().size = (size.width, size.height);
+                        window.resize(baseview::Size::new(size.width.into(), size.height.into()));
+                    }
                 }
             }
         }
+        let requested_size = self
+            .ui
+            .window
+            .read()
+            .unwrap()
+            .requested_size
+            .write()
+            .unwrap()
+            .take();
+        if let Some(size) = requested_size {
+            self.ui.window.write().unwrap().size = (size.width, size.height);
+            window.resize(baseview::Size::new(size.width.into(), size.height.into()));
+        }
         self.ui.handle_input(&Input::Timer);
         self.ui.draw();
         self.ui.render();
@@ -199,6 +266,14 @@ impl<A: 'static + Component + Default + Send + Sync> baseview::WindowHandler for
                         window_info.logical_size().width as u32,
                         window_info.logical_size().height as u32,
                     );
+                    {
+                        let win = win.read().unwrap();
+                        if let Some(handler) = &win.resized_handler {
+                            handler(lemna::Window::logical_size(&*win));
+                        }
+                        // Baseview lets the user resize past the size constraints, so we resize back on the next frame
+                        win.constrain_size();
+                    }
                     self.ui.handle_input(&Input::Resize);
                 }
                 baseview::WindowEvent::WillClose => self.ui.handle_input(&Input::Exit),
@@ -218,33 +293,32 @@ impl<A: 'static + Component + Default + Send + Sync> baseview::WindowHandler for
             baseview::Event::Mouse(event) => match event {
                 baseview::MouseEvent::CursorMoved {
                     position,
-                    modifiers: _,
+                    modifiers,
                 } => {
+                    self.ui
+                        .handle_input(&Input::Modifiers(translate_modifiers(&modifiers)));
                     self.ui.handle_input(&Input::Motion(Motion::Mouse {
                         x: position.x as f32,
                         y: position.y as f32,
                     }));
                 }
-                baseview::MouseEvent::ButtonPressed {
-                    button,
-                    modifiers: _,
-                } => {
+                baseview::MouseEvent::ButtonPressed { button, modifiers } => {
+                    self.ui
+                        .handle_input(&Input::Modifiers(translate_modifiers(&modifiers)));
                     if let Some(button) = translate_mouse_button(&button) {
                         self.ui.handle_input(&Input::Press(button));
                     }
                 }
-                baseview::MouseEvent::ButtonReleased {
-                    button,
-                    modifiers: _,
-                } => {
+                baseview::MouseEvent::ButtonReleased { button, modifiers } => {
+                    self.ui
+                        .handle_input(&Input::Modifiers(translate_modifiers(&modifiers)));
                     if let Some(button) = translate_mouse_button(&button) {
                         self.ui.handle_input(&Input::Release(button));
                     }
                 }
-                baseview::MouseEvent::WheelScrolled {
-                    delta,
-                    modifiers: _,
-                } => {
+                baseview::MouseEvent::WheelScrolled { delta, modifiers } => {
+                    self.ui
+                        .handle_input(&Input::Modifiers(translate_modifiers(&modifiers)));
                     let (mut x, y) = match delta {
                         baseview::ScrollDelta::Lines { x, y } => {
                             let points_per_scroll_line = 10.0;
@@ -264,6 +338,8 @@ impl<A: 'static + Component + Default + Send + Sync> baseview::WindowHandler for
                 baseview::MouseEvent::CursorLeft => self.ui.handle_input(&Input::MouseLeaveWindow),
             },
             baseview::Event::Keyboard(event) => {
+                self.ui
+                    .handle_input(&Input::Modifiers(translate_modifiers(&event.modifiers)));
                 let key = translate_key(event.code);
                 if event.state == keyboard_types::KeyState::Down {
                     self.ui.handle_input(&Input::Press(key));
@@ -279,6 +355,16 @@ impl<A: 'static + Component + Default + Send + Sync> baseview::WindowHandler for
     }
 }
 
+fn translate_modifiers(modifiers: &keyboard_types::Modifiers) -> ModifiersHeld {
+    use keyboard_types::Modifiers;
+    ModifiersHeld {
+        shift: modifiers.contains(Modifiers::SHIFT),
+        alt: modifiers.contains(Modifiers::ALT),
+        ctrl: modifiers.contains(Modifiers::CONTROL),
+        meta: modifiers.contains(Modifiers::META),
+    }
+}
+
 use keyboard_types::Code;
 fn translate_key(key: Code) -> Button {
     Button::Keyboard(match key {
@@ -420,26 +506,69 @@ impl lemna::Window for Window {
     }
 
     fn get_from_clipboard(&self) -> Option<Data> {
+        self.get_from_clipboard_as(&[DataKind::String, DataKind::Image])
+    }
+
+    fn clipboard_kinds(&self) -> Vec<DataKind> {
         let mut clipboard = Clipboard::new().expect("Could get a clipboard");
-        match clipboard.get_text() {
-            Ok(s) => Some(Data::String(s)),
-            _ => None,
+        let mut kinds = vec![];
+        if clipboard.get_text().is_ok() {
+            kinds.push(DataKind::String);
+        }
+        if clipboard.get_image().is_ok() {
+            kinds.push(DataKind::Image);
         }
+        kinds
     }
 
+    /// HTML and file paths cannot be read from the clipboard.
+    fn get_from_clipboard_as(&self, kinds: &[DataKind]) -> Option<Data> {
+        let mut clipboard = Clipboard::new().expect("Could get a clipboard");
+        kinds.iter().find_map(|kind| match kind {
+            DataKind::String => clipboard.get_text().ok().map(Data::String),
+            DataKind::Image => clipboard.get_image().ok().map(|image| Data::Image {
+                width: image.width as u32,
+                height: image.height as u32,
+                rgba: image.bytes.into_owned(),
+            }),
+            _ => None,
+        })
+    }
+
+    /// File paths cannot be put on the clipboard.
     fn put_on_clipboard(&self, data: &Data) {
         let mut clipboard = Clipboard::new().expect("Could get a clipboard");
-        match data {
-            Data::String(s) => {
-                clipboard.set_text(s).unwrap();
+        let result = match data {
+            Data::String(s) => clipboard.set_text(s),
+            Data::Html(html) => clipboard.set_html(html, None),
+            Data::Image {
+                width,
+                height,
+                rgba,
+            } => clipboard.set_image(ImageData {
+                width: *width as usize,
+                height: *height as usize,
+                bytes: rgba.into(),
+            }),
+            d => {
+                warn!("Cannot put {:?} data on the clipboard", d.kind());
+                return;
             }
-            _ => (),
+        };
+        if let Err(e) = result {
+            warn!(
+                "Failed to put {:?} data on the clipboard: {}",
+                data.kind(),
+                e
+            );
         }
     }
 
     fn start_drag(&self, data: Data) {
         if let Some(win) = self.baseview_window {
-            win.start_drag(lemna_data_to_baseview(data));
+            if let Some(data) = lemna_data_to_baseview(data) {
+                win.start_drag(data);
+            }
         }
     }
 
@@ -447,22 +576,43 @@ impl lemna::Window for Window {
         *self.drop_target_valid.write().unwrap() = valid
     }
 
-    fn set_cursor(&self, cursor_type: &str) {
-        let ct = match cursor_type {
-            "Arrow" => MouseCursor::Default,
-            "None" => MouseCursor::Hidden,
-            "Hidden" => MouseCursor::Hidden,
-            "Ibeam" | "Text" => MouseCursor::Text,
-            "PointingHand" => MouseCursor::PointingHand,
-            "Hand" => MouseCursor::Hand,
-            "HandGrabbing" => MouseCursor::HandGrabbing,
-            "NoEntry" => MouseCursor::NotAllowed,
-            "Cross" => MouseCursor::Crosshair,
-            "Size" | "Move" => MouseCursor::Move,
-            "SizeNWSE" => MouseCursor::NwseResize,
-            "SizeNS" => MouseCursor::NsResize,
-            "SizeNESW" => MouseCursor::NeswResize,
-            "SizeWE" => MouseCursor::EwResize,
+    fn request_resize(&self, size: PixelSize) {
+        let size = self.clamp_size(size);
+        if self.resize_handler.as_ref().map_or(true, |f| f(size)) {
+            *self.requested_size.write().unwrap() = Some(size);
+        }
+    }
+
+    /// Baseview cannot stop the user from resizing past this size, so the window is resized back to it on the next frame. Also limits the sizes of [`#request_resize`][lemna::Window#method.request_resize].
+    fn set_min_size(&self, size: Option<PixelSize>) {
+        *self.min_size.write().unwrap() = size;
+        self.constrain_size();
+    }
+
+    /// Baseview cannot stop the user from resizing past this size, so the window is resized back to it on the next frame. Also limits the sizes of [`#request_resize`][lemna::Window#method.request_resize].
+    fn set_max_size(&self, size: Option<PixelSize>) {
+        *self.max_size.write().unwrap() = size;
+        self.constrain_size();
+    }
+
+    /// Baseview does not support custom cursor images, so their fallback is shown.
+    fn set_cursor(&self, cursor: Cursor) {
+        if *self.cursor.read().unwrap() == cursor {
+            return;
+        }
+        let ct = match cursor.named() {
+            Cursor::Hidden => MouseCursor::Hidden,
+            Cursor::Text => MouseCursor::Text,
+            Cursor::PointingHand => MouseCursor::PointingHand,
+            Cursor::Hand => MouseCursor::Hand,
+            Cursor::HandGrabbing => MouseCursor::HandGrabbing,
+            Cursor::NoEntry => MouseCursor::NotAllowed,
+            Cursor::Cross => MouseCursor::Crosshair,
+            Cursor::Move => MouseCursor::Move,
+            Cursor::SizeNWSE => MouseCursor::NwseResize,
+            Cursor::SizeNS => MouseCursor::NsResize,
+            Cursor::SizeNESW => MouseCursor::NeswResize,
+            Cursor::SizeWE => MouseCursor::EwResize,
             _ => MouseCursor::Default,
         };
         if let Some(win) = self.baseview_window {
@@ -472,18 +622,12 @@ impl lemna::Window for Window {
                 let baseview_window: &mut baseview::Window = &mut *baseview_window.get();
                 baseview_window.set_mouse_cursor(ct);
             }
+            *self.cursor.write().unwrap() = cursor;
         }
     }
 
     fn unset_cursor(&self) {
-        if let Some(win) = self.baseview_window {
-            unsafe {
-                let t = win as *const _ as *const UnsafeCell<baseview::Window>;
-                let baseview_window: &UnsafeCell<baseview::Window> = &*t;
-                let baseview_window: &mut baseview::Window = &mut *baseview_window.get();
-                baseview_window.set_mouse_cursor(MouseCursor::Default);
-            }
-        }
+        self.set_cursor(Cursor::Arrow);
     }
 }
 
@@ -494,9 +638,13 @@ fn baseview_data_to_lemna(d: baseview::Data) -> Data {
     }
 }
 
-fn lemna_data_to_baseview(d: Data) -> baseview::Data {
+fn lemna_data_to_baseview(d: Data) -> Option<baseview::Data> {
     match d {
-        Data::Filepath(p) => baseview::Data::Filepath(p),
-        Data::String(s) => baseview::Data::String(s),
+        Data::Filepath(p) => Some(baseview::Data::Filepath(p)),
+        Data::String(s) => Some(baseview::Data::String(s)),
+        d => {
+            warn!("Cannot drag {:?} data", d.kind());
+            None
+        }
     }
 }
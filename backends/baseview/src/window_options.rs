@@ -1,11 +1,18 @@
+use std::sync::{Arc, RwLock};
+
+use lemna::PixelSize;
+
 #[derive(Debug, Clone)]
 pub struct WindowOptions {
     pub title: String,
     pub width: u32,
     pub height: u32,
     pub resizable: bool,
+    pub min_size: Option<PixelSize>,
+    pub max_size: Option<PixelSize>,
     pub(crate) scale_policy: baseview::WindowScalePolicy,
     pub(crate) fonts: Vec<(String, &'static [u8])>,
+    pub(crate) render_options: lemna::RenderOptions,
 }
 
 impl WindowOptions {
@@ -16,8 +23,11 @@ impl WindowOptions {
             width: dims.0,
             height: dims.1,
             resizable: true,
+            min_size: None,
+            max_size: None,
             scale_policy: baseview::WindowScalePolicy::SystemScaleFactor,
             fonts: vec![],
+            render_options: Default::default(),
         }
     }
 
@@ -40,4 +50,96 @@ impl WindowOptions {
         self.resizable = resizable;
         self
     }
+
+    /// The smallest logical size that the window may be resized to. The same as calling [`lemna::Window#method.set_min_size`] once the window is open.
+    pub fn min_size(mut self, dims: (u32, u32)) -> Self {
+        self.min_size = Some(PixelSize::new(dims.0, dims.1));
+        self
+    }
+
+    /// The largest logical size that the window may be resized to. The same as calling [`lemna::Window#method.set_max_size`] once the window is open.
+    pub fn max_size(mut self, dims: (u32, u32)) -> Self {
+        self.max_size = Some(PixelSize::new(dims.0, dims.1));
+        self
+    }
+
+    /// The size to open the window at: the requested size, fit to the size constraints.
+    pub(crate) fn initial_size(&self) -> PixelSize {
+        let mut size = PixelSize::new(self.width, self.height);
+        if let Some(min) = self.min_size {
+            size.width = size.width.max(min.width);
+            size.height = size.height.max(min.height);
+        }
+        if let Some(max) = self.max_size {
+            size.width = size.width.min(max.width);
+            size.height = size.height.min(max.height);
+        }
+        size
+    }
+
+    /// The options that baseview opens the window with. Parented windows are sized by their host, so they are never resizable by the user.
+    pub(crate) fn open_options(
+        &self,
+        parented: bool,
+        drop_target_valid: Arc<RwLock<bool>>,
+    ) -> baseview::WindowOpenOptions {
+        let size = self.initial_size();
+        baseview::WindowOpenOptions {
+            title: self.title.clone(),
+            size: baseview::Size::new(size.width.into(), size.height.into()),
+            scale: self.scale_policy,
+            resizable: self.resizable && !parented,
+            drop_target_valid: Some(Box::new(move || -> bool {
+                *drop_target_valid.read().unwrap()
+            })),
+        }
+    }
+
+    /// How frames are presented. Defaults to [`PresentMode::AutoVsync`][lemna::PresentMode::AutoVsync].
+    pub fn present_mode(mut self, present_mode: lemna::PresentMode) -> Self {
+        self.render_options.present_mode = present_mode;
+        self
+    }
+
+    /// Limit rendering to at most `fps` frames per second.
+    pub fn frame_cap(mut self, fps: u32) -> Self {
+        self.render_options.frame_cap = Some(fps);
+        self
+    }
+
+    /// The number of samples used to anti-alias shapes. Defaults to 4.
+    pub fn msaa_samples(mut self, samples: u32) -> Self {
+        self.render_options.msaa_samples = samples;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_options() {
+        let options = WindowOptions::new("Test", (400, 300))
+            .min_size((500, 200))
+            .max_size((800, 250));
+        let open_options = options.open_options(false, Arc::new(RwLock::new(true)));
+        assert_eq!(open_options.title, "Test");
+        assert!(open_options.resizable);
+        // The initial size is fit to the constraints
+        assert_eq!(open_options.size.width, 500.0);
+        assert_eq!(open_options.size.height, 250.0);
+
+        assert!(
+            !options
+                .open_options(true, Arc::new(RwLock::new(true)))
+                .resizable
+        );
+        assert!(
+            !options
+                .resizable(false)
+                .open_options(false, Arc::new(RwLock::new(true)))
+                .resizable
+        );
+    }
 }
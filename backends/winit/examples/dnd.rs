@@ -0,0 +1,96 @@
+use lemna::{style::HorizontalPosition, *};
+
+/// Drag a file from your file manager onto the target: It should highlight while the file is over it, and the path should be printed when dropped. Switching to another window and back should print focus changes.
+#[derive(Debug, Default)]
+pub struct App {}
+
+impl lemna::Component for App {
+    fn view(&self) -> Option<Node> {
+        Some(
+            node!(
+                widgets::Div::new(),
+                [size_pct: [100.0], padding: [10.0],
+                 axis_alignment: Center, cross_alignment: Center]
+            )
+            .push(node!(DropTarget::new(), lay!(size: size!(200.0)))),
+        )
+    }
+
+    fn on_focus(&mut self, _event: &mut Event<event::Focus>) {
+        println!("Window focused");
+    }
+
+    fn on_blur(&mut self, _event: &mut Event<event::Blur>) {
+        println!("Window unfocused");
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct DropTargetState {
+    active: bool,
+}
+
+#[component(State = "DropTargetState")]
+#[derive(Debug)]
+pub struct DropTarget {}
+
+impl DropTarget {
+    fn new() -> Self {
+        Self {
+            state: Some(DropTargetState::default()),
+            dirty: false,
+        }
+    }
+}
+
+#[state_component_impl(DropTargetState)]
+impl Component for DropTarget {
+    fn view(&self) -> Option<Node> {
+        Some(
+            node!(
+                widgets::Div::new()
+                    .bg(if self.state_ref().active {
+                        Color::rgb(1.0, 0.5, 0.5)
+                    } else {
+                        Color::rgb(0.5, 1.0, 0.5)
+                    })
+                    .border(Color::BLACK, 2.0),
+                [
+                    size_pct: [100],
+                    padding: [5],
+                    cross_alignment: Center,
+                    axis_alignment: Center,
+                ],
+            )
+            .push(node!(widgets::Text::new(txt!("Drag a file onto me"))
+                .style("h_alignment", HorizontalPosition::Center))),
+        )
+    }
+
+    fn on_drag_drop(&mut self, event: &mut Event<event::DragDrop>) {
+        println!("Got {:?}", event.input.0);
+        self.state_mut().active = false;
+    }
+
+    fn on_drag_enter(&mut self, event: &mut Event<event::DragEnter>) {
+        println!("Dragging {:?}", event.input.0);
+        self.state_mut().active = true;
+    }
+
+    fn on_drag_leave(&mut self, _event: &mut Event<event::DragLeave>) {
+        self.state_mut().active = false;
+    }
+
+    fn on_drag_target(&mut self, event: &mut Event<event::DragTarget>) {
+        event.stop_bubbling();
+    }
+}
+
+fn main() {
+    lemna_winit::Window::open_blocking::<App>(
+        "Drag and drop",
+        400,
+        300,
+        vec![("noto sans regular".to_string(), ttf_noto_sans::REGULAR)],
+    );
+}
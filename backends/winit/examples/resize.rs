@@ -0,0 +1,48 @@
+use lemna::{widgets::*, *};
+
+/// Resize the window (and drag it between monitors with different scale factors): The quadrants should always fill the window, and the text should stay sharp.
+///
+/// F11 toggles fullscreen, which should also fill the screen with the quadrants.
+#[derive(Debug, Default)]
+pub struct App {}
+
+impl lemna::Component for App {
+    fn on_key_down(&mut self, event: &mut Event<event::KeyDown>) {
+        if event.input.0 == input::Key::F11 {
+            if let Some(w) = current_window() {
+                w.set_fullscreen(!w.is_fullscreen());
+            }
+        }
+    }
+
+    fn view(&self) -> Option<Node> {
+        let quadrant = |color: Color, label: &str| {
+            node!(
+                Div::new().bg(color),
+                lay!(
+                    size: size_pct!(50.0),
+                    cross_alignment: Center,
+                    axis_alignment: Center
+                )
+            )
+            .push(node!(Text::new(txt!(label.to_string()))))
+        };
+
+        Some(
+            node!(Div::new(), lay!(size: size_pct!(100.0), wrap: true))
+                .push(quadrant(Color::rgb(1.0, 0.6, 0.6), "Top left"))
+                .push(quadrant(Color::rgb(0.6, 1.0, 0.6), "Top right"))
+                .push(quadrant(Color::rgb(0.6, 0.6, 1.0), "Bottom left"))
+                .push(quadrant(Color::rgb(1.0, 1.0, 0.6), "Bottom right")),
+        )
+    }
+}
+
+fn main() {
+    lemna_winit::Window::open_blocking::<App>(
+        "Resize me",
+        600,
+        300,
+        vec![("noto sans regular".to_string(), ttf_noto_sans::REGULAR)],
+    );
+}
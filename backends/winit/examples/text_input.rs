@@ -0,0 +1,64 @@
+use lemna::{widgets::*, *};
+
+#[derive(Debug)]
+pub enum AppMsg {
+    Changed(String),
+}
+
+#[derive(Debug, Default)]
+pub struct AppState {
+    value: String,
+}
+
+#[component(State = "AppState")]
+#[derive(Debug, Default)]
+pub struct App {}
+
+#[state_component_impl(AppState)]
+impl lemna::Component for App {
+    fn init(&mut self) {
+        self.state = Some(AppState::default())
+    }
+
+    fn view(&self) -> Option<Node> {
+        Some(
+            node!(
+                Div::new(),
+                lay!(
+                    size: size_pct!(100.0),
+                    direction: Column,
+                    padding: [20]
+                )
+            )
+            .push(node!(Text::new(txt!(
+                "Type below. Backspace and the arrow keys should edit the text."
+            ))))
+            .push(node!(
+                TextBox::new(Some(self.state_ref().value.clone()))
+                    .on_change(Box::new(|s| msg!(AppMsg::Changed(s.to_string())))),
+                lay!(size: [300, 30], margin: [10])
+            ))
+            .push(node!(Text::new(txt!(format!(
+                "You typed: {}",
+                self.state_ref().value
+            ))))),
+        )
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<AppMsg>() {
+            Some(AppMsg::Changed(s)) => self.state_mut().value = s.clone(),
+            None => return vec![message],
+        }
+        vec![]
+    }
+}
+
+fn main() {
+    lemna_winit::Window::open_blocking::<App>(
+        "Text input",
+        400,
+        200,
+        vec![("noto sans regular".to_string(), ttf_noto_sans::REGULAR)],
+    );
+}
@@ -0,0 +1,77 @@
+use lemna::{widgets::*, *};
+
+/// Switch your OS between light and dark mode: The window should follow.
+#[derive(Debug)]
+pub struct AppState {
+    scheme: ColorScheme,
+}
+
+#[component(State = "AppState")]
+#[derive(Debug, Default)]
+pub struct App {}
+
+struct Palette {
+    background: Color,
+    panel: Color,
+    text: Color,
+}
+
+const LIGHT: Palette = Palette {
+    background: colors::WHITE_SMOKE,
+    panel: colors::LIGHT_STEEL_BLUE,
+    text: colors::BLACK,
+};
+
+const DARK: Palette = Palette {
+    background: colors::DARK_SLATE_GRAY,
+    panel: colors::MIDNIGHT_BLUE,
+    text: colors::WHITE_SMOKE,
+};
+
+#[state_component_impl(AppState)]
+impl lemna::Component for App {
+    fn init(&mut self) {
+        let scheme = current_window()
+            .and_then(|w| w.color_scheme())
+            .unwrap_or(ColorScheme::Light);
+        self.state = Some(AppState { scheme });
+    }
+
+    fn on_theme_change(&mut self, event: &mut Event<event::ThemeChange>) {
+        self.state_mut().scheme = event.input.0;
+    }
+
+    fn view(&self) -> Option<Node> {
+        let scheme = self.state_ref().scheme;
+        let palette = match scheme {
+            ColorScheme::Light => &LIGHT,
+            ColorScheme::Dark => &DARK,
+        };
+
+        Some(
+            node!(
+                Div::new().bg(palette.background),
+                lay!(
+                    size: size_pct!(100.0),
+                    cross_alignment: Center,
+                    axis_alignment: Center
+                )
+            )
+            .push(
+                node!(Div::new().bg(palette.panel), lay!(padding: rect!(20.0))).push(node!(
+                    Text::new(txt!(format!("The OS is in {:?} mode", scheme)))
+                        .style("color", palette.text)
+                )),
+            ),
+        )
+    }
+}
+
+fn main() {
+    lemna_winit::Window::open_blocking::<App>(
+        "Light or dark",
+        400,
+        200,
+        vec![("noto sans regular".to_string(), ttf_noto_sans::REGULAR)],
+    );
+}
@@ -0,0 +1,84 @@
+use lemna::{style::StyleVal, widgets::*, *};
+
+/// The built-in widgets take their colors and sizes from the current `Theme`. Switching it restyles all of them.
+#[derive(Debug)]
+pub enum AppMsg {
+    ToggleTheme,
+}
+
+#[derive(Debug, Default)]
+pub struct AppState {
+    dark: bool,
+}
+
+#[component(State = "AppState")]
+#[derive(Debug, Default)]
+pub struct App {}
+
+fn theme_color(token: &'static str) -> Color {
+    style::current_theme_val(token).unwrap().color()
+}
+
+#[state_component_impl(AppState)]
+impl lemna::Component for App {
+    fn init(&mut self) {
+        self.state = Some(AppState::default())
+    }
+
+    fn view(&self) -> Option<Node> {
+        let label = if self.state_ref().dark {
+            "Use the light theme"
+        } else {
+            "Use the dark theme"
+        };
+
+        Some(
+            node!(
+                Div::new().bg(theme_color("surface")),
+                lay!(
+                    size: size_pct!(100.0),
+                    direction: Column,
+                    padding: rect!(20.0)
+                )
+            )
+            .push(node!(
+                Button::new(txt!(label)).on_click(Box::new(|| msg!(AppMsg::ToggleTheme))),
+                lay!(margin: rect!(10.0))
+            ))
+            .push(node!(
+                Select::new(vec!["One", "Two", "Three"], 0),
+                lay!(margin: rect!(10.0))
+            ))
+            .push(node!(
+                TextBox::new(Some("Some text".to_string())),
+                lay!(size: size!(200.0, 30.0), margin: rect!(10.0))
+            ))
+            .push(node!(
+                // Tokens can be used to style individual widgets, too
+                Text::new(txt!("An accented message")).style("color", StyleVal::Token("accent")),
+                lay!(margin: rect!(10.0))
+            )),
+        )
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<AppMsg>() {
+            Some(AppMsg::ToggleTheme) => {
+                let dark = !self.state_ref().dark;
+                style::set_current_theme(if dark { Theme::dark() } else { Theme::light() });
+                self.state_mut().dark = dark;
+            }
+            None => return vec![message],
+        }
+        vec![]
+    }
+}
+
+fn main() {
+    lemna_winit::Window::open_blocking::<App>(
+        "Theming",
+        400,
+        300,
+        vec![("noto sans regular".to_string(), ttf_noto_sans::REGULAR)],
+    );
+}
@@ -0,0 +1,42 @@
+use lemna::{widgets::*, *};
+
+/// Two windows running different apps on the same event loop: Typing in one should not affect the other, and the program should exit once both are closed.
+#[derive(Debug, Default)]
+pub struct Editor {}
+
+impl lemna::Component for Editor {
+    fn view(&self) -> Option<Node> {
+        Some(
+            node!(
+                Div::new(),
+                [size_pct: [100], padding: [10], direction: Column]
+            )
+            .push(node!(Text::new(txt!("Editor"))))
+            .push(node!(TextBox::new(None), lay!(size: size!(200.0, Auto)))),
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Inspector {}
+
+impl lemna::Component for Inspector {
+    fn view(&self) -> Option<Node> {
+        Some(
+            node!(
+                Div::new().bg(colors::LIGHT_STEEL_BLUE),
+                [size_pct: [100], padding: [10], direction: Column]
+            )
+            .push(node!(Text::new(txt!("Inspector"))))
+            .push(node!(TextBox::new(None), lay!(size: size!(150.0, Auto)))),
+        )
+    }
+}
+
+fn main() {
+    let fonts = vec![("noto sans regular".to_string(), ttf_noto_sans::REGULAR)];
+    let mut windows = lemna_winit::Windows::new();
+    windows.open::<Editor>("Editor", 400, 300, fonts.clone());
+    windows.open::<Inspector>("Inspector", 250, 300, fonts);
+    windows.run();
+}
@@ -1,118 +1,619 @@
-use lemna::input::{Button, Input, Motion, MouseButton};
-use lemna::{Component, PixelSize, UI};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use arboard::{Clipboard, ImageData};
+use lemna::input::{Button, Drag, Input, Key, Motion, MouseButton, StylusPhase};
+use lemna::{ColorScheme, Component, Cursor, Data, DataKind, PixelSize, UI};
+use log::warn;
 use raw_window_handle::{
     HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
 };
 use winit::{
-    dpi::LogicalSize,
-    event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    dpi::{LogicalSize, PhysicalSize},
+    event::{ElementState, Event, ModifiersState, TouchPhase, VirtualKeyCode, WindowEvent},
+    event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget},
+    window::{CursorIcon, Fullscreen, Theme, WindowBuilder, WindowId, WindowLevel},
 };
 
 pub struct Window {
     winit_window: winit::window::Window,
+    // The last cursor set, so that setting the same one again is free
+    cursor: RwLock<Cursor>,
+    #[cfg(feature = "accesskit")]
+    accessibility: accesskit_winit::Adapter,
 }
 unsafe impl Send for Window {}
 unsafe impl Sync for Window {}
 
 impl Window {
+    /// Open a single window running the app `A`, and run the event loop until it is closed. Use [`Windows`] to open more than one window.
     pub fn open_blocking<A>(
         title: &str,
         width: u32,
         height: u32,
-        mut fonts: Vec<(String, &'static [u8])>,
+        fonts: Vec<(String, &'static [u8])>,
     ) where
         A: 'static + Component + Default + Send + Sync,
     {
-        let event_loop = EventLoop::new();
+        let mut windows = Windows::new();
+        windows.open::<A>(title, width, height, fonts);
+        windows.run();
+    }
+
+    fn open<A>(
+        target: &EventLoopWindowTarget<UserEvent>,
+        _proxy: &EventLoopProxy<UserEvent>,
+        title: &str,
+        width: u32,
+        height: u32,
+        mut fonts: Vec<(String, &'static [u8])>,
+    ) -> (WindowId, OpenWindow)
+    where
+        A: 'static + Component + Default + Send + Sync,
+    {
         let window = WindowBuilder::new()
             .with_title(title)
             .with_inner_size(LogicalSize::new(width as f32, height as f32))
-            .build(&event_loop)
+            // The accessibility adapter must be created before the window is shown
+            .with_visible(!cfg!(feature = "accesskit"))
+            .build(target)
             .unwrap();
+        #[cfg(feature = "accesskit")]
+        let accessibility =
+            accesskit_winit::Adapter::new(&window, initial_accessibility_tree, _proxy.clone());
+        #[cfg(feature = "accesskit")]
+        window.set_visible(true);
+        let id = window.id();
         let mut ui: UI<Window, A> = UI::new(Window {
             winit_window: window,
+            cursor: Default::default(),
+            #[cfg(feature = "accesskit")]
+            accessibility,
         });
         for (name, data) in fonts.drain(..) {
             ui.add_font(name, data);
         }
+        (id, OpenWindow::new(Box::new(ui)))
+    }
+}
+
+/// Windows that share one event loop, each running its own app with its own [`UI`]. Events are sent to the window that they happened in, and the event loop exits once every window has been closed.
+///
+/// ```ignore
+/// let mut windows = lemna_winit::Windows::new();
+/// windows.open::<Editor>("Editor", 800, 600, fonts.clone());
+/// windows.open::<Inspector>("Inspector", 300, 600, fonts);
+/// windows.run();
+/// ```
+pub struct Windows {
+    event_loop: EventLoop<UserEvent>,
+    windows: HashMap<WindowId, OpenWindow>,
+}
+
+impl Default for Windows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Windows {
+    pub fn new() -> Self {
+        Self {
+            event_loop: EventLoopBuilder::<UserEvent>::with_user_event().build(),
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Open a window running the app `A`. It is shown right away, but does not handle any events until [`#run`][Windows#method.run] is called.
+    pub fn open<A>(
+        &mut self,
+        title: &str,
+        width: u32,
+        height: u32,
+        fonts: Vec<(String, &'static [u8])>,
+    ) -> WindowId
+    where
+        A: 'static + Component + Default + Send + Sync,
+    {
+        let (id, window) = Window::open::<A>(
+            &self.event_loop,
+            &self.event_loop.create_proxy(),
+            title,
+            width,
+            height,
+            fonts,
+        );
+        self.windows.insert(id, window);
+        id
+    }
+
+    /// A handle that opens more windows once the event loop is running, e.g. to detach a panel.
+    pub fn opener(&self) -> WindowOpener {
+        WindowOpener(self.event_loop.create_proxy())
+    }
+
+    /// Run the event loop until every window has been closed.
+    pub fn run(self) -> ! {
+        let Self {
+            event_loop,
+            mut windows,
+        } = self;
+        let proxy = event_loop.create_proxy();
 
-        event_loop.run(move |event, _, control_flow| {
+        event_loop.run(move |event, target, control_flow| {
             *control_flow = ControlFlow::Wait;
             // inst(&format!("event_handler <{:?}>", &event));
 
             match event {
                 Event::MainEventsCleared => {
-                    ui.draw();
-                }
-                Event::RedrawRequested(_) => ui.render(),
-                Event::WindowEvent { event, .. } => match event {
-                    WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
-                    WindowEvent::CursorMoved { position, .. } => {
-                        let scale_factor = ui.window.read().unwrap().winit_window.scale_factor();
-                        // println!("{:?}", position);
-                        ui.handle_input(&Input::Motion(Motion::Mouse {
-                            x: position.x as f32 / scale_factor as f32,
-                            y: position.y as f32 / scale_factor as f32,
-                        }));
+                    for window in windows.values_mut() {
+                        window.ui.draw();
                     }
-                    WindowEvent::MouseInput {
-                        button: _,
-                        state: winit::event::ElementState::Pressed,
-                        ..
-                    } => {
-                        ui.handle_input(&Input::Press(Button::Mouse(MouseButton::Left)));
+                }
+                Event::RedrawRequested(window_id) => {
+                    if let Some(window) = windows.get_mut(&window_id) {
+                        window.ui.render();
                     }
-                    WindowEvent::MouseInput {
-                        button: _,
-                        state: winit::event::ElementState::Released,
-                        ..
-                    } => {
-                        ui.handle_input(&Input::Release(Button::Mouse(MouseButton::Left)));
+                }
+                Event::UserEvent(UserEvent::Open(open)) => {
+                    let (id, window) = open(target, &proxy);
+                    windows.insert(id, window);
+                }
+                #[cfg(feature = "accesskit")]
+                Event::UserEvent(UserEvent::Accessibility(
+                    accesskit_winit::ActionRequestEvent { window_id, request },
+                )) => {
+                    if let Some(window) = windows.get_mut(&window_id) {
+                        window.ui.handle_input(&Input::Accessibility(request));
                     }
-                    WindowEvent::MouseWheel { delta, .. } => {
-                        // println!("scroll delta{:?}", delta);
-                        let scroll = match delta {
-                            winit::event::MouseScrollDelta::LineDelta(x, y) => Motion::Scroll {
-                                x: x * -10.0,
-                                y: y * -10.0,
-                            },
-                            winit::event::MouseScrollDelta::PixelDelta(
-                                winit::dpi::PhysicalPosition { x, y },
-                            ) => Motion::Scroll {
-                                x: -x as f32,
-                                y: -y as f32,
-                            },
-                        };
-                        ui.handle_input(&Input::Motion(scroll));
+                }
+                Event::WindowEvent { window_id, event } => {
+                    if !route_window_event(&mut windows, window_id, &event) {
+                        *control_flow = ControlFlow::Exit;
                     }
-                    _ => (),
-                },
+                }
                 _ => (),
             };
 
             // inst_end();
-        });
+        })
     }
 }
 
-impl lemna::Window for Window {
-    // TODO: This isn't good
+/// Opens windows from anywhere, once the [`Windows`] that it came from is running. Windows are opened on the event loop's thread, so they appear shortly after they are asked for.
+#[derive(Clone)]
+pub struct WindowOpener(EventLoopProxy<UserEvent>);
+
+impl WindowOpener {
+    /// Open a window running the app `A`. Does nothing if the event loop has already exited.
+    pub fn open<A>(&self, title: &str, width: u32, height: u32, fonts: Vec<(String, &'static [u8])>)
+    where
+        A: 'static + Component + Default + Send + Sync,
+    {
+        let title = title.to_string();
+        let _ = self
+            .0
+            .send_event(UserEvent::Open(Box::new(move |target, proxy| {
+                Window::open::<A>(target, proxy, &title, width, height, fonts)
+            })));
+    }
+}
+
+type Opener = Box<
+    dyn FnOnce(
+            &EventLoopWindowTarget<UserEvent>,
+            &EventLoopProxy<UserEvent>,
+        ) -> (WindowId, OpenWindow)
+        + Send,
+>;
+
+enum UserEvent {
+    Open(Opener),
+    #[cfg(feature = "accesskit")]
+    Accessibility(accesskit_winit::ActionRequestEvent),
+}
+
+#[cfg(feature = "accesskit")]
+impl From<accesskit_winit::ActionRequestEvent> for UserEvent {
+    fn from(event: accesskit_winit::ActionRequestEvent) -> Self {
+        Self::Accessibility(event)
+    }
+}
+
+/// The parts of a window's [`UI`] that the event loop uses, so that windows running different apps can be kept together.
+trait WindowHandler {
+    fn handle_input(&mut self, input: &Input);
+    fn scale_factor(&self) -> f64;
+    fn draw(&mut self);
+    fn render(&mut self);
+    /// Ask the UI to exit. Returns false if it was vetoed, and the window should stay open.
+    fn exit(&mut self) -> bool;
+    /// Whether `event` should be handled, after giving it to the accessibility adapter.
+    #[cfg(feature = "accesskit")]
+    fn on_accessibility_event(&self, event: &WindowEvent) -> bool;
+}
+
+impl<A: 'static + Component + Default + Send + Sync> WindowHandler for UI<Window, A> {
+    fn handle_input(&mut self, input: &Input) {
+        UI::handle_input(self, input);
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.window.read().unwrap().winit_window.scale_factor()
+    }
+
+    fn draw(&mut self) {
+        UI::draw(self);
+    }
+
+    fn render(&mut self) {
+        UI::render(self);
+    }
+
+    fn exit(&mut self) -> bool {
+        UI::request_exit(self)
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn on_accessibility_event(&self, event: &WindowEvent) -> bool {
+        let window = self.window.read().unwrap();
+        window.accessibility.on_event(&window.winit_window, event)
+    }
+}
+
+/// A window that is open, and the input state that is tracked for it.
+struct OpenWindow {
+    ui: Box<dyn WindowHandler>,
+    modifiers: ModifiersState,
+    // Whether files are being dragged over the window
+    hovering_file: bool,
+}
+
+impl OpenWindow {
+    fn new(ui: Box<dyn WindowHandler>) -> Self {
+        Self {
+            ui,
+            modifiers: ModifiersState::empty(),
+            hovering_file: false,
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        #[cfg(feature = "accesskit")]
+        if !self.ui.on_accessibility_event(event) {
+            return;
+        }
+
+        let ui = &mut self.ui;
+        match event {
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                ui.handle_input(&Input::Resize);
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let scale_factor = ui.scale_factor();
+                // println!("{:?}", position);
+                ui.handle_input(&Input::Motion(Motion::Mouse {
+                    x: position.x as f32 / scale_factor as f32,
+                    y: position.y as f32 / scale_factor as f32,
+                }));
+                if self.hovering_file {
+                    ui.handle_input(&Input::Drag(Drag::Dragging));
+                }
+            }
+            WindowEvent::Touch(touch) => {
+                // Only touches that report a force come from a stylus. Plain touches
+                // are left to the platform's mouse emulation
+                if let Some(force) = touch.force {
+                    let scale_factor = ui.scale_factor();
+                    ui.handle_input(&Input::Stylus {
+                        x: (touch.location.x / scale_factor) as f32,
+                        y: (touch.location.y / scale_factor) as f32,
+                        pressure: force.normalized() as f32,
+                        tilt: None,
+                        phase: translate_touch_phase(touch.phase),
+                    });
+                }
+            }
+            WindowEvent::HoveredFile(path) => {
+                // Sent once per file, but we only start one drag
+                if !self.hovering_file {
+                    self.hovering_file = true;
+                    ui.handle_input(&Input::Drag(Drag::Start(Data::Filepath(path.clone()))));
+                }
+            }
+            WindowEvent::HoveredFileCancelled => {
+                self.hovering_file = false;
+                ui.handle_input(&Input::Drag(Drag::End));
+            }
+            WindowEvent::DroppedFile(path) => {
+                self.hovering_file = false;
+                ui.handle_input(&Input::Drag(Drag::Drop(Data::Filepath(path.clone()))));
+            }
+            WindowEvent::Focused(focused) => ui.handle_input(&Input::Focus(*focused)),
+            WindowEvent::ThemeChanged(theme) => {
+                ui.handle_input(&Input::ThemeChanged(translate_theme(*theme)))
+            }
+            WindowEvent::CursorEntered { .. } => ui.handle_input(&Input::MouseEnterWindow),
+            WindowEvent::CursorLeft { .. } => ui.handle_input(&Input::MouseLeaveWindow),
+            WindowEvent::MouseInput { button, state, .. } => {
+                if let Some(button) = translate_mouse_button(*button) {
+                    ui.handle_input(&match state {
+                        ElementState::Pressed => Input::Press(button),
+                        ElementState::Released => Input::Release(button),
+                    });
+                }
+            }
+            WindowEvent::KeyboardInput { input, .. } => {
+                if let Some(key) = input.virtual_keycode {
+                    let button = Button::Keyboard(translate_key(key));
+                    ui.handle_input(&match input.state {
+                        ElementState::Pressed => Input::Press(button),
+                        ElementState::Released => Input::Release(button),
+                    });
+                }
+            }
+            WindowEvent::ReceivedCharacter(c) => {
+                if let Some(input) = text_input(*c) {
+                    ui.handle_input(&input);
+                }
+            }
+            WindowEvent::ModifiersChanged(new_modifiers) => {
+                // Modifier keys can be released without a key event (e.g. when the window
+                // loses focus), so release any that are no longer held
+                for (modifier, key) in [
+                    (ModifiersState::SHIFT, Key::LShift),
+                    (ModifiersState::CTRL, Key::LCtrl),
+                    (ModifiersState::ALT, Key::LAlt),
+                    (ModifiersState::LOGO, Key::LMeta),
+                ] {
+                    if self.modifiers.contains(modifier) && !new_modifiers.contains(modifier) {
+                        ui.handle_input(&Input::Release(Button::Keyboard(key)));
+                    }
+                }
+                self.modifiers = *new_modifiers;
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                // println!("scroll delta{:?}", delta);
+                let scroll = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(x, y) => Motion::Scroll {
+                        x: x * -10.0,
+                        y: y * -10.0,
+                    },
+                    winit::event::MouseScrollDelta::PixelDelta(winit::dpi::PhysicalPosition {
+                        x,
+                        y,
+                    }) => Motion::Scroll {
+                        x: -x as f32,
+                        y: -y as f32,
+                    },
+                };
+                ui.handle_input(&Input::Motion(scroll));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Send `event` to the window with the id `window_id`, closing the window if it was asked to and its app did not veto it. Returns false once every window has been closed.
+fn route_window_event(
+    windows: &mut HashMap<WindowId, OpenWindow>,
+    window_id: WindowId,
+    event: &WindowEvent,
+) -> bool {
+    if let WindowEvent::CloseRequested = event {
+        if windows
+            .get_mut(&window_id)
+            .map_or(true, |window| window.ui.exit())
+        {
+            windows.remove(&window_id);
+        }
+    } else if let Some(window) = windows.get_mut(&window_id) {
+        window.handle_event(event);
+    }
+    !windows.is_empty()
+}
+
+/// The tree that the OS is given before the first draw: an empty window.
+#[cfg(feature = "accesskit")]
+fn initial_accessibility_tree() -> lemna::accesskit::TreeUpdate {
+    use lemna::accesskit::{NodeBuilder, NodeClassSet, NodeId, Role, Tree, TreeUpdate};
+    let root = NodeId(std::num::NonZeroU128::MIN);
+    TreeUpdate {
+        nodes: vec![(
+            root,
+            NodeBuilder::new(Role::Window).build(&mut NodeClassSet::lock_global()),
+        )],
+        tree: Some(Tree::new(root)),
+        focus: None,
+    }
+}
+
+fn translate_theme(theme: Theme) -> ColorScheme {
+    match theme {
+        Theme::Light => ColorScheme::Light,
+        Theme::Dark => ColorScheme::Dark,
+    }
+}
+
+/// Text entered as `c`, unless it is a control character (e.g. backspace or delete), which are handled as key presses instead.
+fn text_input(c: char) -> Option<Input> {
+    if c.is_control() {
+        None
+    } else {
+        Some(Input::Text(c.to_string()))
+    }
+}
 
+// The (back, forward) side buttons are numbered differently on each platform
+#[cfg(target_os = "windows")]
+const BACK_FORWARD_BUTTONS: (u16, u16) = (1, 2);
+#[cfg(target_os = "macos")]
+const BACK_FORWARD_BUTTONS: (u16, u16) = (3, 4);
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const BACK_FORWARD_BUTTONS: (u16, u16) = (8, 9);
+
+fn translate_touch_phase(phase: TouchPhase) -> StylusPhase {
+    match phase {
+        TouchPhase::Started => StylusPhase::Down,
+        TouchPhase::Moved => StylusPhase::Move,
+        TouchPhase::Ended => StylusPhase::Up,
+        TouchPhase::Cancelled => StylusPhase::Cancel,
+    }
+}
+
+fn translate_mouse_button(button: winit::event::MouseButton) -> Option<Button> {
+    match button {
+        winit::event::MouseButton::Left => Some(Button::Mouse(MouseButton::Left)),
+        winit::event::MouseButton::Right => Some(Button::Mouse(MouseButton::Right)),
+        winit::event::MouseButton::Middle => Some(Button::Mouse(MouseButton::Middle)),
+        winit::event::MouseButton::Other(b) if b == BACK_FORWARD_BUTTONS.1 => {
+            Some(Button::Mouse(MouseButton::Aux1))
+        }
+        winit::event::MouseButton::Other(b) if b == BACK_FORWARD_BUTTONS.0 => {
+            Some(Button::Mouse(MouseButton::Aux2))
+        }
+        winit::event::MouseButton::Other(_) => None,
+    }
+}
+
+fn translate_key(key: VirtualKeyCode) -> Key {
+    match key {
+        VirtualKeyCode::Back => Key::Backspace,
+        VirtualKeyCode::Tab => Key::Tab,
+        VirtualKeyCode::Return => Key::Return,
+        VirtualKeyCode::Escape => Key::Escape,
+        VirtualKeyCode::Space => Key::Space,
+        VirtualKeyCode::Period => Key::Period,
+        VirtualKeyCode::Grave => Key::Backquote,
+
+        VirtualKeyCode::Apostrophe => Key::Quote,
+        VirtualKeyCode::Comma => Key::Comma,
+        VirtualKeyCode::Minus => Key::Minus,
+        VirtualKeyCode::Slash => Key::Slash,
+        VirtualKeyCode::Key0 => Key::D0,
+        VirtualKeyCode::Key1 => Key::D1,
+        VirtualKeyCode::Key2 => Key::D2,
+        VirtualKeyCode::Key3 => Key::D3,
+        VirtualKeyCode::Key4 => Key::D4,
+        VirtualKeyCode::Key5 => Key::D5,
+        VirtualKeyCode::Key6 => Key::D6,
+        VirtualKeyCode::Key7 => Key::D7,
+        VirtualKeyCode::Key8 => Key::D8,
+        VirtualKeyCode::Key9 => Key::D9,
+        VirtualKeyCode::Semicolon => Key::Semicolon,
+        VirtualKeyCode::Equals => Key::Equals,
+        VirtualKeyCode::A => Key::A,
+        VirtualKeyCode::B => Key::B,
+        VirtualKeyCode::C => Key::C,
+        VirtualKeyCode::D => Key::D,
+        VirtualKeyCode::E => Key::E,
+        VirtualKeyCode::F => Key::F,
+        VirtualKeyCode::G => Key::G,
+        VirtualKeyCode::H => Key::H,
+        VirtualKeyCode::I => Key::I,
+        VirtualKeyCode::J => Key::J,
+        VirtualKeyCode::K => Key::K,
+        VirtualKeyCode::L => Key::L,
+        VirtualKeyCode::M => Key::M,
+        VirtualKeyCode::N => Key::N,
+        VirtualKeyCode::O => Key::O,
+        VirtualKeyCode::P => Key::P,
+        VirtualKeyCode::Q => Key::Q,
+        VirtualKeyCode::R => Key::R,
+        VirtualKeyCode::S => Key::S,
+        VirtualKeyCode::T => Key::T,
+        VirtualKeyCode::U => Key::U,
+        VirtualKeyCode::V => Key::V,
+        VirtualKeyCode::W => Key::W,
+        VirtualKeyCode::X => Key::X,
+        VirtualKeyCode::Y => Key::Y,
+        VirtualKeyCode::Z => Key::Z,
+        VirtualKeyCode::LBracket => Key::LeftBracket,
+        VirtualKeyCode::Backslash => Key::Backslash,
+        VirtualKeyCode::RBracket => Key::RightBracket,
+
+        VirtualKeyCode::LShift => Key::LShift,
+        VirtualKeyCode::LAlt => Key::LAlt,
+        VirtualKeyCode::LControl => Key::LCtrl,
+        VirtualKeyCode::LWin => Key::LMeta,
+        VirtualKeyCode::RShift => Key::RShift,
+        VirtualKeyCode::RAlt => Key::RAlt,
+        VirtualKeyCode::RControl => Key::RCtrl,
+        VirtualKeyCode::RWin => Key::RMeta,
+
+        VirtualKeyCode::Delete => Key::Delete,
+        VirtualKeyCode::Capital => Key::CapsLock,
+        VirtualKeyCode::End => Key::End,
+        VirtualKeyCode::Home => Key::Home,
+        VirtualKeyCode::Left => Key::Left,
+        VirtualKeyCode::Up => Key::Up,
+        VirtualKeyCode::Right => Key::Right,
+        VirtualKeyCode::Down => Key::Down,
+        VirtualKeyCode::Insert => Key::Insert,
+
+        VirtualKeyCode::Numpad0 => Key::NumPad0,
+        VirtualKeyCode::Numpad1 => Key::NumPad1,
+        VirtualKeyCode::Numpad2 => Key::NumPad2,
+        VirtualKeyCode::Numpad3 => Key::NumPad3,
+        VirtualKeyCode::Numpad4 => Key::NumPad4,
+        VirtualKeyCode::Numpad5 => Key::NumPad5,
+        VirtualKeyCode::Numpad6 => Key::NumPad6,
+        VirtualKeyCode::Numpad7 => Key::NumPad7,
+        VirtualKeyCode::Numpad8 => Key::NumPad8,
+        VirtualKeyCode::Numpad9 => Key::NumPad9,
+
+        VirtualKeyCode::F1 => Key::F1,
+        VirtualKeyCode::F2 => Key::F2,
+        VirtualKeyCode::F3 => Key::F3,
+        VirtualKeyCode::F4 => Key::F4,
+        VirtualKeyCode::F5 => Key::F5,
+        VirtualKeyCode::F6 => Key::F6,
+        VirtualKeyCode::F7 => Key::F7,
+        VirtualKeyCode::F8 => Key::F8,
+        VirtualKeyCode::F9 => Key::F9,
+        VirtualKeyCode::F10 => Key::F10,
+        VirtualKeyCode::F11 => Key::F11,
+        VirtualKeyCode::F12 => Key::F12,
+
+        VirtualKeyCode::PageUp => Key::PageUp,
+        VirtualKeyCode::PageDown => Key::PageDown,
+
+        VirtualKeyCode::NumpadEnter => Key::NumPadEnter,
+        VirtualKeyCode::NumpadMultiply => Key::NumPadMultiply,
+        VirtualKeyCode::NumpadAdd => Key::NumPadPlus,
+        VirtualKeyCode::NumpadSubtract => Key::NumPadMinus,
+        VirtualKeyCode::NumpadDecimal => Key::NumPadPeriod,
+        VirtualKeyCode::NumpadDivide => Key::NumPadDivide,
+
+        _ => Key::Unknown,
+    }
+}
+
+/// winit reports the size of a window in physical pixels.
+fn physical_to_logical(size: PhysicalSize<u32>, scale_factor: f64) -> PixelSize {
+    let size: LogicalSize<u32> = size.to_logical(scale_factor);
+    PixelSize {
+        width: size.width,
+        height: size.height,
+    }
+}
+
+impl lemna::Window for Window {
     fn logical_size(&self) -> PixelSize {
+        physical_to_logical(
+            self.winit_window.inner_size(),
+            self.winit_window.scale_factor(),
+        )
+    }
+
+    fn physical_size(&self) -> PixelSize {
         let size = self.winit_window.inner_size();
         PixelSize {
             width: size.width,
-            height: size.width,
+            height: size.height,
         }
     }
 
-    fn physical_size(&self) -> PixelSize {
-        // let size = self.winit_window.inner_size();
-        self.logical_size() // This should transform to device size
-    }
-
     fn scale_factor(&self) -> f32 {
         winit::window::Window::scale_factor(&self.winit_window) as f32
     }
@@ -120,6 +621,151 @@ impl lemna::Window for Window {
     fn redraw(&self) {
         self.winit_window.request_redraw();
     }
+
+    fn get_from_clipboard(&self) -> Option<Data> {
+        self.get_from_clipboard_as(&[DataKind::String, DataKind::Image])
+    }
+
+    fn clipboard_kinds(&self) -> Vec<DataKind> {
+        let mut clipboard = Clipboard::new().expect("Could get a clipboard");
+        let mut kinds = vec![];
+        if clipboard.get_text().is_ok() {
+            kinds.push(DataKind::String);
+        }
+        if clipboard.get_image().is_ok() {
+            kinds.push(DataKind::Image);
+        }
+        kinds
+    }
+
+    /// HTML and file paths cannot be read from the clipboard.
+    fn get_from_clipboard_as(&self, kinds: &[DataKind]) -> Option<Data> {
+        let mut clipboard = Clipboard::new().expect("Could get a clipboard");
+        kinds.iter().find_map(|kind| match kind {
+            DataKind::String => clipboard.get_text().ok().map(Data::String),
+            DataKind::Image => clipboard.get_image().ok().map(|image| Data::Image {
+                width: image.width as u32,
+                height: image.height as u32,
+                rgba: image.bytes.into_owned(),
+            }),
+            _ => None,
+        })
+    }
+
+    /// File paths cannot be put on the clipboard.
+    fn put_on_clipboard(&self, data: &Data) {
+        let mut clipboard = Clipboard::new().expect("Could get a clipboard");
+        let result = match data {
+            Data::String(s) => clipboard.set_text(s),
+            Data::Html(html) => clipboard.set_html(html, None),
+            Data::Image {
+                width,
+                height,
+                rgba,
+            } => clipboard.set_image(ImageData {
+                width: *width as usize,
+                height: *height as usize,
+                bytes: rgba.into(),
+            }),
+            d => {
+                warn!("Cannot put {:?} data on the clipboard", d.kind());
+                return;
+            }
+        };
+        if let Err(e) = result {
+            warn!(
+                "Failed to put {:?} data on the clipboard: {}",
+                data.kind(),
+                e
+            );
+        }
+    }
+
+    /// Custom cursor images are not supported by winit 0.28, so their fallback is shown.
+    fn set_cursor(&self, cursor: Cursor) {
+        if *self.cursor.read().unwrap() == cursor {
+            return;
+        }
+        let icon = match cursor.named() {
+            Cursor::Hidden => None,
+            Cursor::Text => Some(CursorIcon::Text),
+            Cursor::PointingHand => Some(CursorIcon::Hand),
+            Cursor::Hand => Some(CursorIcon::Grab),
+            Cursor::HandGrabbing => Some(CursorIcon::Grabbing),
+            Cursor::NoEntry => Some(CursorIcon::NotAllowed),
+            Cursor::Cross => Some(CursorIcon::Crosshair),
+            Cursor::Move => Some(CursorIcon::Move),
+            Cursor::SizeNWSE => Some(CursorIcon::NwseResize),
+            Cursor::SizeNS => Some(CursorIcon::NsResize),
+            Cursor::SizeNESW => Some(CursorIcon::NeswResize),
+            Cursor::SizeWE => Some(CursorIcon::EwResize),
+            _ => Some(CursorIcon::Default),
+        };
+        if let Some(icon) = icon {
+            self.winit_window.set_cursor_visible(true);
+            self.winit_window.set_cursor_icon(icon);
+        } else {
+            self.winit_window.set_cursor_visible(false);
+        }
+        *self.cursor.write().unwrap() = cursor;
+    }
+
+    fn unset_cursor(&self) {
+        self.set_cursor(Cursor::Arrow);
+    }
+
+    #[cfg(feature = "accesskit")]
+    fn accessibility_update(&self, update: lemna::accesskit::TreeUpdate) {
+        self.accessibility.update(update);
+    }
+
+    fn set_title(&self, title: &str) {
+        self.winit_window.set_title(title);
+    }
+
+    fn request_resize(&self, size: PixelSize) {
+        self.winit_window
+            .set_inner_size(LogicalSize::new(size.width, size.height));
+    }
+
+    fn set_min_size(&self, size: Option<PixelSize>) {
+        self.winit_window
+            .set_min_inner_size(size.map(|s| LogicalSize::new(s.width, s.height)));
+    }
+
+    fn set_max_size(&self, size: Option<PixelSize>) {
+        self.winit_window
+            .set_max_inner_size(size.map(|s| LogicalSize::new(s.width, s.height)));
+    }
+
+    fn set_fullscreen(&self, fullscreen: bool) {
+        self.winit_window
+            .set_fullscreen(fullscreen.then_some(Fullscreen::Borderless(None)));
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.winit_window.fullscreen().is_some()
+    }
+
+    fn set_maximized(&self, maximized: bool) {
+        self.winit_window.set_maximized(maximized);
+    }
+
+    fn set_minimized(&self, minimized: bool) {
+        self.winit_window.set_minimized(minimized);
+    }
+
+    fn color_scheme(&self) -> Option<ColorScheme> {
+        self.winit_window.theme().map(translate_theme)
+    }
+
+    fn set_always_on_top(&self, always_on_top: bool) {
+        self.winit_window.set_window_level(if always_on_top {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        });
+    }
 }
 
 unsafe impl HasRawWindowHandle for Window {
@@ -133,3 +779,132 @@ unsafe impl HasRawDisplayHandle for Window {
         self.winit_window.raw_display_handle()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct RecordingHandler(Arc<Mutex<Vec<Input>>>);
+
+    impl WindowHandler for RecordingHandler {
+        fn handle_input(&mut self, input: &Input) {
+            self.0.lock().unwrap().push(input.clone());
+        }
+
+        fn scale_factor(&self) -> f64 {
+            1.0
+        }
+
+        fn draw(&mut self) {}
+
+        fn render(&mut self) {}
+
+        fn exit(&mut self) -> bool {
+            true
+        }
+
+        #[cfg(feature = "accesskit")]
+        fn on_accessibility_event(&self, _event: &WindowEvent) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_window_routing() {
+        let (first, second) = (WindowId::from(1), WindowId::from(2));
+        let (first_inputs, second_inputs) = (Arc::default(), Arc::default());
+        let mut windows = HashMap::new();
+        windows.insert(
+            first,
+            OpenWindow::new(Box::new(RecordingHandler(Arc::clone(&first_inputs)))),
+        );
+        windows.insert(
+            second,
+            OpenWindow::new(Box::new(RecordingHandler(Arc::clone(&second_inputs)))),
+        );
+
+        assert!(route_window_event(
+            &mut windows,
+            second,
+            &WindowEvent::ReceivedCharacter('a')
+        ));
+        assert!(first_inputs.lock().unwrap().is_empty());
+        assert_eq!(
+            *second_inputs.lock().unwrap(),
+            vec![Input::Text("a".to_string())]
+        );
+
+        // Closing one window leaves the other running
+        assert!(route_window_event(
+            &mut windows,
+            first,
+            &WindowEvent::CloseRequested
+        ));
+        assert!(!windows.contains_key(&first));
+        assert!(route_window_event(
+            &mut windows,
+            second,
+            &WindowEvent::Focused(true)
+        ));
+        assert_eq!(second_inputs.lock().unwrap().len(), 2);
+        assert!(!route_window_event(
+            &mut windows,
+            second,
+            &WindowEvent::CloseRequested
+        ));
+    }
+
+    #[test]
+    fn test_keyboard_translation() {
+        assert_eq!(translate_key(VirtualKeyCode::Back), Key::Backspace);
+        assert_eq!(translate_key(VirtualKeyCode::Left), Key::Left);
+        assert_eq!(translate_key(VirtualKeyCode::LControl), Key::LCtrl);
+        assert_eq!(translate_key(VirtualKeyCode::Mail), Key::Unknown);
+
+        assert_eq!(text_input('a'), Some(Input::Text("a".to_string())));
+        assert_eq!(text_input('é'), Some(Input::Text("é".to_string())));
+        // Backspace and delete
+        assert_eq!(text_input('\u{8}'), None);
+        assert_eq!(text_input('\u{7f}'), None);
+    }
+
+    #[test]
+    fn test_mouse_buttons() {
+        assert_eq!(
+            translate_mouse_button(winit::event::MouseButton::Right),
+            Some(Button::Mouse(MouseButton::Right))
+        );
+        assert_eq!(
+            translate_mouse_button(winit::event::MouseButton::Middle),
+            Some(Button::Mouse(MouseButton::Middle))
+        );
+        assert_eq!(
+            translate_mouse_button(winit::event::MouseButton::Other(BACK_FORWARD_BUTTONS.0)),
+            Some(Button::Mouse(MouseButton::Aux2))
+        );
+        assert_eq!(
+            translate_mouse_button(winit::event::MouseButton::Other(42)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sizes() {
+        let physical = PhysicalSize::new(1600, 900);
+        assert_eq!(
+            physical_to_logical(physical, 2.0),
+            PixelSize {
+                width: 800,
+                height: 450
+            }
+        );
+        assert_eq!(
+            physical_to_logical(physical, 1.0),
+            PixelSize {
+                width: 1600,
+                height: 900
+            }
+        );
+    }
+}
@@ -1,17 +1,12 @@
 use lemna::{self, layout::*, widgets, *};
 use ttf_noto_sans;
-use wx_rs::{Menu, MenuBar, MenuEntry};
 
 #[derive(Debug)]
 pub struct AppState {
-    menu_bar: MenuBar<HelloMenu>,
     radio_selection: Vec<usize>,
     toggle_state: bool,
 }
 
-unsafe impl Send for AppState {}
-unsafe impl Sync for AppState {}
-
 #[component(State = "AppState")]
 #[derive(Debug, Default)]
 pub struct App {}
@@ -49,24 +44,43 @@ enum HelloMenu {
     Paste,
 }
 
+impl HelloMenu {
+    const ALL: [HelloMenu; 5] = [
+        HelloMenu::Open,
+        HelloMenu::Close,
+        HelloMenu::Copy,
+        HelloMenu::Cut,
+        HelloMenu::Paste,
+    ];
+
+    fn item(self, label: &str) -> MenuItem {
+        MenuItem::new(self as i32, label)
+    }
+
+    fn from_id(id: i32) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| *m as i32 == id)
+    }
+}
+
 #[state_component_impl(AppState)]
 impl lemna::Component for App {
     fn init(&mut self) {
-        let mut menu_bar = MenuBar::new();
-        menu_bar.append(
-            Menu::new(Some("&File".to_string()))
-                .push_entry(MenuEntry::new(HelloMenu::Open, "&Open".to_string()))
-                .push_entry(MenuEntry::new(HelloMenu::Close, "&Close".to_string())),
-        );
-        menu_bar.append(
-            Menu::new(Some("&Edit".to_string()))
-                .push_entry(MenuEntry::new(HelloMenu::Copy, "&Copy".to_string()))
-                .push_entry(MenuEntry::new(HelloMenu::Cut, "&Cut".to_string()))
-                .push_entry(MenuEntry::new(HelloMenu::Paste, "&Paste".to_string())),
-        );
-        menu_bar.show();
+        let menu = MenuDescription::new()
+            .menu(
+                Menu::new("&File")
+                    .item(HelloMenu::Open.item("&Open").accelerator("Ctrl+O"))
+                    .item(HelloMenu::Close.item("&Close")),
+            )
+            .menu(
+                Menu::new("&Edit")
+                    .item(HelloMenu::Copy.item("&Copy").accelerator("Ctrl+C"))
+                    .item(HelloMenu::Cut.item("&Cut").accelerator("Ctrl+X"))
+                    .item(HelloMenu::Paste.item("&Paste").accelerator("Ctrl+V")),
+            );
+        if let Some(window) = lemna::current_window() {
+            window.set_menu(&menu);
+        }
         self.state = Some(AppState {
-            menu_bar,
             radio_selection: vec![],
             toggle_state: false,
         })
@@ -194,13 +208,13 @@ impl lemna::Component for App {
     }
 
     fn on_menu_select(&mut self, event: &mut Event<event::MenuSelect>) {
-        if let Some(menu) = self
-            .state_ref()
-            .menu_bar
-            .get_entry_from_event_id(event.input.0)
-        {
+        if let Some(menu) = HelloMenu::from_id(event.input.0) {
             event.emit(match menu {
-                HelloMenu::Open => msg!(HelloEvent::Menu { selection: menu }),
+                HelloMenu::Open => {
+                    let path = widgets::FileSelector::new("Open a file".to_string()).select();
+                    println!("Chose to open {:?}", path);
+                    msg!(HelloEvent::Menu { selection: menu })
+                }
                 HelloMenu::Close => msg!(HelloEvent::Menu { selection: menu }),
                 HelloMenu::Copy => msg!(widgets::TextBoxAction::Copy),
                 HelloMenu::Cut => msg!(widgets::TextBoxAction::Cut),
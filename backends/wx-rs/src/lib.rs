@@ -3,16 +3,20 @@ use std::cell::UnsafeCell;
 use std::marker::PhantomData;
 use std::mem;
 use std::os::raw::c_void;
+use std::sync::RwLock;
 
 use lemna::input::{Button, Input, Key, Motion, MouseButton};
-use lemna::{Component, Data, PixelSize, UI};
+use lemna::{Component, Cursor, Data, MenuDescription, PixelSize, UI};
+use log::warn;
 use raw_window_handle::{
     HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
 };
-use wx_rs::{CursorType, EventType, WheelAxis};
+use wx_rs::{CursorType, EventType, MenuBar, MenuEntry, WheelAxis};
 
 pub struct Window<A> {
     wx_rs_window: wx_rs::Window,
+    // Maps wx's menu event ids to the ids of the MenuDescription
+    menu_bar: RwLock<Option<MenuBar<i32>>>,
     phantom_app: PhantomData<A>,
 }
 unsafe impl<A> Send for Window<A> {}
@@ -39,6 +43,7 @@ where
         wx_rs::init_app(title, width, height);
         let mut ui: UI<Window<A>, A> = UI::new(Window::<A> {
             wx_rs_window: wx_rs::Window::new(),
+            menu_bar: RwLock::new(None),
             phantom_app: PhantomData,
         });
         for (name, data) in fonts.drain(..) {
@@ -63,12 +68,28 @@ where
 
     extern "C" fn handle_event(event: *const c_void) {
         let ui = ui().downcast_mut::<UI<Window<A>, A>>().unwrap();
-        for input in event_to_input(event).iter() {
+        for input in event_to_input(event).iter_mut() {
+            if let Input::Menu(id) = input {
+                // Menus set with `set_menu` report the app's ids. Other menus (e.g. context menus) keep wx's
+                if let Some(app_id) = ui.window.read().unwrap().menu_item_id(*id) {
+                    *id = app_id;
+                }
+            }
             ui.handle_input(input);
         }
     }
 }
 
+impl<A> Window<A> {
+    fn menu_item_id(&self, event_id: i32) -> Option<i32> {
+        self.menu_bar
+            .read()
+            .unwrap()
+            .as_ref()
+            .and_then(|menu_bar| menu_bar.get_entry_from_event_id(event_id))
+    }
+}
+
 impl<A> lemna::Window for Window<A>
 where
     A: 'static,
@@ -85,28 +106,39 @@ where
         wx_rs::get_scale_factor()
     }
 
+    /// Only text and file paths can be put on the clipboard.
     fn put_on_clipboard(&self, data: &Data) {
-        unsafe { wx_rs::put_on_clipboard(mem::transmute(data)) }
+        let data = match data {
+            Data::String(s) => wx_rs::Data::String(s.clone()),
+            Data::Filepath(p) => wx_rs::Data::Filepath(p.clone()),
+            d => {
+                warn!("Cannot put {:?} data on the clipboard", d.kind());
+                return;
+            }
+        };
+        wx_rs::put_on_clipboard(&data)
     }
 
     fn get_from_clipboard(&self) -> Option<Data> {
-        unsafe { mem::transmute(wx_rs::get_from_clipboard()) }
+        wx_rs::get_from_clipboard().map(|d| match d {
+            wx_rs::Data::String(s) => Data::String(s),
+            wx_rs::Data::Filepath(p) => Data::Filepath(p),
+        })
     }
 
-    fn set_cursor(&self, cursor_type: &str) {
-        let ct = match cursor_type {
-            "Arrow" => CursorType::Arrow,
-            "None" => CursorType::None,
-            "Ibeam" => CursorType::Ibeam,
-            "Hand" => CursorType::Hand,
-            "Pencil" => CursorType::Pencil,
-            "NoEntry" => CursorType::NoEntry,
-            "Cross" => CursorType::Cross,
-            "Size" => CursorType::Size,
-            "SizeNWSE" => CursorType::SizeNWSE,
-            "SizeNS" => CursorType::SizeNS,
-            "SizeNESW" => CursorType::SizeNESW,
-            "SizeWE" => CursorType::SizeWE,
+    /// Custom cursor images are not supported, so their fallback is shown.
+    fn set_cursor(&self, cursor: Cursor) {
+        let ct = match cursor.named() {
+            Cursor::Hidden => CursorType::None,
+            Cursor::Text => CursorType::Ibeam,
+            Cursor::PointingHand | Cursor::Hand | Cursor::HandGrabbing => CursorType::Hand,
+            Cursor::NoEntry => CursorType::NoEntry,
+            Cursor::Cross => CursorType::Cross,
+            Cursor::Move => CursorType::Size,
+            Cursor::SizeNWSE => CursorType::SizeNWSE,
+            Cursor::SizeNS => CursorType::SizeNS,
+            Cursor::SizeNESW => CursorType::SizeNESW,
+            Cursor::SizeWE => CursorType::SizeWE,
             _ => CursorType::Arrow,
         };
         wx_rs::set_cursor(ct);
@@ -115,6 +147,27 @@ where
     fn unset_cursor(&self) {
         wx_rs::set_cursor(CursorType::Arrow);
     }
+
+    /// Accelerators are shown next to their item's label. Items cannot be disabled or checked, so disabled items are left out, and checked items are labeled with a check mark.
+    fn set_menu(&self, menu: &MenuDescription) {
+        let mut menu_bar = MenuBar::new();
+        for m in menu.menus.iter() {
+            let mut wx_menu = wx_rs::Menu::new(Some(m.label.clone()));
+            for item in m.items.iter().filter(|i| i.enabled) {
+                let mut label = item.label.clone();
+                if item.checked == Some(true) {
+                    label = format!("\u{2713} {}", label);
+                }
+                if let Some(accelerator) = &item.accelerator {
+                    label = format!("{}\t{}", label, accelerator);
+                }
+                wx_menu = wx_menu.push_entry(MenuEntry::new(item.id, label));
+            }
+            menu_bar.append(wx_menu);
+        }
+        menu_bar.show();
+        *self.menu_bar.write().unwrap() = Some(menu_bar);
+    }
 }
 
 unsafe impl<A> HasRawWindowHandle for Window<A> {
@@ -1,19 +1,34 @@
 extern crate proc_macro;
 
-use global_counter::primitive::exact::CounterU64;
 use proc_macro::{Group, TokenStream, TokenTree};
-use quote::quote;
+use quote::{quote, quote_spanned};
 use std::iter::FromIterator;
+use syn::spanned::Spanned;
 use syn::{self, parse_macro_input, Lit, Meta, MetaNameValue, NestedMeta};
 
-static ID_COUNTER: CounterU64 = CounterU64::new(0);
-
 /// TODO
 ///
 /// Assumes the `style` module is in scope, when using the `Styled` attribute.
 ///
 /// e.g. `#[component(State = "ButtonState", Styled)]`
 /// e.g. `#[component(State = "StateType", Styled = "ComponentNameOverride")]`
+///
+/// `Composed` components are stateless wrappers around other Nodes. They implement `Component` with a `view` that returns their `fn body(&self) -> Node` method, so they can't have a `State` or implement `Component` themselves. Fields marked with `#[builder]` get a builder-style setter of the same name:
+///
+/// ```ignore
+/// #[component(Composed)]
+/// #[derive(Debug, Default)]
+/// pub struct Labeled {
+///     #[builder]
+///     pub label: String,
+/// }
+///
+/// impl Labeled {
+///     fn body(&self) -> Node {
+///         node!(widgets::Text::new(txt!(self.label.clone())))
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
     let attr = parse_macro_input!(attr as syn::AttributeArgs);
@@ -32,6 +47,13 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
             false
         }
     });
+    let composed = attr.iter().find_map(|v| {
+        if let NestedMeta::Meta(m) = v {
+            (m.path().segments.last().unwrap().ident == "Composed").then(|| m.path().span())
+        } else {
+            None
+        }
+    });
     let state_type = attr
         .iter()
         .find_map(|v| {
@@ -78,6 +100,35 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
         quote! { style::Styled }
     };
 
+    let (component_ref, node_ref) = if is_internal {
+        (quote! { crate::Component }, quote! { crate::Node })
+    } else {
+        (quote! { lemna::Component }, quote! { lemna::Node })
+    };
+
+    if let (Some(span), Some(_)) = (composed, &state_type) {
+        return syn::Error::new(
+            span,
+            "`Composed` components are stateless, so they can't have a `State`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // Pull out the fields that get builder methods, removing their `builder` attributes
+    let mut item = parse_macro_input!(input as syn::ItemStruct);
+    let mut builder_fields = vec![];
+    for field in item.fields.iter_mut() {
+        let len = field.attrs.len();
+        field.attrs.retain(|a| !a.path.is_ident("builder"));
+        if field.attrs.len() != len {
+            if let Some(ident) = &field.ident {
+                builder_fields.push((ident.clone(), field.ty.clone()));
+            }
+        }
+    }
+    let input = TokenStream::from(quote! { #item });
+
     // Add in fields
     let mut i: Vec<_> = input.clone().into_iter().collect();
     if let Some(TokenTree::Group(g)) = i.last() {
@@ -93,7 +144,7 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
         if is_styled {
             let styled_fields = quote! {
-                class: Option<&'static str>,
+                classes: Vec<&'static str>,
                 style_overrides: #style_override_ref
             };
 
@@ -139,11 +190,11 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
                 fn name() -> &'static str {
                     #component_name
                 }
-                fn class(&self) -> Option<&'static str> {
-                    self.class
+                fn classes(&self) -> &[&'static str] {
+                    &self.classes
                 }
-                fn class_mut(&mut self) -> &mut Option<&'static str> {
-                    &mut self.class
+                fn classes_mut(&mut self) -> &mut Vec<&'static str> {
+                    &mut self.classes
                 }
                 fn style_overrides(&self) -> & #style_override_ref {
                     &self.style_overrides
@@ -156,6 +207,41 @@ pub fn component(attr: TokenStream, input: TokenStream) -> TokenStream {
         struct_def.extend(TokenStream::from(expanded));
     }
 
+    // Composed impl
+    if let Some(span) = composed {
+        let view = quote_spanned! {span=>
+            fn view(&self) -> Option<#node_ref> {
+                Some(Self::body(self))
+            }
+        };
+        let expanded = quote!(
+            impl #impl_generics #component_ref for #struct_name #ty_generics #where_clause {
+                #view
+            }
+        );
+        struct_def.extend(TokenStream::from(expanded));
+    }
+
+    // Builder methods
+    if !builder_fields.is_empty() {
+        let setters = builder_fields.iter().map(|(ident, ty)| {
+            let doc = format!("Set the `{}` of this component.", ident);
+            quote! {
+                #[doc = #doc]
+                pub fn #ident(mut self, #ident: #ty) -> Self {
+                    self.#ident = #ident;
+                    self
+                }
+            }
+        });
+        let expanded = quote!(
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #(#setters)*
+            }
+        );
+        struct_def.extend(TokenStream::from(expanded));
+    }
+
     struct_def
 }
 
@@ -185,6 +271,10 @@ pub fn state_component_impl(attr: TokenStream, input: TokenStream) -> TokenStrea
             self.dirty = false;
             d
         }
+
+        fn as_any(&self) -> Option<&dyn std::any::Any> {
+            Some(self)
+        }
     };
 
     let mut i: Vec<_> = input.into_iter().collect();
@@ -199,8 +289,34 @@ pub fn state_component_impl(attr: TokenStream, input: TokenStream) -> TokenStrea
 }
 
 /// Used by the `node` macro, to generate node keys.
+///
+/// The id is a `u64` hash of the module path (which starts with the crate name), file, line and column of the call site, along with any tokens passed as a discriminator, e.g. `static_id!(1)`. When called from another macro, like `node`, the call site is that of the outermost macro. So:
+/// - The same call site always gets the same id, across builds and incremental compilations, as long as it does not move.
+/// - Call sites in different places, modules or crates get different ids (barring hash collisions), even if they were expanded by separate compilations.
+/// - Calls that share a call site, e.g. several calls in one `macro_rules` expansion, get the same id unless they are given different discriminators.
+/// - Moving a call site, e.g. by adding a line above it, changes its id. Ids are only compared within a running app, so this just means that state is not carried over from a build where the call site was elsewhere.
 #[proc_macro]
-pub fn static_id(_item: TokenStream) -> TokenStream {
-    let id = ID_COUNTER.inc();
-    quote! { #id }.into()
+pub fn static_id(item: TokenStream) -> TokenStream {
+    let discriminator = item.to_string();
+    // The hash is computed at compile time, from the location that the compiler gives the call site
+    quote! {
+        {
+            const ID: u64 = {
+                let bytes = concat!(
+                    module_path!(), "|", file!(), "|", line!(), "|", column!(), "|", #discriminator
+                ).as_bytes();
+                // FNV-1a
+                let mut hash: u64 = 0xcbf29ce484222325;
+                let mut i = 0;
+                while i < bytes.len() {
+                    hash ^= bytes[i] as u64;
+                    hash = hash.wrapping_mul(0x100000001b3);
+                    i += 1;
+                }
+                hash
+            };
+            ID
+        }
+    }
+    .into()
 }
@@ -0,0 +1,42 @@
+// The same file is compiled as two modules, so their ids are expanded at the same file, line and column
+#[path = "static_id/call_site.rs"]
+mod first;
+#[path = "static_id/call_site.rs"]
+mod second;
+
+fn id() -> u64 {
+    lemna_macros::static_id!()
+}
+
+// Both of these are expanded at the call site of `sibling_ids`, so only their discriminators tell them apart
+macro_rules! sibling_ids {
+    () => {
+        [lemna_macros::static_id!(0), lemna_macros::static_id!(1)]
+    };
+}
+
+macro_rules! shared_ids {
+    () => {
+        [lemna_macros::static_id!(), lemna_macros::static_id!()]
+    };
+}
+
+#[test]
+fn test_stable_ids() {
+    assert_eq!(id(), id());
+    assert_eq!(first::id(), first::id());
+}
+
+#[test]
+fn test_distinct_ids() {
+    assert_ne!(first::id(), second::id());
+    assert_ne!(first::id(), id());
+}
+
+#[test]
+fn test_macro_rules_ids() {
+    let ids = sibling_ids!();
+    assert_ne!(ids[0], ids[1]);
+    let ids = shared_ids!();
+    assert_eq!(ids[0], ids[1]);
+}
@@ -0,0 +1,3 @@
+pub fn id() -> u64 {
+    lemna_macros::static_id!()
+}
@@ -0,0 +1,5 @@
+#[lemna::component(Composed, State = "Counter")]
+#[derive(Debug)]
+pub struct Counted {}
+
+fn main() {}
@@ -0,0 +1,5 @@
+#[lemna::component(Composed)]
+#[derive(Debug)]
+pub struct NoBody {}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use lemna::{widgets::*, *};
+
+#[component(Composed)]
+#[derive(Debug, Default)]
+pub struct Labeled {
+    #[builder]
+    pub label: String,
+    #[builder]
+    pub width: f32,
+}
+
+impl Labeled {
+    fn body(&self) -> Node {
+        node!(
+            Text::new(txt!(self.label.clone())),
+            lay!(size: size!(self.width, Auto))
+        )
+    }
+}
+
+fn main() {
+    let labeled = Labeled::default().label("Name".to_string()).width(100.0);
+    assert_eq!(labeled.label, "Name");
+    assert!(labeled.view().is_some());
+}
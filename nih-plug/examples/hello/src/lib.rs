@@ -1,7 +1,30 @@
 use lemna::{self, widgets, *};
 use lemna_nih_plug::nih_plug;
 use nih_plug::prelude::*;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
+
+/// A brushed metal texture, tiled over the background of the plugin.
+fn brushed_metal() -> &'static [u8] {
+    static TEXTURE: OnceLock<Vec<u8>> = OnceLock::new();
+    TEXTURE.get_or_init(|| {
+        const SIZE: usize = 64;
+        let mut seed: u32 = 1;
+        let mut random = move || {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            (seed >> 24) as u8
+        };
+        // Each row gets a streak of its own, with a little noise along it
+        let streaks: Vec<u8> = (0..SIZE).map(|_| random() / 8).collect();
+        let mut data = Vec::with_capacity(SIZE * SIZE * 4);
+        for streak in streaks {
+            for _ in 0..SIZE {
+                let v = 160 + streak + random() / 16;
+                data.extend([v, v, v, 255]);
+            }
+        }
+        data
+    })
+}
 
 #[derive(Debug, Default)]
 pub struct App {}
@@ -10,7 +33,7 @@ impl lemna::Component for App {
     fn view(&self) -> Option<Node> {
         Some(
             node!(
-                widgets::Div::new(),
+                widgets::Div::new().bg_image(brushed_metal(), PixelSize::new(64, 64)),
                 [size_pct: [100.0], wrap: true,
                      padding: [10.0],
                      axis_alignment: Center, cross_alignment: Center]
@@ -37,7 +60,7 @@ impl lemna::Component for App {
 
     fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
         if let Some(w) = crate::current_window() {
-            w.set_cursor("Cross");
+            w.set_cursor(lemna::Cursor::Cross);
         }
     }
 }
@@ -1,11 +1,15 @@
 use lemna::{self, widgets, *};
-use lemna_nih_plug::nih_plug;
+use lemna_nih_plug::{nih_plug, LemnaState, ParamBinding, ParamChange, ParamSlider};
 use nih_plug::prelude::*;
 use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct AppState {
     params: Arc<AppParams>,
+    // Set once the editor has a GuiContext
+    bindings: Vec<ParamBinding>,
+    // The ID of the last parameter that the host changed
+    last_change: Option<String>,
 }
 
 #[component(State = "AppState")]
@@ -17,18 +21,49 @@ impl lemna::Component for App {
     fn init(&mut self) {
         self.state = Some(AppState {
             params: Default::default(),
+            bindings: vec![],
+            last_change: None,
         })
     }
 
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        if let Some(change) = message.downcast_ref::<ParamChange>() {
+            self.state_mut().last_change = change.id().map(|id| id.to_string());
+        }
+        vec![]
+    }
+
     fn view(&self) -> Option<Node> {
-        Some(node!(
-            widgets::Div::new().bg(Color::rgb(
-                self.state_ref().params.red.value(),
-                self.state_ref().params.green.value(),
-                self.state_ref().params.blue.value()
-            )),
-            lay!(size: size_pct!(100.0))
-        ))
+        let mut sliders = node!(
+            widgets::Div::new(),
+            lay!(direction: Column, padding: rect!(20.0))
+        );
+        for (i, binding) in self.state_ref().bindings.iter().enumerate() {
+            sliders = sliders.push(node!(
+                ParamSlider::new(binding.clone()),
+                lay!(size: size!(200.0, 20.0), margin: rect!(5.0)),
+                i as u64
+            ));
+        }
+        if let Some(name) = &self.state_ref().last_change {
+            sliders = sliders.push(node!(
+                widgets::Text::new(txt!(format!("{} changed", name))),
+                lay!(margin: rect!(5.0)),
+                100
+            ));
+        }
+
+        Some(
+            node!(
+                widgets::Div::new().bg(Color::rgb(
+                    self.state_ref().params.red.value(),
+                    self.state_ref().params.green.value(),
+                    self.state_ref().params.blue.value()
+                )),
+                lay!(size: size_pct!(100.0))
+            )
+            .push(sliders),
+        )
     }
 }
 
@@ -39,6 +74,8 @@ pub struct ParamsPlugin {
 
 #[derive(Params, Debug)]
 struct AppParams {
+    #[persist = "editor-state"]
+    editor_state: Arc<LemnaState>,
     #[id = "red"]
     pub red: FloatParam,
     #[id = "green"]
@@ -50,6 +87,7 @@ struct AppParams {
 impl Default for AppParams {
     fn default() -> Self {
         Self {
+            editor_state: LemnaState::from_size(400, 300),
             red: FloatParam::new("Red", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 }),
             green: FloatParam::new("Green", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 }),
             blue: FloatParam::new("Blue", 0.5, FloatRange::Linear { min: 0.0, max: 1.0 }),
@@ -87,12 +125,24 @@ impl Plugin for ParamsPlugin {
 
     fn editor(&self, _async_executor: AsyncExecutor<Self>) -> Option<Box<dyn Editor>> {
         let app_params = self.params.clone();
-        lemna_nih_plug::create_lemna_editor::<App, _, _>(
+        lemna_nih_plug::create_lemna_editor_with_state::<App, _, _>(
+            self.params.editor_state.clone(),
             lemna_nih_plug::WindowOptions::new("Hello Lemna Params", (400, 300)),
-            move |_ctx, ui| {
-                ui.state_mut::<AppState, _>(|s| s.params = app_params.clone());
+            move |ctx, ui| {
+                ui.state_mut::<AppState, _>(|s| {
+                    s.bindings = vec![
+                        ParamBinding::new(&app_params.red, ctx.clone()),
+                        ParamBinding::new(&app_params.green, ctx.clone()),
+                        ParamBinding::new(&app_params.blue, ctx.clone()),
+                    ];
+                    s.params = app_params.clone();
+                });
+            },
+            // Only the changes to the color params matter to the app
+            |change| match change.id() {
+                Some("red" | "green" | "blue") | None => vec![msg!(change)],
+                _ => vec![],
             },
-            || vec![msg!(())], // Trigger an update, the message doesn't matter
         )
     }
 }
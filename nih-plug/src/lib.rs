@@ -1,7 +1,10 @@
+use crossbeam::atomic::AtomicCell;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use lemna::UI;
 use lemna_baseview::{self, Message, ParentMessage, Window};
+use nih_plug::params::persist::PersistentField;
 use nih_plug::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::{
     marker::PhantomData,
     sync::{Arc, RwLock},
@@ -10,19 +13,81 @@ use std::{
 pub extern crate nih_plug;
 pub use lemna_baseview::WindowOptions;
 
+mod param_binding;
+pub use param_binding::*;
+
+/// The logical size of a lemna editor. Store an `Arc<LemnaState>` in a `#[persist = "editor-state"]` field of your plugin's `Params` and pass it to [`create_lemna_editor_with_state`] to have the size that the user chose saved with the plugin's state.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LemnaState {
+    #[serde(with = "nih_plug::params::persist::serialize_atomic_cell")]
+    size: AtomicCell<(u32, u32)>,
+}
+
+impl LemnaState {
+    pub fn from_size(width: u32, height: u32) -> Arc<Self> {
+        Arc::new(Self {
+            size: AtomicCell::new((width, height)),
+        })
+    }
+
+    /// The current logical size of the editor.
+    pub fn size(&self) -> (u32, u32) {
+        self.size.load()
+    }
+}
+
+impl<'a> PersistentField<'a, LemnaState> for Arc<LemnaState> {
+    fn set(&self, new_value: LemnaState) {
+        self.size.store(new_value.size.load());
+    }
+
+    fn map<F, R>(&self, f: F) -> R
+    where
+        F: Fn(&LemnaState) -> R,
+    {
+        f(self)
+    }
+}
+
+/// A change to one or more of the plugin's parameters, as reported by the host. Passed to the `on_param_change` callback of [`create_lemna_editor_with_state`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamChange {
+    /// The parameter with the given ID was set to a new normalized value.
+    Changed { id: String, normalized: f32 },
+    /// The modulation offset of the parameter with the given ID changed.
+    Modulated { id: String, modulation_offset: f32 },
+    /// Any number of parameters may have changed, e.g. because a preset was loaded. Also sent when the editor opens.
+    All,
+}
+
+impl ParamChange {
+    /// The ID of the changed parameter, or `None` for [`ParamChange::All`].
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::Changed { id, .. } | Self::Modulated { id, .. } => Some(id),
+            Self::All => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 struct LemnaEditor<A: lemna::Component + Default + Send + Sync> {
     window_options: WindowOptions,
     phantom_app: PhantomData<A>,
     scale_factor: Arc<RwLock<Option<f32>>>,
+    // The logical size, which can be changed with `lemna::Window::request_resize` if the editor is resizable
+    state: Arc<LemnaState>,
     // Called when initializing the app
     build: Arc<dyn Fn(Arc<dyn GuiContext>, &mut UI<Window, A>) + 'static + Send + Sync>,
-    on_param_change: Arc<dyn Fn() -> Vec<Message> + 'static + Send + Sync>,
+    on_param_change: Arc<dyn Fn(ParamChange) -> Vec<Message> + 'static + Send + Sync>,
     // Used to communicate with the baseview WindowHandler
     sender: Sender<ParentMessage>,
     receiver: Receiver<ParentMessage>,
 }
 
+/// Create an editor with the size given by `options`. If `options` is [`resizable`][WindowOptions#method.resizable] (the default), the app can resize the editor with [`lemna::Window::request_resize`], which the host may refuse. Use [`create_lemna_editor_with_state`] to have the size persisted.
+///
+/// `on_param_change` is called whenever the host changes any parameter, and the messages it returns are sent to the app. Use [`create_lemna_editor_with_state`] to be told which parameter changed.
 pub fn create_lemna_editor<A, B, P>(
     options: WindowOptions,
     build: B,
@@ -32,10 +97,31 @@ where
     A: 'static + lemna::Component + Default + Send + Sync,
     B: Fn(Arc<dyn GuiContext>, &mut UI<Window, A>) + 'static + Send + Sync,
     P: Fn() -> Vec<Message> + 'static + Send + Sync,
+{
+    let state = LemnaState::from_size(options.width, options.height);
+    create_lemna_editor_with_state(state, options, build, move |_: ParamChange| {
+        on_param_change()
+    })
+}
+
+/// Like [`create_lemna_editor`], but the editor's size is read from and stored in `state`, instead of the size given by `options`.
+///
+/// `on_param_change` is given the [`ParamChange`] reported by the host, so that the app only needs to update what depends on the changed parameter. Changes reported together, e.g. when a preset is loaded, are delivered as a single [`ParamChange::All`].
+pub fn create_lemna_editor_with_state<A, B, P>(
+    state: Arc<LemnaState>,
+    options: WindowOptions,
+    build: B,
+    on_param_change: P,
+) -> Option<Box<dyn Editor>>
+where
+    A: 'static + lemna::Component + Default + Send + Sync,
+    B: Fn(Arc<dyn GuiContext>, &mut UI<Window, A>) + 'static + Send + Sync,
+    P: Fn(ParamChange) -> Vec<Message> + 'static + Send + Sync,
 {
     let (sender, receiver) = unbounded::<ParentMessage>();
 
     Some(Box::new(LemnaEditor::<A> {
+        state,
         window_options: options,
         scale_factor: Arc::new(RwLock::new(None)),
         phantom_app: PhantomData,
@@ -46,6 +132,17 @@ where
     }))
 }
 
+impl<A> LemnaEditor<A>
+where
+    A: 'static + lemna::Component + Default + Send + Sync,
+{
+    fn send_param_change(&self, change: ParamChange) {
+        for m in (self.on_param_change)(change).drain(..) {
+            self.sender.send(ParentMessage::AppMessage(m)).unwrap();
+        }
+    }
+}
+
 impl<A> Editor for LemnaEditor<A>
 where
     A: 'static + lemna::Component + Default + Send + Sync,
@@ -59,9 +156,7 @@ where
         // Trigger a resize on the first frame
         self.sender.send(ParentMessage::Resize).unwrap();
         // And trigger a param change too
-        for m in (self.on_param_change)().drain(..) {
-            self.sender.send(ParentMessage::AppMessage(m)).unwrap();
-        }
+        self.send_param_change(ParamChange::All);
 
         let mut options = self.window_options.clone();
         options = if let Some(factor) = *self.scale_factor.read().unwrap() {
@@ -70,36 +165,65 @@ where
             options.system_scale_factor()
         };
 
+        let state = self.state.clone();
+        let (width, height) = state.size();
+        options.width = width;
+        options.height = height;
+        let resizable = options.resizable;
+
         let handle = lemna_baseview::Window::open_parented::<_, A, _>(
             &parent,
             options,
-            move |ui| (build)(context.clone(), ui),
+            move |ui| {
+                let resize_context = context.clone();
+                let mut window = ui.window.write().unwrap();
+                // Keep track of resizes made by the host
+                let resized_state = state.clone();
+                window.on_resized(Box::new(move |new_size| {
+                    resized_state.size.store((new_size.width, new_size.height));
+                }));
+                // The host asks the editor for its new size, and may refuse it
+                let state = state.clone();
+                window.on_resize_request(Box::new(move |new_size| {
+                    if !resizable {
+                        return false;
+                    }
+                    let old_size = state.size.swap((new_size.width, new_size.height));
+                    let accepted = resize_context.request_resize();
+                    if !accepted {
+                        state.size.store(old_size);
+                    }
+                    accepted
+                }));
+                drop(window);
+                (build)(context.clone(), ui)
+            },
             Some(self.receiver.clone()),
         );
         Box::new(LemnaEditorHandle { _window: handle })
     }
 
     fn size(&self) -> (u32, u32) {
-        (self.window_options.width, self.window_options.height)
+        self.state.size()
     }
     fn set_scale_factor(&self, factor: f32) -> bool {
         *self.scale_factor.write().unwrap() = Some(factor);
         true
     }
-    fn param_value_changed(&self, _id: &str, _normalized_value: f32) {
-        for m in (self.on_param_change)().drain(..) {
-            self.sender.send(ParentMessage::AppMessage(m)).unwrap();
-        }
+    fn param_value_changed(&self, id: &str, normalized_value: f32) {
+        self.send_param_change(ParamChange::Changed {
+            id: id.to_string(),
+            normalized: normalized_value,
+        });
     }
-    fn param_modulation_changed(&self, _id: &str, _modulation_offset: f32) {
-        for m in (self.on_param_change)().drain(..) {
-            self.sender.send(ParentMessage::AppMessage(m)).unwrap();
-        }
+    fn param_modulation_changed(&self, id: &str, modulation_offset: f32) {
+        self.send_param_change(ParamChange::Modulated {
+            id: id.to_string(),
+            modulation_offset,
+        });
     }
     fn param_values_changed(&self) {
-        for m in (self.on_param_change)().drain(..) {
-            self.sender.send(ParentMessage::AppMessage(m)).unwrap();
-        }
+        self.send_param_change(ParamChange::All);
     }
 }
 
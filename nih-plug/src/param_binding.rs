@@ -0,0 +1,366 @@
+use lemna::accessibility::{AccessibilityInfo, Role};
+use lemna::renderables::Rect;
+use lemna::{
+    component, event, state_component_impl, Color, Component, ComponentHasher, Message, Pos,
+    RenderContext, Renderable, Scale,
+};
+use nih_plug::prelude::*;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Connects a parameter to the host, through the editor's [`GuiContext`]. Widgets that change a parameter must bracket their changes with [`#begin_gesture`][ParamBinding#method.begin_gesture] and [`#end_gesture`][ParamBinding#method.end_gesture], otherwise hosts may not record automation correctly. [`ParamSlider`] does this for you.
+///
+/// `to_string` gives the parameter's current value, formatted with its unit.
+#[derive(Clone)]
+pub struct ParamBinding {
+    param: ParamPtr,
+    context: Arc<dyn GuiContext>,
+}
+
+// ParamPtrs point to the plugin's params, which outlive the editor
+unsafe impl Send for ParamBinding {}
+unsafe impl Sync for ParamBinding {}
+
+impl ParamBinding {
+    pub fn new<P: Param>(param: &P, context: Arc<dyn GuiContext>) -> Self {
+        Self {
+            param: param.as_ptr(),
+            context,
+        }
+    }
+
+    /// Tell the host that the user has started changing the parameter.
+    pub fn begin_gesture(&self) {
+        unsafe { self.context.raw_begin_set_parameter(self.param) }
+    }
+
+    /// Set the parameter, from a value between 0 and 1. Should be called between [`#begin_gesture`][ParamBinding#method.begin_gesture] and [`#end_gesture`][ParamBinding#method.end_gesture].
+    pub fn set_normalized(&self, value: f32) {
+        unsafe {
+            self.context
+                .raw_set_parameter_normalized(self.param, value.clamp(0.0, 1.0))
+        }
+    }
+
+    /// Tell the host that the user has finished changing the parameter.
+    pub fn end_gesture(&self) {
+        unsafe { self.context.raw_end_set_parameter(self.param) }
+    }
+
+    /// The current (modulated) value of the parameter, between 0 and 1.
+    pub fn value(&self) -> f32 {
+        unsafe { self.param.modulated_normalized_value() }
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe { self.param.name() }
+    }
+}
+
+impl fmt::Display for ParamBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = unsafe { self.param.normalized_value_to_string(self.value(), true) };
+        write!(f, "{}", value)
+    }
+}
+
+impl fmt::Debug for ParamBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParamBinding")
+            .field("param", &self.name())
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ParamSliderState {
+    dragging: bool,
+    // The last value that was set
+    value: f32,
+}
+
+/// A horizontal bar, filled in proportion to the value of the bound parameter. Click or drag to change it. Gestures are sent to the host at the start and end of each drag.
+///
+/// [`#on_change`][ParamSlider#method.on_change] is called with every new value during a drag, while [`#on_commit`][ParamSlider#method.on_commit] is called once with the final value, when the gesture ends. A click is both.
+#[component(State = "ParamSliderState")]
+pub struct ParamSlider {
+    binding: ParamBinding,
+    color: Color,
+    background_color: Color,
+    on_change: Option<Box<dyn Fn(f32) -> Message + Send + Sync>>,
+    on_commit: Option<Box<dyn Fn(f32) -> Message + Send + Sync>>,
+}
+
+impl fmt::Debug for ParamSlider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParamSlider")
+            .field("binding", &self.binding)
+            .finish()
+    }
+}
+
+impl ParamSlider {
+    pub fn new(binding: ParamBinding) -> Self {
+        Self {
+            binding,
+            color: Color::rgb(0.3, 0.5, 0.8),
+            background_color: Color::rgb(0.85, 0.85, 0.85),
+            on_change: None,
+            on_commit: None,
+            state: Some(ParamSliderState::default()),
+            dirty: false,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Called with the normalized value of the parameter, each time it is changed.
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(f32) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    /// Called with the normalized value of the parameter when a change is finished, i.e. at the same time as [`ParamBinding#end_gesture`].
+    pub fn on_commit(mut self, commit_fn: Box<dyn Fn(f32) -> Message + Send + Sync>) -> Self {
+        self.on_commit = Some(commit_fn);
+        self
+    }
+
+    /// The normalized value under the mouse, if the slider has any width.
+    fn value_at<T: event::EventInput>(event: &event::Event<T>) -> Option<f32> {
+        let width = event.current_logical_aabb().width();
+        (width > 0.0).then(|| (event.relative_logical_position().x / width).clamp(0.0, 1.0))
+    }
+
+    fn set_value(&mut self, value: Option<f32>) -> Vec<Message> {
+        if let Some(value) = value {
+            self.binding.set_normalized(value);
+            self.state_mut().value = value;
+            self.on_change.iter().map(|f| f(value)).collect()
+        } else {
+            vec![]
+        }
+    }
+
+    fn commit(&mut self) -> Vec<Message> {
+        self.binding.end_gesture();
+        self.on_commit
+            .iter()
+            .map(|f| f(self.state_ref().value))
+            .collect()
+    }
+
+    // The event handlers, given the value under the mouse. They return the messages to emit.
+
+    fn drag_start(&mut self, value: Option<f32>) -> Vec<Message> {
+        self.binding.begin_gesture();
+        self.state_mut().dragging = true;
+        self.set_value(value)
+    }
+
+    fn drag(&mut self, value: Option<f32>) -> Vec<Message> {
+        // Only change the parameter inside of a gesture
+        if !self.state_ref().dragging {
+            return vec![];
+        }
+        let messages = self.set_value(value);
+        // Redraw with the new value
+        self.state_mut();
+        messages
+    }
+
+    fn drag_end(&mut self) -> Vec<Message> {
+        let messages = if self.state_ref().dragging {
+            self.commit()
+        } else {
+            vec![]
+        };
+        self.state_mut().dragging = false;
+        messages
+    }
+
+    fn click(&mut self, value: Option<f32>) -> Vec<Message> {
+        self.binding.begin_gesture();
+        let mut messages = self.set_value(value);
+        messages.extend(self.commit());
+        self.state_mut();
+        messages
+    }
+}
+
+fn emit_all<T: event::EventInput>(event: &mut event::Event<T>, messages: Vec<Message>) {
+    for m in messages {
+        event.emit(m);
+    }
+}
+
+#[state_component_impl(ParamSliderState)]
+impl Component for ParamSlider {
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(
+            AccessibilityInfo::new(Role::Slider)
+                .name(self.binding.name())
+                .value(self.binding.to_string())
+                .range(self.binding.value() as f64, 0.0, 1.0),
+        )
+    }
+
+    fn on_drag_start(&mut self, event: &mut event::Event<event::DragStart>) {
+        event.stop_bubbling();
+        let messages = self.drag_start(Self::value_at(event));
+        emit_all(event, messages);
+    }
+
+    fn on_drag(&mut self, event: &mut event::Event<event::Drag>) {
+        let messages = self.drag(Self::value_at(event));
+        emit_all(event, messages);
+    }
+
+    fn on_drag_end(&mut self, event: &mut event::Event<event::DragEnd>) {
+        let messages = self.drag_end();
+        emit_all(event, messages);
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.stop_bubbling();
+        let messages = self.click(Self::value_at(event));
+        emit_all(event, messages);
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.binding.value().to_bits().hash(hasher);
+        self.color.hash(hasher);
+        self.background_color.hash(hasher);
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let size = context.aabb.size();
+        Some(vec![
+            Renderable::Rect(Rect::new(Pos::default(), size, self.background_color)),
+            Renderable::Rect(Rect::new(
+                Pos {
+                    z: 0.1,
+                    ..Default::default()
+                },
+                Scale {
+                    width: size.width * self.binding.value(),
+                    height: size.height,
+                },
+                self.color,
+            )),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lemna::msg;
+    use nih_plug::wrapper::state::PluginState;
+    use std::sync::Mutex;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Begin,
+        Set(f32),
+        End,
+    }
+
+    /// Records what the editor asks of the host.
+    #[derive(Default)]
+    struct RecordingContext {
+        calls: Mutex<Vec<Call>>,
+    }
+
+    impl GuiContext for RecordingContext {
+        fn plugin_api(&self) -> PluginApi {
+            PluginApi::Clap
+        }
+
+        fn request_resize(&self) -> bool {
+            false
+        }
+
+        unsafe fn raw_begin_set_parameter(&self, _param: ParamPtr) {
+            self.calls.lock().unwrap().push(Call::Begin);
+        }
+
+        unsafe fn raw_set_parameter_normalized(&self, _param: ParamPtr, normalized: f32) {
+            self.calls.lock().unwrap().push(Call::Set(normalized));
+        }
+
+        unsafe fn raw_end_set_parameter(&self, _param: ParamPtr) {
+            self.calls.lock().unwrap().push(Call::End);
+        }
+
+        fn get_state(&self) -> PluginState {
+            unimplemented!()
+        }
+
+        fn set_state(&self, _state: PluginState) {}
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum SliderMessage {
+        Change(f32),
+        Commit(f32),
+    }
+
+    #[test]
+    fn test_change_and_commit() {
+        let param = FloatParam::new("Gain", 0.0, FloatRange::Linear { min: 0.0, max: 1.0 });
+        let context = Arc::new(RecordingContext::default());
+        let mut slider = ParamSlider::new(ParamBinding::new(&param, context.clone()))
+            .on_change(Box::new(|v| msg!(SliderMessage::Change(v))))
+            .on_commit(Box::new(|v| msg!(SliderMessage::Commit(v))));
+        let received = |messages: Vec<Message>| -> Vec<SliderMessage> {
+            messages
+                .into_iter()
+                .map(|m| *m.downcast::<SliderMessage>().unwrap())
+                .collect()
+        };
+
+        // Every value of a drag is a change
+        let mut messages = slider.drag_start(Some(0.25));
+        messages.extend(slider.drag(Some(0.5)));
+        messages.extend(slider.drag(Some(0.75)));
+        assert_eq!(
+            received(messages),
+            vec![
+                SliderMessage::Change(0.25),
+                SliderMessage::Change(0.5),
+                SliderMessage::Change(0.75)
+            ]
+        );
+
+        // Releasing commits the last one, once
+        assert_eq!(
+            received(slider.drag_end()),
+            vec![SliderMessage::Commit(0.75)]
+        );
+        assert!(slider.drag_end().is_empty());
+        assert!(slider.drag(Some(1.0)).is_empty());
+
+        // All inside of a single gesture
+        assert_eq!(
+            *context.calls.lock().unwrap(),
+            vec![
+                Call::Begin,
+                Call::Set(0.25),
+                Call::Set(0.5),
+                Call::Set(0.75),
+                Call::End
+            ]
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Describe Components to assistive technologies, like screen readers.
+//!
+//! Components describe themselves with [`Component#accessibility`][crate::Component#method.accessibility]. When the `accesskit` feature is enabled, the [`UI`][crate::UI] builds an [AccessKit](https://accesskit.dev) tree out of these descriptions, which backends hand to the OS. Nodes whose Components do not describe themselves are left out of the tree, and their descendants are attached to the nearest ancestor that is in it.
+//!
+//! Assistive technologies can also act on the Nodes in the tree. These actions are turned into events:
+//! - Focus: the Node is focused, as it would be with [`UI#focus`][crate::UI#method.focus].
+//! - Default: a left [`Click`][crate::event::Click] is sent to the middle of the Node.
+//! - Set value: a [`SetValue`][crate::event::SetValue] event is sent to the Node.
+
+use crate::font_cache::TextSegment;
+
+/// What kind of thing a Component is, to an assistive technology.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// A Component that only groups its children.
+    #[default]
+    Group,
+    Window,
+    StaticText,
+    Image,
+    Link,
+    Button,
+    CheckBox,
+    RadioButton,
+    /// An on/off toggle.
+    Switch,
+    /// A button that opens a list of choices, like a [`Select`][crate::widgets::Select].
+    PopupButton,
+    ListBoxOption,
+    TextField,
+    Slider,
+}
+
+/// The semantics of a Component: its [`Role`], and a name and value to read out.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AccessibilityInfo {
+    pub role: Role,
+    /// The label of the Component, e.g. the text of a button.
+    pub name: Option<String>,
+    /// The current value, e.g. the text of a text field.
+    pub value: Option<String>,
+    /// `Some` for Components that can be checked, like a [`Toggle`][crate::widgets::Toggle].
+    pub checked: Option<bool>,
+    /// The value of a numeric Component, along with its minimum and maximum.
+    pub range: Option<(f64, f64, f64)>,
+    pub disabled: bool,
+}
+
+impl AccessibilityInfo {
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            ..Default::default()
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn range(mut self, value: f64, min: f64, max: f64) -> Self {
+        self.range = Some((value, min, max));
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// The plain text of `text`, e.g. to use as a name.
+    pub fn text(text: &[TextSegment]) -> String {
+        text.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+#[cfg(feature = "accesskit")]
+pub(crate) use tree::*;
+
+#[cfg(feature = "accesskit")]
+mod tree {
+    use std::num::NonZeroU64;
+
+    use accesskit::{Action, NodeBuilder, NodeClassSet, NodeId, Rect, Tree, TreeUpdate};
+
+    use super::*;
+    use crate::node::Node;
+
+    impl From<Role> for accesskit::Role {
+        fn from(role: Role) -> Self {
+            match role {
+                Role::Group => Self::GenericContainer,
+                Role::Window => Self::Window,
+                Role::StaticText => Self::StaticText,
+                Role::Image => Self::Image,
+                Role::Link => Self::Link,
+                Role::Button => Self::Button,
+                Role::CheckBox => Self::CheckBox,
+                Role::RadioButton => Self::RadioButton,
+                Role::Switch => Self::Switch,
+                Role::PopupButton => Self::PopupButton,
+                Role::ListBoxOption => Self::ListBoxOption,
+                Role::TextField => Self::TextField,
+                Role::Slider => Self::Slider,
+            }
+        }
+    }
+
+    /// Node ids start at 1, so they are never zero.
+    pub(crate) fn node_id(id: u64) -> NodeId {
+        NonZeroU64::new(id).unwrap().into()
+    }
+
+    pub(crate) fn lemna_id(id: NodeId) -> u64 {
+        id.0.get() as u64
+    }
+
+    /// The full accessibility tree of `root`. `focus` is the id of the focused Node, if the window has the keyboard focus.
+    pub(crate) fn tree_update(root: &Node, focus: Option<u64>) -> TreeUpdate {
+        let mut classes = NodeClassSet::lock_global();
+        let root_id = node_id(root.id);
+        let mut nodes = vec![];
+        let mut children = vec![];
+        let mut focus_id = None;
+        for child in root.children.iter() {
+            push_nodes(
+                child,
+                root_id,
+                focus,
+                &mut focus_id,
+                &mut children,
+                &mut nodes,
+                &mut classes,
+            );
+        }
+        let info = root
+            .component
+            .accessibility()
+            .unwrap_or_else(|| AccessibilityInfo::new(Role::Window));
+        nodes.push((
+            root_id,
+            build_node(root, info, children).build(&mut classes),
+        ));
+        TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(root_id)),
+            focus: focus.map(|_| focus_id.unwrap_or(root_id)),
+        }
+    }
+
+    /// Add `node` and its descendants to `nodes`. The ids of the accessible Nodes that are closest to `node` are pushed onto `siblings`. `parent` is the nearest accessible ancestor, which is focused in place of a focused Node that is not accessible.
+    fn push_nodes(
+        node: &Node,
+        parent: NodeId,
+        focus: Option<u64>,
+        focus_id: &mut Option<NodeId>,
+        siblings: &mut Vec<NodeId>,
+        nodes: &mut Vec<(NodeId, accesskit::Node)>,
+        classes: &mut NodeClassSet,
+    ) {
+        if node.layout.hidden {
+            return;
+        }
+        let info = node.component.accessibility();
+        let id = if info.is_some() {
+            node_id(node.id)
+        } else {
+            parent
+        };
+        if focus == Some(node.id) {
+            *focus_id = Some(id);
+        }
+
+        let mut children = vec![];
+        for child in node.children.iter() {
+            push_nodes(
+                child,
+                id,
+                focus,
+                focus_id,
+                if info.is_some() {
+                    &mut children
+                } else {
+                    siblings
+                },
+                nodes,
+                classes,
+            );
+        }
+
+        if let Some(info) = info {
+            siblings.push(id);
+            nodes.push((id, build_node(node, info, children).build(classes)));
+        }
+    }
+
+    fn build_node(node: &Node, info: AccessibilityInfo, children: Vec<NodeId>) -> NodeBuilder {
+        let mut builder = NodeBuilder::new(info.role.into());
+        if let Some(name) = info.name {
+            builder.set_name(name);
+        }
+        if let Some(value) = info.value {
+            builder.set_value(value);
+        }
+        if let Some(checked) = info.checked {
+            builder.set_checked_state(if checked {
+                accesskit::CheckedState::True
+            } else {
+                accesskit::CheckedState::False
+            });
+        }
+        if let Some((value, min, max)) = info.range {
+            builder.set_numeric_value(value);
+            builder.set_min_numeric_value(min);
+            builder.set_max_numeric_value(max);
+        }
+        if info.disabled || node.disabled {
+            builder.set_disabled();
+        } else {
+            if node.component.focusable() {
+                builder.add_action(Action::Focus);
+            }
+            match info.role {
+                Role::Button
+                | Role::CheckBox
+                | Role::RadioButton
+                | Role::Switch
+                | Role::PopupButton
+                | Role::ListBoxOption
+                | Role::Link => builder.add_action(Action::Default),
+                Role::TextField => builder.add_action(Action::SetValue),
+                _ => (),
+            }
+        }
+        let aabb = node.aabb;
+        builder.set_bounds(Rect {
+            x0: aabb.pos.x as f64,
+            y0: aabb.pos.y as f64,
+            x1: aabb.bottom_right.x as f64,
+            y1: aabb.bottom_right.y as f64,
+        });
+        builder.set_children(children);
+        builder
+    }
+}
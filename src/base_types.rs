@@ -10,9 +10,36 @@ use std::path::PathBuf;
 pub enum Data {
     String(String),
     Filepath(PathBuf),
+    /// Non-premultiplied RGBA8 pixels, row by row.
+    Image {
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    },
+    Html(String),
     // Custom(Vec<u8>),
 }
 
+impl Data {
+    pub fn kind(&self) -> DataKind {
+        match self {
+            Data::String(_) => DataKind::String,
+            Data::Filepath(_) => DataKind::Filepath,
+            Data::Image { .. } => DataKind::Image,
+            Data::Html(_) => DataKind::Html,
+        }
+    }
+}
+
+/// The kinds of [`Data`], used to ask for a particular kind with [`Window#method.get_from_clipboard_as`][crate::Window#method.get_from_clipboard_as].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    String,
+    Filepath,
+    Image,
+    Html,
+}
+
 impl From<&str> for Data {
     fn from(s: &str) -> Data {
         Data::String(s.to_string())
@@ -722,6 +749,117 @@ impl Color {
     pub fn rgb(r: f32, g: f32, b: f32) -> Self {
         Self { r, g, b, a: 1.0 }
     }
+
+    /// Parse a CSS-style hex color: `"#RGB"`, `"#RGBA"`, `"#RRGGBB"`, or `"#RRGGBBAA"`. The leading `#` is optional. Returns `None` if `s` is not one of these forms.
+    pub fn hex(s: &str) -> Option<Self> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize, width: usize| -> f32 {
+            let v = u8::from_str_radix(&digits[i * width..(i + 1) * width], 16).unwrap();
+            // Short forms repeat each digit, e.g. `f` is `ff`
+            u8_to_norm(if width == 1 { v * 17 } else { v })
+        };
+        match digits.len() {
+            3 | 6 => {
+                let w = digits.len() / 3;
+                Some(Self::rgb(channel(0, w), channel(1, w), channel(2, w)))
+            }
+            4 | 8 => {
+                let w = digits.len() / 4;
+                Some(Self::new(
+                    channel(0, w),
+                    channel(1, w),
+                    channel(2, w),
+                    channel(3, w),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// The `"#RRGGBBAA"` representation of the color.
+    pub fn to_hex(&self) -> String {
+        // Round rather than truncate, so that parsing the result gives back the same color
+        let channel = |x: f32| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            channel(self.r),
+            channel(self.g),
+            channel(self.b),
+            channel(self.a)
+        )
+    }
+
+    /// HSL constructor, with `A = 1.0`. `h` is the hue in degrees, `s` and `l` are the saturation and lightness (0.0--1.0). Out of range values are wrapped (`h`) or clamped (`s`, `l`).
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        Self::rgb(r + m, g + m, b + m)
+    }
+
+    /// The `(hue, saturation, lightness)` of the color, as given to [`Color::from_hsl`]. The alpha is ignored.
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let chroma = max - min;
+        let l = (max + min) / 2.0;
+        if chroma == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let h = if max == self.r {
+            ((self.g - self.b) / chroma).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / chroma + 2.0
+        } else {
+            (self.r - self.g) / chroma + 4.0
+        };
+        let s = chroma / (1.0 - (2.0 * l - 1.0).abs());
+        (h * 60.0, s.clamp(0.0, 1.0), l)
+    }
+
+    /// The same color with the given alpha, clamped to 0.0--1.0.
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self {
+            a: a.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Linearly interpolate between this color and `other`, including the alpha. `t = 0.0` gives this color and `t = 1.0` gives `other`. `t` is clamped to 0.0--1.0.
+    pub fn mix(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f32, b: f32| (a + (b - a) * t).clamp(0.0, 1.0);
+        Self {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+            a: lerp(self.a, other.a),
+        }
+    }
+
+    /// Mix the color with white by `f` (0.0--1.0), keeping its alpha.
+    pub fn lighten(self, f: f32) -> Self {
+        self.mix(Self::WHITE.with_alpha(self.a), f)
+    }
+
+    /// Mix the color with black by `f` (0.0--1.0), keeping its alpha.
+    pub fn darken(self, f: f32) -> Self {
+        self.mix(Self::BLACK.with_alpha(self.a), f)
+    }
 }
 
 impl From<[f32; 4]> for Color {
@@ -884,4 +1022,68 @@ mod tests {
         let c: Color = (0.49803921568).into();
         assert_eq!(c, Into::<Color>::into(Into::<u32>::into(c)))
     }
+
+    fn assert_color_eq(a: Color, b: Color) {
+        let [ar, ag, ab, aa]: [f32; 4] = a.into();
+        let [br, bg, bb, ba]: [f32; 4] = b.into();
+        assert!(
+            (ar - br).abs() < 0.001
+                && (ag - bg).abs() < 0.001
+                && (ab - bb).abs() < 0.001
+                && (aa - ba).abs() < 0.001,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn test_color_hex() {
+        let c = Color::hex("#ff8800").unwrap();
+        assert_color_eq(c, Color::rgb(1.0, 0x88 as f32 / 255.0, 0.0));
+        assert_eq!(c.to_hex(), "#ff8800ff");
+        assert_eq!(Color::hex(&c.to_hex()), Some(c));
+        assert_eq!(Color::hex("f80"), Some(c));
+        assert_eq!(Color::hex("#ff880080").unwrap().a, 128.0 / 255.0);
+        assert_eq!(
+            Color::hex("#ff88"),
+            Some(Color::new(
+                1.0,
+                1.0,
+                0x88 as f32 / 255.0,
+                0x88 as f32 / 255.0
+            ))
+        );
+        assert_eq!(Color::hex("#ff880"), None);
+        assert_eq!(Color::hex("#gg8800"), None);
+    }
+
+    #[test]
+    fn test_color_hsl() {
+        assert_color_eq(Color::from_hsl(0.0, 1.0, 0.5), Color::RED);
+        assert_color_eq(Color::from_hsl(120.0, 1.0, 0.5), Color::GREEN);
+        assert_color_eq(Color::from_hsl(240.0, 1.0, 0.5), Color::BLUE);
+        assert_color_eq(Color::from_hsl(-120.0, 1.0, 0.5), Color::BLUE);
+        assert_color_eq(Color::from_hsl(90.0, 0.0, 0.3), Color::DARK_GREY);
+
+        for c in [
+            Color::rgb(0.2, 0.4, 0.6),
+            Color::rgb(0.9, 0.1, 0.5),
+            Color::rgb(0.5, 0.8, 0.1),
+        ] {
+            let (h, s, l) = c.to_hsl();
+            assert_color_eq(Color::from_hsl(h, s, l), c);
+        }
+    }
+
+    #[test]
+    fn test_color_adjustments() {
+        let c = Color::new(0.2, 0.4, 0.6, 0.5);
+        assert_eq!(c.lighten(0.0), c);
+        assert_eq!(c.darken(0.0), c);
+        assert_eq!(c.lighten(1.0), Color::WHITE.with_alpha(0.5));
+        assert_eq!(c.darken(2.0), Color::BLACK.with_alpha(0.5));
+        assert_color_eq(c.mix(Color::WHITE, 0.5), Color::new(0.6, 0.7, 0.8, 0.75));
+        assert_eq!(c.with_alpha(1.5).a, 1.0);
+    }
 }
@@ -0,0 +1,327 @@
+//! The named colors of CSS, as [`Color`] constants.
+//!
+//! These are the standard sRGB values, built with [`color!`][crate::color], so they are equal to the corresponding `Color::rgb(r / 255.0, g / 255.0, b / 255.0)`. Note that some CSS names differ from the [`Color`] constants of the same name: [`GREEN`] is CSS's `#008000`, while [`Color::GREEN`] is the same as [`LIME`].
+//!
+//! ```
+//! use lemna::colors;
+//! let background = colors::CORNFLOWER_BLUE;
+//! ```
+
+use crate::{color, Color};
+
+/// `#f0f8ff`
+pub const ALICE_BLUE: Color = color!(240, 248, 255);
+/// `#faebd7`
+pub const ANTIQUE_WHITE: Color = color!(250, 235, 215);
+/// `#00ffff`
+pub const AQUA: Color = color!(0, 255, 255);
+/// `#7fffd4`
+pub const AQUAMARINE: Color = color!(127, 255, 212);
+/// `#f0ffff`
+pub const AZURE: Color = color!(240, 255, 255);
+/// `#f5f5dc`
+pub const BEIGE: Color = color!(245, 245, 220);
+/// `#ffe4c4`
+pub const BISQUE: Color = color!(255, 228, 196);
+/// `#000000`
+pub const BLACK: Color = color!(0, 0, 0);
+/// `#ffebcd`
+pub const BLANCHED_ALMOND: Color = color!(255, 235, 205);
+/// `#0000ff`
+pub const BLUE: Color = color!(0, 0, 255);
+/// `#8a2be2`
+pub const BLUE_VIOLET: Color = color!(138, 43, 226);
+/// `#a52a2a`
+pub const BROWN: Color = color!(165, 42, 42);
+/// `#deb887`
+pub const BURLYWOOD: Color = color!(222, 184, 135);
+/// `#5f9ea0`
+pub const CADET_BLUE: Color = color!(95, 158, 160);
+/// `#7fff00`
+pub const CHARTREUSE: Color = color!(127, 255, 0);
+/// `#d2691e`
+pub const CHOCOLATE: Color = color!(210, 105, 30);
+/// `#ff7f50`
+pub const CORAL: Color = color!(255, 127, 80);
+/// `#6495ed`
+pub const CORNFLOWER_BLUE: Color = color!(100, 149, 237);
+/// `#fff8dc`
+pub const CORNSILK: Color = color!(255, 248, 220);
+/// `#dc143c`
+pub const CRIMSON: Color = color!(220, 20, 60);
+/// `#00ffff`
+pub const CYAN: Color = color!(0, 255, 255);
+/// `#00008b`
+pub const DARK_BLUE: Color = color!(0, 0, 139);
+/// `#008b8b`
+pub const DARK_CYAN: Color = color!(0, 139, 139);
+/// `#b8860b`
+pub const DARK_GOLDENROD: Color = color!(184, 134, 11);
+/// `#a9a9a9`
+pub const DARK_GRAY: Color = color!(169, 169, 169);
+/// `#006400`
+pub const DARK_GREEN: Color = color!(0, 100, 0);
+/// `#a9a9a9`
+pub const DARK_GREY: Color = color!(169, 169, 169);
+/// `#bdb76b`
+pub const DARK_KHAKI: Color = color!(189, 183, 107);
+/// `#8b008b`
+pub const DARK_MAGENTA: Color = color!(139, 0, 139);
+/// `#556b2f`
+pub const DARK_OLIVE_GREEN: Color = color!(85, 107, 47);
+/// `#ff8c00`
+pub const DARK_ORANGE: Color = color!(255, 140, 0);
+/// `#9932cc`
+pub const DARK_ORCHID: Color = color!(153, 50, 204);
+/// `#8b0000`
+pub const DARK_RED: Color = color!(139, 0, 0);
+/// `#e9967a`
+pub const DARK_SALMON: Color = color!(233, 150, 122);
+/// `#8fbc8f`
+pub const DARK_SEA_GREEN: Color = color!(143, 188, 143);
+/// `#483d8b`
+pub const DARK_SLATE_BLUE: Color = color!(72, 61, 139);
+/// `#2f4f4f`
+pub const DARK_SLATE_GRAY: Color = color!(47, 79, 79);
+/// `#2f4f4f`
+pub const DARK_SLATE_GREY: Color = color!(47, 79, 79);
+/// `#00ced1`
+pub const DARK_TURQUOISE: Color = color!(0, 206, 209);
+/// `#9400d3`
+pub const DARK_VIOLET: Color = color!(148, 0, 211);
+/// `#ff1493`
+pub const DEEP_PINK: Color = color!(255, 20, 147);
+/// `#00bfff`
+pub const DEEP_SKY_BLUE: Color = color!(0, 191, 255);
+/// `#696969`
+pub const DIM_GRAY: Color = color!(105, 105, 105);
+/// `#696969`
+pub const DIM_GREY: Color = color!(105, 105, 105);
+/// `#1e90ff`
+pub const DODGER_BLUE: Color = color!(30, 144, 255);
+/// `#b22222`
+pub const FIREBRICK: Color = color!(178, 34, 34);
+/// `#fffaf0`
+pub const FLORAL_WHITE: Color = color!(255, 250, 240);
+/// `#228b22`
+pub const FOREST_GREEN: Color = color!(34, 139, 34);
+/// `#ff00ff`
+pub const FUCHSIA: Color = color!(255, 0, 255);
+/// `#dcdcdc`
+pub const GAINSBORO: Color = color!(220, 220, 220);
+/// `#f8f8ff`
+pub const GHOST_WHITE: Color = color!(248, 248, 255);
+/// `#ffd700`
+pub const GOLD: Color = color!(255, 215, 0);
+/// `#daa520`
+pub const GOLDENROD: Color = color!(218, 165, 32);
+/// `#808080`
+pub const GRAY: Color = color!(128, 128, 128);
+/// `#008000`
+pub const GREEN: Color = color!(0, 128, 0);
+/// `#adff2f`
+pub const GREEN_YELLOW: Color = color!(173, 255, 47);
+/// `#808080`
+pub const GREY: Color = color!(128, 128, 128);
+/// `#f0fff0`
+pub const HONEYDEW: Color = color!(240, 255, 240);
+/// `#ff69b4`
+pub const HOT_PINK: Color = color!(255, 105, 180);
+/// `#cd5c5c`
+pub const INDIAN_RED: Color = color!(205, 92, 92);
+/// `#4b0082`
+pub const INDIGO: Color = color!(75, 0, 130);
+/// `#fffff0`
+pub const IVORY: Color = color!(255, 255, 240);
+/// `#f0e68c`
+pub const KHAKI: Color = color!(240, 230, 140);
+/// `#e6e6fa`
+pub const LAVENDER: Color = color!(230, 230, 250);
+/// `#fff0f5`
+pub const LAVENDER_BLUSH: Color = color!(255, 240, 245);
+/// `#7cfc00`
+pub const LAWN_GREEN: Color = color!(124, 252, 0);
+/// `#fffacd`
+pub const LEMON_CHIFFON: Color = color!(255, 250, 205);
+/// `#add8e6`
+pub const LIGHT_BLUE: Color = color!(173, 216, 230);
+/// `#f08080`
+pub const LIGHT_CORAL: Color = color!(240, 128, 128);
+/// `#e0ffff`
+pub const LIGHT_CYAN: Color = color!(224, 255, 255);
+/// `#fafad2`
+pub const LIGHT_GOLDENROD_YELLOW: Color = color!(250, 250, 210);
+/// `#d3d3d3`
+pub const LIGHT_GRAY: Color = color!(211, 211, 211);
+/// `#90ee90`
+pub const LIGHT_GREEN: Color = color!(144, 238, 144);
+/// `#d3d3d3`
+pub const LIGHT_GREY: Color = color!(211, 211, 211);
+/// `#ffb6c1`
+pub const LIGHT_PINK: Color = color!(255, 182, 193);
+/// `#ffa07a`
+pub const LIGHT_SALMON: Color = color!(255, 160, 122);
+/// `#20b2aa`
+pub const LIGHT_SEA_GREEN: Color = color!(32, 178, 170);
+/// `#87cefa`
+pub const LIGHT_SKY_BLUE: Color = color!(135, 206, 250);
+/// `#778899`
+pub const LIGHT_SLATE_GRAY: Color = color!(119, 136, 153);
+/// `#778899`
+pub const LIGHT_SLATE_GREY: Color = color!(119, 136, 153);
+/// `#b0c4de`
+pub const LIGHT_STEEL_BLUE: Color = color!(176, 196, 222);
+/// `#ffffe0`
+pub const LIGHT_YELLOW: Color = color!(255, 255, 224);
+/// `#00ff00`
+pub const LIME: Color = color!(0, 255, 0);
+/// `#32cd32`
+pub const LIME_GREEN: Color = color!(50, 205, 50);
+/// `#faf0e6`
+pub const LINEN: Color = color!(250, 240, 230);
+/// `#ff00ff`
+pub const MAGENTA: Color = color!(255, 0, 255);
+/// `#800000`
+pub const MAROON: Color = color!(128, 0, 0);
+/// `#66cdaa`
+pub const MEDIUM_AQUAMARINE: Color = color!(102, 205, 170);
+/// `#0000cd`
+pub const MEDIUM_BLUE: Color = color!(0, 0, 205);
+/// `#ba55d3`
+pub const MEDIUM_ORCHID: Color = color!(186, 85, 211);
+/// `#9370db`
+pub const MEDIUM_PURPLE: Color = color!(147, 112, 219);
+/// `#3cb371`
+pub const MEDIUM_SEA_GREEN: Color = color!(60, 179, 113);
+/// `#7b68ee`
+pub const MEDIUM_SLATE_BLUE: Color = color!(123, 104, 238);
+/// `#00fa9a`
+pub const MEDIUM_SPRING_GREEN: Color = color!(0, 250, 154);
+/// `#48d1cc`
+pub const MEDIUM_TURQUOISE: Color = color!(72, 209, 204);
+/// `#c71585`
+pub const MEDIUM_VIOLET_RED: Color = color!(199, 21, 133);
+/// `#191970`
+pub const MIDNIGHT_BLUE: Color = color!(25, 25, 112);
+/// `#f5fffa`
+pub const MINT_CREAM: Color = color!(245, 255, 250);
+/// `#ffe4e1`
+pub const MISTY_ROSE: Color = color!(255, 228, 225);
+/// `#ffe4b5`
+pub const MOCCASIN: Color = color!(255, 228, 181);
+/// `#ffdead`
+pub const NAVAJO_WHITE: Color = color!(255, 222, 173);
+/// `#000080`
+pub const NAVY: Color = color!(0, 0, 128);
+/// `#fdf5e6`
+pub const OLD_LACE: Color = color!(253, 245, 230);
+/// `#808000`
+pub const OLIVE: Color = color!(128, 128, 0);
+/// `#6b8e23`
+pub const OLIVE_DRAB: Color = color!(107, 142, 35);
+/// `#ffa500`
+pub const ORANGE: Color = color!(255, 165, 0);
+/// `#ff4500`
+pub const ORANGE_RED: Color = color!(255, 69, 0);
+/// `#da70d6`
+pub const ORCHID: Color = color!(218, 112, 214);
+/// `#eee8aa`
+pub const PALE_GOLDENROD: Color = color!(238, 232, 170);
+/// `#98fb98`
+pub const PALE_GREEN: Color = color!(152, 251, 152);
+/// `#afeeee`
+pub const PALE_TURQUOISE: Color = color!(175, 238, 238);
+/// `#db7093`
+pub const PALE_VIOLET_RED: Color = color!(219, 112, 147);
+/// `#ffefd5`
+pub const PAPAYA_WHIP: Color = color!(255, 239, 213);
+/// `#ffdab9`
+pub const PEACH_PUFF: Color = color!(255, 218, 185);
+/// `#cd853f`
+pub const PERU: Color = color!(205, 133, 63);
+/// `#ffc0cb`
+pub const PINK: Color = color!(255, 192, 203);
+/// `#dda0dd`
+pub const PLUM: Color = color!(221, 160, 221);
+/// `#b0e0e6`
+pub const POWDER_BLUE: Color = color!(176, 224, 230);
+/// `#800080`
+pub const PURPLE: Color = color!(128, 0, 128);
+/// `#663399`
+pub const REBECCA_PURPLE: Color = color!(102, 51, 153);
+/// `#ff0000`
+pub const RED: Color = color!(255, 0, 0);
+/// `#bc8f8f`
+pub const ROSY_BROWN: Color = color!(188, 143, 143);
+/// `#4169e1`
+pub const ROYAL_BLUE: Color = color!(65, 105, 225);
+/// `#8b4513`
+pub const SADDLE_BROWN: Color = color!(139, 69, 19);
+/// `#fa8072`
+pub const SALMON: Color = color!(250, 128, 114);
+/// `#f4a460`
+pub const SANDY_BROWN: Color = color!(244, 164, 96);
+/// `#2e8b57`
+pub const SEA_GREEN: Color = color!(46, 139, 87);
+/// `#fff5ee`
+pub const SEASHELL: Color = color!(255, 245, 238);
+/// `#a0522d`
+pub const SIENNA: Color = color!(160, 82, 45);
+/// `#c0c0c0`
+pub const SILVER: Color = color!(192, 192, 192);
+/// `#87ceeb`
+pub const SKY_BLUE: Color = color!(135, 206, 235);
+/// `#6a5acd`
+pub const SLATE_BLUE: Color = color!(106, 90, 205);
+/// `#708090`
+pub const SLATE_GRAY: Color = color!(112, 128, 144);
+/// `#708090`
+pub const SLATE_GREY: Color = color!(112, 128, 144);
+/// `#fffafa`
+pub const SNOW: Color = color!(255, 250, 250);
+/// `#00ff7f`
+pub const SPRING_GREEN: Color = color!(0, 255, 127);
+/// `#4682b4`
+pub const STEEL_BLUE: Color = color!(70, 130, 180);
+/// `#d2b48c`
+pub const TAN: Color = color!(210, 180, 140);
+/// `#008080`
+pub const TEAL: Color = color!(0, 128, 128);
+/// `#d8bfd8`
+pub const THISTLE: Color = color!(216, 191, 216);
+/// `#ff6347`
+pub const TOMATO: Color = color!(255, 99, 71);
+/// `#40e0d0`
+pub const TURQUOISE: Color = color!(64, 224, 208);
+/// `#ee82ee`
+pub const VIOLET: Color = color!(238, 130, 238);
+/// `#f5deb3`
+pub const WHEAT: Color = color!(245, 222, 179);
+/// `#ffffff`
+pub const WHITE: Color = color!(255, 255, 255);
+/// `#f5f5f5`
+pub const WHITE_SMOKE: Color = color!(245, 245, 245);
+/// `#ffff00`
+pub const YELLOW: Color = color!(255, 255, 0);
+/// `#9acd32`
+pub const YELLOW_GREEN: Color = color!(154, 205, 50);
+/// Fully transparent black, as CSS's `transparent`.
+pub const TRANSPARENT: Color = Color::TRANSPARENT;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_colors() {
+        assert_eq!(RED, Color::RED);
+        assert_eq!(WHITE, Color::WHITE);
+        assert_eq!(LIME, Color::GREEN);
+        assert_eq!(
+            CORNFLOWER_BLUE,
+            Color::rgb(100.0 / 255.0, 149.0 / 255.0, 237.0 / 255.0)
+        );
+        assert_eq!(GREEN, Color::rgb(0.0, 128.0 / 255.0, 0.0));
+        assert_eq!(Color::hex("#663399"), Some(REBECCA_PURPLE));
+    }
+}
@@ -1,14 +1,18 @@
 use std::any::Any;
 use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use ahash::AHasher;
 
+use crate::accessibility::AccessibilityInfo;
 use crate::base_types::*;
 use crate::event::{self, Event};
 use crate::font_cache::FontCache;
 use crate::layout::*;
 use crate::node::Node;
 use crate::render::{Caches, Renderable};
+use crate::widgets::FocusRing;
 
 /// A `Box<dyn Any>` type, used to convey information from a [`Component`] to one of its parent nodes. Passed to [`Event#emit`][Event#method.emit].
 pub type Message = Box<dyn Any>;
@@ -28,6 +32,81 @@ macro_rules! msg {
     };
 }
 
+/// A side effect that [`Component#update`][Component#method.update] asks for, by returning it among its messages. Commands are carried out instead of being passed to the parent's `update`:
+///
+/// ```ignore
+/// fn update(&mut self, msg: Message) -> Vec<Message> {
+///     match msg.downcast_ref::<SaveMsg>() {
+///         Some(SaveMsg::Save) => {
+///             self.state_mut().saving = true;
+///             vec![Command::after(Duration::from_secs(1), SaveMsg::Saved)]
+///         }
+///         Some(SaveMsg::Saved) => {
+///             self.state_mut().saving = false;
+///             vec![]
+///         }
+///         None => vec![msg],
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Command {
+    /// Call `update` on the same Component with the message, once the current `update` returns.
+    Then(Message),
+    /// Call `update` on the same Component with the message, once the duration has passed. Timers are checked when the backend sends [`Input::Timer`][crate::input::Input::Timer].
+    After(Duration, Box<dyn Any + Send>),
+}
+
+impl Command {
+    /// A [`Command::Then`] message.
+    pub fn then<M: Any>(msg: M) -> Message {
+        Box::new(Command::Then(Box::new(msg)))
+    }
+
+    /// A [`Command::After`] message.
+    pub fn after<M: Any + Send>(duration: Duration, msg: M) -> Message {
+        Box::new(Command::After(duration, Box::new(msg)))
+    }
+}
+
+struct ScheduledMessage {
+    node_id: u64,
+    at: Instant,
+    message: Box<dyn Any + Send>,
+}
+
+/// The messages of [`Command::After`]s that are waiting for their time to come. Each UI has its own, shared by the Events it sends.
+#[derive(Default, Clone)]
+pub(crate) struct ScheduledQueue(Arc<Mutex<Vec<ScheduledMessage>>>);
+
+impl ScheduledQueue {
+    /// Deliver `message` to the Node with the id `node_id` once `duration` has passed.
+    pub(crate) fn push(&self, node_id: u64, duration: Duration, message: Box<dyn Any + Send>) {
+        self.0.lock().unwrap().push(ScheduledMessage {
+            node_id,
+            at: Instant::now() + duration,
+            message,
+        });
+    }
+
+    /// Remove the scheduled messages whose time has come, returning them with the ids of the Nodes they are for.
+    pub(crate) fn take_due(&self) -> Vec<(u64, Message)> {
+        let now = Instant::now();
+        let mut scheduled = self.0.lock().unwrap();
+        let mut due = vec![];
+        let mut i = 0;
+        while i < scheduled.len() {
+            if scheduled[i].at <= now {
+                let s = scheduled.remove(i);
+                due.push((s.node_id, s.message as Message));
+            } else {
+                i += 1;
+            }
+        }
+        due
+    }
+}
+
 /// Passed to [`Component#render`][Component#method.render], with context required for rendering.
 pub struct RenderContext {
     /// The `AABB` that contains the given [`Component`] instance.
@@ -40,6 +119,10 @@ pub struct RenderContext {
     pub prev_state: Option<Vec<Renderable>>,
     /// The scale factor of the current monitor. Renderables should be scaled by this value.
     pub scale_factor: f32,
+    /// Whether this Component's Node has [`Layout#pixel_snap`][crate::layout::Layout#structfield.pixel_snap] set. When `true`, lines and borders should be snapped to device pixel boundaries.
+    pub pixel_snap: bool,
+    /// Whether this Component's Node is [disabled][crate::Node#method.disabled], by itself or one of its ancestors. Disabled Components may want to draw themselves dimmed.
+    pub disabled: bool,
 }
 
 /// The primary interface of Lemna. Components are the -- optionally stateful -- elements that are drawn on a window that a user interacts with.
@@ -63,7 +146,7 @@ pub trait Component: fmt::Debug {
     /// Called during the View phase any time [`#props_hash`][Component#method.props_hash] generates a new value relative to the Node's previous incarnation.
     fn new_props(&mut self) {}
 
-    /// Called when a child Node has emitted a [`Message`] via [`Event#emit`][Event#method.emit], or if a child has passed on a `Message` from one of its descendants. The return value will be passed to the `update` of a Component's parent Node.
+    /// Called when a child Node has emitted a [`Message`] via [`Event#emit`][Event#method.emit], or if a child has passed on a `Message` from one of its descendants. The return value will be passed to the `update` of a Component's parent Node, except for any [`Command`]s, which are carried out instead.
     ///
     /// By default this forwards any incoming Messages, returning `vec![msg]`.
     fn update(&mut self, msg: Message) -> Vec<Message> {
@@ -79,16 +162,45 @@ pub trait Component: fmt::Debug {
 
     /// Called to determine whether anything about the Component that will effect rendering has changed. If a Node's `render_hash` differs from the `render_hash` is previous incarnation had created, then [`#render`][Component#method.render] will be called.
     ///
+    /// Otherwise `render` is skipped, and the renderables of the previous frame are reused as they are. Rendering also happens when the Node's size, [disabled][crate::Node#method.disabled] state or [clip path][crate::Node#method.clip_path] change, and when the theme or a stylesheet does.
+    ///
+    /// Not called when the Component provides a [`#memo_key`][Component#method.memo_key].
+    ///
     /// Defaults to [`#props_hash`][Component#method.props_hash].
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.props_hash(hasher);
     }
 
+    /// Opt in to memoized rendering, by returning a key that changes whenever the output of [`#render`][Component#method.render] would. While the key is unchanged from the previous frame, `render` is skipped and the previous renderables are reused -- [`#render_hash`][Component#method.render_hash] is not consulted. This keeps expensive renderables -- a complex [`Shape`][crate::renderables::Shape], or a long run of [`Text`][crate::renderables::Text] -- from being rebuilt every frame. The Node's size, disabled state, clip path, and theme changes still cause a re-render.
+    ///
+    /// Defaults to `None`.
+    fn memo_key(&self) -> Option<u64> {
+        None
+    }
+
     /// Called to determine whether the inputs to the Component have changed, and thus whether [`#new_props`][Component#method.new_props] should be called. Mutate the `hasher` (you will almost certainly want to import the [`std::hash::Hash`] trait, to make this method available on implementing types).
     ///
     /// There's no need to implement this method unless `new_props` is also implemented, or if it is the desired value for [`#render_hash`][Component#method.render_hash].
     fn props_hash(&self, _hasher: &mut ComponentHasher) {}
 
+    /// Called to determine whether the inputs to the Component have changed, by comparing them with those of `prev`: the Component of the Node's previous incarnation. When this returns `Some`, it is used in place of [`#props_hash`][Component#method.props_hash] to decide whether [`#new_props`][Component#method.new_props] should be called, with `Some(true)` meaning that nothing has changed. This suits props that can be compared with [`PartialEq`] but not hashed, like `f32`s:
+    ///
+    /// ```ignore
+    /// fn props_eq(&self, prev: &dyn Any) -> Option<bool> {
+    ///     prev.downcast_ref::<Self>().map(|prev| self.size == prev.size)
+    /// }
+    /// ```
+    ///
+    /// This is only called when the previous Component implements [`#as_any`][Component#method.as_any].
+    fn props_eq(&self, _prev: &dyn Any) -> Option<bool> {
+        None
+    }
+
+    /// Return `Some(self)`, so that this Component can be compared with its next incarnation by [`#props_eq`][Component#method.props_eq]. Implemented by the [`state_component_impl`][crate::state_component_impl] attribute macro.
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
+
     /// Some Components are designed to have others embedded in them. If you don't return anything from the [`#view`][Component#method.view] method, then you can [`Node#push`][crate::Node#method.push] children onto the Node of Container.
     /// Otherwise, if you return a `Some` value from both `#view` and this method, then the value returned here is the index into the child node that [`Node#push`][crate::Node#method.push] will push children into.
     /// For instance `Some(vec![0, 1])` will cause children to be attached to second child of the first Node returned by `view`. A Node with that index _must_ exist after the call to this Component's `view`. In other words, it cannot be the index of a Node that's created by a child's `#view` method.
@@ -148,6 +260,36 @@ pub trait Component: fmt::Debug {
         (None, None)
     }
 
+    /// The distance, in logical pixels, from the top of the Component to the baseline of the first line of text that it displays, if any. This is used by [`Alignment::Baseline`][crate::layout::Alignment::Baseline], and is only called after [`#fill_bounds`][Component#method.fill_bounds], so Components that lay out text can compute it then.
+    fn baseline(&self) -> Option<f32> {
+        None
+    }
+
+    /// Can this Component take the focus through keyboard navigation, i.e. [`UI#focus_next`][crate::UI#method.focus_next] and [`UI#focus_prev`][crate::UI#method.focus_prev], which are called when Tab or Shift+Tab are pressed? Components that focus themselves when clicked generally should be.
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    /// Describe this Component to assistive technologies. Components that return `None`, the default, are left out of the [`accessibility`][crate::accessibility] tree, but their children are not.
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        None
+    }
+
+    /// The text of a [`ToolTip`][crate::widgets::ToolTip] for this Component, which behaves like one given to its Node with [`Node#tooltip`][crate::Node#method.tooltip]. The Node's takes precedence. This is how the `tooltip` builders of the built-in widgets work.
+    fn tooltip_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// The ring that is drawn around this Component when it has been focused with the keyboard. Return `None` to opt out of it, e.g. when the Component shows its focus in some other way.
+    fn focus_ring(&self) -> Option<FocusRing> {
+        Some(FocusRing::new())
+    }
+
+    /// The name of this Component's type, for debugging tools like [`UI#focus_path_names`][crate::UI#method.focus_path_names].
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     /// Give the Component full control over its own [`AABB`]. When this returns `true`, [`#set_aabb`][Component#method.set_aabb] will be called while drawing a given Node.
     fn full_control(&self) -> bool {
         false
@@ -180,6 +322,14 @@ pub trait Component: fmt::Debug {
         None
     }
 
+    /// Should only be overridden by scrollable containers. Move the scroll position to `position`, which is given in the same terms as [`#scroll_position`][Component#method.scroll_position]. This is how Nodes are scrolled into view, e.g. when they are focused with the keyboard.
+    fn scroll_to(&mut self, _position: Point) {}
+
+    /// Should only be overridden by scrollable containers. The space, in logical pixels, to leave between the edges of the scrollable area and a Node that is scrolled into view.
+    fn scroll_padding(&self) -> f32 {
+        0.0
+    }
+
     /// Should only be overridden by scrollable containers. Used to limit the bounds of the scrollable area.
     /// Should return an [`AABB`] that is inside the bounds of the input `aabb` which belongs to the current Node. `inner_scale` is the size of its child Nodes.
     ///
@@ -193,6 +343,8 @@ pub trait Component: fmt::Debug {
     fn on_click(&mut self, _event: &mut Event<event::Click>) {}
     /// Handle mouse double click events. These events will only be sent if the mouse is over the Component.
     fn on_double_click(&mut self, _event: &mut Event<event::DoubleClick>) {}
+    /// Handle context menu events, sent when the user right-clicks on the Component. These events will only be sent if the mouse is over the Component.
+    fn on_context_menu(&mut self, _event: &mut Event<event::ContextMenu>) {}
     /// Handle mouse down events. These events will only be sent if the mouse is over the Component.
     fn on_mouse_down(&mut self, _event: &mut Event<event::MouseDown>) {}
     /// Handle mouse up events. These events will only be sent if the mouse is over the Component.
@@ -205,6 +357,8 @@ pub trait Component: fmt::Debug {
     fn on_mouse_motion(&mut self, _event: &mut Event<event::MouseMotion>) {}
     /// Handle scroll events. These events will only be sent if the mouse is over the Component.
     fn on_scroll(&mut self, _event: &mut Event<event::Scroll>) {}
+    /// Handle pen or stylus events. These events will only be sent if the stylus is over the Component. Call [`stop_bubbling`][Event#method.stop_bubbling] to keep the stylus from also acting as the mouse.
+    fn on_stylus(&mut self, _event: &mut Event<event::Stylus>) {}
     /// Handle mouse drag events (i.e. the user clicks a mouse button over the Component and starts moving it). These events will only be sent if the mouse is over the Component.
     fn on_drag(&mut self, _event: &mut Event<event::Drag>) {}
     /// Handle the start of a mouse drag events (i.e. the user clicks a mouse button over the Component and starts moving it). These events will only be sent if the mouse is over the Component.
@@ -215,6 +369,8 @@ pub trait Component: fmt::Debug {
     fn on_focus(&mut self, _event: &mut Event<event::Focus>) {}
     /// Handle blue events. This event occurs when this component loses its focus, either by another component gaining focus, or [`Event#blur`][crate::Event#method.blur] being called on an event belonging to this component.
     fn on_blur(&mut self, _event: &mut Event<event::Blur>) {}
+    /// Handle the focus moving into or out of the subtree of this component (see [`FocusWithin`][event::FocusWithin]). This can be used to highlight a group of widgets while any of them is focused.
+    fn on_focus_within(&mut self, _event: &mut Event<event::FocusWithin>) {}
     /// Handle tick events, which occur regularly on a short interval
     /// (window backend dependent). This can be used to create animated effects.
     fn on_tick(&mut self, _event: &mut Event<event::Tick>) {}
@@ -226,6 +382,8 @@ pub trait Component: fmt::Debug {
     fn on_key_press(&mut self, _event: &mut Event<event::KeyPress>) {}
     /// Handle text entry events. These events will only be sent if this component is focused.
     fn on_text_entry(&mut self, _event: &mut Event<event::TextEntry>) {}
+    /// Handle set value events, sent by assistive technologies. See [`accessibility`][crate::accessibility].
+    fn on_set_value(&mut self, _event: &mut Event<event::SetValue>) {}
     /// Handle a drag and drop event moving over the component.
     fn on_drag_target(&mut self, _event: &mut Event<event::DragTarget>) {}
     /// Handle a drag and drop event the first it moves over this component.
@@ -234,6 +392,10 @@ pub trait Component: fmt::Debug {
     fn on_drag_leave(&mut self, _event: &mut Event<event::DragLeave>) {}
     /// Handle a drag and drop event dropping onto this component.
     fn on_drag_drop(&mut self, _event: &mut Event<event::DragDrop>) {}
-    #[doc(hidden)]
+    /// Handle the selection of an item from the window's menu (see [`Window#method.set_menu`][crate::Window#method.set_menu]). This event is sent to the focused component first, then to the root component.
     fn on_menu_select(&mut self, _event: &mut Event<event::MenuSelect>) {}
+    /// Handle the OS switching between light and dark mode. Only sent to the root component. Every component is re-rendered afterwards.
+    fn on_theme_change(&mut self, _event: &mut Event<event::ThemeChange>) {}
+    /// Handle the UI being closed, e.g. to save state or flush files. Sent to every component, children before their parents, before the UI is torn down. [`Event#veto`][Event#method.veto] keeps it alive, e.g. to prompt about unsaved changes; messages emitted alongside are delivered to ancestors as usual.
+    fn on_exit(&mut self, _event: &mut Event<event::Exit>) {}
 }
@@ -1,10 +1,13 @@
 //! Types that relate to event handling.
 
 use std::collections::HashSet;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use super::base_types::*;
-use super::input::{Key, MouseButton};
+use super::input::{Key, MouseButton, StylusPhase};
+use crate::component::ScheduledQueue;
+use crate::widgets::ToastQueue;
+use crate::window::ColorScheme;
 use crate::Message;
 
 /// How much time (ms) can elapse between clicks before it's no longer considered a double click.
@@ -17,6 +20,32 @@ pub const DRAG_THRESHOLD: f32 = 15.0; // px
 ///
 /// Note that this is longer than [`DRAG_THRESHOLD`].
 pub const DRAG_CLICK_MAX_DIST: f32 = 30.0; // px
+/// The timings and distances that turn mouse input into double clicks and drags. The defaults are [`DOUBLE_CLICK_INTERVAL_MS`], [`DOUBLE_CLICK_MAX_DIST`], [`DRAG_THRESHOLD`] and [`DRAG_CLICK_MAX_DIST`], but apps may change them with [`UI#set_gesture_thresholds`][crate::UI#method.set_gesture_thresholds], e.g. to match the settings of the OS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureThresholds {
+    /// How much time (ms) can elapse between clicks before it's no longer considered a double click.
+    pub double_click_interval_ms: u128,
+    /// How much mouse travel (px) is allowed before it's no longer considered a double click.
+    pub double_click_max_dist: f32,
+    /// How much distance (px) is required before we start a drag event.
+    pub drag_threshold: f32,
+    /// How much mouse travel (px) is allowed until we'll no longer send a click event at the end of a drag.
+    pub drag_click_max_dist: f32,
+}
+
+impl Default for GestureThresholds {
+    fn default() -> Self {
+        Self {
+            double_click_interval_ms: DOUBLE_CLICK_INTERVAL_MS,
+            double_click_max_dist: DOUBLE_CLICK_MAX_DIST,
+            drag_threshold: DRAG_THRESHOLD,
+            drag_click_max_dist: DRAG_CLICK_MAX_DIST,
+        }
+    }
+}
+
+/// Whether [`ContextMenu`] events are sent when the right mouse button is pressed, as on macOS, rather than when it is released, as on Windows and Linux.
+pub const CONTEXT_MENU_ON_PRESS: bool = cfg!(target_os = "macos");
 
 /// The contextual data that is sent to a [`Component`][crate::Component]'s `on_EVENT` methods.
 pub struct Event<T: EventInput> {
@@ -27,6 +56,8 @@ pub struct Event<T: EventInput> {
     pub(crate) mouse_position: Point,
     /// What keyboard modifiers (Shift, Alt, Ctr, Meta) were held when this event was fired.
     pub modifiers_held: ModifiersHeld,
+    pub(crate) keys_held: HashSet<Key>,
+    pub(crate) mouse_buttons_held: MouseButtonsHeld,
     pub(crate) current_node_id: Option<u64>,
     pub(crate) current_aabb: Option<AABB>,
     pub(crate) current_inner_scale: Option<Scale>,
@@ -34,7 +65,12 @@ pub struct Event<T: EventInput> {
     pub(crate) over_subchild_n: Option<usize>,
     pub(crate) target: Option<u64>,
     pub(crate) focus: Option<u64>,
+    /// Set by [`#focus_node`][Event#method.focus_node], and resolved once the Event has been handled.
+    pub(crate) focus_reference: Option<String>,
     pub(crate) scale_factor: f32,
+    pub(crate) reduce_motion: bool,
+    pub(crate) toasts: ToastQueue,
+    pub(crate) scheduled: ScheduledQueue,
     pub(crate) messages: Vec<Message>,
     pub(crate) registrations: Vec<crate::node::Registration>,
 }
@@ -47,6 +83,8 @@ impl<T: EventInput> std::fmt::Debug for Event<T> {
             .field("dirty", &self.dirty)
             .field("mouse_position", &self.mouse_position)
             .field("modifiers_held", &self.modifiers_held)
+            .field("keys_held", &self.keys_held)
+            .field("mouse_buttons_held", &self.mouse_buttons_held)
             .field("current_node_id", &self.current_node_id)
             .field("current_aabb", &self.current_aabb)
             .field("current_inner_scale", &self.current_inner_scale)
@@ -54,7 +92,9 @@ impl<T: EventInput> std::fmt::Debug for Event<T> {
             .field("over_subchild_n", &self.over_subchild_n)
             .field("target", &self.target)
             .field("focus", &self.focus)
+            .field("focus_reference", &self.focus_reference)
             .field("scale_factor", &self.scale_factor)
+            .field("reduce_motion", &self.reduce_motion)
             .finish()
     }
 }
@@ -69,15 +109,34 @@ pub trait EventInput: std::fmt::Debug {
 }
 
 /// [`EventInput`] type for focus events.
-#[derive(Debug)]
-pub struct Focus;
+#[derive(Debug, Default)]
+pub struct Focus {
+    /// The id of the Node that is losing the focus, if any.
+    pub related_target: Option<u64>,
+    /// The keys of the Nodes from the root down to `related_target`.
+    pub related_key_path: Vec<u64>,
+    /// The [references][crate::Node#method.reference] of `related_target` and its ancestors.
+    pub(crate) related_references: Vec<String>,
+}
 impl EventInput for Focus {}
 
 /// [`EventInput`] type for blur events.
-#[derive(Debug)]
-pub struct Blur;
+#[derive(Debug, Default)]
+pub struct Blur {
+    /// The id of the Node that is getting the focus, if any.
+    pub related_target: Option<u64>,
+    /// The keys of the Nodes from the root down to `related_target`.
+    pub related_key_path: Vec<u64>,
+    /// The [references][crate::Node#method.reference] of `related_target` and its ancestors.
+    pub(crate) related_references: Vec<String>,
+}
 impl EventInput for Blur {}
 
+/// [`EventInput`] type for focus-within events, the equivalent of CSS's `:focus-within`. Sent with `true` to a Node, and to each of its ancestors, when the focus moves into its subtree (including the Node itself), and with `false` when the focus leaves it.
+#[derive(Debug)]
+pub struct FocusWithin(pub bool);
+impl EventInput for FocusWithin {}
+
 /// [`EventInput`] type for tick events.
 #[derive(Debug)]
 pub struct Tick;
@@ -114,7 +173,7 @@ impl EventInput for MouseEnter {}
 pub struct MouseLeave;
 impl EventInput for MouseLeave {}
 
-/// [`EventInput`] type for mouse click events.
+/// [`EventInput`] type for mouse click events. The event's [`modifiers_held`][Event#structfield.modifiers_held] are those that were held when the button was pressed, so that e.g. Ctrl+click and Shift+click can be told apart from a plain click.
 #[derive(Debug)]
 pub struct Click(
     /// The [`MouseButton`] clicked.
@@ -122,7 +181,7 @@ pub struct Click(
 );
 impl EventInput for Click {}
 
-/// [`EventInput`] type for mouse double click events.
+/// [`EventInput`] type for mouse double click events. Like [`Click`], its modifiers are those held when the button was pressed.
 #[derive(Debug)]
 pub struct DoubleClick(
     ///  The [`MouseButton`] clicked.
@@ -130,6 +189,14 @@ pub struct DoubleClick(
 );
 impl EventInput for DoubleClick {}
 
+/// [`EventInput`] type for context menu events, sent when the user right-clicks. Depending on the platform, this happens either when the right mouse button is pressed or when it is released (see [`CONTEXT_MENU_ON_PRESS`]), but it is only ever sent once per click.
+#[derive(Debug)]
+pub struct ContextMenu {
+    /// The physical position of the mouse when the context menu was requested.
+    pub position: Point,
+}
+impl EventInput for ContextMenu {}
+
 /// [`EventInput`] type for key down events.
 #[derive(Debug)]
 pub struct KeyDown(
@@ -192,6 +259,14 @@ pub struct TextEntry(
 );
 impl EventInput for TextEntry {}
 
+/// [`EventInput`] type for set value events, sent when an assistive technology sets the value of a Component, e.g. the text of a text field. See [`accessibility`][crate::accessibility].
+#[derive(Debug)]
+pub struct SetValue(
+    /// The new value.
+    pub String,
+);
+impl EventInput for SetValue {}
+
 /// [`EventInput`] type for scroll events.
 #[derive(Debug, Copy, Clone)]
 pub struct Scroll {
@@ -202,6 +277,18 @@ pub struct Scroll {
 }
 impl EventInput for Scroll {}
 
+/// [`EventInput`] type for stylus events. The position of the stylus is the event's [`mouse_position`][Event#structfield.mouse_position].
+#[derive(Debug, Copy, Clone)]
+pub struct Stylus {
+    /// How hard the stylus is pressed, from 0 to 1.
+    pub pressure: f32,
+    /// The angle of the stylus from the vertical along the x and y axes, in radians, if the hardware reports it.
+    pub tilt: Option<(f32, f32)>,
+    /// Where this sample is in the stroke.
+    pub phase: StylusPhase,
+}
+impl EventInput for Stylus {}
+
 /// [`EventInput`] type for drag events.
 #[derive(Debug, Copy, Clone)]
 pub struct Drag {
@@ -256,9 +343,24 @@ pub struct DragDrop(
 );
 impl EventInput for DragDrop {}
 
-#[doc(hidden)]
+/// [`EventInput`] type for theme change events, which are sent to the root Component when the OS switches between light and dark mode. The whole UI is redrawn afterwards, so styles may be swapped in response.
 #[derive(Debug)]
-pub struct MenuSelect(pub i32);
+pub struct ThemeChange(pub ColorScheme);
+impl EventInput for ThemeChange {}
+
+/// [`EventInput`] type for exit events, which are sent to every Component before the UI is torn down. See [`Component#method.on_exit`][crate::Component#method.on_exit].
+#[derive(Debug, Default)]
+pub struct Exit {
+    pub(crate) vetoed: bool,
+}
+impl EventInput for Exit {}
+
+/// [`EventInput`] type for menu selection events.
+#[derive(Debug)]
+pub struct MenuSelect(
+    /// The `id` of the chosen [`MenuItem`][crate::MenuItem].
+    pub i32,
+);
 impl EventInput for MenuSelect {}
 
 /// Returned by [`Component#register`][crate::Component#method.register].
@@ -304,8 +406,11 @@ impl<T: EventInput> Event<T> {
             bubbles: true,
             dirty: false,
             modifiers_held: event_cache.modifiers_held,
+            keys_held: event_cache.keys_held.clone(),
+            mouse_buttons_held: event_cache.mouse_buttons_held,
             mouse_position: event_cache.mouse_position,
             focus: Some(event_cache.focus),
+            focus_reference: None,
             target: None,
             current_node_id: None,
             current_aabb: None,
@@ -313,22 +418,44 @@ impl<T: EventInput> Event<T> {
             over_child_n: None,
             over_subchild_n: None,
             scale_factor: event_cache.scale_factor,
+            reduce_motion: event_cache.reduce_motion,
+            toasts: event_cache.toasts.clone(),
+            scheduled: event_cache.scheduled.clone(),
             messages: vec![],
             registrations: vec![],
         }
     }
 
+    /// An event that completes a mouse press, e.g. a [`Click`]. The modifiers are those that were held when the button was pressed, rather than when it was released.
+    pub(crate) fn new_from_press(input: T, event_cache: &EventCache) -> Self {
+        let mut event = Self::new(input, event_cache);
+        event.modifiers_held = event_cache.press_modifiers;
+        event
+    }
+
     /// Set the current Node to be "focused".
     /// This will cause it to receive [`Blur`], [`KeyDown`], [`KeyUp`], [`KeyPress`], [`TextEntry`], [`Drag`], and [`DragEnd`] events.
     ///
     /// Note that any other Nodes may also request focus.
     pub fn focus(&mut self) {
         self.focus = self.current_node_id;
+        self.focus_reference = None;
+    }
+
+    /// Focus the Node with the given [`reference`][crate::Node#method.reference], e.g. a search field when Ctrl+F is pressed, and scroll it into view. The Node does not need to be related to the current one. If there is no such Node, the focus is left as it is.
+    pub fn focus_node(&mut self, reference: &str) {
+        self.focus_reference = Some(reference.to_string());
+    }
+
+    /// Show a transient notification with the text `message` for `duration`. See [`Toasts`][crate::widgets::Toasts].
+    pub fn toast(&self, message: impl Into<String>, duration: Duration) {
+        self.toasts.push(message.into(), duration);
     }
 
-    /// Remove focus from this Node, if applicable.
+    /// Remove focus from this Node, if applicable. The root Node gets the focus instead.
     pub fn blur(&mut self) {
         self.focus = None;
+        self.focus_reference = None;
     }
 
     /// Prevent this Event from being sent to one of the ancestor Nodes of the current one.
@@ -376,12 +503,27 @@ impl<T: EventInput> Event<T> {
         self.mouse_position - Point { x: pos.x, y: pos.y }
     }
 
+    /// Whether the user prefers reduced motion. Animated Components should jump straight to the end of their animations when this is set.
+    pub fn reduce_motion(&self) -> bool {
+        self.reduce_motion
+    }
+
     /// The current mouse position relative to this Node's AABB, in logical coordinates.
     pub fn relative_logical_position(&self) -> Point {
         let pos = self.current_aabb.unwrap().pos;
         (self.mouse_position - Point { x: pos.x, y: pos.y }).unscale(self.scale_factor)
     }
 
+    /// Was the `key` held when this event was fired? Modifier keys are reported without regard to their side, e.g. `Key::LShift` is held if either Shift key is.
+    pub fn is_key_held(&self, key: Key) -> bool {
+        key_held(&self.keys_held, self.modifiers_held, key)
+    }
+
+    /// Was the mouse button `b` held when this event was fired?
+    pub fn is_mouse_button_held(&self, b: MouseButton) -> bool {
+        self.mouse_buttons_held.is_held(b)
+    }
+
     /// Returns which child of this Node the mouse is over, if any.
     pub fn over_child_n(&self) -> Option<usize> {
         self.over_child_n
@@ -431,6 +573,39 @@ impl Event<Drag> {
     }
 }
 
+impl Event<Focus> {
+    /// Is the focus coming from the Node with the given [`reference`][crate::Node#method.reference], or from one of its descendants?
+    pub fn focus_moving_within(&self, reference: &str) -> bool {
+        self.input.related_references.iter().any(|r| r == reference)
+    }
+}
+
+impl Event<Blur> {
+    /// Is the focus going to the Node with the given [`reference`][crate::Node#method.reference], or to one of its descendants? E.g. a row of fields can commit an edit only when the focus leaves the row.
+    pub fn focus_moving_within(&self, reference: &str) -> bool {
+        self.input.related_references.iter().any(|r| r == reference)
+    }
+}
+
+impl Event<Exit> {
+    /// Keep the UI alive, e.g. to ask the user whether to save their changes. Backends whose windows cannot be kept open, like a plugin window closed by its host, are torn down regardless.
+    pub fn veto(&mut self) {
+        self.input.vetoed = true;
+    }
+
+    /// Whether a Component has already vetoed the exit.
+    pub fn vetoed(&self) -> bool {
+        self.input.vetoed
+    }
+}
+
+impl Event<ContextMenu> {
+    /// The position of the mouse when the context menu was requested, in logical coordinates.
+    pub fn logical_position(&self) -> Point {
+        self.input.position.unscale(self.scale_factor)
+    }
+}
+
 impl Event<DragEnd> {
     /// The distance dragged, in physical coordinates.
     pub fn physical_delta(&self) -> Point {
@@ -462,8 +637,30 @@ pub(crate) struct MouseButtonsHeld {
     pub aux2: bool,
 }
 
+impl MouseButtonsHeld {
+    fn is_held(&self, b: MouseButton) -> bool {
+        match b {
+            MouseButton::Left => self.left,
+            MouseButton::Right => self.right,
+            MouseButton::Middle => self.middle,
+            MouseButton::Aux1 => self.aux1,
+            MouseButton::Aux2 => self.aux2,
+        }
+    }
+}
+
+fn key_held(keys_held: &HashSet<Key>, modifiers_held: ModifiersHeld, key: Key) -> bool {
+    match key {
+        Key::LCtrl | Key::RCtrl => modifiers_held.ctrl,
+        Key::LShift | Key::RShift => modifiers_held.shift,
+        Key::LAlt | Key::RAlt => modifiers_held.alt,
+        Key::LMeta | Key::RMeta => modifiers_held.meta,
+        _ => keys_held.contains(&key),
+    }
+}
+
 /// The keyboard modifiers that are held down while an [`Event`] is fired.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct ModifiersHeld {
     pub shift: bool,
     pub alt: bool,
@@ -471,12 +668,32 @@ pub struct ModifiersHeld {
     pub meta: bool,
 }
 
+impl ModifiersHeld {
+    /// Is no modifier held?
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Is the platform's command modifier held? This is Meta (⌘) on macOS and Ctrl elsewhere, e.g. for adding to a selection.
+    pub fn command(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            self.meta
+        } else {
+            self.ctrl
+        }
+    }
+}
+
 /// Points are all logical positions.
 pub(crate) struct EventCache {
     pub focus: u64,
+    /// Was the focus acquired with the keyboard? The [`FocusRing`][crate::widgets::FocusRing] is only shown when it was.
+    pub focus_visible: bool,
     pub keys_held: HashSet<Key>,
     pub modifiers_held: ModifiersHeld,
     pub mouse_buttons_held: MouseButtonsHeld,
+    // The modifiers held when a mouse button was last pressed
+    pub press_modifiers: ModifiersHeld,
     pub mouse_over: Option<u64>,
     pub mouse_position: Point,
     // Used to detect double clicks
@@ -489,15 +706,24 @@ pub(crate) struct EventCache {
     pub drag_target: Option<u64>,
     pub scale_factor: f32,
     pub drag_data: Vec<Data>,
+    /// Whether animations should be skipped, see [`Event#reduce_motion`][Event#method.reduce_motion].
+    pub reduce_motion: bool,
+    pub gesture_thresholds: GestureThresholds,
+    /// The toasts sent by the UI that this belongs to.
+    pub(crate) toasts: ToastQueue,
+    /// The delayed messages sent by the Components of the UI that this belongs to.
+    pub(crate) scheduled: ScheduledQueue,
 }
 
 impl std::fmt::Debug for EventCache {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         f.debug_struct("EventCache")
             .field("focus", &self.focus)
+            .field("focus_visible", &self.focus_visible)
             .field("keys_held", &self.keys_held)
             .field("modifiers_held", &self.modifiers_held)
             .field("mouse_buttons_held", &self.mouse_buttons_held)
+            .field("press_modifiers", &self.press_modifiers)
             .field("mouse_over", &self.mouse_over)
             .field("mouse_position", &self.mouse_position)
             .field("drag_started", &self.drag_started)
@@ -505,6 +731,8 @@ impl std::fmt::Debug for EventCache {
             .field("drag_target", &self.drag_target)
             .field("scale_factor", &self.scale_factor)
             .field("drag_data", &self.drag_data)
+            .field("reduce_motion", &self.reduce_motion)
+            .field("gesture_thresholds", &self.gesture_thresholds)
             .finish()
     }
 }
@@ -513,9 +741,11 @@ impl EventCache {
     pub fn new(scale_factor: f32) -> Self {
         Self {
             focus: 0,
+            focus_visible: false,
             keys_held: Default::default(),
             modifiers_held: Default::default(),
             mouse_buttons_held: Default::default(),
+            press_modifiers: Default::default(),
             mouse_over: None,
             mouse_position: Default::default(),
             last_mouse_click: Instant::now(),
@@ -525,6 +755,10 @@ impl EventCache {
             drag_target: None,
             drag_data: vec![],
             scale_factor,
+            reduce_motion: false,
+            gesture_thresholds: Default::default(),
+            toasts: Default::default(),
+            scheduled: Default::default(),
         }
     }
 
@@ -570,21 +804,17 @@ impl EventCache {
         }
     }
 
+    /// Reconcile the held modifiers with those reported by the backend.
+    pub(crate) fn set_modifiers(&mut self, modifiers: ModifiersHeld) {
+        self.modifiers_held = modifiers;
+    }
+
     pub(crate) fn key_held(&self, key: Key) -> bool {
-        match key {
-            Key::LCtrl => self.modifiers_held.ctrl,
-            Key::LShift => self.modifiers_held.shift,
-            Key::LAlt => self.modifiers_held.alt,
-            Key::LMeta => self.modifiers_held.meta,
-            Key::RCtrl => self.modifiers_held.ctrl,
-            Key::RShift => self.modifiers_held.shift,
-            Key::RAlt => self.modifiers_held.alt,
-            Key::RMeta => self.modifiers_held.meta,
-            _ => self.keys_held.contains(&key),
-        }
+        key_held(&self.keys_held, self.modifiers_held, key)
     }
 
     pub(crate) fn mouse_down(&mut self, b: MouseButton) {
+        self.press_modifiers = self.modifiers_held;
         match b {
             MouseButton::Left => self.mouse_buttons_held.left = true,
             MouseButton::Right => self.mouse_buttons_held.right = true,
@@ -605,13 +835,36 @@ impl EventCache {
     }
 
     pub(crate) fn is_mouse_button_held(&self, b: MouseButton) -> bool {
-        match b {
-            MouseButton::Left => self.mouse_buttons_held.left,
-            MouseButton::Right => self.mouse_buttons_held.right,
-            MouseButton::Middle => self.mouse_buttons_held.middle,
-            MouseButton::Aux1 => self.mouse_buttons_held.aux1,
-            MouseButton::Aux2 => self.mouse_buttons_held.aux2,
-        }
+        self.mouse_buttons_held.is_held(b)
+    }
+
+    /// Is pressing (or releasing, when `pressed` is false) the mouse button `b` the gesture that requests a context menu on this platform?
+    pub(crate) fn is_context_menu_gesture(&self, b: MouseButton, pressed: bool) -> bool {
+        b == MouseButton::Right
+            && pressed == CONTEXT_MENU_ON_PRESS
+            && (pressed || self.is_mouse_button_held(b))
+    }
+
+    /// Has the mouse moved far enough from where a button was pressed to start a drag?
+    pub(crate) fn is_drag_start(&self, start: Point, pos: Point) -> bool {
+        let threshold = self.gesture_thresholds.drag_threshold;
+        (start.x - pos.x).abs() > threshold || (start.y - pos.y).abs() > threshold
+    }
+
+    /// Record a left click at the mouse position, returning whether it completes a double click.
+    pub(crate) fn left_click(&mut self) -> bool {
+        let is_double_click = self.last_mouse_click.elapsed().as_millis()
+            < self.gesture_thresholds.double_click_interval_ms
+            && self.last_mouse_click_position.dist(self.mouse_position)
+                < self.gesture_thresholds.double_click_max_dist;
+        self.last_mouse_click = Instant::now();
+        self.last_mouse_click_position = self.mouse_position;
+        is_double_click
+    }
+
+    /// Was a drag short enough that it should also count as a click?
+    pub(crate) fn is_drag_click(&self, start: Point) -> bool {
+        start.dist(self.mouse_position) < self.gesture_thresholds.drag_click_max_dist
     }
 
     pub(crate) fn mouse_button_held(&self) -> Option<MouseButton> {
@@ -630,3 +883,102 @@ impl EventCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_click_interval() {
+        let mut cache = EventCache::new(1.0);
+        let click_after = |cache: &mut EventCache, ms: u64| {
+            cache.last_mouse_click = Instant::now() - Duration::from_millis(ms);
+            cache.left_click()
+        };
+        assert!(click_after(&mut cache, 300));
+
+        // Clicks that were far enough apart to be a double click no longer are
+        cache.gesture_thresholds.double_click_interval_ms = 250;
+        assert!(!click_after(&mut cache, 300));
+        assert!(click_after(&mut cache, 200));
+
+        // Nor are clicks that are too far apart
+        cache.last_mouse_click_position = Point::new(50.0, 0.0);
+        assert!(!click_after(&mut cache, 0));
+    }
+
+    #[test]
+    fn test_stuck_modifier() {
+        let mut cache = EventCache::new(1.0);
+        cache.key_down(Key::LCtrl);
+        // The release of Ctrl was missed, e.g. because it happened in another window
+        assert!(cache.key_held(Key::LCtrl));
+
+        // A plain click is reported without modifiers
+        cache.set_modifiers(ModifiersHeld::default());
+        let event = Event::new(Click(MouseButton::Left), &cache);
+        assert!(!event.modifiers_held.ctrl);
+        assert!(!cache.key_held(Key::LCtrl));
+
+        cache.set_modifiers(ModifiersHeld {
+            shift: true,
+            ..Default::default()
+        });
+        assert!(cache.key_held(Key::RShift));
+    }
+
+    #[test]
+    fn test_click_modifiers() {
+        let mut cache = EventCache::new(1.0);
+        cache.key_down(Key::LCtrl);
+        cache.mouse_down(MouseButton::Left);
+        // Ctrl is released before the mouse button is
+        cache.key_up(Key::LCtrl);
+        cache.mouse_up(MouseButton::Left);
+
+        let event = Event::new_from_press(Click(MouseButton::Left), &cache);
+        assert!(event.modifiers_held.ctrl);
+        assert!(!event.modifiers_held.shift);
+        assert!(!event.modifiers_held.is_empty());
+
+        // A later plain click has no modifiers
+        cache.mouse_down(MouseButton::Left);
+        cache.mouse_up(MouseButton::Left);
+        let event = Event::new_from_press(Click(MouseButton::Left), &cache);
+        assert!(event.modifiers_held.is_empty());
+    }
+
+    #[test]
+    fn test_held_state() {
+        let mut cache = EventCache::new(2.0);
+        cache.key_down(Key::LShift);
+        cache.key_down(Key::A);
+        cache.mouse_down(MouseButton::Left);
+        cache.mouse_position = Point::new(20.0, 10.0);
+
+        // Events fired afterwards see what is held
+        let event = Event::new(
+            Drag {
+                button: MouseButton::Left,
+                start_pos: Point::default(),
+            },
+            &cache,
+        );
+        assert!(event.modifiers_held.shift);
+        assert!(event.is_key_held(Key::LShift));
+        assert!(event.is_key_held(Key::RShift));
+        assert!(event.is_key_held(Key::A));
+        assert!(!event.is_key_held(Key::B));
+        assert!(event.is_mouse_button_held(MouseButton::Left));
+        assert!(!event.is_mouse_button_held(MouseButton::Right));
+        assert_eq!(event.logical_mouse_position(), Point::new(10.0, 5.0));
+
+        // But not what is released later
+        cache.key_up(Key::LShift);
+        cache.mouse_up(MouseButton::Left);
+        assert!(event.is_key_held(Key::LShift));
+        let event = Event::new(Click(MouseButton::Left), &cache);
+        assert!(!event.is_key_held(Key::LShift));
+        assert!(!event.is_mouse_button_held(MouseButton::Left));
+    }
+}
@@ -9,6 +9,7 @@
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
+use crate::base_types::{Point, Pos, AABB};
 use crate::style::HorizontalPosition;
 use glyph_brush_layout::{
     ab_glyph::*, FontId, GlyphPositioner, HorizontalAlign, SectionGeometry, SectionText,
@@ -27,6 +28,8 @@ pub const SIZE_SCALE: f32 = 1.5;
 pub struct FontCache {
     pub(crate) fonts: Fonts,
     pub(crate) font_names: HashMap<String, usize>,
+    // Glyphs queued by `prewarm`, to be cached by the renderer
+    prewarmed: Vec<SectionGlyph>,
 }
 
 impl FontCache {
@@ -77,16 +80,20 @@ impl FontCache {
 
         let section_text: Vec<_> = text
             .iter()
-            .map(|TextSegment { text, size, font }| SectionText {
-                text,
-                scale: size
-                    .map_or(scaled_size, |s| s * scale_factor * SIZE_SCALE)
-                    .into(),
-                font_id: font
-                    .as_ref()
-                    .and_then(|f| self.font(f))
-                    .unwrap_or(base_font),
-            })
+            .map(
+                |TextSegment {
+                     text, size, font, ..
+                 }| SectionText {
+                    text,
+                    scale: size
+                        .map_or(scaled_size, |s| s * scale_factor * SIZE_SCALE)
+                        .into(),
+                    font_id: font
+                        .as_ref()
+                        .and_then(|f| self.font(f))
+                        .unwrap_or(base_font),
+                },
+            )
             .collect();
 
         let screen_position = (
@@ -133,6 +140,35 @@ impl FontCache {
             })
             .collect()
     }
+
+    /// Queue the glyphs of `text`, in the given `font` (or the default font) and `font_size`, to be rasterized and cached by the renderer before the next frame is rendered. Use this for text that is known ahead of time, so that its glyphs don't need to be rasterized when the text first appears. Glyphs that are already cached are not rasterized again.
+    ///
+    /// [`UI#prewarm_text`][crate::UI#method.prewarm_text] calls this with the window's scale factor.
+    pub fn prewarm(&mut self, text: &str, font: Option<&str>, font_size: f32, scale_factor: f32) {
+        let glyphs = self.layout_text(
+            &[text.into()],
+            font,
+            font_size,
+            scale_factor,
+            HorizontalPosition::Left,
+            (f32::INFINITY, f32::INFINITY),
+        );
+        self.prewarmed.extend(glyphs);
+    }
+
+    /// The area that `glyph` takes up in its line: its advance, by the height of its font. Like the glyph's position, this is in physical pixels relative to the laid out text.
+    pub fn glyph_bounds(&self, glyph: &SectionGlyph) -> AABB {
+        let font = self.fonts[glyph.font_id.0].as_scaled(glyph.glyph.scale);
+        let p = glyph.glyph.position;
+        AABB {
+            pos: Pos::new(p.x, p.y - font.ascent(), 0.0),
+            bottom_right: Point::new(p.x + font.h_advance(glyph.glyph.id), p.y - font.descent()),
+        }
+    }
+
+    pub(crate) fn take_prewarmed(&mut self) -> Vec<SectionGlyph> {
+        std::mem::take(&mut self.prewarmed)
+    }
 }
 
 /// Used by [`FontCache#layout_text`][FontCache#method.layout_text] as an input. Accordingly, it is also commonly used as the input to Components that display text, e.g. [`widgets::Text`][crate::widgets::Text] and [`widgets::Button`][crate::widgets::Button].
@@ -146,6 +182,16 @@ pub struct TextSegment {
     pub size: Option<f32>,
     /// An optional font name. A default will be selected if `None`.
     pub font: Option<String>,
+    /// An optional URL that the text links to. See [`Text#on_link`][crate::widgets::Text#method.on_link].
+    pub link: Option<String>,
+}
+
+impl TextSegment {
+    /// Link this segment to `url`, returns itself.
+    pub fn link(mut self, url: impl Into<String>) -> Self {
+        self.link = Some(url.into());
+        self
+    }
 }
 
 impl From<&str> for TextSegment {
@@ -160,6 +206,7 @@ impl From<String> for TextSegment {
             text,
             size: None,
             font: None,
+            link: None,
         }
     }
 }
@@ -235,18 +282,21 @@ macro_rules! txt {
         text: $text.into(),
         size: Some($size),
         font: None,
+        link: None,
     } };
 
     (@as_txt_seg  ($text:expr, $font:expr, $size:expr)) => { $crate::font_cache::TextSegment {
         text: $text.into(),
         size: Some($size),
         font: Some($font.into()),
+        link: None,
     } };
 
     (@as_txt_seg  ($text:expr, $font:expr)) => { $crate::font_cache::TextSegment {
         text: $text.into(),
         size: None,
         font: Some($font.into()),
+        link: None,
     } };
 
     (@as_txt_seg  $e:expr) => {
@@ -267,5 +317,50 @@ impl Hash for TextSegment {
         self.size.map(|s| (s * 100.0) as u32).hash(state);
         self.font.hash(state);
         self.text.hash(state);
+        self.link.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::glyph_brush_draw_cache::DrawCache;
+
+    #[test]
+    fn test_prewarm() {
+        let mut font_cache = FontCache::default();
+        font_cache.add_font("noto sans regular".to_string(), ttf_noto_sans::REGULAR);
+        let mut draw_cache = DrawCache::builder()
+            .dimensions(256, 256)
+            .multithread(false)
+            .build();
+        let text = "Hello prewarm";
+
+        let mut cache = |glyphs: Vec<SectionGlyph>, font_cache: &FontCache| {
+            for g in glyphs {
+                draw_cache.queue_glyph(g.font_id.0, g.glyph);
+            }
+            let mut insertions = 0;
+            draw_cache
+                .cache_queued(&font_cache.fonts, |_, _| insertions += 1)
+                .unwrap();
+            insertions
+        };
+
+        font_cache.prewarm(text, None, 12.0, 1.0);
+        let prewarmed = font_cache.take_prewarmed();
+        assert!(cache(prewarmed, &font_cache) > 0);
+        assert!(font_cache.take_prewarmed().is_empty());
+
+        // Rendering the same text afterwards hits the cache for every glyph
+        let rendered = font_cache.layout_text(
+            &[text.into()],
+            None,
+            12.0,
+            1.0,
+            HorizontalPosition::Left,
+            (f32::INFINITY, f32::INFINITY),
+        );
+        assert_eq!(cache(rendered, &font_cache), 0);
     }
 }
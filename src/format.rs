@@ -0,0 +1,382 @@
+//! Format numbers with their units for display, e.g. as the value of a plugin parameter, and parse what the user types back into numbers.
+//!
+//! Each [`ValueFormatter`] parses its own output, so values can be round-tripped through a text box, and is lenient about what else it accepts: [`Hertz`] reads `"1k"`, `"1 kHz"` and `"1000"` as the same value. Numbers are written and read with the [`decimal_separator`][crate::locale::Locale#structfield.decimal_separator] of the current [`Locale`][crate::locale::Locale], and negative numbers are written with a minus sign (`−`) rather than a hyphen, though either is parsed.
+//!
+//! ```
+//! use lemna::format::{Hertz, ValueFormatter};
+//!
+//! assert_eq!(Hertz::default().format(1234.0), "1.2 kHz");
+//! assert_eq!(Hertz::default().parse("1.2k"), Some(1200.0));
+//! ```
+
+use crate::locale::decimal_separator;
+
+/// Converts values to text and back. Widgets that display a number can be given a `&dyn ValueFormatter` to control how it looks.
+pub trait ValueFormatter: Send + Sync {
+    /// `value` as text, with its unit.
+    fn format(&self, value: f64) -> String;
+
+    /// The value that `text` represents, or `None` if it cannot be read as one. Accepts the output of [`#format`][ValueFormatter#method.format], as well as the other ways a user may reasonably type the value.
+    fn parse(&self, text: &str) -> Option<f64>;
+}
+
+const MINUS: char = '\u{2212}';
+
+/// `value` with `precision` decimal places, written with a minus sign and the current locale's decimal separator. Values that round to zero are never negative.
+pub fn format_number(value: f64, precision: usize) -> String {
+    write_number(value, precision, decimal_separator())
+}
+
+fn write_number(value: f64, precision: usize, separator: char) -> String {
+    let s = format!("{:.*}", precision, value);
+    let s = match s.strip_prefix('-') {
+        Some(abs) if abs.chars().all(|c| c == '0' || c == '.') => abs.to_string(),
+        _ => s,
+    };
+    s.chars()
+        .map(|c| match c {
+            '-' => MINUS,
+            '.' => separator,
+            c => c,
+        })
+        .collect()
+}
+
+/// Split `text` into the number that it starts with, and the rest of it trimmed and lowercased.
+fn read_number(text: &str, separator: char) -> Option<(f64, String)> {
+    let text: String = text
+        .trim()
+        .chars()
+        .map(|c| match c {
+            MINUS => '-',
+            c if c == separator => '.',
+            c => c,
+        })
+        .collect();
+    let end = text
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && (c == '-' || c == '+'))))
+        .map_or(text.len(), |(i, _)| i);
+    let number = text[..end].parse().ok()?;
+    Some((number, text[end..].trim().to_lowercase()))
+}
+
+fn round(value: f64, precision: usize) -> f64 {
+    let m = 10f64.powi(precision as i32);
+    (value * m).round() / m
+}
+
+/// `value` in the largest of `units` that it is at least one of, once rounded, so that 999.96 Hz is written as 1.0 kHz rather than 1000.0 Hz. `units` are ordered from smallest to largest.
+fn write_scaled(value: f64, precision: usize, units: &[(f64, &str)], separator: char) -> String {
+    let (scale, unit) = units
+        .iter()
+        .rev()
+        .find(|(scale, _)| round(value.abs() / scale, precision) >= 1.0)
+        .unwrap_or(&units[0]);
+    format!(
+        "{} {}",
+        write_number(value / scale, precision, separator),
+        unit
+    )
+}
+
+/// Decibels, e.g. `"−6.0 dB"`. Negative infinity is written as `"−∞ dB"`, and `"-inf"` is also parsed as it. Numbers without a unit are read as decibels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decibels {
+    pub precision: usize,
+}
+
+impl Default for Decibels {
+    fn default() -> Self {
+        Self { precision: 1 }
+    }
+}
+
+impl Decibels {
+    fn write(&self, value: f64, separator: char) -> String {
+        if value == f64::NEG_INFINITY {
+            format!("{}∞ dB", MINUS)
+        } else {
+            format!("{} dB", write_number(value, self.precision, separator))
+        }
+    }
+
+    fn read(&self, text: &str, separator: char) -> Option<f64> {
+        let lower = text.trim().to_lowercase().replace(MINUS, "-");
+        let without_unit = lower.strip_suffix("db").unwrap_or(&lower).trim();
+        if without_unit == "-inf" || without_unit == "-∞" {
+            return Some(f64::NEG_INFINITY);
+        }
+        match read_number(text, separator)? {
+            (n, unit) if unit.is_empty() || unit == "db" => Some(n),
+            _ => None,
+        }
+    }
+}
+
+impl ValueFormatter for Decibels {
+    fn format(&self, value: f64) -> String {
+        self.write(value, decimal_separator())
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        self.read(text, decimal_separator())
+    }
+}
+
+/// Frequencies in hertz, written in Hz, kHz or MHz depending on their size, e.g. `"440.0 Hz"` or `"1.2 kHz"`. Parses the units in full or abbreviated, as in `"1k"`, and numbers without a unit as hertz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hertz {
+    pub precision: usize,
+}
+
+impl Default for Hertz {
+    fn default() -> Self {
+        Self { precision: 1 }
+    }
+}
+
+impl Hertz {
+    const UNITS: [(f64, &'static str); 3] = [(1.0, "Hz"), (1e3, "kHz"), (1e6, "MHz")];
+
+    fn read(&self, text: &str, separator: char) -> Option<f64> {
+        let (n, unit) = read_number(text, separator)?;
+        let scale = match unit.as_str() {
+            "" | "hz" => 1.0,
+            "k" | "khz" => 1e3,
+            "m" | "mhz" => 1e6,
+            _ => return None,
+        };
+        Some(n * scale)
+    }
+}
+
+impl ValueFormatter for Hertz {
+    fn format(&self, value: f64) -> String {
+        write_scaled(value, self.precision, &Self::UNITS, decimal_separator())
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        self.read(text, decimal_separator())
+    }
+}
+
+/// Durations in seconds, written as whole milliseconds below a second, e.g. `"437 ms"`, and in seconds with `precision` decimal places above, e.g. `"1.20 s"`. Numbers without a unit are read as seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Time {
+    pub precision: usize,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self { precision: 2 }
+    }
+}
+
+impl Time {
+    fn write(&self, value: f64, separator: char) -> String {
+        let ms = value * 1000.0;
+        if ms.abs().round() < 1000.0 {
+            format!("{} ms", write_number(ms, 0, separator))
+        } else {
+            format!("{} s", write_number(value, self.precision, separator))
+        }
+    }
+
+    fn read(&self, text: &str, separator: char) -> Option<f64> {
+        let (n, unit) = read_number(text, separator)?;
+        match unit.as_str() {
+            "" | "s" | "sec" => Some(n),
+            "ms" => Some(n / 1000.0),
+            _ => None,
+        }
+    }
+}
+
+impl ValueFormatter for Time {
+    fn format(&self, value: f64) -> String {
+        self.write(value, decimal_separator())
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        self.read(text, decimal_separator())
+    }
+}
+
+/// Fractions written as percentages, so that `0.5` is `"50%"`. Numbers without a unit are read as percentages.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Percent {
+    pub precision: usize,
+}
+
+impl Percent {
+    fn write(&self, value: f64, separator: char) -> String {
+        format!(
+            "{}%",
+            write_number(value * 100.0, self.precision, separator)
+        )
+    }
+
+    fn read(&self, text: &str, separator: char) -> Option<f64> {
+        match read_number(text, separator)? {
+            (n, unit) if unit.is_empty() || unit == "%" => Some(n / 100.0),
+            _ => None,
+        }
+    }
+}
+
+impl ValueFormatter for Percent {
+    fn format(&self, value: f64) -> String {
+        self.write(value, decimal_separator())
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        self.read(text, decimal_separator())
+    }
+}
+
+/// MIDI note numbers written as note names, where 60 is `"C4"`. Values are rounded to the nearest note, and written with sharps. Parses sharps and flats, as `#`/`♯` and `b`/`♭`, as well as plain note numbers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NoteName;
+
+impl NoteName {
+    const NAMES: [&'static str; 12] = [
+        "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+    ];
+}
+
+impl ValueFormatter for NoteName {
+    fn format(&self, value: f64) -> String {
+        let note = value.round() as i64;
+        format!(
+            "{}{}",
+            Self::NAMES[note.rem_euclid(12) as usize],
+            note.div_euclid(12) - 1
+        )
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        if let Some((n, unit)) = read_number(text, decimal_separator()) {
+            return if unit.is_empty() {
+                Some(n.round())
+            } else {
+                None
+            };
+        }
+        let text = text.trim().replace(MINUS, "-");
+        let mut chars = text.chars();
+        let step = match chars.next()?.to_ascii_uppercase() {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return None,
+        };
+        let rest = chars.as_str();
+        let (accidental, octave) = if let Some(octave) = rest.strip_prefix(['#', '♯']) {
+            (1, octave)
+        } else if let Some(octave) = rest.strip_prefix(['b', '♭']) {
+            (-1, octave)
+        } else {
+            (0, rest)
+        };
+        let octave: i64 = octave.trim().parse().ok()?;
+        Some(((octave + 1) * 12 + step + accidental) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(write_number(-6.0, 1, '.'), "−6.0");
+        assert_eq!(write_number(-0.01, 1, '.'), "0.0");
+        assert_eq!(write_number(1.25, 2, ','), "1,25");
+        assert_eq!(
+            read_number(" −1,5 kHz", ','),
+            Some((-1.5, "khz".to_string()))
+        );
+        assert_eq!(read_number("dB", '.'), None);
+    }
+
+    #[test]
+    fn test_decibels() {
+        let db = Decibels::default();
+        assert_eq!(db.format(-6.0), "−6.0 dB");
+        assert_eq!(db.format(f64::NEG_INFINITY), "−∞ dB");
+        assert_eq!(db.parse("−6.0 dB"), Some(-6.0));
+        assert_eq!(db.parse("-6db"), Some(-6.0));
+        assert_eq!(db.parse("3"), Some(3.0));
+        assert_eq!(db.parse("-inf"), Some(f64::NEG_INFINITY));
+        assert_eq!(
+            db.parse(&db.format(f64::NEG_INFINITY)),
+            Some(f64::NEG_INFINITY)
+        );
+        assert_eq!(db.parse("3 Hz"), None);
+        assert_eq!(db.write(-6.0, ','), "−6,0 dB");
+        assert_eq!(db.read("−6,5 dB", ','), Some(-6.5));
+    }
+
+    #[test]
+    fn test_hertz() {
+        let hz = Hertz::default();
+        assert_eq!(hz.format(440.0), "440.0 Hz");
+        assert_eq!(hz.format(1234.0), "1.2 kHz");
+        assert_eq!(hz.format(2_500_000.0), "2.5 MHz");
+        // Rounding up moves to the next unit
+        assert_eq!(hz.format(999.96), "1.0 kHz");
+        assert_eq!(hz.format(0.0), "0.0 Hz");
+        for text in ["1k", "1 kHz", "1000", "1000 Hz", "1KHZ"] {
+            assert_eq!(hz.parse(text), Some(1000.0), "{}", text);
+        }
+        assert_eq!(hz.parse(&hz.format(1200.0)), Some(1200.0));
+        assert_eq!(hz.parse("1 ms"), None);
+    }
+
+    #[test]
+    fn test_time() {
+        let time = Time::default();
+        assert_eq!(time.format(0.437), "437 ms");
+        assert_eq!(time.format(1.2), "1.20 s");
+        assert_eq!(time.format(0.9996), "1.00 s");
+        assert_eq!(time.parse("437 ms"), Some(0.437));
+        assert_eq!(time.parse("1.2s"), Some(1.2));
+        assert_eq!(time.parse("2"), Some(2.0));
+        assert_eq!(time.parse("2 min"), None);
+    }
+
+    #[test]
+    fn test_percent() {
+        let percent = Percent::default();
+        assert_eq!(percent.format(0.5), "50%");
+        assert_eq!(Percent { precision: 1 }.format(0.1234), "12.3%");
+        assert_eq!(percent.parse("50%"), Some(0.5));
+        assert_eq!(percent.parse("25"), Some(0.25));
+    }
+
+    #[test]
+    fn test_note_name() {
+        assert_eq!(NoteName.format(60.0), "C4");
+        assert_eq!(NoteName.format(69.2), "A4");
+        assert_eq!(NoteName.format(61.0), "C#4");
+        assert_eq!(NoteName.format(0.0), "C-1");
+        assert_eq!(NoteName.parse("A4"), Some(69.0));
+        assert_eq!(NoteName.parse("c#4"), Some(61.0));
+        assert_eq!(NoteName.parse("Db4"), Some(61.0));
+        assert_eq!(NoteName.parse("B♭3"), Some(58.0));
+        assert_eq!(NoteName.parse("C-1"), Some(0.0));
+        assert_eq!(NoteName.parse("64"), Some(64.0));
+        assert_eq!(NoteName.parse("H4"), None);
+        for note in 0..128 {
+            assert_eq!(
+                NoteName.parse(&NoteName.format(note as f64)),
+                Some(note as f64)
+            );
+        }
+    }
+}
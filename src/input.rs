@@ -3,6 +3,10 @@
 //! These are most typically interacted with through event-handling methods of [`Component`][crate::Component]. For instance [`#on_click`][crate::Component#method.on_click] receives an `Event<Click>`. A [`Click`][crate::event::Click], holds a [`MouseButton`] input type. If the user cares what kind of click they are reacting to, they need to match this input to the desired mouse button.
 
 use crate::base_types::Data;
+use crate::event::ModifiersHeld;
+use crate::window::ColorScheme;
+
+pub mod touch_adapter;
 
 /// Mouse movement or scrolling
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -11,6 +15,19 @@ pub enum Motion {
     Scroll { x: f32, y: f32 },
 }
 
+/// Where a [`Input::Stylus`] sample is in a stroke.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StylusPhase {
+    /// The stylus touched the surface.
+    Down,
+    /// The stylus moved while touching the surface.
+    Move,
+    /// The stylus was lifted from the surface.
+    Up,
+    /// The stroke was interrupted, e.g. because the window lost focus.
+    Cancel,
+}
+
 /// A keyboard key
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Key {
@@ -259,12 +276,28 @@ pub enum Input {
     Release(Button),
     Resize,
     Motion(Motion),
+    /// A sample from a pen or stylus, at the logical position `x`, `y`. `pressure` ranges from 0 to 1, and `tilt` is the angle of the stylus from the vertical along the x and y axes, in radians, when the hardware reports it. Samples that are not handled by [`Component#on_stylus`][crate::Component#method.on_stylus] are treated as left mouse button input.
+    Stylus {
+        x: f32,
+        y: f32,
+        pressure: f32,
+        tilt: Option<(f32, f32)>,
+        phase: StylusPhase,
+    },
     Text(String),
     Focus(bool),
     Menu(i32),
     MouseLeaveWindow,
     MouseEnterWindow,
     Timer,
+    /// The window is closing. Components are told with [`on_exit`][crate::Component#method.on_exit] before the UI is torn down. See [`UI#request_exit`][crate::UI#method.request_exit].
     Exit,
     Drag(Drag),
+    /// The OS switched between light and dark mode.
+    ThemeChanged(ColorScheme),
+    /// An assistive technology requested an action. See [`accessibility`][crate::accessibility].
+    #[cfg(feature = "accesskit")]
+    Accessibility(accesskit::ActionRequest),
+    /// The keyboard modifiers that are currently held. Backends that know the modifier state (e.g. because it is attached to their mouse and keyboard events) should send this before the event it came with, so that missed modifier key releases do not leave modifiers stuck.
+    Modifiers(ModifiersHeld),
 }
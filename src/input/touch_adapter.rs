@@ -0,0 +1,274 @@
+//! Turn raw samples from a touch controller into [`Input`]s.
+//!
+//! Touch controllers (e.g. resistive or capacitive panels) report whether the panel is being touched, and where, in the controller's own coordinate space. A [`TouchAdapter`] takes these samples, maps them into logical window coordinates with a [`Calibration`], debounces them, and outputs the [`Input`]s that should be passed to [`UI#handle_input`][crate::UI#method.handle_input].
+//!
+//! Small movements while a touch is held are suppressed until they exceed [`DRAG_THRESHOLD`], so that the jitter of a finger at rest does not turn taps into drags.
+
+use super::{Button, Input, Motion, MouseButton};
+use crate::base_types::Point;
+use crate::event::DRAG_THRESHOLD;
+
+/// An affine transformation from touch panel coordinates to logical window coordinates:
+///
+/// ```text
+/// x' = a * x + b * y + c
+/// y' = d * x + e * y + f
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Calibration {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Default for Calibration {
+    /// The identity transformation.
+    fn default() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 0.0,
+            e: 1.0,
+            f: 0.0,
+        }
+    }
+}
+
+impl Calibration {
+    /// Create a calibration that linearly maps the panel's raw range onto a window of the given logical `width` and `height`.
+    pub fn from_range(raw_min: Point, raw_max: Point, width: f32, height: f32) -> Self {
+        let a = width / (raw_max.x - raw_min.x);
+        let e = height / (raw_max.y - raw_min.y);
+        Self {
+            a,
+            b: 0.0,
+            c: -raw_min.x * a,
+            d: 0.0,
+            e,
+            f: -raw_min.y * e,
+        }
+    }
+
+    /// Compute the calibration that best maps each raw panel point to its expected screen point, given at least three `(raw, screen)` pairs. Five points (the four corners and the center) is typical, and more points average out measurement error.
+    ///
+    /// Returns `None` if there are fewer than three points, or if they are collinear.
+    pub fn from_points(points: &[(Point, Point)]) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+        // Least squares fit: Solve the normal equations (AᵀA)p = Aᵀb, where each row of A is [x, y, 1]
+        let mut ata = [[0.0f64; 3]; 3];
+        let mut atx = [0.0f64; 3];
+        let mut aty = [0.0f64; 3];
+        for (raw, screen) in points.iter() {
+            let row = [raw.x as f64, raw.y as f64, 1.0];
+            for (i, r) in row.iter().enumerate() {
+                for (cell, r2) in ata[i].iter_mut().zip(row.iter()) {
+                    *cell += r * r2;
+                }
+                atx[i] += r * screen.x as f64;
+                aty[i] += r * screen.y as f64;
+            }
+        }
+        let [a, b, c] = solve3(ata, atx)?;
+        let [d, e, f] = solve3(ata, aty)?;
+        Some(Self {
+            a: a as f32,
+            b: b as f32,
+            c: c as f32,
+            d: d as f32,
+            e: e as f32,
+            f: f as f32,
+        })
+    }
+
+    /// Map a point from panel coordinates to logical window coordinates.
+    pub fn apply(&self, p: Point) -> Point {
+        Point {
+            x: self.a * p.x + self.b * p.y + self.c,
+            y: self.d * p.x + self.e * p.y + self.f,
+        }
+    }
+}
+
+/// Solve the 3x3 system `m * x = v` with Cramer's rule.
+fn solve3(m: [[f64; 3]; 3], v: [f64; 3]) -> Option<[f64; 3]> {
+    let det = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det(m);
+    if d.abs() < f64::EPSILON {
+        return None;
+    }
+    let mut x = [0.0; 3];
+    for (col, x) in x.iter_mut().enumerate() {
+        let mut mc = m;
+        for (row, v) in mc.iter_mut().zip(v.iter()) {
+            row[col] = *v;
+        }
+        *x = det(mc) / d;
+    }
+    Some(x)
+}
+
+/// Converts raw touch samples into [`Input`]s. See the [module documentation][self].
+#[derive(Debug, Clone)]
+pub struct TouchAdapter {
+    calibration: Calibration,
+    /// How many consecutive samples must agree before a touch or release is registered.
+    debounce_samples: usize,
+    pressed: bool,
+    pending: usize,
+    press_position: Point,
+    last_position: Point,
+    dragging: bool,
+}
+
+impl Default for TouchAdapter {
+    fn default() -> Self {
+        Self::new(Calibration::default())
+    }
+}
+
+impl TouchAdapter {
+    pub fn new(calibration: Calibration) -> Self {
+        Self {
+            calibration,
+            debounce_samples: 2,
+            pressed: false,
+            pending: 0,
+            press_position: Point::default(),
+            last_position: Point::default(),
+            dragging: false,
+        }
+    }
+
+    /// Set how many consecutive samples must agree before a touch or release is registered. Defaults to 2. A value of 1 disables debouncing.
+    pub fn debounce_samples(mut self, n: usize) -> Self {
+        self.debounce_samples = n.max(1);
+        self
+    }
+
+    /// The calibration currently in use.
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Replace the calibration. This can be done at any time, e.g. from a calibration screen.
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Feed a raw sample from the touch controller, in panel coordinates. When not `pressed`, `x` and `y` are ignored. Returns the [`Input`]s that should be handled by the [`UI`][crate::UI].
+    pub fn sample(&mut self, pressed: bool, x: f32, y: f32) -> Vec<Input> {
+        let mut inputs = vec![];
+
+        if pressed != self.pressed {
+            self.pending += 1;
+            if self.pending < self.debounce_samples {
+                return inputs;
+            }
+            self.pending = 0;
+            self.pressed = pressed;
+
+            if pressed {
+                let p = self.calibration.apply(Point { x, y });
+                self.press_position = p;
+                self.last_position = p;
+                self.dragging = false;
+                inputs.push(Input::Motion(Motion::Mouse { x: p.x, y: p.y }));
+                inputs.push(Input::Press(Button::Mouse(MouseButton::Left)));
+            } else {
+                inputs.push(Input::Release(Button::Mouse(MouseButton::Left)));
+            }
+            return inputs;
+        }
+        self.pending = 0;
+
+        if pressed {
+            let p = self.calibration.apply(Point { x, y });
+            if !self.dragging && p.dist(self.press_position) >= DRAG_THRESHOLD {
+                self.dragging = true;
+            }
+            if self.dragging && p != self.last_position {
+                self.last_position = p;
+                inputs.push(Input::Motion(Motion::Mouse { x: p.x, y: p.y }));
+            }
+        }
+
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f32, y: f32) -> Point {
+        Point { x, y }
+    }
+
+    #[test]
+    fn test_calibration_from_points() {
+        let expected = Calibration {
+            a: 0.1,
+            b: 0.01,
+            c: -20.0,
+            d: -0.02,
+            e: 0.08,
+            f: -10.0,
+        };
+        let points: Vec<(Point, Point)> = [
+            p(300.0, 300.0),
+            p(3800.0, 300.0),
+            p(300.0, 3800.0),
+            p(3800.0, 3800.0),
+            p(2050.0, 2050.0),
+        ]
+        .iter()
+        .map(|raw| (*raw, expected.apply(*raw)))
+        .collect();
+        let c = Calibration::from_points(&points).unwrap();
+        let test = c.apply(p(1000.0, 2000.0));
+        let expected = expected.apply(p(1000.0, 2000.0));
+        assert!((test.x - expected.x).abs() < 0.01);
+        assert!((test.y - expected.y).abs() < 0.01);
+
+        assert!(Calibration::from_points(&points[0..2]).is_none());
+    }
+
+    #[test]
+    fn test_tap_and_drag() {
+        let mut t = TouchAdapter::default();
+        // Debounced press
+        assert!(t.sample(true, 10.0, 10.0).is_empty());
+        assert_eq!(
+            t.sample(true, 10.0, 10.0),
+            vec![
+                Input::Motion(Motion::Mouse { x: 10.0, y: 10.0 }),
+                Input::Press(Button::Mouse(MouseButton::Left))
+            ]
+        );
+        // Jitter is suppressed
+        assert!(t.sample(true, 12.0, 11.0).is_empty());
+        // A bouncing release is ignored
+        assert!(t.sample(false, 0.0, 0.0).is_empty());
+        assert!(t.sample(true, 11.0, 10.0).is_empty());
+        // Dragging
+        assert_eq!(
+            t.sample(true, 40.0, 10.0),
+            vec![Input::Motion(Motion::Mouse { x: 40.0, y: 10.0 })]
+        );
+        assert!(t.sample(false, 0.0, 0.0).is_empty());
+        assert_eq!(
+            t.sample(false, 0.0, 0.0),
+            vec![Input::Release(Button::Mouse(MouseButton::Left))]
+        );
+    }
+}
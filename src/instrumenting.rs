@@ -3,6 +3,8 @@
 //! Traces are captured in the format used by <https://superluminal.eu/>. Logs are output using [log], which can be set up with any of many loggers.
 //!
 //! Lemna itself outputs spans relating to key phases, such as event handling, drawing, and rendering.
+//!
+//! These spans, along with frame boundaries and per-frame counters, can also be captured to a file that can be viewed in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev/), with [`start_capture`] and [`stop_capture`].
 
 use std::cell::UnsafeCell;
 use std::time::Instant;
@@ -35,6 +37,7 @@ pub fn inst(name: &str) {
     let now = Instant::now();
     info!("{:?} {} START", &now, name);
     inst_stack_push(name, now);
+    capture::record(name, 'B', vec![]);
 }
 
 /// Start an instrumented span with the given name.
@@ -52,6 +55,7 @@ pub fn inst_end() {
         name,
         now.duration_since(prev).as_micros()
     );
+    capture::record(&name, 'E', vec![]);
 }
 
 /// Ends the last instrumentation span that was started, logging the time it took.
@@ -62,8 +66,229 @@ pub fn inst_end() {}
 pub fn evt(name: &str) {
     let now = Instant::now();
     info!("{:?} {}", now, name);
+    capture::record(name, 'i', vec![]);
 }
 
 /// Log an event with the given name.
 #[cfg(not(feature = "instrumented"))]
 pub fn evt(_name: &str) {}
+
+/// Log a change of focus, when [`UI#focus_debug`][crate::UI#method.focus_debug] is on. Unlike the rest of this module, this does not need the "instrumented" feature.
+pub(crate) fn focus_evt(message: &str) {
+    log::info!("{:?} {}", Instant::now(), message);
+}
+
+#[cfg(feature = "instrumented")]
+mod capture {
+    use std::fmt::Write as _;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    /// A Chrome trace event. See <https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU>.
+    pub(super) struct TraceEvent {
+        pub name: String,
+        /// `B`egin, `E`nd, `i`nstant, `C`ounter or `M`etadata
+        pub phase: char,
+        /// Microseconds since the capture started
+        pub ts: f64,
+        pub tid: u64,
+        pub args: Vec<(String, String)>,
+    }
+
+    pub(super) struct Capture {
+        pub path: PathBuf,
+        pub start: Instant,
+        pub events: Vec<TraceEvent>,
+        /// The threads that have been named in the capture
+        pub named_threads: Vec<u64>,
+    }
+
+    pub(super) static CAPTURING: AtomicBool = AtomicBool::new(false);
+    pub(super) static CAPTURE: Mutex<Option<Capture>> = Mutex::new(None);
+
+    static THREAD_ID_ATOMIC: AtomicU64 = AtomicU64::new(1);
+    thread_local!(static THREAD_ID: u64 = THREAD_ID_ATOMIC.fetch_add(1, Ordering::SeqCst));
+
+    /// Add an event to the capture, if one is running.
+    pub(super) fn record(name: &str, phase: char, args: Vec<(String, String)>) {
+        if !CAPTURING.load(Ordering::Relaxed) {
+            return;
+        }
+        let now = Instant::now();
+        let tid = THREAD_ID.with(|id| *id);
+        if let Some(capture) = CAPTURE.lock().unwrap().as_mut() {
+            if !capture.named_threads.contains(&tid) {
+                capture.named_threads.push(tid);
+                let thread = std::thread::current();
+                let thread_name = thread.name().unwrap_or("unnamed");
+                capture.events.push(TraceEvent {
+                    name: "thread_name".to_string(),
+                    phase: 'M',
+                    ts: 0.0,
+                    tid,
+                    args: vec![("name".to_string(), json_string(thread_name))],
+                });
+            }
+            capture.events.push(TraceEvent {
+                name: name.to_string(),
+                phase,
+                ts: now.duration_since(capture.start).as_secs_f64() * 1_000_000.0,
+                tid,
+                args,
+            });
+        }
+    }
+
+    fn json_string(s: &str) -> String {
+        let mut out = String::with_capacity(s.len() + 2);
+        out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    /// The events in the Chrome trace event JSON format.
+    pub(super) fn to_json(events: &[TraceEvent]) -> String {
+        let mut out = String::from("{\"traceEvents\":[\n");
+        for (i, event) in events.iter().enumerate() {
+            if i > 0 {
+                out.push_str(",\n");
+            }
+            write!(
+                out,
+                "{{\"name\":{},\"ph\":\"{}\",\"ts\":{:.3},\"pid\":1,\"tid\":{}",
+                json_string(&event.name),
+                event.phase,
+                event.ts,
+                event.tid
+            )
+            .unwrap();
+            if event.phase == 'i' {
+                // Instant events are drawn across every thread
+                out.push_str(",\"s\":\"g\"");
+            }
+            if !event.args.is_empty() {
+                out.push_str(",\"args\":{");
+                for (j, (key, value)) in event.args.iter().enumerate() {
+                    if j > 0 {
+                        out.push(',');
+                    }
+                    write!(out, "{}:{}", json_string(key), value).unwrap();
+                }
+                out.push('}');
+            }
+            out.push('}');
+        }
+        out.push_str("\n],\"displayTimeUnit\":\"ms\"}\n");
+        out
+    }
+}
+
+/// Start capturing spans, events, frames and counters, to be written to `path` by [`stop_capture`] in the [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU). The resulting file can be opened in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev/). Any capture that was already running is discarded.
+#[cfg(feature = "instrumented")]
+pub fn start_capture<P: Into<std::path::PathBuf>>(path: P) {
+    *capture::CAPTURE.lock().unwrap() = Some(capture::Capture {
+        path: path.into(),
+        start: Instant::now(),
+        events: vec![],
+        named_threads: vec![],
+    });
+    capture::CAPTURING.store(true, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Start capturing spans, events, frames and counters, to be written to `path` by [`stop_capture`] in the [Chrome trace event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU). The resulting file can be opened in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev/). Does nothing without the "instrumented" feature.
+#[cfg(not(feature = "instrumented"))]
+pub fn start_capture<P: Into<std::path::PathBuf>>(_path: P) {}
+
+/// Stop the capture started by [`start_capture`], writing it to its file.
+#[cfg(feature = "instrumented")]
+pub fn stop_capture() -> std::io::Result<()> {
+    capture::CAPTURING.store(false, std::sync::atomic::Ordering::Relaxed);
+    if let Some(c) = capture::CAPTURE.lock().unwrap().take() {
+        std::fs::write(&c.path, capture::to_json(&c.events))?;
+    }
+    Ok(())
+}
+
+/// Stop the capture started by [`start_capture`], writing it to its file. Does nothing without the "instrumented" feature.
+#[cfg(not(feature = "instrumented"))]
+pub fn stop_capture() -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Is a capture running? Use this to skip gathering [`counters`] that nobody will see.
+#[cfg(feature = "instrumented")]
+pub fn capturing() -> bool {
+    capture::CAPTURING.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Is a capture running? Use this to skip gathering [`counters`] that nobody will see.
+#[cfg(not(feature = "instrumented"))]
+pub fn capturing() -> bool {
+    false
+}
+
+/// Mark the end of a frame in the capture.
+#[cfg(feature = "instrumented")]
+pub fn frame() {
+    capture::record("Frame", 'i', vec![]);
+}
+
+/// Mark the end of a frame in the capture.
+#[cfg(not(feature = "instrumented"))]
+pub fn frame() {}
+
+/// Record the values of some counters, e.g. the number of draw calls made in a frame, under the given name.
+#[cfg(feature = "instrumented")]
+pub fn counters(name: &str, values: &[(&str, usize)]) {
+    capture::record(
+        name,
+        'C',
+        values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+    );
+}
+
+/// Record the values of some counters, e.g. the number of draw calls made in a frame, under the given name.
+#[cfg(not(feature = "instrumented"))]
+pub fn counters(_name: &str, _values: &[(&str, usize)]) {}
+
+#[cfg(all(test, feature = "instrumented"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chrome_trace() {
+        let path = std::env::temp_dir().join("lemna_test_chrome_trace.json");
+        start_capture(&path);
+        inst("span \"quoted\"");
+        evt("marker");
+        counters("Draw", &[("nodes", 3)]);
+        inst_end();
+        frame();
+        stop_capture().unwrap();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(json.starts_with("{\"traceEvents\":["));
+        assert!(json.contains("\"name\":\"thread_name\",\"ph\":\"M\""));
+        assert!(json.contains("\"name\":\"span \\\"quoted\\\"\",\"ph\":\"B\""));
+        assert!(json.contains("\"name\":\"span \\\"quoted\\\"\",\"ph\":\"E\""));
+        assert!(json.contains("\"name\":\"marker\",\"ph\":\"i\""));
+        assert!(json.contains("\"args\":{\"nodes\":3}"));
+        assert!(json.contains("\"name\":\"Frame\",\"ph\":\"i\""));
+        // Nothing is recorded once the capture is stopped
+        assert!(!capturing());
+    }
+}
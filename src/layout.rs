@@ -533,6 +533,8 @@ pub enum Alignment {
     End,
     Center,
     Stretch,
+    /// Only meaningful as a `cross_alignment` of a [`Direction::Row`]: Aligns children by the first text baseline that their content reports (see [`Component#baseline`][crate::Component#method.baseline]). Children without a baseline are aligned as though this were `Start`.
+    Baseline,
 }
 
 impl Default for Alignment {
@@ -557,6 +559,10 @@ pub struct Layout {
     pub min_size: Size,
     pub z_index: Option<f64>,
     pub z_index_increment: f64,
+    /// A hidden node takes up no space, is not rendered, and does not receive mouse events. It does however stay in the tree, so showing it again is cheap.
+    pub hidden: bool,
+    /// Snap the edges of this node to device pixel boundaries, even when it has been offset by a fractional amount (e.g. by scrolling), and tell its Component to keep its strokes crisp. Off by default, since it can shift content by up to half a device pixel.
+    pub pixel_snap: bool,
     pub debug: Option<String>,
 }
 
@@ -579,6 +585,8 @@ impl Default for Layout {
             },
             z_index: None,
             z_index_increment: 0.0,
+            hidden: false,
+            pixel_snap: false,
             debug: None,
         }
     }
@@ -614,6 +622,17 @@ impl super::node::Node {
         // dbg!(&self.component, inner_size);
 
         for child in self.children.iter_mut() {
+            if child.layout.hidden {
+                child.layout_result = LayoutResult {
+                    size: Size {
+                        width: Dimension::Px(0.0),
+                        height: Dimension::Px(0.0),
+                    },
+                    ..Default::default()
+                };
+                continue;
+            }
+
             // Stretch alignment
             if self.layout.cross_alignment == Alignment::Stretch {
                 *child.layout_result.size.cross_mut(dir) = Dimension::Pct(100.0)
@@ -686,7 +705,7 @@ impl super::node::Node {
         }
         main_remaining = main_remaining.max(0.0);
 
-        for child in self.children.iter_mut() {
+        for child in self.children.iter_mut().filter(|c| !c.layout.hidden) {
             if self.layout.axis_alignment == Alignment::Stretch
                 && !child.layout_result.size.main(dir).resolved()
             {
@@ -778,11 +797,40 @@ impl super::node::Node {
         let mut row_lengths: Vec<(f64, usize)> = vec![];
         let mut row_elements_count: usize = 0;
 
+        // The lowest baseline of all the children, which the others will be aligned to
+        let max_baseline = if cross_align == Alignment::Baseline && dir == Direction::Row {
+            self.children
+                .iter()
+                .filter(|c| !c.layout.hidden && c.layout.position_type == PositionType::Relative)
+                .filter_map(|c| {
+                    c.baseline().map(|b| {
+                        b + f64::from(
+                            c.layout
+                                .margin
+                                .maybe_resolve(&size)
+                                .top
+                                .maybe_px()
+                                .unwrap_or(0.0),
+                        )
+                    })
+                })
+                .reduce(f64::max)
+        } else {
+            None
+        };
+
         // Reverse the calculation when End axis_aligned
         let mut children: Vec<&mut Self> = if axis_align == Alignment::End {
-            self.children.iter_mut().rev().collect()
+            self.children
+                .iter_mut()
+                .rev()
+                .filter(|c| !c.layout.hidden)
+                .collect()
         } else {
-            self.children.iter_mut().collect()
+            self.children
+                .iter_mut()
+                .filter(|c| !c.layout.hidden)
+                .collect()
         };
 
         for child in children.iter_mut() {
@@ -816,13 +864,23 @@ impl super::node::Node {
                 *child.layout_result.position.cross_mut(dir, cross_align) +=
                     margin.cross(dir, cross_align);
 
+                // TODO: Align baselines per row when wrapping
+                let baseline_offset = match (max_baseline, child.baseline()) {
+                    (Some(max), Some(b)) => {
+                        max - b - f64::from(margin.top.maybe_px().unwrap_or(0.0))
+                    }
+                    _ => 0.0,
+                };
+                *child.layout_result.position.cross_mut(dir, cross_align) +=
+                    Dimension::Px(baseline_offset);
+
                 child.resolve_position(size);
 
                 // Push bounds
                 main_pos += f64::from(child_outer_size.main(dir));
                 row_elements_count += 1;
-                if f64::from(child_outer_size.cross(dir)) > max_cross_size {
-                    max_cross_size = child_outer_size.cross(dir).into();
+                if f64::from(child_outer_size.cross(dir)) + baseline_offset > max_cross_size {
+                    max_cross_size = f64::from(child_outer_size.cross(dir)) + baseline_offset;
                 }
 
                 if cfg!(debug_assertions) && child.layout.debug.is_some() {
@@ -856,7 +914,7 @@ impl super::node::Node {
         row_lengths.push((main_pos + main_end_padding, row_elements_count));
 
         // Combined size of children
-        let mut children_size = if self.children.is_empty() {
+        let mut children_size = if self.children.iter().all(|c| c.layout.hidden) {
             Size::default()
         } else {
             // This won't be accurate for wrapped elements, but it doesn't really matter
@@ -890,7 +948,7 @@ impl super::node::Node {
             let mut elements_positioned_in_row = 0;
             let mut current_row = 0;
             for child in self.children.iter_mut() {
-                if child.layout.position_type == PositionType::Absolute {
+                if child.layout.position_type == PositionType::Absolute || child.layout.hidden {
                     continue;
                 }
                 let main_offset = if self.layout.wrap {
@@ -933,6 +991,21 @@ impl super::node::Node {
         children_size
     }
 
+    /// The distance from the top of this node to the first text baseline of its content, if any.
+    /// Falls back to the baseline of the first child that has one.
+    fn baseline(&self) -> Option<f64> {
+        if let Some(b) = self.component.baseline() {
+            return Some(b.into());
+        }
+        self.children
+            .iter()
+            .filter(|c| !c.layout.hidden)
+            .find_map(|c| {
+                c.baseline()
+                    .map(|b| b + f64::from(c.layout_result.position.top.maybe_px().unwrap_or(0.0)))
+            })
+    }
+
     /// Make sure the node has a size, either taken from its children or from itself
     fn resolve_size(&mut self, mut size: Size, children_size: Size) {
         let min_size = self.layout.min_size;
@@ -1184,6 +1257,12 @@ macro_rules! lay {
                 $param : $crate::layout::Alignment::Stretch,
         ))
     );
+    ( @ { $(,)* $param:ident : Baseline $($rest:tt)* } -> ($($result:tt)*) ) => (
+        lay!(@ { $($rest)* } -> (
+            $($result)*
+                $param : $crate::layout::Alignment::Baseline,
+        ))
+    );
 
     // z_index
     ( @ { $(,)* z_index : $z_index:expr, $($rest:tt)* } -> ($($result:tt)*) ) => (
@@ -2002,6 +2081,58 @@ mod tests {
         assert_eq!(nodes.children[2].layout_result.position.top, px!(258.5));
     }
 
+    #[test]
+    fn test_hidden() {
+        let nodes = |hidden: bool| {
+            node!(Div::new(), lay!(direction: Direction::Row))
+                .push(node!(Div::new(), lay!(size: size!(100.0))))
+                .push(node!(
+                    Div::new(),
+                    lay!(size: size!(50.0, 200.0), hidden: hidden)
+                ))
+                .push(node!(Div::new(), lay!(size: size!(100.0))))
+        };
+
+        let mut hidden = nodes(true);
+        hidden.calculate_layout(&crate::font_cache::FontCache::default(), 1.0);
+        assert_eq!(hidden.layout_result.size, size!(200.0, 100.0));
+        assert_eq!(hidden.children[2].layout_result.position.left, px!(100.0));
+
+        let mut shown = nodes(false);
+        shown.calculate_layout(&crate::font_cache::FontCache::default(), 1.0);
+        assert_eq!(shown.layout_result.size, size!(250.0, 200.0));
+        assert_eq!(shown.children[1].layout_result.position.left, px!(100.0));
+        assert_eq!(shown.children[2].layout_result.position.left, px!(150.0));
+    }
+
+    #[test]
+    fn test_baseline_alignment() {
+        use crate::style::Styled;
+        use crate::txt;
+        use crate::widgets::Text;
+
+        let mut font_cache = crate::font_cache::FontCache::default();
+        font_cache.add_font("noto sans regular".to_string(), ttf_noto_sans::REGULAR);
+
+        let mut nodes = node!(
+            Div::new(),
+            lay!(size: size!(300.0), direction: Direction::Row, cross_alignment: Alignment::Baseline)
+        )
+        .push(node!(Text::new(txt!("Small")).style("size", 12.0)))
+        .push(node!(Text::new(txt!("Big")).style("size", 32.0)));
+        nodes.calculate_layout(&font_cache, 1.0);
+
+        let small = &nodes.children[0];
+        let big = &nodes.children[1];
+        let small_baseline = f64::from(small.layout_result.position.top)
+            + f64::from(small.component.baseline().unwrap());
+        let big_baseline = f64::from(big.layout_result.position.top)
+            + f64::from(big.component.baseline().unwrap());
+        assert!((small_baseline - big_baseline).abs() < 0.01);
+        assert!(f64::from(small.layout_result.position.top) > 0.0);
+        assert_eq!(big.layout_result.position.top, px!(0.0));
+    }
+
     #[test]
     fn test_absolute_positioning() {
         let mut nodes = node!(
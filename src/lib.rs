@@ -20,6 +20,8 @@ pub mod instrumenting;
 mod base_types;
 pub use base_types::*;
 
+pub mod colors;
+
 #[macro_use]
 pub mod layout;
 
@@ -46,10 +48,17 @@ pub use component::*;
 
 pub mod font_cache;
 
+pub mod accessibility;
+
+#[macro_use]
+pub mod locale;
+
+pub mod format;
+
 #[macro_use]
 pub mod style;
 #[doc(inline)]
-pub use style::{Style, Styled};
+pub use style::{Style, StyleSheet, Styled, Theme};
 
 mod ui;
 pub use ui::*;
@@ -69,6 +78,14 @@ pub use open_iconic::Icon;
 /// Used to construct the geometry used by [`renderables::Shape`].
 pub extern crate lyon;
 
+/// Used by backends to hand the [`accessibility`] tree to the OS.
+#[cfg(feature = "accesskit")]
+pub extern crate accesskit;
+
+/// Provides the dates used by [`widgets::DatePicker`].
+#[cfg(feature = "date-picker")]
+pub extern crate chrono;
+
 // Test stub window
 #[cfg(feature = "docs")]
 #[doc(hidden)]
@@ -110,5 +127,13 @@ pub mod lemna_baseview {
         pub fn resizable(self, _resizable: bool) -> Self {
             self
         }
+
+        pub fn min_size(self, _dims: (u32, u32)) -> Self {
+            self
+        }
+
+        pub fn max_size(self, _dims: (u32, u32)) -> Self {
+            self
+        }
     }
 }
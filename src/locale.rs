@@ -0,0 +1,182 @@
+//! Translate the strings shown by an app, and lay text out in the direction of the user's language.
+//!
+//! The current [`Locale`] is set with [`UI#set_locale`][crate::UI#method.set_locale]. Strings are translated with the [`tr!`][crate::tr] macro, which looks them up with the current `Locale`. The built-in widgets translate their strings this way, with their English text as the key, so a lookup function only needs to know the strings that it translates:
+//!
+//! ```ignore
+//! ui.set_locale(Locale::new("de").lookup(|key| match key {
+//!     "Tu" => Some("Di".into()),
+//!     _ => None,
+//! }));
+//! ```
+//!
+//! Strings that are not found are shown untranslated.
+
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::style::HorizontalPosition;
+
+/// The direction that a language is written in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Mirror `alignment` for right-to-left text, so that [`HorizontalPosition::Left`] is always where lines start.
+    pub fn align(self, alignment: HorizontalPosition) -> HorizontalPosition {
+        match (self, alignment) {
+            (Self::RightToLeft, HorizontalPosition::Left) => HorizontalPosition::Right,
+            (Self::RightToLeft, HorizontalPosition::Right) => HorizontalPosition::Left,
+            (_, a) => a,
+        }
+    }
+}
+
+type Lookup = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// The language that an app is shown in: how its strings are translated, and which direction its text runs in.
+#[derive(Clone)]
+pub struct Locale {
+    /// The name of the locale, e.g. `"de"` or `"en-US"`.
+    pub name: String,
+    pub direction: TextDirection,
+    /// The character that separates the whole and fractional parts of numbers, e.g. `','` in German. Used by the formatters in [`format`][crate::format].
+    pub decimal_separator: char,
+    lookup: Option<Lookup>,
+}
+
+impl fmt::Debug for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Locale")
+            .field("name", &self.name)
+            .field("direction", &self.direction)
+            .field("decimal_separator", &self.decimal_separator)
+            .finish()
+    }
+}
+
+impl Default for Locale {
+    /// English, which does not translate anything.
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl Locale {
+    /// A left-to-right locale that does not translate anything.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            direction: TextDirection::LeftToRight,
+            decimal_separator: '.',
+            lookup: None,
+        }
+    }
+
+    /// Translate strings with `lookup`, which is given a key and returns its translation, if it has one.
+    pub fn lookup<F>(mut self, lookup: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.lookup = Some(Arc::new(lookup));
+        self
+    }
+
+    /// Write text right to left, as in Arabic or Hebrew.
+    pub fn rtl(mut self) -> Self {
+        self.direction = TextDirection::RightToLeft;
+        self
+    }
+
+    /// Separate the whole and fractional parts of numbers with `separator`.
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// The translation of `key`, or `key` itself if there is none.
+    pub fn translate(&self, key: &str) -> String {
+        self.lookup
+            .as_ref()
+            .and_then(|lookup| lookup(key))
+            .unwrap_or_else(|| key.to_string())
+    }
+}
+
+fn locale_lock() -> &'static RwLock<Locale> {
+    static CURRENT_LOCALE: OnceLock<RwLock<Locale>> = OnceLock::new();
+    CURRENT_LOCALE.get_or_init(Default::default)
+}
+
+/// The [`Locale`] that the UI is currently using.
+pub fn current_locale() -> Locale {
+    locale_lock().read().unwrap().clone()
+}
+
+pub(crate) fn set_locale(locale: Locale) {
+    *locale_lock().write().unwrap() = locale;
+}
+
+/// The direction of the current [`Locale`]'s text.
+pub fn text_direction() -> TextDirection {
+    locale_lock().read().unwrap().direction
+}
+
+/// The decimal separator of the current [`Locale`].
+pub fn decimal_separator() -> char {
+    locale_lock().read().unwrap().decimal_separator
+}
+
+/// Translate `key` with the current [`Locale`]. See [`tr!`][crate::tr].
+pub fn translate(key: &str) -> String {
+    locale_lock().read().unwrap().translate(key)
+}
+
+/// Translate a string with the current [`Locale`][crate::locale::Locale], returning a `String`. Strings without a translation are returned as they are.
+///
+/// ```ignore
+/// node!(Button::new(txt!(tr!("Save"))))
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::locale::translate($key)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let locale = Locale::new("de").lookup(|key| match key {
+            "Tu" => Some("Di".to_string()),
+            _ => None,
+        });
+        assert_eq!(locale.translate("Tu"), "Di");
+        // Missing translations fall back to the key
+        assert_eq!(locale.translate("Mo"), "Mo");
+        assert_eq!(Locale::default().translate("Tu"), "Tu");
+    }
+
+    #[test]
+    fn test_rtl_alignment() {
+        let rtl = Locale::new("he").rtl().direction;
+        assert_eq!(
+            rtl.align(HorizontalPosition::Left),
+            HorizontalPosition::Right
+        );
+        assert_eq!(
+            rtl.align(HorizontalPosition::Center),
+            HorizontalPosition::Center
+        );
+        assert_eq!(
+            TextDirection::LeftToRight.align(HorizontalPosition::Left),
+            HorizontalPosition::Left
+        );
+    }
+}
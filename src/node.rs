@@ -1,6 +1,10 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
 
 use crate::base_types::*;
 use crate::component::*;
@@ -8,13 +12,69 @@ use crate::event::{self, Event, EventInput};
 use crate::font_cache::FontCache;
 use crate::layout::*;
 use crate::render::{Caches, Renderable};
+use crate::style::Styled;
+use crate::widgets::{Badge, ClipMesh, ClipPath, FocusRing, PerfOverlay, Toasts, ToolTip};
 
 static NODE_ID_ATOMIC: AtomicU64 = AtomicU64::new(1);
 
+/// The key of the [`ToolTip`] Node that is pushed onto a Node with a [`Node#method.tooltip`].
+const TOOLTIP_KEY: u64 = u64::MAX;
+/// The key of the [`FocusRing`] Node that is pushed onto a Node that was focused with the keyboard.
+const FOCUS_RING_KEY: u64 = u64::MAX - 1;
+/// The key of the [`Badge`] Node that is pushed onto a Node with a [`Node#method.badge`].
+const BADGE_KEY: u64 = u64::MAX - 2;
+/// The key of the [`Toasts`] Node that is pushed onto the root Node.
+const TOASTS_KEY: u64 = u64::MAX - 3;
+/// The key of the outline that is pushed onto the focused Node and its ancestors, when [`UI#focus_debug`][crate::UI#method.focus_debug] is on.
+const FOCUS_DEBUG_KEY: u64 = u64::MAX - 4;
+/// The key of the [`PerfOverlay`] Node that is pushed onto the root Node, when [`UI#perf_overlay`][crate::UI#method.perf_overlay] is on.
+const PERF_OVERLAY_KEY: u64 = u64::MAX - 5;
+
+/// Call `update` on `component`, the Component of the Node with the id `id`. See [`Node#update`].
+fn update_component(
+    component: &mut (dyn Component + Send + Sync),
+    id: u64,
+    message: Message,
+    scheduled: &ScheduledQueue,
+) -> Vec<Message> {
+    let mut messages = vec![];
+    let mut pending = VecDeque::from([message]);
+    while let Some(message) = pending.pop_front() {
+        for message in component.update(message) {
+            match message.downcast::<Command>() {
+                Ok(command) => match *command {
+                    Command::Then(message) => pending.push_back(message),
+                    Command::After(duration, message) => scheduled.push(id, duration, message),
+                },
+                Err(message) => messages.push(message),
+            }
+        }
+    }
+    messages
+}
+
+thread_local!(
+    static VIEWING_DISABLED: Cell<bool> = const { Cell::new(false) };
+    static LAYOUT_WINDOW: Cell<Option<AABB>> = const { Cell::new(None) };
+);
+
+/// The physical bounds of the window whose Nodes are being laid out, if any.
+pub(crate) fn layout_window() -> Option<AABB> {
+    LAYOUT_WINDOW.with(|w| w.get())
+}
+
+/// Whether the Component whose [`view`][Component#method.view] is being built is within a [disabled][Node#method.disabled] Node, e.g. so that it can draw itself with the `:disabled` variants of its styles. See [`Interaction`][crate::style::Interaction]. Always `false` outside of `view`; [`RenderContext#disabled`][RenderContext#structfield.disabled] is the equivalent for `render`.
+pub fn subtree_disabled() -> bool {
+    VIEWING_DISABLED.with(|d| d.get())
+}
+
+/// The id of a [`Node`]. A Node keeps its id from one view to the next, as long as it and its ancestors keep their [keys][Node#method.key]. Look one up by [reference][Node#method.reference] with [`UI#node_id`][crate::UI#method.node_id].
+pub type NodeId = u64;
+
 // (<Event that the node desires to receive>, <Node ID>)
-pub(crate) type Registration = (event::Register, u64);
+pub(crate) type Registration = (event::Register, NodeId);
 
-fn new_node_id() -> u64 {
+fn new_node_id() -> NodeId {
     NODE_ID_ATOMIC.fetch_add(1, Ordering::SeqCst)
 }
 
@@ -78,7 +138,7 @@ macro_rules! node {
 ///
 /// When combined together, `Node`s form a graph that represents the application: the graph is responsible for handling events, it knows how to render itself, and it holds all of the required state. See the [tutorial][crate] for an explanation of how to use Nodes to create an application.
 pub struct Node {
-    pub(crate) id: u64,
+    pub(crate) id: NodeId,
     pub(crate) component: Box<dyn Component + Send + Sync>,
     pub(crate) render_cache: Option<Vec<Renderable>>,
     pub(crate) children: Vec<Node>,
@@ -94,6 +154,49 @@ pub struct Node {
     pub(crate) props_hash: u64,
     pub(crate) render_hash: u64,
     pub(crate) key: u64,
+    pub(crate) tooltip: Option<String>,
+    show_tooltip: bool,
+    tooltip_state: ToolTipState,
+    badge: Option<Badge>,
+    /// Whether this Node shows the [`Toasts`], which only the root Node does.
+    pub(crate) toasts: bool,
+    /// The [`PerfOverlay`] shown over this Node, which only the root Node has.
+    pub(crate) perf_overlay: Option<PerfOverlay>,
+    pub(crate) reference: Option<String>,
+    scroll_margin: f32,
+    /// Whether this Node or one of its ancestors is disabled.
+    pub(crate) disabled: bool,
+    clip_path: Option<ClipPath>,
+    /// The tessellated `clip_path`, for the Node's current size.
+    clip_mesh: Option<Arc<ClipMesh>>,
+    tab_index: i32,
+    // Whether the scope traps the focus
+    focus_scope: Option<bool>,
+    autofocus: bool,
+    focus_visible: bool,
+    focus_debug: Option<FocusOutline>,
+}
+
+/// How a Node is outlined when [`UI#focus_debug`][crate::UI#method.focus_debug] is on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FocusOutline {
+    Focused,
+    Ancestor,
+}
+
+/// Tracks the mouse and keyboard focus over a Node with a [`Node#method.tooltip`].
+#[derive(Debug, Default)]
+struct ToolTipState {
+    hover_start: Option<Instant>,
+    mouse_position: Point,
+    /// When the keyboard focus moved into the Node, while it is still there.
+    focus_start: Option<Instant>,
+    /// Whether the tool tip was dismissed with Escape. It stays closed until the mouse and focus leave the Node.
+    dismissed: bool,
+    /// Where the [`ToolTip`] was opened, relative to the Node, in logical coordinates.
+    open: Option<Point>,
+    /// Whether the tool tip is open below the Node, rather than next to the mouse.
+    anchored: bool,
 }
 
 impl fmt::Debug for Node {
@@ -108,11 +211,23 @@ impl fmt::Debug for Node {
             .field("props_hash", &self.props_hash)
             .field("render_hash", &self.render_hash)
             .field("key", &self.key)
+            .field("reference", &self.reference)
             .field("children", &self.children)
             .finish()
     }
 }
 
+/// How far to scroll so that the span from `start` to `end` is inside of the frame from `frame_start` to `frame_end`. Spans that don't fit are aligned to the start of the frame.
+fn reveal_delta(start: f32, end: f32, frame_start: f32, frame_end: f32) -> f32 {
+    if start < frame_start || end - start > frame_end - frame_start {
+        start - frame_start
+    } else if end > frame_end {
+        end - frame_end
+    } else {
+        0.0
+    }
+}
+
 fn expand_aabb(a: &mut AABB, b: AABB) {
     if a.pos.x > b.pos.x {
         a.pos.x = b.pos.x;
@@ -145,6 +260,22 @@ impl Node {
             render_cache: None,
             props_hash: u64::max_value(),
             render_hash: u64::max_value(),
+            tooltip: None,
+            show_tooltip: false,
+            tooltip_state: Default::default(),
+            badge: None,
+            toasts: false,
+            perf_overlay: None,
+            reference: None,
+            scroll_margin: 0.0,
+            disabled: false,
+            clip_path: None,
+            clip_mesh: None,
+            tab_index: 0,
+            focus_scope: None,
+            autofocus: false,
+            focus_visible: false,
+            focus_debug: None,
         }
     }
 
@@ -160,6 +291,81 @@ impl Node {
         self
     }
 
+    /// Name the current Node, returns itself. The name can be used to find the Node from outside of the Component tree, e.g. to [`focus`][crate::UI#method.focus] it.
+    pub fn reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    /// Leave `margin` logical pixels around the current Node when it is scrolled into view, returns itself. This is added to the `scroll_padding` of the containers that scroll it, e.g. [`Div#scroll_padding`][crate::widgets::Div#method.scroll_padding].
+    pub fn scroll_margin(mut self, margin: f32) -> Self {
+        self.scroll_margin = margin;
+        self
+    }
+
+    /// Disable the current Node and all of its descendants, returns itself. They receive no mouse, keyboard or focus events, and are left out of the Tab order, so that e.g. a whole section of settings can be disabled without disabling each widget in it. Components can check [`subtree_disabled`] and [`RenderContext#disabled`][RenderContext#structfield.disabled] to draw themselves dimmed.
+    ///
+    /// Disabling stacks: a descendant of a disabled Node is disabled, whatever it is given here.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Clip the descendants of the current Node to `clip_path`, returns itself. Clip paths nest: a Node inside of a clipped Node is clipped by both, as well as by the frames of any scrollable ancestors. Mouse events outside of the path fall through to whatever is beneath the current Node.
+    ///
+    /// The current Node's own renderables are not clipped.
+    pub fn clip_path<C: Into<ClipPath>>(mut self, clip_path: C) -> Self {
+        self.clip_path = Some(clip_path.into());
+        self
+    }
+
+    /// Set the position of the current Node in the Tab order, returns itself. Nodes with higher indexes come later, and Nodes with equal indexes (the default is `0`) are ordered as they are in the tree. A negative index takes a Node out of the Tab order, though it can still be focused with a click or [`UI#focus`][crate::UI#method.focus].
+    pub fn tab_index(mut self, tab_index: i32) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Make the current Node a focus scope, returns itself. The focusable Nodes of a scope are ordered by their [`tab_index`][Node#method.tab_index] amongst themselves, and the scope as a whole takes the place of the current Node in the Tab order.
+    ///
+    /// When `trap` is true, Tab and Shift+Tab cycle through the scope without ever leaving it, as a modal dialog should. If the focus is outside of every trapping scope, Tab moves it into the last one.
+    pub fn focus_scope(mut self, trap: bool) -> Self {
+        self.focus_scope = Some(trap);
+        self
+    }
+
+    /// Focus the current Node when the UI is first drawn, returns itself. If the Node is not [focusable][Component#method.focusable], its first focusable descendant is focused instead, e.g. the text of a [`TextBox`][crate::widgets::TextBox]. Only the first autofocus Node of the tree gets the focus.
+    pub fn autofocus(mut self) -> Self {
+        self.autofocus = true;
+        self
+    }
+
+    /// Attach a text [`ToolTip`] to the current Node, returns itself. The tool tip is shown once the mouse has rested over the Node for a second, and is dismissed when the mouse leaves the Node. It is also shown below the Node shortly after the keyboard moves the focus into the Node, until the focus leaves it. Escape dismisses it.
+    pub fn tooltip(mut self, text: impl Into<String>) -> Self {
+        self.tooltip = Some(text.into());
+        self
+    }
+
+    /// Show the [`tooltip`][Node#method.tooltip] of the current Node below it while `show` is true, whatever the mouse and focus are doing, returns itself. E.g. to point out a validation error on a field:
+    ///
+    /// ```ignore
+    /// node!(TextBox::new(None)).tooltip("Not a number").show_tooltip(invalid)
+    /// ```
+    pub fn show_tooltip(mut self, show: bool) -> Self {
+        self.show_tooltip = show;
+        self
+    }
+
+    /// Show a [`Badge`] over the top right corner of the current Node, returns itself. A count of `0` is hidden, unless the badge is made with [`Badge#show_zero`][Badge#method.show_zero]:
+    ///
+    /// ```ignore
+    /// node!(Button::new(txt!("Inbox"))).badge(unread)
+    /// node!(Button::new(txt!("Updates"))).badge(Badge::dot())
+    /// ```
+    pub fn badge(mut self, badge: impl Into<Badge>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
     pub(crate) fn view(
         &mut self,
         mut prev: Option<&mut Self>,
@@ -170,6 +376,9 @@ impl Node {
         let mut hasher = ComponentHasher::new_with_keys(0, 0);
         if let Some(prev) = &mut prev {
             self.id = prev.id;
+            self.tooltip_state = std::mem::take(&mut prev.tooltip_state);
+            self.focus_visible = prev.focus_visible;
+            self.focus_debug = prev.focus_debug;
             if let Some(state) = prev.component.take_state() {
                 self.component.replace_state(state);
             }
@@ -177,7 +386,12 @@ impl Node {
             self.component.props_hash(&mut hasher);
             self.props_hash = hasher.finish();
 
-            if self.props_hash != prev.props_hash {
+            // Props that can be compared take precedence over the hash
+            let props_eq = prev
+                .component
+                .as_any()
+                .and_then(|prev| self.component.props_eq(prev));
+            if !props_eq.unwrap_or(self.props_hash == prev.props_hash) {
                 self.component.new_props();
             } // Maybe TODO: If nodes were clonable, it could make sense to clone them here rather than create them with `view`
         } else {
@@ -188,7 +402,10 @@ impl Node {
         }
 
         // Create children
-        if let Some(mut child) = self.component.view() {
+        VIEWING_DISABLED.with(|d| d.set(self.disabled));
+        let view = self.component.view();
+        VIEWING_DISABLED.with(|d| d.set(false));
+        if let Some(mut child) = view {
             if let Some(indexes) = self.component.container() {
                 // Pull out the children that were pushed onto this node, since we need to moves
                 // them to the correct position.
@@ -217,7 +434,83 @@ impl Node {
             }
         }
 
+        if let Some(badge) = self.badge.take().filter(|b| b.visible()) {
+            let layout = badge.layout();
+            self.children
+                .push(Node::new(Box::new(badge), BADGE_KEY, layout));
+        }
+
+        if self.toasts {
+            let toasts = Toasts::new();
+            let layout = toasts.layout();
+            self.children
+                .push(Node::new(Box::new(toasts), TOASTS_KEY, layout));
+        }
+
+        if let Some(overlay) = self.perf_overlay.take() {
+            let layout = overlay.layout();
+            self.children
+                .push(Node::new(Box::new(overlay), PERF_OVERLAY_KEY, layout));
+        }
+
+        if let (Some(p), Some(text)) = (self.tooltip_state.open, self.tooltip_text()) {
+            let mut tool_tip = ToolTip::new(text.to_string());
+            if self.tooltip_state.anchored {
+                // Flip up over the Node, rather than the mouse
+                tool_tip = tool_tip.anchor_height(p.y);
+            }
+            self.children.push(Node::new(
+                Box::new(tool_tip),
+                TOOLTIP_KEY,
+                Layout {
+                    position_type: PositionType::Absolute,
+                    z_index_increment: 1000.0,
+                    position: p.into(),
+                    ..Default::default()
+                },
+            ));
+        }
+
+        if self.focus_visible {
+            if let Some(ring) = self.component.focus_ring() {
+                self.children.push(Node::new(
+                    Box::new(ring),
+                    FOCUS_RING_KEY,
+                    Layout {
+                        position_type: PositionType::Absolute,
+                        z_index_increment: 1000.0,
+                        ..Default::default()
+                    },
+                ));
+            }
+        }
+
+        if let Some(outline) = self.focus_debug {
+            let color = match outline {
+                FocusOutline::Focused => Color::new(1.0, 0.0, 0.0, 1.0),
+                FocusOutline::Ancestor => Color::new(1.0, 0.0, 0.0, 0.3),
+            };
+            self.children.push(Node::new(
+                Box::new(
+                    FocusRing::new()
+                        .style("color", color)
+                        .style("width", 1.0)
+                        .style("radius", 0.0)
+                        .style("offset", 0.0),
+                ),
+                FOCUS_DEBUG_KEY,
+                Layout {
+                    position_type: PositionType::Absolute,
+                    z_index_increment: 1000.0,
+                    ..Default::default()
+                },
+            ));
+        }
+
         // View children
+        for child in self.children.iter_mut() {
+            child.disabled |= self.disabled;
+        }
         if let Some(prev) = prev.as_mut() {
             let prev_children = &mut prev.children;
             for child in self.children.iter_mut() {
@@ -267,6 +560,9 @@ impl Node {
         self.aabb.bottom_right += parent_pos.into();
         self.aabb.pos.z = (self.layout.z_index.unwrap_or((parent_pos.z + 1.0).into())
             + self.layout.z_index_increment) as f32;
+        if self.layout.pixel_snap {
+            self.aabb.round_mut();
+        }
 
         if full_control {
             let children: Vec<(&mut AABB, Option<Scale>, Option<Point>)> = self
@@ -318,7 +614,7 @@ impl Node {
         }
 
         let scrollable = self.scrollable();
-        for child in self.children.iter_mut() {
+        for child in self.children.iter_mut().filter(|c| !c.layout.hidden) {
             let mut scroll_offset: Size = parent_scroll_pos.into();
             if !child.layout.position.top.resolved() && !child.layout.position.bottom.resolved() {
                 scroll_offset.height = Dimension::Px(0.0);
@@ -347,44 +643,53 @@ impl Node {
 
     pub(crate) fn layout(&mut self, _prev: &Self, font_cache: &FontCache, scale_factor: f32) {
         self.calculate_layout(font_cache, scale_factor);
+        let window = (AABB::from(self.layout_result) * scale_factor).round();
+        LAYOUT_WINDOW.with(|w| w.set(Some(window)));
         self.set_aabb(
             Pos::default(),
             self.aabb,
             ScrollPosition::default(),
             false,
-            (AABB::from(self.layout_result) * scale_factor).round(),
+            window,
             scale_factor,
         );
+        LAYOUT_WINDOW.with(|w| w.set(None));
     }
 
-    /// Return whether to redraw the screen
+    /// Return whether to redraw the screen. When `force` is set, every node is rendered, even if its render hash has not changed.
     pub(crate) fn render(
         &mut self,
         caches: Caches,
         prev: Option<&mut Self>,
         scale_factor: f32,
+        force: bool,
     ) -> bool {
+        if self.layout.hidden {
+            self.render_cache = None;
+            self.render_hash = 0;
+            return false;
+        }
         // TODO: skip non-visible nodes
-        let mut hasher = ComponentHasher::new_with_keys(0, 0);
+        self.render_hash = self.compute_render_hash();
         if let Some(prev) = prev {
             let mut ret = false;
-            self.component.render_hash(&mut hasher);
-            self.aabb.size().hash(&mut hasher);
-            self.inner_scale.hash(&mut hasher);
-            self.render_hash = hasher.finish();
 
-            if self.render_hash != prev.render_hash {
+            if force || self.render_hash != prev.render_hash {
                 let context = RenderContext {
                     aabb: self.aabb,
                     inner_scale: self.inner_scale,
                     caches: caches.clone(),
                     prev_state: prev.render_cache.take(),
                     scale_factor,
+                    pixel_snap: self.layout.pixel_snap,
+                    disabled: self.disabled,
                 };
                 self.render_cache = self.component.render(context);
+                self.clip_mesh = self.render_clip_mesh(scale_factor);
                 ret = true;
             } else {
                 self.render_cache = prev.render_cache.take();
+                self.clip_mesh = prev.clip_mesh.take();
             }
 
             let prev_children = &mut prev.children;
@@ -393,6 +698,7 @@ impl Node {
                     caches.clone(),
                     prev_children.iter_mut().find(|x| x.key == child.key),
                     scale_factor,
+                    force,
                 )
             }
 
@@ -404,19 +710,36 @@ impl Node {
                 caches: caches.clone(),
                 prev_state: None,
                 scale_factor,
+                pixel_snap: self.layout.pixel_snap,
+                disabled: self.disabled,
             };
             self.render_cache = self.component.render(context);
-            self.component.render_hash(&mut hasher);
-            self.render_hash = hasher.finish();
+            self.clip_mesh = self.render_clip_mesh(scale_factor);
 
             for child in self.children.iter_mut() {
-                child.render(caches.clone(), None, scale_factor);
+                child.render(caches.clone(), None, scale_factor, false);
             }
 
             true
         }
     }
 
+    /// Hash everything that the renderables of this Node depend on. When this is unchanged from the previous frame, rendering is skipped.
+    fn compute_render_hash(&self) -> u64 {
+        let mut hasher = ComponentHasher::new_with_keys(0, 0);
+        if let Some(key) = self.component.memo_key() {
+            key.hash(&mut hasher);
+        } else {
+            self.component.render_hash(&mut hasher);
+        }
+        self.aabb.size().hash(&mut hasher);
+        self.inner_scale.hash(&mut hasher);
+        self.layout.pixel_snap.hash(&mut hasher);
+        self.disabled.hash(&mut hasher);
+        self.clip_path.hash(&mut hasher);
+        hasher.finish()
+    }
+
     pub(crate) fn scroll_x(&self) -> Option<f32> {
         self.component.scroll_position().and_then(|p| p.x)
     }
@@ -429,6 +752,39 @@ impl Node {
         self.scroll_x().is_some() || self.scroll_y().is_some()
     }
 
+    fn render_clip_mesh(&self, scale_factor: f32) -> Option<Arc<ClipMesh>> {
+        self.clip_path
+            .as_ref()
+            .map(|c| Arc::new(c.mesh(self.aabb.size(), scale_factor)))
+    }
+
+    /// The frames that the children of this Node are clipped to, given the `frames` that this Node is clipped to. `None` if they are the same.
+    fn child_frames(&self, frames: &[ClipFrame]) -> Option<Vec<ClipFrame>> {
+        if !self.scrollable() && self.clip_mesh.is_none() {
+            return None;
+        }
+        let mut f = frames.to_vec();
+        if self.scrollable() {
+            f.push(ClipFrame::Scroll(
+                self.component.frame_bounds(self.aabb, self.inner_scale),
+            ));
+        }
+        if let Some(mesh) = &self.clip_mesh {
+            f.push(ClipFrame::Path(mesh.clone(), self.aabb.pos));
+        }
+        Some(f)
+    }
+
+    /// Is the physical `position` inside of the Node's clip path? Always true when it doesn't have one.
+    fn clip_contains(&self, position: Point) -> bool {
+        self.clip_mesh.as_ref().map_or(true, |mesh| {
+            mesh.contains(Point::new(
+                position.x - self.aabb.pos.x,
+                position.y - self.aabb.pos.y,
+            ))
+        })
+    }
+
     pub(crate) fn iter_renderables(&self) -> NodeRenderableIterator<'_> {
         NodeRenderableIterator {
             queue: vec![self],
@@ -468,15 +824,22 @@ impl Node {
         event.over_child_n = None;
         event.over_subchild_n = None;
         for (n, child) in self.children.iter_mut().enumerate() {
-            if child
-                .component
-                .is_mouse_maybe_over(event.mouse_position, child.inclusive_aabb)
+            if !child.layout.hidden
+                && !child.disabled
+                && child
+                    .component
+                    .is_mouse_maybe_over(event.mouse_position, child.inclusive_aabb)
             {
                 for message in child
                     ._handle_event_under_mouse(event, handler, node_order)
                     .drain(..)
                 {
-                    m.append(&mut self.component.update(message));
+                    m.append(&mut update_component(
+                        self.component.as_mut(),
+                        self.id,
+                        message,
+                        &event.scheduled,
+                    ));
                     if self.component.is_dirty() {
                         event.dirty();
                     }
@@ -523,6 +886,10 @@ impl Node {
     }
 
     fn _nodes_under<E: EventInput>(&self, event: &Event<E>, collector: &mut Vec<(u64, f32)>) {
+        if !self.clip_contains(event.mouse_position) {
+            return;
+        }
+
         if self
             .component
             .is_mouse_over(event.mouse_position, self.aabb)
@@ -540,9 +907,11 @@ impl Node {
         }
 
         for child in self.children.iter() {
-            if child
-                .component
-                .is_mouse_maybe_over(event.mouse_position, child.inclusive_aabb)
+            if !child.layout.hidden
+                && !child.disabled
+                && child
+                    .component
+                    .is_mouse_maybe_over(event.mouse_position, child.inclusive_aabb)
             {
                 child._nodes_under(event, collector);
             }
@@ -575,6 +944,310 @@ impl Node {
         current
     }
 
+    /// Scroll the scrollable ancestors of the Node with the id `target` so that it is in view, along with its [`scroll_margin`][Node#method.scroll_margin] and their [`scroll_padding`][Component#method.scroll_padding]. Returns whether anything was scrolled.
+    pub(crate) fn ensure_visible(&mut self, target: u64, scale_factor: f32) -> bool {
+        let stack = match self.get_target_stack(target) {
+            Some(stack) => stack,
+            None => return false,
+        };
+        let target = self.get_target_from_stack(&stack);
+        let margin = target.scroll_margin * scale_factor;
+        let mut rect = target.aabb;
+        rect.pos.x -= margin;
+        rect.pos.y -= margin;
+        rect.bottom_right.x += margin;
+        rect.bottom_right.y += margin;
+
+        let mut scrolled = false;
+        // Innermost first, since scrolling it moves the target within the outer ones
+        for depth in (0..stack.len()).rev() {
+            let node = self.get_target_from_stack(&stack[..depth]);
+            if !node.scrollable() {
+                continue;
+            }
+            let padding = node.component.scroll_padding() * scale_factor;
+            let frame = node.component.frame_bounds(node.aabb, node.inner_scale);
+            let max = node.inner_scale.unwrap() - node.aabb.size();
+            let clamp = |v: f32, max: f32| v.min(max).max(0.0);
+            let from = Point::new(
+                clamp(node.scroll_x().unwrap_or(0.0), max.width),
+                clamp(node.scroll_y().unwrap_or(0.0), max.height),
+            );
+            let mut to = from;
+            if node.scroll_x().is_some() {
+                to.x = clamp(
+                    from.x
+                        + reveal_delta(
+                            rect.pos.x - padding,
+                            rect.bottom_right.x + padding,
+                            frame.pos.x,
+                            frame.bottom_right.x,
+                        ),
+                    max.width,
+                );
+            }
+            if node.scroll_y().is_some() {
+                to.y = clamp(
+                    from.y
+                        + reveal_delta(
+                            rect.pos.y - padding,
+                            rect.bottom_right.y + padding,
+                            frame.pos.y,
+                            frame.bottom_right.y,
+                        ),
+                    max.height,
+                );
+            }
+
+            if to != from {
+                node.component.scroll_to(to);
+                rect.translate_mut(from.x - to.x, from.y - to.y);
+                scrolled = true;
+            }
+        }
+        scrolled
+    }
+
+    /// The id of the first Node in this subtree with the given [`reference`][Node#method.reference].
+    pub(crate) fn get_reference(&self, reference: &str) -> Option<NodeId> {
+        if self.reference.as_deref() == Some(reference) {
+            return Some(self.id);
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.get_reference(reference))
+    }
+
+    /// The [references][Node#method.reference] of the Nodes of this subtree, with their ids, in tree order.
+    pub(crate) fn references(&self) -> Vec<(String, NodeId)> {
+        let mut references = vec![];
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if let Some(reference) = &node.reference {
+                references.push((reference.clone(), node.id));
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        references
+    }
+
+    /// The keys of the Nodes from this one down to the Node with the id `id`, along with the [references][Node#method.reference] found along the way.
+    pub(crate) fn key_path(&self, id: u64) -> Option<(Vec<u64>, Vec<String>)> {
+        let path = self.path_to(id)?;
+        let keys = path.iter().map(|n| n.key).collect();
+        let references = path.iter().filter_map(|n| n.reference.clone()).collect();
+        Some((keys, references))
+    }
+
+    /// The number of Nodes and [`Renderable`]s in this subtree, leaving out the [`PerfOverlay`] so that it does not count itself.
+    pub(crate) fn frame_counts(&self) -> (usize, usize) {
+        self.children
+            .iter()
+            .filter(|c| c.key != PERF_OVERLAY_KEY)
+            .map(Node::frame_counts)
+            .fold(
+                (1, self.render_cache.as_ref().map_or(0, Vec::len)),
+                |(nodes, renderables), (n, r)| (nodes + n, renderables + r),
+            )
+    }
+
+    /// The Nodes from this one down to the Node with the id `id`.
+    pub(crate) fn path_to(&self, id: u64) -> Option<Vec<&Node>> {
+        let stack = self.get_target_stack(id)?;
+        let mut node = self;
+        let mut path = vec![node];
+        for i in stack {
+            node = &node.children[i];
+            path.push(node);
+        }
+        Some(path)
+    }
+
+    /// The AABB of the Node in this subtree with the id `id`.
+    pub(crate) fn aabb_of(&self, id: u64) -> Option<AABB> {
+        if self.id == id {
+            return Some(self.aabb);
+        }
+        self.children.iter().find_map(|child| child.aabb_of(id))
+    }
+
+    /// The ids of the [focusable][Component#method.focusable] Nodes of this subtree, in tree order.
+    pub(crate) fn focusable_ids(&self) -> Vec<u64> {
+        let mut ids = vec![];
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if node.disabled {
+                continue;
+            }
+            if node.component.focusable() {
+                ids.push(node.id);
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        ids
+    }
+
+    /// The id of the Node that the first [`autofocus`][Node#method.autofocus] Node of this subtree should focus.
+    pub(crate) fn autofocus_target(&self) -> Option<u64> {
+        if self.autofocus {
+            return Some(self.focusable_ids().first().copied().unwrap_or(self.id));
+        }
+        self.children
+            .iter()
+            .find_map(|child| child.autofocus_target())
+    }
+
+    /// The ids of the focusable Nodes of this subtree, in Tab order. See [`Node#tab_index`][Node#method.tab_index] and [`Node#focus_scope`][Node#method.focus_scope].
+    pub(crate) fn tab_order(&self) -> Vec<u64> {
+        let mut entries = vec![];
+        if self.disabled {
+            return vec![];
+        }
+        if self.component.focusable() && self.tab_index >= 0 {
+            entries.push((self.tab_index, vec![self.id]));
+        }
+        for child in self.children.iter() {
+            child.tab_entries(&mut entries);
+        }
+        // Stable, so equal indexes stay in tree order
+        entries.sort_by_key(|(tab_index, _)| *tab_index);
+        entries.into_iter().flat_map(|(_, ids)| ids).collect()
+    }
+
+    fn tab_entries(&self, entries: &mut Vec<(i32, Vec<u64>)>) {
+        if self.disabled {
+            return;
+        }
+        if self.focus_scope.is_some() {
+            entries.push((self.tab_index.max(0), self.tab_order()));
+        } else {
+            if self.component.focusable() && self.tab_index >= 0 {
+                entries.push((self.tab_index, vec![self.id]));
+            }
+            for child in self.children.iter() {
+                child.tab_entries(entries);
+            }
+        }
+    }
+
+    /// The innermost trapping focus scope that contains `focus`, or else the last trapping scope of the tree.
+    fn trap_scope(&self, focus: u64) -> Option<&Self> {
+        if let Some(stack) = self.get_target_stack(focus) {
+            let mut node = self;
+            let mut scope = None;
+            for i in stack.iter() {
+                if node.focus_scope == Some(true) {
+                    scope = Some(node);
+                }
+                node = &node.children[*i];
+            }
+            if node.focus_scope == Some(true) {
+                scope = Some(node);
+            }
+            if scope.is_some() {
+                return scope;
+            }
+        }
+        self.last_trap_scope()
+    }
+
+    fn last_trap_scope(&self) -> Option<&Self> {
+        self.children
+            .iter()
+            .rev()
+            .find_map(|child| child.last_trap_scope())
+            .or((self.focus_scope == Some(true)).then_some(self))
+    }
+
+    /// The focusable Node that comes after (or before, when `forward` is false) the one with the id `focus` in the Tab order, wrapping around at the ends. If `focus` is not in the Tab order, the first (or last) Node of it is returned. Trapping focus scopes confine the result to themselves.
+    pub(crate) fn next_focus(&self, focus: u64, forward: bool) -> Option<u64> {
+        let ids = self.trap_scope(focus).unwrap_or(self).tab_order();
+        let len = ids.len();
+        let next = match ids.iter().position(|id| *id == focus) {
+            Some(i) if forward => (i + 1) % len,
+            Some(i) => (i + len - 1) % len,
+            None if forward => 0,
+            None => len.checked_sub(1)?,
+        };
+        ids.get(next).copied()
+    }
+
+    /// Show the [`FocusRing`] of the Node with the id `focus` if `visible`, hiding any other. Returns whether any Node changed.
+    pub(crate) fn set_focus_visible(&mut self, focus: u64, visible: bool) -> bool {
+        let show = visible && self.id == focus;
+        let mut changed = std::mem::replace(&mut self.focus_visible, show) != show;
+        for child in self.children.iter_mut() {
+            changed |= child.set_focus_visible(focus, visible);
+        }
+        changed
+    }
+
+    /// Outline the Node with the id `focus`, and more faintly its ancestors, for [`UI#focus_debug`][crate::UI#method.focus_debug]. A `focus` of `None` removes the outlines. Returns whether any Node changed.
+    pub(crate) fn set_focus_debug(&mut self, focus: Option<u64>) -> bool {
+        self.outline_focus(focus).0
+    }
+
+    /// Returns whether any Node changed, and whether this subtree contains `focus`.
+    fn outline_focus(&mut self, focus: Option<u64>) -> (bool, bool) {
+        let mut changed = false;
+        let mut within = false;
+        for child in self.children.iter_mut() {
+            let (c, w) = child.outline_focus(focus);
+            changed |= c;
+            within |= w;
+        }
+        let outline = if focus == Some(self.id) {
+            Some(FocusOutline::Focused)
+        } else if within {
+            Some(FocusOutline::Ancestor)
+        } else {
+            None
+        };
+        changed |= std::mem::replace(&mut self.focus_debug, outline) != outline;
+        (changed, within || outline.is_some())
+    }
+
+    /// Does the subtree of this Node, including the Node itself, contain the Node with the id `focus`? This is the equivalent of CSS's `:focus-within`.
+    pub fn focus_within(&self, focus: u64) -> bool {
+        self.get_target_stack(focus).is_some()
+    }
+
+    /// Send [`FocusWithin`][event::FocusWithin] events to the Nodes whose subtrees contain only one of `old_focus` and `new_focus`. Returns whether any of them became dirty.
+    pub(crate) fn focus_within_change(
+        &mut self,
+        old_focus: u64,
+        new_focus: u64,
+        event_cache: &event::EventCache,
+    ) -> bool {
+        let old = self.get_target_stack(old_focus);
+        let new = self.get_target_stack(new_focus);
+        // Both paths share the Nodes up to this depth
+        let shared = match (&old, &new) {
+            (Some(o), Some(n)) => Some(o.iter().zip(n.iter()).take_while(|(a, b)| a == b).count()),
+            _ => None,
+        };
+        let mut dirty = false;
+        for (stack, within) in [(old, false), (new, true)] {
+            if let Some(stack) = stack {
+                for depth in shared.map_or(0, |s| s + 1)..=stack.len() {
+                    let node = self.get_target_from_stack(&stack[..depth]);
+                    let mut event = Event::new(event::FocusWithin(within), event_cache);
+                    event.current_node_id = Some(node.id);
+                    event.current_aabb = Some(node.aabb);
+                    event.current_inner_scale = node.inner_scale;
+                    node.component.on_focus_within(&mut event);
+                    if node.component.is_dirty() {
+                        dirty = true;
+                    }
+                    if node.tooltip_text().is_some() {
+                        dirty |= node.tooltip_focus(within && event_cache.focus_visible);
+                    }
+                }
+            }
+        }
+        dirty
+    }
+
     pub(crate) fn get_target_stack(&self, target: u64) -> Option<Vec<usize>> {
         struct Frame<'a> {
             node: &'a Node,
@@ -643,6 +1316,9 @@ impl Node {
     ) {
         if let Some(mut stack) = self.get_target_stack(event.target.unwrap()) {
             let node = self.get_target_from_stack(&stack);
+            if node.disabled {
+                return;
+            }
             event.current_node_id = Some(node.id);
             event.current_aabb = Some(node.aabb);
             event.current_inner_scale = node.inner_scale;
@@ -660,7 +1336,7 @@ impl Node {
                 let mut dirty = false;
                 let mut next_messages: Vec<Message> = vec![];
                 for message in event.messages.drain(..) {
-                    next_messages.append(&mut node.component.update(message));
+                    next_messages.append(&mut node.update(message, &event.scheduled));
                     if node.component.is_dirty() {
                         dirty = true;
                     }
@@ -677,17 +1353,23 @@ impl Node {
         }
     }
 
+    /// Call [`Component#update`] with `message`, carrying out any [`Command`]s that are returned. The messages of [`Command::After`]s are added to `scheduled`. The other messages are returned, to be passed on to the parent Node.
+    pub(crate) fn update(&mut self, message: Message, scheduled: &ScheduledQueue) -> Vec<Message> {
+        update_component(self.component.as_mut(), self.id, message, scheduled)
+    }
+
     pub(crate) fn send_messages(
         &mut self,
         mut target_stack: Vec<usize>,
         messages: &mut Vec<Message>,
+        scheduled: &ScheduledQueue,
     ) -> bool {
         let mut dirty = false;
         loop {
             let node = self.get_target_from_stack(&target_stack);
             let mut next_messages: Vec<Message> = vec![];
             for message in messages.drain(..) {
-                next_messages.append(&mut node.component.update(message));
+                next_messages.append(&mut node.update(message, scheduled));
                 if node.component.is_dirty() {
                     dirty = true;
                 }
@@ -711,6 +1393,14 @@ impl Node {
         self.handle_event_under_mouse(event, |node, e| node.component.on_scroll(e));
     }
 
+    pub(crate) fn stylus(&mut self, event: &mut Event<event::Stylus>) {
+        self.handle_event_under_mouse(event, |node, e| node.component.on_stylus(e));
+    }
+
+    pub(crate) fn context_menu(&mut self, event: &mut Event<event::ContextMenu>) {
+        self.handle_event_under_mouse(event, |node, e| node.component.on_context_menu(e));
+    }
+
     pub(crate) fn mouse_down(&mut self, event: &mut Event<event::MouseDown>) {
         self.handle_event_under_mouse(event, |node, e| node.component.on_mouse_down(e));
     }
@@ -744,6 +1434,17 @@ impl Node {
     }
 
     pub(crate) fn key_down(&mut self, event: &mut Event<event::KeyDown>) {
+        if event.input.0 == crate::input::Key::Escape {
+            if let Some(stack) = event.target.and_then(|t| self.get_target_stack(t)) {
+                // Dismiss the tool tips of the focused Node and its ancestors
+                for depth in 0..=stack.len() {
+                    let node = self.get_target_from_stack(&stack[..depth]);
+                    if node.tooltip_text().is_some() && node.dismiss_tooltip() {
+                        event.dirty();
+                    }
+                }
+            }
+        }
         self.handle_targeted_event(event, |node, e| node.component.on_key_down(e));
     }
 
@@ -759,6 +1460,11 @@ impl Node {
         self.handle_targeted_event(event, |node, e| node.component.on_text_entry(e));
     }
 
+    #[cfg(feature = "accesskit")]
+    pub(crate) fn set_value(&mut self, event: &mut Event<event::SetValue>) {
+        self.handle_targeted_event(event, |node, e| node.component.on_set_value(e));
+    }
+
     pub(crate) fn drag(&mut self, event: &mut Event<event::Drag>) {
         self.handle_targeted_event(event, |node, e| node.component.on_drag(e));
     }
@@ -803,13 +1509,21 @@ impl Node {
 
         for child in self.children.iter_mut() {
             for message in child.tick(event).drain(..) {
-                m.append(&mut self.component.update(message));
+                m.append(&mut update_component(
+                    self.component.as_mut(),
+                    self.id,
+                    message,
+                    &event.scheduled,
+                ));
             }
         }
 
         event.current_node_id = Some(self.id);
         event.current_aabb = Some(self.aabb);
         event.current_inner_scale = self.inner_scale;
+        if self.tooltip_text().is_some() && self.tooltip_tick(event) {
+            event.dirty();
+        }
         self.component.on_tick(event);
         if self.component.is_dirty() {
             event.dirty();
@@ -818,19 +1532,148 @@ impl Node {
 
         m
     }
+
+    pub(crate) fn exit(&mut self, event: &mut Event<event::Exit>) -> Vec<Message> {
+        let mut m: Vec<Message> = vec![];
+
+        for child in self.children.iter_mut() {
+            for message in child.exit(event).drain(..) {
+                m.append(&mut update_component(
+                    self.component.as_mut(),
+                    self.id,
+                    message,
+                    &event.scheduled,
+                ));
+            }
+        }
+
+        event.current_node_id = Some(self.id);
+        event.current_aabb = Some(self.aabb);
+        event.current_inner_scale = self.inner_scale;
+        self.component.on_exit(event);
+        if self.component.is_dirty() {
+            event.dirty();
+        }
+        m.append(&mut event.messages);
+
+        m
+    }
+
+    /// The text of the tool tip: the Node's own [`tooltip`][Node#method.tooltip], or else the Component's [`tooltip_text`][Component#method.tooltip_text].
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tooltip
+            .as_deref()
+            .or_else(|| self.component.tooltip_text())
+    }
+
+    /// Open or close the tool tip, based on where the mouse is and how long it has been resting there, how long the Node has had the keyboard focus, and [`Node#show_tooltip`][Node#method.show_tooltip]. Returns true if the tool tip was opened, moved or closed.
+    fn tooltip_tick(&mut self, event: &Event<event::Tick>) -> bool {
+        let mouse_over = self
+            .component
+            .is_mouse_over(event.mouse_position, self.aabb);
+        let state = &mut self.tooltip_state;
+        let was_open = state.open;
+
+        if !mouse_over {
+            state.hover_start = None;
+            state.dismissed &= state.focus_start.is_some();
+        } else if state.hover_start.is_none() || state.mouse_position != event.mouse_position {
+            // The mouse has only just come to rest
+            state.hover_start = Some(Instant::now());
+            state.mouse_position = event.mouse_position;
+        }
+
+        let waited = |start: Option<Instant>, delay: u128| {
+            start
+                .map(|s| s.elapsed().as_millis() > delay)
+                .unwrap_or(false)
+        };
+        let anchored = self.show_tooltip
+            || (!state.dismissed && waited(state.focus_start, ToolTip::FOCUS_DELAY));
+        state.open = if anchored {
+            Some(Point::new(0.0, event.current_logical_aabb().height()))
+        } else if state.dismissed || !mouse_over {
+            None
+        } else if was_open.is_some() && !state.anchored {
+            // Stay where it was opened while the mouse moves over the Node
+            was_open
+        } else if waited(state.hover_start, ToolTip::DELAY) {
+            Some(event.relative_logical_position() + ToolTip::MOUSE_OFFSET)
+        } else {
+            None
+        };
+        state.anchored = anchored;
+        state.open != was_open
+    }
+
+    /// Start or stop the delay before the tool tip is shown for the keyboard focus. Returns true if the tool tip was closed.
+    fn tooltip_focus(&mut self, focused: bool) -> bool {
+        let state = &mut self.tooltip_state;
+        state.focus_start = focused.then(Instant::now);
+        state.dismissed = false;
+        if !focused && state.anchored && !self.show_tooltip {
+            state.open = None;
+            state.anchored = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Close the tool tip until the mouse and focus leave the Node. Returns true if it was open.
+    fn dismiss_tooltip(&mut self) -> bool {
+        let state = &mut self.tooltip_state;
+        state.dismissed = true;
+        if state.open.is_some() && !self.show_tooltip {
+            state.open = None;
+            state.anchored = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// What renderables are clipped to.
+#[derive(Debug, Clone)]
+pub(crate) enum ClipFrame {
+    /// The [`frame_bounds`][Component#method.frame_bounds] of a scrollable Node.
+    Scroll(AABB),
+    /// The [`clip_path`][Node#method.clip_path] of a Node, positioned at the top left of the Node.
+    Path(Arc<ClipMesh>, Pos),
+}
+
+impl PartialEq for ClipFrame {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Scroll(a), Self::Scroll(b)) => a == b,
+            (Self::Path(a, p), Self::Path(b, q)) => Arc::ptr_eq(a, b) && p == q,
+            _ => false,
+        }
+    }
 }
 
-pub(crate) type ScrollFrame = AABB;
+impl ClipFrame {
+    /// Is the physical `position` inside of the frame?
+    pub(crate) fn contains(&self, position: Point) -> bool {
+        match self {
+            Self::Scroll(aabb) => aabb.is_under(position),
+            Self::Path(mesh, pos) => {
+                mesh.contains(Point::new(position.x - pos.x, position.y - pos.y))
+            }
+        }
+    }
+}
 
 pub(crate) struct NodeRenderableIterator<'a> {
     queue: Vec<&'a Node>,
-    current_frame: Vec<ScrollFrame>,
-    frame_queue: Vec<(&'a Node, Vec<ScrollFrame>)>,
+    current_frame: Vec<ClipFrame>,
+    frame_queue: Vec<(&'a Node, Vec<ClipFrame>)>,
     i: usize,
 }
 
 impl<'a> Iterator for NodeRenderableIterator<'a> {
-    type Item = (&'a Renderable, &'a AABB, Vec<ScrollFrame>);
+    type Item = (&'a Renderable, &'a AABB, Vec<ClipFrame>);
 
     fn next(&mut self) -> Option<Self::Item> {
         while let Some(n) = self.queue.pop() {
@@ -839,30 +1682,29 @@ impl<'a> Iterator for NodeRenderableIterator<'a> {
 
                 if i == c.len() {
                     self.i = 0;
-                    if n.scrollable() {
-                        let mut f = self.current_frame.clone();
-                        f.push(n.component.frame_bounds(n.aabb, n.inner_scale));
+                    if let Some(f) = n.child_frames(&self.current_frame) {
                         self.frame_queue.push((n, f));
                     } else {
-                        self.queue.extend(n.children.iter().collect::<Vec<&Node>>());
+                        self.queue
+                            .extend(n.children.iter().filter(|c| !c.layout.hidden));
                     }
                 } else {
                     self.i += 1;
                     self.queue.push(n);
                     return Some((&c[i], &n.aabb, self.current_frame.clone()));
                 }
-            } else if n.scrollable() {
-                let mut f = self.current_frame.clone();
-                f.push(n.component.frame_bounds(n.aabb, n.inner_scale));
+            } else if let Some(f) = n.child_frames(&self.current_frame) {
                 self.frame_queue.push((n, f));
             } else {
-                self.queue.extend(n.children.iter().collect::<Vec<&Node>>());
+                self.queue
+                    .extend(n.children.iter().filter(|c| !c.layout.hidden));
             }
 
             if self.queue.is_empty() && !self.frame_queue.is_empty() {
                 let (n, f) = self.frame_queue.pop().unwrap();
                 self.current_frame = f;
-                self.queue.extend(n.children.iter().collect::<Vec<&Node>>());
+                self.queue
+                    .extend(n.children.iter().filter(|c| !c.layout.hidden));
             }
         }
         None
@@ -872,11 +1714,12 @@ impl<'a> Iterator for NodeRenderableIterator<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::render::{Renderable, Renderer};
+    use crate::render::{RenderOptions, Renderable, Renderer};
     use crate::window::Window;
     use raw_window_handle::{
         HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
     };
+    use std::sync::{Arc, Mutex};
 
     pub struct TestWindow {}
     impl Window for TestWindow {
@@ -913,7 +1756,7 @@ mod tests {
     #[derive(Debug)]
     pub struct TestRenderer {}
     impl Renderer for TestRenderer {
-        fn new<W: Window>(_window: &W) -> Self {
+        fn new<W: Window>(_window: &W, _options: &RenderOptions) -> Self {
             Self {}
         }
     }
@@ -1037,17 +1880,62 @@ mod tests {
         }
     }
 
-    mod test_app {
+    mod props_eq_widget {
         use super::*;
+        use std::any::Any;
 
-        #[derive(Debug, Default)]
-        pub struct TestApp {
-            pub state: Option<AppState>,
-        }
-
+        /// Counts up by `step`, starting again whenever `step` changes.
         #[derive(Debug)]
-        pub struct AppState {
-            pub foo: usize,
+        pub struct Counter {
+            pub step: f32,
+            pub state: Option<CounterState>,
+        }
+
+        #[derive(Debug, Default)]
+        pub struct CounterState {
+            pub count: f32,
+        }
+
+        impl Component for Counter {
+            fn init(&mut self) {
+                self.state = Some(CounterState::default());
+            }
+
+            fn new_props(&mut self) {
+                self.state = Some(CounterState::default());
+            }
+
+            fn props_eq(&self, prev: &dyn Any) -> Option<bool> {
+                prev.downcast_ref::<Self>()
+                    .map(|prev| self.step == prev.step)
+            }
+
+            fn as_any(&self) -> Option<&dyn Any> {
+                Some(self)
+            }
+
+            fn replace_state(&mut self, other_state: State) {
+                let s = other_state.downcast::<CounterState>().unwrap();
+                self.state = Some(*s);
+            }
+
+            fn take_state(&mut self) -> Option<State> {
+                self.state.take().map(|s| Box::new(s) as State)
+            }
+        }
+    }
+
+    mod test_app {
+        use super::*;
+
+        #[derive(Debug, Default)]
+        pub struct TestApp {
+            pub state: Option<AppState>,
+        }
+
+        #[derive(Debug)]
+        pub struct AppState {
+            pub foo: usize,
         }
 
         #[derive(Debug)]
@@ -1121,7 +2009,7 @@ mod tests {
         let mut n = Node::new(Box::new(test_app::TestApp::default()), 0, Layout::default());
         n.view(None, &mut vec![]);
         //n.layout();
-        n.render(renderer.caches(), None, 1.0);
+        n.render(renderer.caches(), None, 1.0, false);
         //println!("{:#?}", n);
         assert_eq!(
             n.render_cache,
@@ -1152,7 +2040,7 @@ mod tests {
         assert_eq!(n.children[0].id, new_n.children[0].id);
 
         //new_n.layout();
-        new_n.render(renderer.caches(), Some(&mut n), 1.0);
+        new_n.render(renderer.caches(), Some(&mut n), 1.0, false);
         //println!("{:#?}", new_n);
         assert_eq!(
             new_n.render_cache,
@@ -1171,6 +2059,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_render_memoized() {
+        #[derive(Debug)]
+        struct Memo {
+            key: u64,
+            // Changes every frame, but does not affect the output of `render`
+            frame: u64,
+        }
+
+        impl Component for Memo {
+            fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+                // Count how many times this has been rendered
+                Some(vec![Renderable::Inc {
+                    repr: self.key.to_string(),
+                    i: context.prev_state.map_or(1, |r| match r[0] {
+                        Renderable::Inc { i, .. } => i + 1,
+                        _ => panic!(),
+                    }),
+                }])
+            }
+
+            fn render_hash(&self, hasher: &mut ComponentHasher) {
+                self.frame.hash(hasher);
+            }
+
+            fn memo_key(&self) -> Option<u64> {
+                Some(self.key)
+            }
+        }
+
+        let renderer = TestRenderer {};
+        let mut frames = 0;
+        let mut frame = |key: u64, prev: Option<&mut Node>, force: bool| {
+            frames += 1;
+            let mut n = Node::new(Box::new(Memo { key, frame: frames }), 0, Layout::default());
+            let rendered = n.render(renderer.caches(), prev, 1.0, force);
+            (n, rendered)
+        };
+        let renders = |n: &Node| match n.render_cache.as_ref().unwrap()[0] {
+            Renderable::Inc { i, .. } => i,
+            _ => panic!(),
+        };
+
+        let (mut n, rendered) = frame(1, None, false);
+        assert!(rendered);
+        assert_eq!(renders(&n), 1);
+
+        // The key is unchanged, so the renderables of the last frame are reused, even though the render hash is not
+        for _ in 0..3 {
+            let (next, rendered) = frame(1, Some(&mut n), false);
+            assert!(!rendered);
+            n = next;
+        }
+        assert_eq!(renders(&n), 1);
+
+        // Changing the key renders again
+        let (mut n, rendered) = frame(2, Some(&mut n), false);
+        assert!(rendered);
+        assert_eq!(renders(&n), 2);
+        assert_eq!(
+            n.render_cache,
+            Some(vec![Renderable::Inc {
+                repr: "2".to_string(),
+                i: 2
+            }])
+        );
+
+        // As does a full render, e.g. after the theme changes
+        let (n, rendered) = frame(2, Some(&mut n), true);
+        assert!(rendered);
+        assert_eq!(renders(&n), 3);
+    }
+
     mod test_scroll_app {
         use super::*;
 
@@ -1361,7 +2322,7 @@ mod tests {
         assert_eq!(scroll_node.inner_scale.unwrap(), [200.0, 150.0].into());
 
         // Expect renderables to be laid out in the right order, with the correct Frames
-        n.render(renderer.caches(), None, 1.0);
+        n.render(renderer.caches(), None, 1.0, false);
         let renderables = n.iter_renderables().collect::<Vec<_>>();
         assert_eq!(renderables.len(), 9);
         // First three (App, Top Div, Scroll Div) do not have Frames
@@ -1421,4 +2382,1406 @@ mod tests {
         assert_eq!(registrations[1].0, event::Register::KeyPress);
         assert_eq!(registrations[2].0, event::Register::KeyDown);
     }
+
+    #[test]
+    fn test_pixel_snap() {
+        let layout = |pixel_snap: bool| Layout {
+            size: Size {
+                width: Dimension::Px(10.0),
+                height: Dimension::Px(10.0),
+            },
+            pixel_snap,
+            ..Default::default()
+        };
+        let mut snapped = Node::new(Box::new(container::Container {}), 0, layout(true));
+        let mut unsnapped = Node::new(Box::new(container::Container {}), 0, layout(false));
+        let font_cache = FontCache::default();
+
+        for n in [&mut snapped, &mut unsnapped] {
+            n.calculate_layout(&font_cache, 1.5);
+            // A fractional offset, as from a scrolled parent
+            n.set_aabb(
+                Pos::new(0.0, 10.4, 0.0),
+                AABB::default(),
+                ScrollPosition::default(),
+                false,
+                AABB::default(),
+                1.5,
+            );
+        }
+
+        assert_eq!(snapped.aabb.pos.y, 10.0);
+        assert_eq!(snapped.aabb.bottom_right.y, 25.0);
+        assert_eq!(unsnapped.aabb.pos.y, 10.4);
+    }
+
+    mod recorder {
+        use super::*;
+        use std::sync::{Arc, Mutex};
+
+        /// Keeps the messages of type `T` that reach it.
+        #[derive(Debug)]
+        pub struct Recorder<T> {
+            pub messages: Arc<Mutex<Vec<T>>>,
+        }
+
+        impl<T: 'static + fmt::Debug + Clone> Component for Recorder<T> {
+            fn update(&mut self, message: Message) -> Vec<Message> {
+                if let Some(m) = message.downcast_ref::<T>() {
+                    self.messages.lock().unwrap().push(m.clone());
+                }
+                vec![]
+            }
+        }
+    }
+
+    /// A 100 by 100 pixel root, with `child` filling it, that keeps the messages of type `T` sent up from `child`. Messages emitted by the root Node itself go nowhere.
+    fn recording_root<T: 'static + fmt::Debug + Clone + Send>(
+        child: Box<dyn Component + Send + Sync>,
+    ) -> (Node, Arc<Mutex<Vec<T>>>) {
+        let messages: Arc<Mutex<Vec<T>>> = Default::default();
+        let recorder = recorder::Recorder {
+            messages: messages.clone(),
+        };
+        let mut n = Node::new(
+            Box::new(recorder),
+            0,
+            Layout {
+                size: Size {
+                    width: Dimension::Px(100.0),
+                    height: Dimension::Px(100.0),
+                },
+                ..Default::default()
+            },
+        )
+        .push(Node::new(
+            child,
+            0,
+            Layout {
+                size: Size {
+                    width: Dimension::Pct(100.0),
+                    height: Dimension::Pct(100.0),
+                },
+                ..Default::default()
+            },
+        ));
+        n.calculate_layout(&FontCache::default(), 1.0);
+        n.set_aabb(
+            Pos::default(),
+            AABB::default(),
+            ScrollPosition::default(),
+            false,
+            AABB::default(),
+            1.0,
+        );
+        (n, messages)
+    }
+
+    mod test_context_menu {
+        use super::*;
+
+        #[derive(Debug)]
+        pub struct Target {}
+
+        impl Component for Target {
+            fn on_context_menu(&mut self, event: &mut Event<event::ContextMenu>) {
+                event.emit(Box::new(event.input.position));
+            }
+        }
+    }
+
+    #[test]
+    fn test_context_menu() {
+        use crate::input::MouseButton;
+
+        let (mut n, messages) = recording_root::<Point>(Box::new(test_context_menu::Target {}));
+
+        let mut cache = event::EventCache::new(1.0);
+        cache.mouse_position = Point::new(20.0, 30.0);
+        // Right click down, then up
+        for pressed in [true, false] {
+            if pressed {
+                cache.mouse_down(MouseButton::Right);
+            }
+            if cache.is_context_menu_gesture(MouseButton::Right, pressed) {
+                let mut event = Event::new(
+                    event::ContextMenu {
+                        position: cache.mouse_position,
+                    },
+                    &cache,
+                );
+                n.context_menu(&mut event);
+            }
+            if !pressed {
+                cache.mouse_up(MouseButton::Right);
+            }
+        }
+
+        let messages = messages.lock().unwrap();
+        assert_eq!(*messages, vec![Point::new(20.0, 30.0)]);
+    }
+
+    mod test_stylus {
+        use super::*;
+
+        #[derive(Debug)]
+        pub struct Canvas {}
+
+        impl Component for Canvas {
+            fn on_stylus(&mut self, event: &mut Event<event::Stylus>) {
+                event.emit(Box::new((event.input.phase, event.input.pressure)));
+                event.stop_bubbling();
+            }
+        }
+    }
+
+    #[test]
+    fn test_stylus() {
+        use crate::input::StylusPhase;
+
+        let (mut n, received) =
+            recording_root::<(StylusPhase, f32)>(Box::new(test_stylus::Canvas {}));
+
+        let cache = event::EventCache::new(1.0);
+        let stroke = [
+            (StylusPhase::Down, 0.1),
+            (StylusPhase::Move, 0.4),
+            (StylusPhase::Move, 0.9),
+            (StylusPhase::Up, 0.0),
+        ];
+        for (i, (phase, pressure)) in stroke.iter().enumerate() {
+            let mut event = Event::new(
+                event::Stylus {
+                    pressure: *pressure,
+                    tilt: None,
+                    phase: *phase,
+                },
+                &cache,
+            );
+            event.mouse_position = Point::new(10.0 + i as f32 * 10.0, 50.0);
+            n.stylus(&mut event);
+            assert!(!event.bubbles);
+        }
+        assert_eq!(*received.lock().unwrap(), stroke);
+
+        // Samples outside of the Node don't reach it
+        let mut event = Event::new(
+            event::Stylus {
+                pressure: 0.5,
+                tilt: None,
+                phase: StylusPhase::Down,
+            },
+            &cache,
+        );
+        event.mouse_position = Point::new(150.0, 50.0);
+        n.stylus(&mut event);
+        assert_eq!(received.lock().unwrap().len(), stroke.len());
+    }
+
+    #[test]
+    fn test_tooltip() {
+        let new_node = || {
+            Node::new(
+                Box::new(container::Container {}),
+                0,
+                Layout {
+                    size: Size {
+                        width: Dimension::Px(100.0),
+                        height: Dimension::Px(100.0),
+                    },
+                    ..Default::default()
+                },
+            )
+            .tooltip("Some help")
+        };
+        let mut n = new_node();
+        n.view(None, &mut vec![]);
+        n.calculate_layout(&FontCache::default(), 1.0);
+        n.set_aabb(
+            Pos::default(),
+            AABB::default(),
+            ScrollPosition::default(),
+            false,
+            AABB::default(),
+            1.0,
+        );
+
+        let mut cache = event::EventCache::new(1.0);
+        cache.mouse_position = Point::new(20.0, 30.0);
+        let mut event = Event::new(event::Tick, &cache);
+        n.tick(&mut event);
+        assert!(!event.dirty);
+        assert!(n.tooltip_state.hover_start.is_some());
+
+        // Wait out the delay
+        n.tooltip_state.hover_start =
+            Some(Instant::now() - std::time::Duration::from_millis(ToolTip::DELAY as u64 + 1));
+        let mut event = Event::new(event::Tick, &cache);
+        n.tick(&mut event);
+        assert!(event.dirty);
+
+        let mut next = new_node();
+        next.view(Some(&mut n), &mut vec![]);
+        assert_eq!(next.children.len(), 1);
+        assert_eq!(next.children[0].key, TOOLTIP_KEY);
+        assert!(format!("{:?}", next.children[0].component).contains("Some help"));
+        assert_eq!(
+            next.children[0].layout.position,
+            crate::layout::Rect::from(Point::new(20.0, 30.0) + ToolTip::MOUSE_OFFSET)
+        );
+    }
+
+    #[test]
+    fn test_tooltip_focus() {
+        let new_node = |show: bool| {
+            container(0).push(
+                Node::new(
+                    Box::new(focus_tracker::FocusRecorder {
+                        focused: Default::default(),
+                    }),
+                    0,
+                    Layout {
+                        size: Size {
+                            width: Dimension::Px(100.0),
+                            height: Dimension::Px(40.0),
+                        },
+                        ..Default::default()
+                    },
+                )
+                .tooltip("Some help")
+                .show_tooltip(show),
+            )
+        };
+        // The tool tip's text needs a font to lay out with
+        let mut font_cache = FontCache::default();
+        font_cache.add_font("noto sans regular".to_string(), ttf_noto_sans::REGULAR);
+        let layout = |n: &mut Node| {
+            n.calculate_layout(&font_cache, 1.0);
+            n.set_aabb(
+                Pos::default(),
+                AABB::default(),
+                ScrollPosition::default(),
+                false,
+                AABB::default(),
+                1.0,
+            );
+        };
+        let has_tooltip = |n: &Node| n.children[0].children.iter().any(|c| c.key == TOOLTIP_KEY);
+        let mut n = new_node(false);
+        n.view(None, &mut vec![]);
+        layout(&mut n);
+        let field = n.children[0].id;
+
+        let mut cache = event::EventCache::new(1.0);
+        // Keep the mouse away
+        cache.mouse_position = Point::new(500.0, 500.0);
+        cache.focus_visible = true;
+        let wait_and_tick = |n: &mut Node, cache: &event::EventCache| {
+            if let Some(start) = n.children[0].tooltip_state.focus_start.as_mut() {
+                *start -= std::time::Duration::from_millis(ToolTip::FOCUS_DELAY as u64 + 1);
+            }
+            let mut event = Event::new(event::Tick, cache);
+            n.tick(&mut event);
+            event.dirty
+        };
+
+        // Focusing the Node with the keyboard opens the tool tip below it, after a delay
+        n.focus_within_change(n.id, field, &cache);
+        assert!(!has_tooltip(&n));
+        assert!(wait_and_tick(&mut n, &cache));
+        let mut next = new_node(false);
+        next.view(Some(&mut n), &mut vec![]);
+        layout(&mut next);
+        assert!(has_tooltip(&next));
+        let tool_tip = next.children[0]
+            .children
+            .iter()
+            .find(|c| c.key == TOOLTIP_KEY)
+            .unwrap();
+        assert_eq!(
+            tool_tip.layout.position,
+            crate::layout::Rect::from(Point::new(0.0, 40.0))
+        );
+
+        // Blurring closes it
+        assert!(next.focus_within_change(field, next.id, &cache));
+        let mut n = new_node(false);
+        n.view(Some(&mut next), &mut vec![]);
+        layout(&mut n);
+        assert!(!has_tooltip(&n));
+
+        // As does Escape, until the focus comes back
+        n.focus_within_change(n.id, field, &cache);
+        assert!(wait_and_tick(&mut n, &cache));
+        let mut event = Event::new(event::KeyDown(crate::input::Key::Escape), &cache);
+        event.target = Some(field);
+        n.key_down(&mut event);
+        assert!(event.dirty);
+        assert!(!wait_and_tick(&mut n, &cache));
+        let mut next = new_node(false);
+        next.view(Some(&mut n), &mut vec![]);
+        assert!(!has_tooltip(&next));
+
+        // Focusing with the mouse doesn't show it
+        next.focus_within_change(field, next.id, &cache);
+        cache.focus_visible = false;
+        next.focus_within_change(next.id, field, &cache);
+        assert!(!wait_and_tick(&mut next, &cache));
+
+        // But it can be shown no matter where the focus is
+        let mut n = new_node(true);
+        n.view(Some(&mut next), &mut vec![]);
+        layout(&mut n);
+        n.focus_within_change(field, n.id, &cache);
+        assert!(wait_and_tick(&mut n, &cache));
+        let mut next = new_node(true);
+        next.view(Some(&mut n), &mut vec![]);
+        assert!(has_tooltip(&next));
+    }
+
+    #[test]
+    fn test_exit() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Debug)]
+        struct Closer {
+            name: &'static str,
+            veto: bool,
+            exited: Arc<Mutex<Vec<&'static str>>>,
+        }
+
+        impl Component for Closer {
+            fn on_exit(&mut self, event: &mut Event<event::Exit>) {
+                self.exited.lock().unwrap().push(self.name);
+                if self.veto {
+                    event.veto();
+                    event.emit(Box::new(self.name));
+                }
+            }
+
+            fn update(&mut self, msg: Message) -> Vec<Message> {
+                if let Some(name) = msg.downcast_ref::<&str>() {
+                    self.exited.lock().unwrap().push(*name);
+                }
+                vec![]
+            }
+        }
+
+        let exited: Arc<Mutex<Vec<&'static str>>> = Default::default();
+        let new_node = |veto: bool| {
+            let closer = |name, veto| Closer {
+                name,
+                veto,
+                exited: exited.clone(),
+            };
+            Node::new(Box::new(closer("root", false)), 0, Layout::default()).push(Node::new(
+                Box::new(closer("editor", veto)),
+                0,
+                Layout::default(),
+            ))
+        };
+        let cache = event::EventCache::new(1.0);
+
+        // Every Component gets the hook, children before the root
+        let mut n = new_node(false);
+        let mut event = Event::new(event::Exit::default(), &cache);
+        n.exit(&mut event);
+        assert!(!event.vetoed());
+        assert_eq!(*exited.lock().unwrap(), vec!["editor", "root"]);
+
+        // A veto is seen by the UI, and its message reaches the root
+        exited.lock().unwrap().clear();
+        let mut n = new_node(true);
+        let mut event = Event::new(event::Exit::default(), &cache);
+        n.exit(&mut event);
+        assert!(event.vetoed());
+        assert_eq!(*exited.lock().unwrap(), vec!["editor", "editor", "root"]);
+    }
+
+    #[test]
+    fn test_component_tooltip() {
+        let new_node = || {
+            container(0).push(
+                Node::new(
+                    Box::new(
+                        crate::widgets::Button::new(vec!["Save".into()])
+                            .tooltip("Save the project"),
+                    ),
+                    0,
+                    Layout::default(),
+                )
+                .show_tooltip(true),
+            )
+        };
+        let has_tooltip = |n: &Node| n.children[0].children.iter().any(|c| c.key == TOOLTIP_KEY);
+        let mut n = new_node();
+        n.view(None, &mut vec![]);
+        assert_eq!(n.children[0].tooltip_text(), Some("Save the project"));
+        assert!(!has_tooltip(&n));
+
+        // The Component's tool tip is shown just like the Node's
+        let cache = event::EventCache::new(1.0);
+        let mut event = Event::new(event::Tick, &cache);
+        n.tick(&mut event);
+        assert!(event.dirty);
+        let mut next = new_node();
+        next.view(Some(&mut n), &mut vec![]);
+        assert!(has_tooltip(&next));
+
+        // And stays open as the tree is reconciled
+        let mut n = new_node();
+        n.view(Some(&mut next), &mut vec![]);
+        assert!(has_tooltip(&n));
+
+        // The Node's own tool tip takes precedence
+        n.children[0].tooltip = Some("Overridden".into());
+        assert_eq!(n.children[0].tooltip_text(), Some("Overridden"));
+    }
+
+    mod focus_tracker {
+        use super::*;
+        use std::sync::{Arc, RwLock};
+
+        #[derive(Debug)]
+        pub struct FocusTracker {
+            pub within: Arc<RwLock<bool>>,
+        }
+
+        impl Component for FocusTracker {
+            fn on_focus_within(&mut self, event: &mut Event<event::FocusWithin>) {
+                *self.within.write().unwrap() = event.input.0;
+            }
+        }
+
+        #[derive(Debug)]
+        pub struct FocusRecorder {
+            pub focused: Arc<RwLock<bool>>,
+        }
+
+        impl Component for FocusRecorder {
+            fn focusable(&self) -> bool {
+                true
+            }
+
+            fn on_focus(&mut self, _event: &mut Event<event::Focus>) {
+                *self.focused.write().unwrap() = true;
+            }
+
+            fn on_blur(&mut self, _event: &mut Event<event::Blur>) {
+                *self.focused.write().unwrap() = false;
+            }
+        }
+
+        /// Focuses the Node referenced as "search" on Ctrl+F, and blurs on Escape.
+        #[derive(Debug)]
+        pub struct Shortcuts {}
+
+        impl Component for Shortcuts {
+            fn on_key_down(&mut self, event: &mut Event<event::KeyDown>) {
+                match event.input.0 {
+                    crate::input::Key::F if event.modifiers_held.ctrl => event.focus_node("search"),
+                    crate::input::Key::Escape => event.blur(),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_focus_within() {
+        let within = std::sync::Arc::new(std::sync::RwLock::new(false));
+        let mut n = container(0)
+            .push(
+                Node::new(
+                    Box::new(focus_tracker::FocusTracker {
+                        within: within.clone(),
+                    }),
+                    0,
+                    Layout::default(),
+                )
+                .push(Node::new(
+                    Box::new(crate::widgets::TextBox::new(None)),
+                    0,
+                    Layout::default(),
+                )),
+            )
+            .push(Node::new(
+                Box::new(crate::widgets::TextBox::new(None)),
+                1,
+                Layout::default(),
+            ));
+        n.view(None, &mut vec![]);
+        let cache = event::EventCache::new(1.0);
+
+        // The innermost node of each TextBox is the one that takes focus
+        let innermost = |mut node: &Node| {
+            while !node.children.is_empty() {
+                node = &node.children[0];
+            }
+            node.id
+        };
+        let inside = innermost(&n.children[0]);
+        let outside = innermost(&n.children[1]);
+
+        n.focus_within_change(n.id, inside, &cache);
+        assert!(n.children[0].focus_within(inside));
+        assert!(*within.read().unwrap());
+
+        n.focus_within_change(inside, outside, &cache);
+        assert!(!n.children[0].focus_within(outside));
+        assert!(!*within.read().unwrap());
+        // The root contains every focus
+        assert!(n.focus_within(outside));
+    }
+
+    #[test]
+    fn test_focus_reference() {
+        let focused = std::sync::Arc::new(std::sync::RwLock::new(false));
+        let mut n = container(0)
+            .push(Node::new(
+                Box::new(crate::widgets::TextBox::new(None)),
+                0,
+                Layout::default(),
+            ))
+            .push(
+                Node::new(
+                    Box::new(focus_tracker::FocusRecorder {
+                        focused: focused.clone(),
+                    }),
+                    1,
+                    Layout::default(),
+                )
+                .reference("recorder"),
+            );
+        n.view(None, &mut vec![]);
+        let cache = event::EventCache::new(1.0);
+
+        let recorder = n.get_reference("recorder");
+        assert_eq!(recorder, Some(n.children[1].id));
+        assert_eq!(n.get_reference("missing"), None);
+        let recorder = recorder.unwrap();
+
+        let mut focus_event = Event::new(event::Focus::default(), &cache);
+        focus_event.target = Some(recorder);
+        n.focus(&mut focus_event);
+        assert!(*focused.read().unwrap());
+
+        let mut blur_event = Event::new(event::Blur::default(), &cache);
+        blur_event.target = Some(recorder);
+        n.blur(&mut blur_event);
+        assert!(!*focused.read().unwrap());
+
+        // The TextBox comes first, and navigation wraps around
+        let textbox = n.focusable_ids()[0];
+        assert_eq!(n.focusable_ids(), vec![textbox, recorder]);
+        assert_eq!(n.next_focus(n.id, true), Some(textbox));
+        assert_eq!(n.next_focus(textbox, true), Some(recorder));
+        assert_eq!(n.next_focus(recorder, true), Some(textbox));
+        assert_eq!(n.next_focus(textbox, false), Some(recorder));
+        assert_eq!(n.next_focus(n.id, false), Some(recorder));
+        assert_eq!(container(2).next_focus(0, true), None);
+    }
+
+    #[test]
+    fn test_props_eq() {
+        use props_eq_widget::Counter;
+
+        let tree = |step: f32| {
+            container(0).push(Node::new(
+                Box::new(Counter { step, state: None }),
+                0,
+                Layout::default(),
+            ))
+        };
+        let mut n = tree(0.5);
+        n.view(None, &mut vec![]);
+        let mut state = n.children[0].component.take_state().unwrap();
+        state
+            .downcast_mut::<props_eq_widget::CounterState>()
+            .unwrap()
+            .count = 1.5;
+        n.children[0].component.replace_state(state);
+
+        // Equal props keep the state, even though the props have no hash
+        let mut next = tree(0.5);
+        next.view(Some(&mut n), &mut vec![]);
+        let counter = |n: &Node| {
+            n.children[0]
+                .component
+                .as_any()
+                .and_then(|c| c.downcast_ref::<Counter>())
+                .unwrap()
+                .state
+                .as_ref()
+                .unwrap()
+                .count
+        };
+        assert_eq!(counter(&next), 1.5);
+
+        // Props that differ start the count again
+        let mut last = tree(0.75);
+        last.view(Some(&mut next), &mut vec![]);
+        assert_eq!(counter(&last), 0.0);
+    }
+
+    #[test]
+    fn test_references() {
+        let renderer = TestRenderer {};
+        let tree = || {
+            Node::new(Box::new(crate::widgets::Div::new()), 0, Layout::default())
+                .push(node!(crate::widgets::Div::new(), Layout::default(), 0).reference("first"))
+                .push(
+                    node!(crate::widgets::Div::new(), Layout::default(), 1).push(
+                        node!(crate::widgets::Div::new(), Layout::default(), 0).reference("nested"),
+                    ),
+                )
+        };
+        let mut n = tree();
+        n.view(None, &mut vec![]);
+        n.render(renderer.caches(), None, 1.0, false);
+
+        let references = n.references();
+        assert_eq!(
+            references,
+            vec![
+                ("first".to_string(), n.children[0].id),
+                ("nested".to_string(), n.children[1].children[0].id),
+            ]
+        );
+        assert_eq!(n.get_reference("nested"), Some(references[1].1));
+        assert_eq!(
+            n.aabb_of(references[1].1),
+            Some(n.children[1].children[0].aabb)
+        );
+
+        // The same Nodes keep their ids in the next view, and Nodes that are gone are not found
+        let mut new_n = tree();
+        new_n.children.pop();
+        new_n.view(Some(&mut n), &mut vec![]);
+        assert_eq!(new_n.references(), references[..1]);
+        assert_eq!(new_n.get_reference("nested"), None);
+    }
+
+    #[test]
+    fn test_badge() {
+        fn badge(n: &Node) -> Option<&Node> {
+            n.children.iter().find(|c| c.key == BADGE_KEY)
+        }
+
+        let mut n = container(0).badge(0);
+        n.view(None, &mut vec![]);
+        assert!(badge(&n).is_none());
+
+        let mut n = container(0).badge(Badge::new(0).show_zero());
+        n.view(None, &mut vec![]);
+        assert!(badge(&n).is_some());
+
+        let mut n = container(0).badge(5);
+        n.view(None, &mut vec![]);
+        let pill = &badge(&n).unwrap().children[0];
+        assert_eq!(pill.children.len(), 1);
+        let text = pill.children[0].component.accessibility().unwrap();
+        assert_eq!(text.name.as_deref(), Some("5"));
+
+        // A dot has no text
+        let mut n = container(0).badge(Badge::dot());
+        n.view(None, &mut vec![]);
+        assert!(badge(&n).unwrap().children[0].children.is_empty());
+    }
+
+    mod test_commands {
+        use super::*;
+        use std::time::Duration;
+
+        #[derive(Debug, PartialEq)]
+        pub enum Step {
+            First,
+            Second,
+            Later,
+            Done(&'static str),
+        }
+
+        #[derive(Debug)]
+        pub struct Chain {}
+
+        impl Component for Chain {
+            fn update(&mut self, msg: Message) -> Vec<Message> {
+                match msg.downcast_ref::<Step>() {
+                    Some(Step::First) => vec![
+                        Command::then(Step::Second),
+                        Command::after(Duration::ZERO, Step::Later),
+                    ],
+                    Some(Step::Second) => vec![Box::new(Step::Done("second"))],
+                    Some(Step::Later) => vec![Box::new(Step::Done("later"))],
+                    _ => vec![msg],
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_commands() {
+        use test_commands::Step;
+
+        let mut n = Node::new(Box::new(test_commands::Chain {}), 0, Layout::default());
+        n.view(None, &mut vec![]);
+
+        // The follow-up message is handled by a second update, whose messages are passed on
+        let scheduled = ScheduledQueue::default();
+        let messages = n.update(Box::new(Step::First), &scheduled);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].downcast_ref::<Step>(),
+            Some(&Step::Done("second"))
+        );
+
+        // The delayed message waits for the UI to deliver it
+        let mut due = scheduled.take_due();
+        assert_eq!(due.len(), 1);
+        let (id, message) = due.pop().unwrap();
+        assert_eq!(id, n.id);
+        let messages = n.update(message, &scheduled);
+        assert_eq!(
+            messages[0].downcast_ref::<Step>(),
+            Some(&Step::Done("later"))
+        );
+    }
+
+    #[test]
+    fn test_frame_counts() {
+        let caches = TestRenderer {}.caches();
+        // The overlay's text needs a font to render with
+        caches
+            .font
+            .write()
+            .unwrap()
+            .add_font("noto sans regular".to_string(), ttf_noto_sans::REGULAR);
+        let mut n = Node::new(Box::new(test_app::TestApp::default()), 0, Layout::default());
+        n.view(None, &mut vec![]);
+        n.render(caches.clone(), None, 1.0, false);
+        fn node_count(n: &Node) -> usize {
+            1 + n.children.iter().map(node_count).sum::<usize>()
+        }
+        let counts = n.frame_counts();
+        assert_eq!(counts, (node_count(&n), n.iter_renderables().count()));
+
+        // The overlay leaves itself out of the counts
+        let mut n = Node::new(Box::new(test_app::TestApp::default()), 0, Layout::default());
+        n.perf_overlay = Some(PerfOverlay::new(Default::default(), vec![1.0, 2.0]));
+        n.view(None, &mut vec![]);
+        n.render(caches, None, 1.0, false);
+        assert!(n.children.iter().any(|c| c.key == PERF_OVERLAY_KEY));
+        assert_eq!(n.frame_counts(), counts);
+    }
+
+    #[cfg(feature = "accesskit")]
+    #[test]
+    fn test_accessibility_tree() {
+        use crate::accessibility::node_id;
+        use crate::txt;
+        use accesskit::Role;
+
+        let mut n = container(0)
+            .push(Node::new(
+                Box::new(crate::widgets::Button::new(txt!("OK"))),
+                0,
+                Layout::default(),
+            ))
+            .push(Node::new(
+                Box::new(crate::widgets::TextBox::new(Some("Hello".to_string()))),
+                1,
+                Layout::default(),
+            ))
+            .push(Node::new(
+                Box::new(focus_tracker::FocusRecorder {
+                    focused: Default::default(),
+                }),
+                2,
+                Layout::default(),
+            ));
+        n.view(None, &mut vec![]);
+        let textbox = n.focusable_ids()[0];
+        let recorder = n.children[2].id;
+
+        let update = crate::accessibility::tree_update(&n, Some(textbox));
+        let get = |id: u64| {
+            update
+                .nodes
+                .iter()
+                .find(|(accesskit_id, _)| *accesskit_id == node_id(id))
+                .map(|(_, node)| node)
+        };
+        // The root stands in for the window, and the containers in between are left out
+        let root = get(n.id).unwrap();
+        assert_eq!(root.role(), Role::Window);
+        assert_eq!(update.tree.as_ref().unwrap().root, node_id(n.id));
+        let button = get(n.children[0].id).unwrap();
+        assert_eq!(button.role(), Role::Button);
+        assert_eq!(button.name(), Some("OK"));
+        assert_eq!(root.children()[0], node_id(n.children[0].id));
+        let text_field = get(textbox).unwrap();
+        assert_eq!(text_field.role(), Role::TextField);
+        assert_eq!(text_field.value(), Some("Hello"));
+        assert_eq!(update.focus, Some(node_id(textbox)));
+        assert!(get(recorder).is_none());
+
+        // A focused Node that is not in the tree is represented by its nearest accessible ancestor
+        let update = crate::accessibility::tree_update(&n, Some(recorder));
+        assert_eq!(update.focus, Some(node_id(n.id)));
+        // Nothing is focused when the window is not
+        let update = crate::accessibility::tree_update(&n, None);
+        assert_eq!(update.focus, None);
+    }
+
+    #[test]
+    fn test_focus_node_request() {
+        let recorder = |focused: &std::sync::Arc<std::sync::RwLock<bool>>| {
+            Box::new(focus_tracker::FocusRecorder {
+                focused: focused.clone(),
+            })
+        };
+        let field = Default::default();
+        let search = Default::default();
+        let mut n = container(0)
+            .push(Node::new(
+                Box::new(focus_tracker::Shortcuts {}),
+                0,
+                Layout::default(),
+            ))
+            .push(Node::new(recorder(&field), 1, Layout::default()).reference("field"))
+            .push(Node::new(recorder(&search), 2, Layout::default()).reference("search"));
+        n.view(None, &mut vec![]);
+        let mut cache = event::EventCache::new(1.0);
+        let shortcuts = n.children[0].id;
+        let field_id = n.children[1].id;
+
+        // The field has the focus
+        cache.focus = field_id;
+        let mut focus_event = Event::new(event::Focus::default(), &cache);
+        focus_event.target = Some(field_id);
+        n.focus(&mut focus_event);
+        assert!(*field.read().unwrap());
+
+        // Ctrl+F requests the search field
+        cache.modifiers_held.ctrl = true;
+        let mut key_event = Event::new(event::KeyDown(crate::input::Key::F), &cache);
+        key_event.target = Some(shortcuts);
+        n.key_down(&mut key_event);
+        assert_eq!(key_event.focus_reference.as_deref(), Some("search"));
+
+        // Which the UI resolves, blurring the field and focusing the search field
+        let search_id = n
+            .get_reference(key_event.focus_reference.as_deref().unwrap())
+            .unwrap();
+        assert_eq!(search_id, n.children[2].id);
+        let mut blur_event = Event::new(event::Blur::default(), &cache);
+        blur_event.target = Some(field_id);
+        n.blur(&mut blur_event);
+        let mut focus_event = Event::new(event::Focus::default(), &cache);
+        focus_event.target = Some(search_id);
+        n.focus(&mut focus_event);
+        assert!(!*field.read().unwrap());
+        assert!(*search.read().unwrap());
+
+        // Escape drops the focus, cancelling any earlier request
+        cache.modifiers_held.ctrl = false;
+        let mut key_event = Event::new(event::KeyDown(crate::input::Key::Escape), &cache);
+        key_event.target = Some(shortcuts);
+        key_event.focus_node("field");
+        n.key_down(&mut key_event);
+        assert_eq!(key_event.focus, None);
+        assert_eq!(key_event.focus_reference, None);
+    }
+
+    #[test]
+    fn test_focus_moving_within() {
+        let text_box = |key| {
+            Node::new(
+                Box::new(crate::widgets::TextBox::new(None)),
+                key,
+                Layout::default(),
+            )
+        };
+        let mut n = container(0)
+            .push(
+                container(1)
+                    .push(text_box(0))
+                    .push(text_box(1))
+                    .reference("row"),
+            )
+            .push(text_box(2));
+        n.view(None, &mut vec![]);
+        let cache = event::EventCache::new(1.0);
+        let first = n.children[0].children[0].id;
+        let second = n.children[0].children[1].id;
+        let outside = n.children[1].id;
+
+        let blur = |to: u64| {
+            let (related_key_path, related_references) = n.key_path(to).unwrap();
+            Event::new(
+                event::Blur {
+                    related_target: Some(to),
+                    related_key_path,
+                    related_references,
+                },
+                &cache,
+            )
+        };
+
+        // Moving between the cells of the row stays within it
+        let event = blur(second);
+        assert_eq!(event.input.related_key_path, vec![0, 1, 1]);
+        assert!(event.focus_moving_within("row"));
+
+        // Moving out of the row does not
+        let event = blur(outside);
+        assert_eq!(event.input.related_key_path, vec![0, 2]);
+        assert!(!event.focus_moving_within("row"));
+
+        // Neither does losing the focus altogether
+        assert!(!Event::new(event::Blur::default(), &cache).focus_moving_within("row"));
+
+        // Coming into the row from outside
+        let (related_key_path, related_references) = n.key_path(outside).unwrap();
+        let event = Event::new(
+            event::Focus {
+                related_target: Some(outside),
+                related_key_path,
+                related_references,
+            },
+            &cache,
+        );
+        assert!(!event.focus_moving_within("row"));
+        assert_eq!(n.key_path(first).unwrap().1, vec!["row".to_string()]);
+    }
+
+    mod test_disabled {
+        use super::*;
+        use std::sync::{Arc, RwLock};
+
+        /// Counts the messages that reach it.
+        #[derive(Debug)]
+        pub struct Clicks {
+            pub count: Arc<RwLock<usize>>,
+        }
+
+        impl Component for Clicks {
+            fn update(&mut self, _message: Message) -> Vec<Message> {
+                *self.count.write().unwrap() += 1;
+                vec![]
+            }
+        }
+    }
+
+    #[test]
+    fn test_disabled() {
+        use crate::txt;
+        use std::sync::RwLock;
+
+        let count: Arc<RwLock<usize>> = Default::default();
+        let tree = |disabled: bool| {
+            Node::new(
+                Box::new(test_disabled::Clicks {
+                    count: count.clone(),
+                }),
+                0,
+                Layout::default(),
+            )
+            .push(
+                node!(crate::widgets::Div::new(), Layout::default(), 0)
+                    .disabled(disabled)
+                    .push(node!(
+                        crate::widgets::Button::new(txt!("OK")).on_click(Box::new(|| Box::new(()))),
+                        Layout::default(),
+                        0
+                    ))
+                    .push(
+                        // Re-enabling a descendant of a disabled Node does nothing
+                        node!(crate::widgets::TextBox::new(None), Layout::default(), 1)
+                            .disabled(false),
+                    ),
+            )
+            .push(Node::new(
+                Box::new(focus_tracker::FocusRecorder {
+                    focused: Default::default(),
+                }),
+                1,
+                Layout::default(),
+            ))
+        };
+        fn cover(n: &mut Node, aabb: AABB) {
+            n.aabb = aabb;
+            n.inclusive_aabb = aabb;
+            for child in n.children.iter_mut() {
+                cover(child, aabb);
+            }
+        }
+        // Put the mouse over the Button, and everything else beside it
+        fn place(n: &mut Node) {
+            cover(n, AABB::new(Pos::default(), Scale::new(100.0, 100.0)));
+            let beside = AABB::new(Pos::new(200.0, 0.0, 0.0), Scale::new(100.0, 100.0));
+            cover(&mut n.children[0].children[1], beside);
+            cover(&mut n.children[1], beside);
+        }
+        let mut cache = event::EventCache::new(1.0);
+        cache.mouse_position = Point::new(20.0, 30.0);
+
+        let mut n = tree(false);
+        n.view(None, &mut vec![]);
+        place(&mut n);
+        let mut event = Event::new(event::Click(crate::input::MouseButton::Left), &cache);
+        n.click(&mut event);
+        assert_eq!(*count.read().unwrap(), 1);
+        assert_eq!(n.tab_order().len(), 2);
+
+        *count.write().unwrap() = 0;
+        let mut n = tree(true);
+        n.view(None, &mut vec![]);
+        place(&mut n);
+        let mut event = Event::new(event::Click(crate::input::MouseButton::Left), &cache);
+        n.click(&mut event);
+        assert_eq!(*count.read().unwrap(), 0);
+
+        // Only the Node outside of the disabled Div can be tabbed to
+        let recorder = n.children[1].id;
+        assert!(n.children[0].children[1].disabled);
+        assert_eq!(n.tab_order(), vec![recorder]);
+        assert_eq!(n.next_focus(recorder, true), Some(recorder));
+        assert_eq!(n.focusable_ids(), vec![recorder]);
+
+        // Events targeted at a disabled Node are dropped
+        let textbox = n.children[0].children[1].id;
+        let mut event = Event::new(event::Focus::default(), &cache);
+        event.target = Some(textbox);
+        n.focus(&mut event);
+        assert_eq!(event.current_node_id, None);
+    }
+
+    #[test]
+    fn test_tab_order() {
+        let focusable = |key, tab_index| {
+            Node::new(
+                Box::new(focus_tracker::FocusRecorder {
+                    focused: Default::default(),
+                }),
+                key,
+                Layout::default(),
+            )
+            .tab_index(tab_index)
+        };
+        let mut n = container(0)
+            .push(focusable(0, 1))
+            .push(focusable(1, 0))
+            .push(focusable(2, -1))
+            .push(
+                container(3)
+                    .focus_scope(false)
+                    .push(focusable(0, 2))
+                    .push(focusable(1, 0)),
+            )
+            .push(focusable(4, 0));
+        n.view(None, &mut vec![]);
+        let id = |path: &[usize]| {
+            let mut node = &n;
+            for i in path {
+                node = &node.children[*i];
+            }
+            node.id
+        };
+
+        // Higher indexes come later, negative ones are left out, and scopes are ordered as one
+        assert_eq!(
+            n.tab_order(),
+            vec![id(&[1]), id(&[3, 1]), id(&[3, 0]), id(&[4]), id(&[0])]
+        );
+        assert_eq!(n.next_focus(id(&[4]), true), Some(id(&[0])));
+        assert_eq!(n.next_focus(id(&[0]), true), Some(id(&[1])));
+        // A Node out of the Tab order still has a place to continue from
+        assert_eq!(n.next_focus(id(&[2]), true), Some(id(&[1])));
+    }
+
+    #[test]
+    fn test_focus_trap() {
+        let focusable = |key| {
+            Node::new(
+                Box::new(focus_tracker::FocusRecorder {
+                    focused: Default::default(),
+                }),
+                key,
+                Layout::default(),
+            )
+        };
+        let mut n = container(0).push(focusable(0)).push(
+            container(1)
+                .focus_scope(true)
+                .push(focusable(0))
+                .push(focusable(1)),
+        );
+        n.view(None, &mut vec![]);
+        let outside = n.children[0].id;
+        let first = n.children[1].children[0].id;
+        let second = n.children[1].children[1].id;
+
+        // Tab wraps around inside of the trap, in both directions
+        assert_eq!(n.next_focus(first, true), Some(second));
+        assert_eq!(n.next_focus(second, true), Some(first));
+        assert_eq!(n.next_focus(first, false), Some(second));
+        // And moves into it from the outside
+        assert_eq!(n.next_focus(outside, true), Some(first));
+        assert_eq!(n.next_focus(n.id, false), Some(second));
+    }
+
+    #[test]
+    fn test_ensure_visible() {
+        let mut scroller = node!(
+            crate::widgets::Div::new().scroll_y().scroll_padding(10.0),
+            lay!(size: size!(100.0), direction: Direction::Column)
+        );
+        for i in 0..10 {
+            let row = node!(container::Container {}, lay!(size: size!(80.0, 50.0)), i);
+            scroller = scroller.push(if i == 1 { row.scroll_margin(5.0) } else { row });
+        }
+        let mut n = container(0).push(scroller);
+        n.view(None, &mut vec![]);
+        n.calculate_layout(&FontCache::default(), 1.0);
+        let set_aabb = |n: &mut Node| {
+            n.set_aabb(
+                Pos::default(),
+                AABB::default(),
+                ScrollPosition::default(),
+                false,
+                AABB::default(),
+                1.0,
+            )
+        };
+        set_aabb(&mut n);
+        let row = |n: &Node, i: usize| n.children[0].children[i].aabb;
+
+        // Revealed below, with the padding between the row and the bottom of the viewport
+        let target = n.children[0].children[5].id;
+        assert!(n.ensure_visible(target, 1.0));
+        set_aabb(&mut n);
+        assert_eq!(row(&n, 5).bottom_right.y, 90.0);
+        // Already visible
+        assert!(!n.ensure_visible(target, 1.0));
+
+        // Revealed above, with both the margin of the row and the padding
+        let target = n.children[0].children[1].id;
+        assert!(n.ensure_visible(target, 1.0));
+        set_aabb(&mut n);
+        assert_eq!(row(&n, 1).pos.y, 15.0);
+    }
+
+    #[test]
+    fn test_autofocus() {
+        let mut n = container(0)
+            .push(Node::new(
+                Box::new(crate::widgets::TextBox::new(None)),
+                0,
+                Layout::default(),
+            ))
+            .push(
+                Node::new(
+                    Box::new(crate::widgets::TextBox::new(None)),
+                    1,
+                    Layout::default(),
+                )
+                .autofocus(),
+            )
+            .push(
+                Node::new(
+                    Box::new(crate::widgets::TextBox::new(None)),
+                    2,
+                    Layout::default(),
+                )
+                .autofocus(),
+            );
+        assert_eq!(container(3).autofocus_target(), None);
+        n.view(None, &mut vec![]);
+
+        // The focusable text of the first autofocus TextBox gets the focus
+        let target = n.autofocus_target().unwrap();
+        assert!(n.children[1].focus_within(target));
+        assert_ne!(target, n.children[1].id);
+        assert_eq!(n.next_focus(target, false), n.next_focus(n.id, true));
+    }
+
+    #[test]
+    fn test_focus_ring() {
+        let renderer = TestRenderer {};
+        let focused = std::sync::Arc::new(std::sync::RwLock::new(false));
+        let new_node = || {
+            container(0).push(Node::new(
+                Box::new(focus_tracker::FocusRecorder {
+                    focused: focused.clone(),
+                }),
+                0,
+                lay!(size: size!(50.0, 20.0), margin: rect!(10.0)),
+            ))
+        };
+        let draw = |n: &mut Node, prev: Option<&mut Node>| {
+            n.view(prev, &mut vec![]);
+            n.calculate_layout(&FontCache::default(), 1.0);
+            n.set_aabb(
+                Pos::default(),
+                AABB::default(),
+                ScrollPosition::default(),
+                false,
+                AABB::default(),
+                1.0,
+            );
+            n.render(renderer.caches(), None, 1.0, false);
+        };
+        let mut n = new_node();
+        draw(&mut n, None);
+        let recorder = n.children[0].id;
+
+        // Focusing with Tab shows the ring, above and around the focused Node
+        assert!(n.set_focus_visible(recorder, true));
+        let mut next = new_node();
+        draw(&mut next, Some(&mut n));
+        let focused_node = &next.children[0];
+        assert_eq!(focused_node.children.len(), 1);
+        let ring = &focused_node.children[0];
+        assert_eq!(ring.key, FOCUS_RING_KEY);
+        assert!(matches!(
+            ring.render_cache.as_deref(),
+            Some([Renderable::Shape(_)])
+        ));
+        assert_eq!(ring.aabb.pos.x, focused_node.aabb.pos.x - 4.0);
+        assert_eq!(
+            ring.aabb.bottom_right.y,
+            focused_node.aabb.bottom_right.y + 4.0
+        );
+        assert!(ring.aabb.pos.z > focused_node.aabb.pos.z);
+
+        // Focusing with a click does not
+        assert!(next.set_focus_visible(recorder, false));
+        let mut last = new_node();
+        draw(&mut last, Some(&mut next));
+        assert!(last.children[0].children.is_empty());
+        assert!(!last.set_focus_visible(recorder, false));
+    }
+
+    #[test]
+    fn test_capture() {
+        let renderer = TestRenderer {};
+        let caches = renderer.caches();
+        let color = Color::new(1.0, 0.0, 0.0, 1.0);
+        let mut n = container(0).push(node!(
+            crate::widgets::Div::new().bg(color),
+            lay!(size: size!(30.0, 20.0), margin: rect!(10.0))
+        ));
+        n.view(None, &mut vec![]);
+        n.calculate_layout(&FontCache::default(), 1.0);
+        n.set_aabb(
+            Pos::default(),
+            AABB::default(),
+            ScrollPosition::default(),
+            false,
+            AABB::default(),
+            1.0,
+        );
+        n.render(caches.clone(), None, 1.0, false);
+
+        let image = crate::render::rasterize(&n.children[0], &caches);
+        assert_eq!((image.width, image.height), (30, 20));
+        for (x, y) in [(0, 0), (29, 0), (15, 10), (0, 19), (29, 19)] {
+            assert_eq!(image.pixel(x, y), [255, 0, 0, 255]);
+        }
+    }
+
+    #[test]
+    fn test_clip_path() {
+        let renderer = TestRenderer {};
+        let caches = renderer.caches();
+        let color = Color::new(1.0, 0.0, 0.0, 1.0);
+        let mut n = container(0).push(
+            node!(crate::widgets::Div::new(), lay!(size: size!(40.0)))
+                .clip_path(crate::widgets::ClipPath::ellipse())
+                .push(node!(
+                    crate::widgets::Div::new().bg(color),
+                    lay!(size: size_pct!(100.0))
+                )),
+        );
+        n.view(None, &mut vec![]);
+        n.calculate_layout(&FontCache::default(), 1.0);
+        n.set_aabb(
+            Pos::default(),
+            AABB::default(),
+            ScrollPosition::default(),
+            false,
+            AABB::default(),
+            1.0,
+        );
+        n.render(caches.clone(), None, 1.0, false);
+
+        // The child is rendered inside of the clip path
+        assert!(n
+            .iter_renderables()
+            .any(|(_, _, frame)| matches!(frame.as_slice(), [ClipFrame::Path(..)])));
+        let image = crate::render::rasterize(&n.children[0], &caches);
+        assert_eq!(image.pixel(20, 20), [255, 0, 0, 255]);
+        assert_eq!(image.pixel(1, 20), [255, 0, 0, 255]);
+        assert_eq!(image.pixel(2, 2), [0, 0, 0, 0]);
+        assert_eq!(image.pixel(38, 38), [0, 0, 0, 0]);
+
+        // And the mouse only reaches it, or the clipping Node, inside of the path
+        let child = n.children[0].children[0].id;
+        let mut cache = event::EventCache::new(1.0);
+        cache.mouse_position = Point::new(20.0, 20.0);
+        let event = Event::new(event::Click(crate::input::MouseButton::Left), &cache);
+        assert!(n.nodes_under(&event).iter().any(|(id, _)| *id == child));
+        cache.mouse_position = Point::new(2.0, 2.0);
+        let event = Event::new(event::Click(crate::input::MouseButton::Left), &cache);
+        let under: Vec<NodeId> = n.nodes_under(&event).iter().map(|(id, _)| *id).collect();
+        assert!(!under.contains(&child));
+        assert!(!under.contains(&n.children[0].id));
+    }
+
+    #[test]
+    fn test_focus_debug() {
+        let new_node = || {
+            container(0).push(container(1).push(Node::new(
+                Box::new(crate::widgets::TextBox::new(None)),
+                0,
+                Layout::default(),
+            )))
+        };
+        let mut n = new_node();
+        n.view(None, &mut vec![]);
+        let text_box = n.children[0].children[0].id;
+
+        let path = n.path_to(text_box).unwrap();
+        assert_eq!(
+            path.iter().map(|n| n.id).collect::<Vec<_>>(),
+            vec![n.id, n.children[0].id, text_box]
+        );
+        assert_eq!(
+            path.last().unwrap().component.type_name(),
+            "lemna::widgets::textbox::TextBox"
+        );
+
+        // The focused Node and its ancestors are outlined
+        assert!(n.set_focus_debug(Some(text_box)));
+        assert!(!n.set_focus_debug(Some(text_box)));
+        let mut next = new_node();
+        next.view(Some(&mut n), &mut vec![]);
+        let outline = |n: &Node| n.children.iter().any(|c| c.key == FOCUS_DEBUG_KEY);
+        assert!(outline(&next));
+        assert!(outline(&next.children[0]));
+        assert!(outline(&next.children[0].children[0]));
+        assert_eq!(
+            next.children[0].children[0].focus_debug,
+            Some(FocusOutline::Focused)
+        );
+        assert_eq!(next.children[0].focus_debug, Some(FocusOutline::Ancestor));
+
+        // Until focus debugging is turned off
+        assert!(next.set_focus_debug(None));
+        let mut last = new_node();
+        last.view(Some(&mut next), &mut vec![]);
+        assert!(!outline(&last));
+        assert!(!outline(&last.children[0].children[0]));
+    }
 }
@@ -0,0 +1,159 @@
+use crate::base_types::*;
+use crate::node::{ClipFrame, Node};
+use crate::render::{renderables::*, Caches};
+
+/// An image with 8-bit RGBA pixels, stored row by row from the top left. Returned by [`UI#capture`][crate::UI#method.capture].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    /// A transparent image.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    /// The RGBA value of the pixel at `x`, `y`.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.data[i],
+            self.data[i + 1],
+            self.data[i + 2],
+            self.data[i + 3],
+        ]
+    }
+
+    /// Paint `color` over the pixel at `x`, `y`.
+    fn blend(&mut self, x: u32, y: u32, color: Color) {
+        let i = ((y * self.width + x) * 4) as usize;
+        let dst_a = self.data[i + 3] as f32 / 255.0;
+        let a = color.a + dst_a * (1.0 - color.a);
+        if a <= 0.0 {
+            return;
+        }
+        for (c, src) in [color.r, color.g, color.b].into_iter().enumerate() {
+            let dst = self.data[i + c] as f32 / 255.0;
+            let v = (src * color.a + dst * dst_a * (1.0 - color.a)) / a;
+            self.data[i + c] = (v * 255.0).round() as u8;
+        }
+        self.data[i + 3] = (a * 255.0).round() as u8;
+    }
+
+    /// Paint the pixels whose centers are inside of the triangle `points`, and for which `visible` is true.
+    fn fill_triangle(&mut self, points: [Point; 3], color: Color, visible: impl Fn(Point) -> bool) {
+        let [a, b, c] = points;
+        let edge =
+            |p: Point, q: Point, r: Point| (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x);
+        let area = edge(a, b, c);
+        if area == 0.0 {
+            return;
+        }
+        let min_x = a.x.min(b.x).min(c.x).floor().max(0.0) as u32;
+        let min_y = a.y.min(b.y).min(c.y).floor().max(0.0) as u32;
+        let max_x = (a.x.max(b.x).max(c.x).ceil().max(0.0) as u32).min(self.width);
+        let max_y = (a.y.max(b.y).max(c.y).ceil().max(0.0) as u32).min(self.height);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+                // The pixel is inside when it is on the same side of every edge as the triangle
+                let w = [edge(b, c, p), edge(c, a, p), edge(a, b, p)];
+                if w.iter().all(|w| w * area >= 0.0) && visible(p) {
+                    self.blend(x, y, color);
+                }
+            }
+        }
+    }
+}
+
+/// What gets painted: triangles in physical coordinates, relative to the captured Node.
+struct Paint {
+    z: f32,
+    triangles: Vec<[Point; 3]>,
+    color: Color,
+    /// The clip paths that the paint is clipped to.
+    clips: Vec<ClipFrame>,
+}
+
+/// Paint the [`Rect`]s and [`Shape`]s of `node` and its descendants on the CPU, into an image the size of the Node's [`AABB`]. Shapes are not anti-aliased, and [`Text`] and [`Raster`]s are left out. Scroll frames do not clip what is painted, while [clip paths][Node#method.clip_path] do.
+pub(crate) fn rasterize(node: &Node, caches: &Caches) -> Image {
+    let origin = node.aabb.pos;
+    let offset = |p: Point| Point::new(p.x - origin.x, p.y - origin.y);
+    let mut paints: Vec<Paint> = vec![];
+    let mut shape_cache = caches.shape_buffer.write().unwrap();
+
+    for (renderable, aabb, frame) in node.iter_renderables() {
+        let clips: Vec<ClipFrame> = frame
+            .into_iter()
+            .filter(|f| matches!(f, ClipFrame::Path(..)))
+            .collect();
+        match renderable {
+            Renderable::Rect(r) => {
+                let i = r.render(aabb);
+                let tl = offset(Point::new(i.pos.x, i.pos.y));
+                let br = Point::new(tl.x + i.scale.width, tl.y + i.scale.height);
+                let tr = Point::new(br.x, tl.y);
+                let bl = Point::new(tl.x, br.y);
+                paints.push(Paint {
+                    z: i.pos.z,
+                    triangles: vec![[tl, tr, br], [tl, br, bl]],
+                    color: i.color,
+                    clips,
+                });
+            }
+            Renderable::Shape(s) => {
+                let instances = s.render(aabb, &mut shape_cache);
+                let (vertex_chunk, index_chunk) = shape_cache.get_chunks(s.buffer_id);
+                let ranges = [
+                    Some(s.fill_range.clone()).filter(|_| s.is_filled()),
+                    Some(s.stroke_range.clone()).filter(|_| s.is_stroked()),
+                ];
+                for (instance, range) in instances.iter().zip(ranges.into_iter().flatten()) {
+                    let vertex = |i: u32| {
+                        let index = shape_cache.index_data[index_chunk.start + i as usize];
+                        let v = shape_cache.vertex_data[vertex_chunk.start + index as usize];
+                        offset(Point::new(
+                            v.pos.x + v.norm.x * instance.stroke_width + instance.pos.x,
+                            v.pos.y + v.norm.y * instance.stroke_width + instance.pos.y,
+                        ))
+                    };
+                    let triangles = range
+                        .step_by(3)
+                        .map(|i| [vertex(i), vertex(i + 1), vertex(i + 2)])
+                        .collect();
+                    paints.push(Paint {
+                        z: instance.pos.z,
+                        triangles,
+                        color: instance.color,
+                        clips: clips.clone(),
+                    });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // Paint from back to front
+    paints.sort_by(|a, b| a.z.total_cmp(&b.z));
+    let mut image = Image::new(
+        node.aabb.width().ceil() as u32,
+        node.aabb.height().ceil() as u32,
+    );
+    for paint in paints.iter() {
+        let visible = |p: Point| {
+            let p = Point::new(p.x + origin.x, p.y + origin.y);
+            paint.clips.iter().all(|c| c.contains(p))
+        };
+        for triangle in paint.triangles.iter() {
+            image.fill_triangle(*triangle, paint.color, visible);
+        }
+    }
+    image
+}
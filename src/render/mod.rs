@@ -6,14 +6,39 @@ use crate::font_cache::FontCache;
 use crate::node::Node;
 use crate::window::Window;
 
+mod capture;
 pub(crate) mod glyph_brush_draw_cache;
 pub mod renderables;
 pub(crate) mod wgpu;
 
 use crate::render::renderables::BufferCache;
 use crate::render::renderables::RasterCache;
+pub use ::wgpu::PresentMode;
+pub(crate) use capture::rasterize;
+pub use capture::Image;
 pub use renderables::Renderable;
 
+/// Options for how a [`UI`][crate::UI] renders its frames. See [`UI#method.with_render_options`][crate::UI#method.with_render_options].
+#[derive(Debug, Clone, Copy)]
+pub struct RenderOptions {
+    /// How rendered frames are presented to the window. The default, [`PresentMode::AutoVsync`], waits for the display's vertical blank, which avoids tearing and saves power. [`PresentMode::AutoNoVsync`] (or `Mailbox`/`Immediate`) presents frames as soon as they are ready, for lower latency. Modes that the platform does not support fall back to [`PresentMode::Fifo`], which is always supported.
+    pub present_mode: PresentMode,
+    /// The maximum number of frames rendered per second, if any. A frame that would exceed the cap is held until enough time has passed since the last one, and then rendered.
+    pub frame_cap: Option<u32>,
+    /// The number of samples per pixel used to anti-alias shapes, when the `msaa_shapes` feature is enabled: 1 (no anti-aliasing), 2, 4 or 8. Higher counts look smoother but cost more. Counts that the device does not support fall back to the nearest lower count that it does.
+    pub msaa_samples: u32,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            present_mode: PresentMode::AutoVsync,
+            frame_cap: None,
+            msaa_samples: 4,
+        }
+    }
+}
+
 /// The caches used by the Renderer. Passed to [`Component#render`][crate::Component#method.render] in a [`RenderContext`][crate::RenderContext].
 #[derive(Clone, Default)]
 pub struct Caches {
@@ -30,7 +55,7 @@ pub struct Caches {
 }
 
 pub(crate) trait Renderer: fmt::Debug + std::marker::Sized + Send + Sync {
-    fn new<W: Window>(window: &W) -> Self;
+    fn new<W: Window>(window: &W, options: &RenderOptions) -> Self;
     fn render(&mut self, _node: &Node, _physical_size: PixelSize) {}
     /// This default is provided for tests, it should be overridden
     fn caches(&self) -> Caches {
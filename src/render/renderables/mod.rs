@@ -10,10 +10,10 @@ pub mod shape;
 pub mod text;
 
 pub use buffer_cache::*;
-pub use raster::Raster;
+pub use raster::{Raster, RasterQuad};
 pub use raster_cache::*;
 pub use rect::Rect;
-pub use shape::Shape;
+pub use shape::{LineCap, LineJoin, Shape, StrokeDash, StrokeStyle};
 pub use text::Text;
 
 /// The type returned by [`Component#render`][crate::Component#method.render], which contains the data required to render a Component (along with the [`Caches`][super::Caches]).
@@ -2,7 +2,7 @@ use bytemuck::{Pod, Zeroable};
 
 use super::{BufferCache, BufferCacheId};
 use super::{RasterCache, RasterCacheId, RasterData};
-use crate::base_types::{Point, Pos, AABB};
+use crate::base_types::{Color, Point, Pos, AABB};
 use crate::PixelSize;
 
 const INDEX_ENTRIES_PER_IMAGE: usize = 6;
@@ -40,6 +40,7 @@ impl crate::render::wgpu::VBDesc for Vertex {
 #[derive(Clone, Copy, Debug, Pod, Zeroable, Default)]
 pub(crate) struct Instance {
     pub pos: Pos,
+    pub tint: Color,
 }
 
 impl crate::render::wgpu::VBDesc for Instance {
@@ -47,19 +48,101 @@ impl crate::render::wgpu::VBDesc for Instance {
         wgpu::VertexBufferLayout {
             array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
             step_mode: wgpu::VertexStepMode::Instance,
-            attributes: &[wgpu::VertexAttribute {
-                format: wgpu::VertexFormat::Float32x3,
-                offset: 0,
-                shader_location: 2,
-            }],
+            attributes: &[
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x3,
+                    offset: 0,
+                    shader_location: 2,
+                },
+                wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x4,
+                    offset: 4 * 3,
+                    shader_location: 3,
+                },
+            ],
         }
     }
 }
 
+/// Distances measured inward from each edge of a rectangle. Used to describe the fixed corners and edges of a nine-slice [`Raster`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Insets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Insets {
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    pub fn uniform(inset: f32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+
+    fn scale(&self, x: f32, y: f32) -> Self {
+        Self::new(self.top * y, self.right * x, self.bottom * y, self.left * x)
+    }
+}
+
+/// Describes how a [`Raster`] is split into nine slices: The corners are drawn at a fixed size, the edges stretch along one axis, and the center stretches along both.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct NineSliceInsets {
+    /// Insets into the source image, in pixels.
+    pub source: Insets,
+    /// Insets into the drawn [`AABB`], in physical pixels.
+    pub dest: Insets,
+}
+
+/// One rectangle of a [`Raster`] that is drawn as several, e.g. as tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RasterQuad {
+    /// The `(top_left, bottom_right)` corners of the quad, in physical pixels from the top left of the drawn [`AABB`].
+    pub dest: (Point, Point),
+    /// The `(top_left, bottom_right)` corners of the part of the image that is drawn in the quad, as fractions of its width and height.
+    pub source: (Point, Point),
+}
+
+/// Split the rectangle from `start` to `end` into nine `(top_left, bottom_right)` rectangles, in row-major order, by the given `insets`.
+pub(crate) fn nine_slice_rects(start: Point, end: Point, insets: Insets) -> [(Point, Point); 9] {
+    let xs = [start.x, start.x + insets.left, end.x - insets.right, end.x];
+    let ys = [start.y, start.y + insets.top, end.y - insets.bottom, end.y];
+    let mut rects = [(Point::default(), Point::default()); 9];
+    for row in 0..3 {
+        for col in 0..3 {
+            rects[row * 3 + col] = (
+                Point {
+                    x: xs[col],
+                    y: ys[row],
+                },
+                Point {
+                    x: xs[col + 1],
+                    y: ys[row + 1],
+                },
+            );
+        }
+    }
+    rects
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Raster {
     pub buffer_id: BufferCacheId,
     pub raster_cache_id: RasterCacheId,
+    pub nine_slice: Option<NineSliceInsets>,
+    /// When given, the raster is drawn as these quads, rather than stretched over its whole [`AABB`].
+    pub quads: Option<Vec<RasterQuad>>,
+    /// Multiplied with the color of each pixel.
+    pub tint: Color,
+    /// Added to the z position of the drawn [`AABB`], to layer the raster with other renderables of its Node.
+    pub z: f32,
 }
 
 impl Raster {
@@ -82,6 +165,199 @@ impl Raster {
         Self {
             buffer_id,
             raster_cache_id,
+            nine_slice: None,
+            quads: None,
+            tint: Color::WHITE,
+            z: 0.0,
+        }
+    }
+
+    /// Like [`Raster::new`], but the raster will be drawn as nine slices, as described by `nine_slice`.
+    pub fn nine_slice(
+        data: RasterData,
+        size: PixelSize,
+        nine_slice: NineSliceInsets,
+        buffer_cache: &mut BufferCache<Vertex, u16>,
+        raster_cache: &mut RasterCache,
+        prev_buffer: Option<BufferCacheId>,
+        prev_raster: Option<RasterCacheId>,
+    ) -> Self {
+        let buffer_id = if let Some(c) = prev_buffer {
+            buffer_cache.alloc_or_reuse_chunk(
+                c,
+                VERTEX_ENTRIES_PER_IMAGE * 9,
+                INDEX_ENTRIES_PER_IMAGE * 9,
+            )
+        } else {
+            buffer_cache.alloc_chunk(VERTEX_ENTRIES_PER_IMAGE * 9, INDEX_ENTRIES_PER_IMAGE * 9)
+        };
+        let raster_cache_id = raster_cache.alloc_or_reuse_chunk(prev_raster);
+        raster_cache.set_raster(raster_cache_id, data, size);
+
+        Self {
+            buffer_id,
+            raster_cache_id,
+            nine_slice: Some(nine_slice),
+            quads: None,
+            tint: Color::WHITE,
+            z: 0.0,
+        }
+    }
+
+    /// Like [`Raster::new`], but the raster will be drawn as each of the given `quads`, e.g. to tile it. The raster is identified by `content_hash`, and is only uploaded again when it changes from that of the previous raster.
+    #[allow(clippy::too_many_arguments)]
+    pub fn quads(
+        data: RasterData,
+        size: PixelSize,
+        content_hash: u64,
+        quads: Vec<RasterQuad>,
+        buffer_cache: &mut BufferCache<Vertex, u16>,
+        raster_cache: &mut RasterCache,
+        prev_buffer: Option<BufferCacheId>,
+        prev_raster: Option<RasterCacheId>,
+    ) -> Self {
+        let (v, i) = (
+            VERTEX_ENTRIES_PER_IMAGE * quads.len(),
+            INDEX_ENTRIES_PER_IMAGE * quads.len(),
+        );
+        let buffer_id = if let Some(c) = prev_buffer {
+            buffer_cache.alloc_or_reuse_chunk(c, v, i)
+        } else {
+            buffer_cache.alloc_chunk(v, i)
+        };
+        let raster_cache_id = raster_cache.alloc_or_reuse_chunk(prev_raster);
+        raster_cache.set_raster_if_changed(raster_cache_id, data, size, content_hash);
+
+        Self {
+            buffer_id,
+            raster_cache_id,
+            nine_slice: None,
+            quads: Some(quads),
+            tint: Color::WHITE,
+            z: 0.0,
+        }
+    }
+
+    pub fn tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn z(mut self, z: f32) -> Self {
+        self.z = z;
+        self
+    }
+
+    fn fill_quad(
+        buffer_cache: &mut BufferCache<Vertex, u16>,
+        quad: usize,
+        v: usize,
+        i: usize,
+        pos: (Point, Point),
+        tex_pos: (Point, Point),
+    ) {
+        let v = v + quad * VERTEX_ENTRIES_PER_IMAGE;
+        let i = i + quad * INDEX_ENTRIES_PER_IMAGE;
+        let first = (quad * VERTEX_ENTRIES_PER_IMAGE) as u16;
+
+        buffer_cache.vertex_data[v] = Vertex {
+            pos: pos.0,
+            tex_pos: tex_pos.0,
+        };
+        buffer_cache.vertex_data[v + 1] = Vertex {
+            pos: Point {
+                x: pos.1.x,
+                y: pos.0.y,
+            },
+            tex_pos: Point {
+                x: tex_pos.1.x,
+                y: tex_pos.0.y,
+            },
+        };
+        buffer_cache.vertex_data[v + 2] = Vertex {
+            pos: Point {
+                x: pos.0.x,
+                y: pos.1.y,
+            },
+            tex_pos: Point {
+                x: tex_pos.0.x,
+                y: tex_pos.1.y,
+            },
+        };
+        buffer_cache.vertex_data[v + 3] = Vertex {
+            pos: pos.1,
+            tex_pos: tex_pos.1,
+        };
+
+        buffer_cache.index_data[i] = first;
+        buffer_cache.index_data[i + 1] = first + 1;
+        buffer_cache.index_data[i + 2] = first + 2;
+        buffer_cache.index_data[i + 3] = first + 2;
+        buffer_cache.index_data[i + 4] = first + 1;
+        buffer_cache.index_data[i + 5] = first + 3;
+    }
+
+    fn fill_nine_slice(
+        &self,
+        nine_slice: NineSliceInsets,
+        aabb: &AABB,
+        tex_coords: (Point, Point),
+        buffer_cache: &mut BufferCache<Vertex, u16>,
+        raster_cache: &RasterCache,
+    ) {
+        let (vertex_chunk, index_chunk) = buffer_cache.get_chunks(self.buffer_id);
+        let size = raster_cache.get_raster_data(self.raster_cache_id).size;
+        // Convert the source insets from pixels into texture coordinates
+        let tex_insets = nine_slice.source.scale(
+            (tex_coords.1.x - tex_coords.0.x) / size.width.max(1) as f32,
+            (tex_coords.1.y - tex_coords.0.y) / size.height.max(1) as f32,
+        );
+        let positions = nine_slice_rects(
+            Point { x: 0.0, y: 0.0 },
+            Point {
+                x: aabb.width(),
+                y: aabb.height(),
+            },
+            nine_slice.dest,
+        );
+        let tex_positions = nine_slice_rects(tex_coords.0, tex_coords.1, tex_insets);
+
+        for (quad, (pos, tex_pos)) in positions.iter().zip(tex_positions.iter()).enumerate() {
+            Self::fill_quad(
+                buffer_cache,
+                quad,
+                vertex_chunk.start,
+                index_chunk.start,
+                *pos,
+                *tex_pos,
+            );
+        }
+    }
+
+    fn fill_quads(
+        &self,
+        quads: &[RasterQuad],
+        tex_coords: (Point, Point),
+        buffer_cache: &mut BufferCache<Vertex, u16>,
+    ) {
+        let (vertex_chunk, index_chunk) = buffer_cache.get_chunks(self.buffer_id);
+        let tex_size = Point {
+            x: tex_coords.1.x - tex_coords.0.x,
+            y: tex_coords.1.y - tex_coords.0.y,
+        };
+        let tex_pos = |p: Point| Point {
+            x: tex_coords.0.x + p.x * tex_size.x,
+            y: tex_coords.0.y + p.y * tex_size.y,
+        };
+        for (i, quad) in quads.iter().enumerate() {
+            Self::fill_quad(
+                buffer_cache,
+                i,
+                vertex_chunk.start,
+                index_chunk.start,
+                quad.dest,
+                (tex_pos(quad.source.0), tex_pos(quad.source.1)),
+            );
         }
     }
 
@@ -101,55 +377,67 @@ impl Raster {
 
         if cache_invalid || !vertex_chunk.filled {
             cache_changed = true;
-            let v = vertex_chunk.start;
-            let i = index_chunk.start;
-            let width = aabb.width();
-            let height = aabb.height();
-
-            buffer_cache.vertex_data[v] = Vertex {
-                pos: Point { x: 0.0, y: 0.0 },
-                tex_pos: Point {
-                    x: tex_coords.0.x,
-                    y: tex_coords.0.y,
-                },
-            };
-            buffer_cache.vertex_data[v + 1] = Vertex {
-                pos: Point { x: width, y: 0.0 },
-                tex_pos: Point {
-                    x: tex_coords.1.x,
-                    y: tex_coords.0.y,
-                },
-            };
-            buffer_cache.vertex_data[v + 2] = Vertex {
-                pos: Point { x: 0.0, y: height },
-                tex_pos: Point {
-                    x: tex_coords.0.x,
-                    y: tex_coords.1.y,
-                },
-            };
-            buffer_cache.vertex_data[v + 3] = Vertex {
-                pos: Point {
-                    x: width,
-                    y: height,
-                },
-                tex_pos: Point {
-                    x: tex_coords.1.x,
-                    y: tex_coords.1.y,
-                },
-            };
-
-            buffer_cache.index_data[i] = 0;
-            buffer_cache.index_data[i + 1] = 1;
-            buffer_cache.index_data[i + 2] = 2;
-            buffer_cache.index_data[i + 3] = 2;
-            buffer_cache.index_data[i + 4] = 1;
-            buffer_cache.index_data[i + 5] = 3;
+            if let Some(quads) = &self.quads {
+                self.fill_quads(quads, tex_coords, buffer_cache);
+            } else if let Some(nine_slice) = self.nine_slice {
+                self.fill_nine_slice(nine_slice, aabb, tex_coords, buffer_cache, raster_cache);
+            } else {
+                Self::fill_quad(
+                    buffer_cache,
+                    0,
+                    vertex_chunk.start,
+                    index_chunk.start,
+                    (
+                        Point { x: 0.0, y: 0.0 },
+                        Point {
+                            x: aabb.width(),
+                            y: aabb.height(),
+                        },
+                    ),
+                    tex_coords,
+                );
+            }
 
             buffer_cache.fill_chunks(self.buffer_id);
         }
 
-        instance_data.push(Instance { pos: aabb.pos });
+        instance_data.push(Instance {
+            pos: Pos {
+                z: aabb.pos.z + self.z,
+                ..aabb.pos
+            },
+            tint: self.tint,
+        });
 
         cache_changed
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nine_slice_rects() {
+        let rects = nine_slice_rects(
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 100.0, y: 50.0 },
+            Insets::new(5.0, 10.0, 15.0, 20.0),
+        );
+        let p = |x, y| Point { x, y };
+        assert_eq!(
+            rects,
+            [
+                (p(0.0, 0.0), p(20.0, 5.0)),
+                (p(20.0, 0.0), p(90.0, 5.0)),
+                (p(90.0, 0.0), p(100.0, 5.0)),
+                (p(0.0, 5.0), p(20.0, 35.0)),
+                (p(20.0, 5.0), p(90.0, 35.0)),
+                (p(90.0, 5.0), p(100.0, 35.0)),
+                (p(0.0, 35.0), p(20.0, 50.0)),
+                (p(20.0, 35.0), p(90.0, 50.0)),
+                (p(90.0, 35.0), p(100.0, 50.0)),
+            ]
+        );
+    }
+}
@@ -29,6 +29,8 @@ pub struct RasterCacheData {
     pub(crate) id: RasterId,
     pub data: RasterData,
     pub size: PixelSize,
+    /// Identifies the content of rasters set with [`RasterCache::set_raster_if_changed`].
+    content_hash: Option<u64>,
     /// Has this raster been altered?
     pub dirty: bool,
     /// Rasters are unmarked at the start of a render pass and marked as each renderable renders to them
@@ -119,6 +121,7 @@ impl RasterCache {
                         id: 0,
                         marked: true,
                         dirty: true,
+                        content_hash: None,
                         size: PixelSize {
                             width: 0,
                             height: 0,
@@ -141,7 +144,51 @@ impl RasterCache {
             id: new_raster_id(),
             marked: true,
             dirty: true,
+            content_hash: None,
             size,
         };
     }
+
+    /// Like [`#set_raster`][RasterCache#method.set_raster], but leave the raster as it is if it was last set with the same `content_hash` and `size`, so that it isn't uploaded again.
+    pub fn set_raster_if_changed<D: Into<RasterData>>(
+        &mut self,
+        raster_cache_id: RasterCacheId,
+        data: D,
+        size: PixelSize,
+        content_hash: u64,
+    ) {
+        let raster = &mut self.rasters[raster_cache_id.0];
+        if raster.content_hash == Some(content_hash) && raster.size == size {
+            raster.marked = true;
+            return;
+        }
+        self.set_raster(raster_cache_id, data, size);
+        self.rasters[raster_cache_id.0].content_hash = Some(content_hash);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_raster_if_changed() {
+        let mut cache = RasterCache::new();
+        let size = PixelSize::new(1, 1);
+        let id = cache.alloc_or_reuse_chunk(None);
+        cache.set_raster_if_changed(id, vec![0, 0, 0, 255], size, 1);
+        let raster_id = cache.get_raster_data(id).id;
+        cache.get_mut_raster_data(id).clean();
+
+        // The same content is not set again
+        cache.set_raster_if_changed(id, vec![0, 0, 0, 255], size, 1);
+        assert_eq!(cache.get_raster_data(id).id, raster_id);
+        assert!(!cache.get_raster_data(id).dirty);
+
+        cache.set_raster_if_changed(id, vec![255, 0, 0, 255], size, 2);
+        assert_ne!(cache.get_raster_data(id).id, raster_id);
+        assert!(cache.get_raster_data(id).dirty);
+        let data: &[u8] = (&cache.get_raster_data(id).data).into();
+        assert_eq!(data, &[255, 0, 0, 255]);
+    }
 }
@@ -3,10 +3,12 @@ use std::ops::Range;
 
 use bytemuck::{Pod, Zeroable};
 use lyon;
-use lyon::path::Path;
+use lyon::path::iterator::PathIterator;
+use lyon::path::{Path, PathEvent};
 use lyon::tessellation;
 use lyon::tessellation::geometry_builder::VertexBuffers;
 use lyon::tessellation::math as lyon_math;
+pub use lyon::tessellation::{LineCap, LineJoin};
 
 use super::{BufferCache, BufferCacheId};
 use crate::base_types::{Color, Point, Pos, AABB};
@@ -117,6 +119,132 @@ impl crate::render::wgpu::VBDesc for Instance {
     }
 }
 
+/// A pattern of alternating dash and gap lengths for strokes, in logical pixels. Like SVG's `stroke-dasharray`, a pattern with an odd number of lengths is repeated to make it even, e.g. `[4.0]` is equivalent to `[4.0, 4.0]`. `offset` is how far into the pattern each sub-path of the stroke starts.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StrokeDash {
+    pub pattern: Vec<f32>,
+    pub offset: f32,
+}
+
+impl StrokeDash {
+    pub fn new(pattern: Vec<f32>) -> Self {
+        Self {
+            pattern,
+            offset: 0.0,
+        }
+    }
+
+    pub fn offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Split `path` into one open sub-path per dash. Curves are flattened first. The pattern is scaled by `scale_factor`, so that dashes are the same logical length on every display.
+    pub fn dash_path(&self, path: &Path, scale_factor: f32) -> Path {
+        let mut pattern: Vec<f32> = self
+            .pattern
+            .iter()
+            .map(|l| l.max(0.0) * scale_factor)
+            .collect();
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(&pattern.clone());
+        }
+        let total: f32 = pattern.iter().sum();
+        if total <= 0.0 {
+            return path.clone();
+        }
+
+        let mut builder = Path::builder();
+        // Position in the pattern: The index of the current dash or gap, how much of it is left, and whether we're inside an open sub-path
+        let mut index = 0;
+        let mut remaining = 0.0;
+        let mut drawing = false;
+        let reset = |index: &mut usize, remaining: &mut f32| {
+            let mut offset = (self.offset * scale_factor).rem_euclid(total);
+            *index = 0;
+            while offset >= pattern[*index] {
+                offset -= pattern[*index];
+                *index = (*index + 1) % pattern.len();
+            }
+            *remaining = pattern[*index] - offset;
+        };
+
+        for event in path.iter().flattened(TOLERANCE) {
+            let (from, to) = match event {
+                PathEvent::Begin { .. } => {
+                    drawing = false;
+                    reset(&mut index, &mut remaining);
+                    continue;
+                }
+                PathEvent::Line { from, to } => (from, to),
+                PathEvent::End {
+                    last,
+                    first,
+                    close: true,
+                } => (last, first),
+                _ => continue,
+            };
+
+            let length = (to - from).length();
+            let mut travelled = 0.0;
+            while travelled < length {
+                let step = remaining.min(length - travelled);
+                let on = index % 2 == 0;
+                if on && !drawing {
+                    builder.move_to(from.lerp(to, travelled / length));
+                    drawing = true;
+                }
+                travelled += step;
+                remaining -= step;
+                if on {
+                    builder.line_to(from.lerp(to, travelled / length));
+                }
+                if remaining <= 0.0 {
+                    drawing = false;
+                    index = (index + 1) % pattern.len();
+                    remaining = pattern[index];
+                }
+            }
+        }
+        builder.build()
+    }
+}
+
+/// How a stroke is drawn: The shape of its corners and ends, and whether it is dashed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub line_join: LineJoin,
+    pub line_cap: LineCap,
+    pub dash: Option<StrokeDash>,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            line_join: LineJoin::Miter,
+            line_cap: LineCap::Butt,
+            dash: None,
+        }
+    }
+}
+
+impl StrokeStyle {
+    pub fn line_join(mut self, line_join: LineJoin) -> Self {
+        self.line_join = line_join;
+        self
+    }
+
+    pub fn line_cap(mut self, line_cap: LineCap) -> Self {
+        self.line_cap = line_cap;
+        self
+    }
+
+    pub fn dash(mut self, dash: StrokeDash) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+}
+
 #[derive(PartialEq)]
 pub struct Shape {
     fill_color: Color,
@@ -190,6 +318,42 @@ impl Shape {
         (geometry, fill_count)
     }
 
+    /// Like [`#path_to_shape_geometry`][Shape#method.path_to_shape_geometry], but the stroke is drawn with the given joins, caps and dashes. `scale_factor` scales the dashes. The fill is not affected.
+    ///
+    /// `stroke_width` is the full width that the stroke will be drawn at, in device pixels. It is not applied to the geometry, but it determines how finely round joins and caps are divided.
+    pub fn path_to_styled_shape_geometry(
+        path: Path,
+        fill: bool,
+        style: &StrokeStyle,
+        stroke_width: f32,
+        scale_factor: f32,
+    ) -> (ShapeGeometry, u32) {
+        let (mut geometry, fill_count) = if fill {
+            Shape::path_to_shape_geometry(path.clone(), true, false)
+        } else {
+            (ShapeGeometry::new(), 0)
+        };
+        let stroke_path = match &style.dash {
+            Some(dash) => dash.dash_path(&path, scale_factor),
+            None => path,
+        };
+        tessellation::StrokeTessellator::new()
+            .tessellate_path(
+                &stroke_path,
+                &Shape::stroke_options()
+                    .with_line_width(stroke_width)
+                    .with_line_join(style.line_join)
+                    .with_line_cap(style.line_cap),
+                &mut tessellation::BuffersBuilder::new(
+                    &mut geometry,
+                    Vertex::stroke_vertex_constructor,
+                ),
+            )
+            .unwrap();
+
+        (geometry, fill_count)
+    }
+
     pub fn new(
         geometry: ShapeGeometry,
         fill_index_count: u32,
@@ -284,3 +448,117 @@ impl Shape {
         ret
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The length of each sub-path of `path`.
+    fn segment_lengths(path: &Path) -> Vec<f32> {
+        let mut lengths = vec![];
+        for event in path.iter() {
+            match event {
+                PathEvent::Begin { .. } => lengths.push(0.0),
+                PathEvent::Line { from, to } => {
+                    *lengths.last_mut().unwrap() += (to - from).length()
+                }
+                _ => (),
+            }
+        }
+        lengths
+    }
+
+    fn assert_lengths(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(
+            actual.len(),
+            expected.len(),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 0.001, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_dash_path() {
+        let mut builder = Path::builder();
+        builder.move_to(lyon_math::point(0.0, 0.0));
+        builder.line_to(lyon_math::point(100.0, 0.0));
+        let line = builder.build();
+
+        let dash = StrokeDash::new(vec![10.0, 5.0]);
+        assert_lengths(segment_lengths(&dash.dash_path(&line, 1.0)), &[10.0; 7]);
+
+        // Scales with the scale factor
+        assert_lengths(
+            segment_lengths(&dash.dash_path(&line, 2.0)),
+            &[20.0, 20.0, 20.0, 10.0],
+        );
+
+        // Starts partway into the pattern
+        assert_lengths(
+            segment_lengths(&dash.clone().offset(5.0).dash_path(&line, 1.0)),
+            &[5.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0],
+        );
+
+        // Odd patterns are repeated
+        assert_lengths(
+            segment_lengths(&StrokeDash::new(vec![25.0]).dash_path(&line, 1.0)),
+            &[25.0, 25.0],
+        );
+
+        // Dashes continue around corners
+        let mut builder = Path::builder();
+        builder.move_to(lyon_math::point(0.0, 0.0));
+        builder.line_to(lyon_math::point(8.0, 0.0));
+        builder.line_to(lyon_math::point(8.0, 20.0));
+        assert_lengths(
+            segment_lengths(&dash.dash_path(&builder.build(), 1.0)),
+            &[10.0, 10.0],
+        );
+    }
+
+    #[test]
+    fn test_round_join() {
+        let mut builder = Path::builder();
+        builder.move_to(lyon_math::point(0.0, 0.0));
+        builder.line_to(lyon_math::point(50.0, 0.0));
+        builder.line_to(lyon_math::point(50.0, 50.0));
+        let corner = builder.build();
+
+        let geometry = |line_join| {
+            Shape::path_to_styled_shape_geometry(
+                corner.clone(),
+                false,
+                &StrokeStyle::default().line_join(line_join),
+                10.0,
+                1.0,
+            )
+            .0
+        };
+        // The corner's vertices are extruded along their normals, so look at the directions of the ones at the corner
+        let corner_normals = |geometry: &ShapeGeometry| -> Vec<Point> {
+            geometry
+                .vertices
+                .iter()
+                .filter(|v| v.pos.x == 50.0 && v.pos.y == 0.0)
+                .map(|v| v.norm)
+                .collect()
+        };
+        let is_diagonal_unit = |n: &Point| {
+            n.x.abs() > 0.2
+                && n.y.abs() > 0.2
+                && ((n.x * n.x + n.y * n.y).sqrt() - 1.0).abs() < 0.01
+        };
+
+        let miter = geometry(LineJoin::Miter);
+        let round = geometry(LineJoin::Round);
+        assert!(round.vertices.len() > miter.vertices.len());
+        // A round join has points on the arc between the two edges' normals
+        assert!(corner_normals(&round).iter().any(is_diagonal_unit));
+        // While a miter join goes straight to the corner
+        assert!(!corner_normals(&miter).iter().any(is_diagonal_unit));
+    }
+}
@@ -1,7 +1,11 @@
+use log::warn;
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
+use crate::render::RenderOptions;
 use crate::PixelSize;
 
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+
 pub struct WGPUContext {
     pub device: wgpu::Device,
     pub depthbuffer: wgpu::TextureView,
@@ -80,7 +84,7 @@ fn depthbuffer(
             mip_level_count: 1,
             sample_count,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Depth24PlusStencil8,
+            format: DEPTH_FORMAT,
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
             label: Some("Depth buffer"),
@@ -88,10 +92,56 @@ fn depthbuffer(
         .create_view(&wgpu::TextureViewDescriptor::default())
 }
 
+/// Use `present_mode` if it is supported, otherwise fall back to `Fifo`, which always is.
+fn supported_present_mode(
+    present_mode: wgpu::PresentMode,
+    supported: &[wgpu::PresentMode],
+) -> wgpu::PresentMode {
+    match present_mode {
+        // wgpu picks a supported mode for these
+        wgpu::PresentMode::AutoVsync | wgpu::PresentMode::AutoNoVsync => present_mode,
+        _ if supported.contains(&present_mode) => present_mode,
+        _ => wgpu::PresentMode::Fifo,
+    }
+}
+
+/// The largest of the MSAA sample counts 8, 4 and 2 that is no more than `requested` and is `supported`, or 1.
+fn supported_sample_count(requested: u32, supported: impl Fn(u32) -> bool) -> u32 {
+    [8, 4, 2]
+        .into_iter()
+        .find(|count| *count <= requested && supported(*count))
+        .unwrap_or(1)
+}
+
+fn surface_configuration(
+    surface_caps: &wgpu::SurfaceCapabilities,
+    width: u32,
+    height: u32,
+    present_mode: wgpu::PresentMode,
+) -> wgpu::SurfaceConfiguration {
+    let format = surface_caps
+        .formats
+        .iter()
+        .copied()
+        .find(|f| !f.is_srgb())
+        .unwrap_or(surface_caps.formats[0]);
+
+    wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT, // We are drawing to the window
+        format,
+        width,
+        height,
+        present_mode: supported_present_mode(present_mode, &surface_caps.present_modes),
+        alpha_mode: surface_caps.alpha_modes[0],
+        view_formats: vec![],
+    }
+}
+
 pub async fn get_wgpu_context<W: HasRawWindowHandle + HasRawDisplayHandle>(
     window: &W,
     width: u32,
     height: u32,
+    options: &RenderOptions,
 ) -> WGPUContext {
     let backends = if cfg!(windows) {
         //wgpu::Backends::VULKAN
@@ -108,8 +158,6 @@ pub async fn get_wgpu_context<W: HasRawWindowHandle + HasRawDisplayHandle>(
             .create_surface(window)
             .expect("Failed to get a surface")
     };
-    // Maybe TODO: Figure out how to set this dynamically?
-    let sample_count = 4; // Max supported on OSX
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
             power_preference: wgpu::PowerPreference::default(),
@@ -119,10 +167,12 @@ pub async fn get_wgpu_context<W: HasRawWindowHandle + HasRawDisplayHandle>(
         .await
         .expect("Failed to get an adapter");
 
+    // Needed for sample counts other than 1 and 4
+    let features = adapter.features() & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
     let (device, queue) = adapter
         .request_device(
             &wgpu::DeviceDescriptor {
-                features: wgpu::Features::empty(),
+                features,
                 limits: wgpu::Limits::default(),
                 label: None,
             },
@@ -132,24 +182,28 @@ pub async fn get_wgpu_context<W: HasRawWindowHandle + HasRawDisplayHandle>(
         .expect("Failed to get a device");
 
     let surface_caps = surface.get_capabilities(&adapter);
-    let format = surface_caps
-        .formats
-        .iter()
-        .copied()
-        .find(|f| !f.is_srgb())
-        .unwrap_or(surface_caps.formats[0]);
-
-    let surface_config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT, // We are drawing to the window
-        format,
-        width,
-        height,
-        present_mode: surface_caps.present_modes[0],
-        alpha_mode: surface_caps.alpha_modes[0],
-        view_formats: vec![],
-    };
+    let surface_config = surface_configuration(&surface_caps, width, height, options.present_mode);
     surface.configure(&device, &surface_config);
 
+    let sample_count = supported_sample_count(options.msaa_samples, |count| {
+        if features.is_empty() {
+            count == 4
+        } else {
+            [surface_config.format, DEPTH_FORMAT].iter().all(|format| {
+                adapter
+                    .get_texture_format_features(*format)
+                    .flags
+                    .sample_count_supported(count)
+            })
+        }
+    });
+    if sample_count != options.msaa_samples {
+        warn!(
+            "MSAA sample count {} is not supported, using {}",
+            options.msaa_samples, sample_count
+        );
+    }
+
     let depthbuff = depthbuffer(&device, width, height, 1);
     let framebuff = framebuffer(&device, width, height, surface_config.format, 1);
     let msaa_depthbuffer = depthbuffer(&device, width, height, sample_count);
@@ -167,3 +221,49 @@ pub async fn get_wgpu_context<W: HasRawWindowHandle + HasRawDisplayHandle>(
         sample_count,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_mode() {
+        let caps = wgpu::SurfaceCapabilities {
+            formats: vec![
+                wgpu::TextureFormat::Bgra8UnormSrgb,
+                wgpu::TextureFormat::Bgra8Unorm,
+            ],
+            present_modes: vec![wgpu::PresentMode::Fifo, wgpu::PresentMode::Mailbox],
+            alpha_modes: vec![wgpu::CompositeAlphaMode::Opaque],
+        };
+
+        let config = surface_configuration(&caps, 100, 50, wgpu::PresentMode::Mailbox);
+        assert_eq!(config.present_mode, wgpu::PresentMode::Mailbox);
+        assert_eq!(config.format, wgpu::TextureFormat::Bgra8Unorm);
+
+        let config = surface_configuration(&caps, 100, 50, wgpu::PresentMode::AutoNoVsync);
+        assert_eq!(config.present_mode, wgpu::PresentMode::AutoNoVsync);
+
+        // Unsupported
+        let config = surface_configuration(&caps, 100, 50, wgpu::PresentMode::Immediate);
+        assert_eq!(config.present_mode, wgpu::PresentMode::Fifo);
+    }
+
+    #[test]
+    fn test_sample_count() {
+        let all = |_| true;
+        assert_eq!(supported_sample_count(4, all), 4);
+        assert_eq!(supported_sample_count(8, all), 8);
+        assert_eq!(supported_sample_count(1, all), 1);
+        assert_eq!(supported_sample_count(0, all), 1);
+        // Not a level
+        assert_eq!(supported_sample_count(3, all), 2);
+        assert_eq!(supported_sample_count(16, all), 8);
+
+        // Unsupported levels fall back to the nearest lower one
+        let only_4 = |count| count == 4;
+        assert_eq!(supported_sample_count(8, only_4), 4);
+        assert_eq!(supported_sample_count(2, only_4), 1);
+        assert_eq!(supported_sample_count(4, only_4), 4);
+    }
+}
@@ -7,7 +7,7 @@ mod context;
 
 use crate::base_types::{PixelSize, AABB};
 use crate::instrumenting::*;
-use crate::node::{Node, ScrollFrame};
+use crate::node::{ClipFrame, Node};
 use crate::render::{renderables::*, Caches};
 use crate::window::Window;
 
@@ -58,7 +58,7 @@ impl fmt::Debug for WGPURenderer {
 
 #[derive(Default)]
 struct FrameRenderables<'a> {
-    frame: Vec<ScrollFrame>,
+    frame: Vec<ClipFrame>,
     rasters: Vec<(&'a Raster, &'a AABB)>,
     rects: Vec<(&'a Rect, &'a AABB)>,
     shapes: Vec<(&'a Shape, &'a AABB)>,
@@ -67,7 +67,7 @@ struct FrameRenderables<'a> {
 }
 
 impl<'a> FrameRenderables<'a> {
-    fn new(frame: Vec<ScrollFrame>) -> Self {
+    fn new(frame: Vec<ClipFrame>) -> Self {
         Self {
             frame,
             ..Default::default()
@@ -76,13 +76,14 @@ impl<'a> FrameRenderables<'a> {
 }
 
 impl super::Renderer for WGPURenderer {
-    fn new<W: Window>(window: &W) -> Self {
+    fn new<W: Window>(window: &W, options: &super::RenderOptions) -> Self {
         let size = window.physical_size();
         let context = block_on(context::get_wgpu_context(
             window,
             // This ensures that the first render will always resize, which resolves issues on some backends
             size.width - 1,
             size.height - 1,
+            options,
         ));
         let device = &context.device;
 
@@ -165,6 +166,97 @@ impl super::Renderer for WGPURenderer {
         self.shape_pipeline.unmark_buffer_cache();
         self.raster_pipeline.unmark_cache();
 
+        let draw_calls = self.draw(node, &view, wgpu::Color::WHITE);
+        output.present();
+        counters("Render", &[("draw_calls", draw_calls)]);
+        frame();
+    }
+
+    fn caches(&self) -> Caches {
+        Caches {
+            shape_buffer: self.shape_pipeline.buffer_cache.cache.clone(),
+            text_buffer: self.text_pipeline.buffer_cache.cache.clone(),
+            image_buffer: self.raster_pipeline.buffer_cache.cache.clone(),
+            raster: self.raster_pipeline.texture_cache.raster_cache.clone(),
+            font: self.text_pipeline.font_cache.clone(),
+        }
+    }
+}
+
+impl WGPURenderer {
+    /// Render `node` and its descendants into a new texture the size of the Node's AABB. The rest of the window is not rendered, so the texture is transparent wherever the Node is.
+    pub(crate) fn capture(&mut self, node: &Node) -> wgpu::Texture {
+        let device = &self.context.device;
+        let frame_size = wgpu::Extent3d {
+            width: self.context.surface_config.width,
+            height: self.context.surface_config.height,
+            depth_or_array_layers: 1,
+        };
+        // Render as if the Node was drawn on an empty window, then cut it out of that frame
+        let frame = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("capture frame"),
+            size: frame_size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.context.surface_config.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let x = (node.aabb.pos.x.max(0.0) as u32).min(frame_size.width);
+        let y = (node.aabb.pos.y.max(0.0) as u32).min(frame_size.height);
+        let size = wgpu::Extent3d {
+            width: (node.aabb.width().ceil() as u32)
+                .min(frame_size.width - x)
+                .max(1),
+            height: (node.aabb.height().ceil() as u32)
+                .min(frame_size.height - y)
+                .max(1),
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("capture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.context.surface_config.format,
+            usage: wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+
+        let view = frame.create_view(&wgpu::TextureViewDescriptor::default());
+        self.draw(node, &view, wgpu::Color::TRANSPARENT);
+
+        let mut encoder =
+            self.context
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("capture encoder"),
+                });
+        encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: &frame,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            size,
+        );
+        self.context.queue.submit(Some(encoder.finish()));
+        texture
+    }
+
+    /// Render `node` and its descendants onto `view`, which is first cleared with `clear_color`. Returns roughly how many draw calls were made.
+    fn draw(&mut self, node: &Node, view: &wgpu::TextureView, clear_color: wgpu::Color) -> usize {
         inst("WGPURenderer::render#collect_frames");
         let mut frames = vec![FrameRenderables::default()];
         let mut num_rects = 0;
@@ -203,7 +295,8 @@ impl super::Renderer for WGPURenderer {
                 _ => (),
             }
         }
-        let mut num_frames = frames.len();
+        // Every clip of every frame is drawn into the stencil buffer
+        let mut num_frames = frames.iter().map(|f| f.frame.len()).sum();
         inst_end();
 
         inst("WGPURenderer::render#alloc_buffers");
@@ -224,7 +317,8 @@ impl super::Renderer for WGPURenderer {
             &frames
                 .iter()
                 .flat_map(|f| f.frame.clone())
-                .collect::<Vec<AABB>>(),
+                .collect::<Vec<ClipFrame>>(),
+            &self.context.device,
             &mut self.context.queue,
         );
         self.rect_pipeline.fill_buffers(
@@ -287,7 +381,8 @@ impl super::Renderer for WGPURenderer {
 
         inst("WGPURenderer::render#render_frames");
         let mut command_buffers: Vec<wgpu::CommandBuffer> = vec![];
-        let mut load_op = wgpu::LoadOp::Clear(wgpu::Color::WHITE);
+        let mut draw_calls = 0;
+        let mut load_op = wgpu::LoadOp::Clear(clear_color);
         num_frames = 0;
         num_rects = 0;
         num_shapes = 0;
@@ -304,7 +399,7 @@ impl super::Renderer for WGPURenderer {
                 // Non-MSAA pass
                 let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &view,
+                        view,
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: load_op,
@@ -377,8 +472,14 @@ impl super::Renderer for WGPURenderer {
             if cfg!(feature = "msaa_shapes") {
                 let mut msaa_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &self.context.msaa_framebuffer,
-                        resolve_target: Some(&self.context.framebuffer),
+                        // Without multisampling there's nothing to resolve, so render straight to the framebuffer
+                        view: if self.context.sample_count > 1 {
+                            &self.context.msaa_framebuffer
+                        } else {
+                            &self.context.framebuffer
+                        },
+                        resolve_target: (self.context.sample_count > 1)
+                            .then_some(&self.context.framebuffer),
                         ops: wgpu::Operations {
                             load: if load_op == wgpu::LoadOp::Load {
                                 wgpu::LoadOp::Load
@@ -451,6 +552,20 @@ impl super::Renderer for WGPURenderer {
 
             // TODO rasters?
 
+            // One call per pipeline used in each pass, except for shapes, which are drawn one by one
+            let passes = if cfg!(feature = "msaa_shapes") { 2 } else { 1 };
+            draw_calls += passes
+                * ([
+                    frame_renderables.frame.is_empty(),
+                    frame_renderables.rects.is_empty(),
+                    frame_renderables.texts.is_empty(),
+                ]
+                .iter()
+                .filter(|empty| !**empty)
+                .count()
+                    + frame_renderables.num_shape_instances)
+                + usize::from(!frame_renderables.rasters.is_empty());
+
             num_frames += frame_renderables.frame.len();
             num_rects += frame_renderables.rects.len();
             num_shapes += frame_renderables.num_shape_instances;
@@ -472,7 +587,7 @@ impl super::Renderer for WGPURenderer {
             {
                 let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &view,
+                        view,
                         resolve_target: None,
                         ops: wgpu::Operations {
                             load: wgpu::LoadOp::Load,
@@ -486,27 +601,16 @@ impl super::Renderer for WGPURenderer {
                 self.msaa_pipeline.render(&mut pass);
             }
             command_buffers.push(encoder.finish());
+            draw_calls += 1;
         }
         inst_end();
 
         inst("WGPURenderer::render#submit_command_buffers");
         self.context.queue.submit(command_buffers);
-        output.present();
         inst_end();
+        draw_calls
     }
 
-    fn caches(&self) -> Caches {
-        Caches {
-            shape_buffer: self.shape_pipeline.buffer_cache.cache.clone(),
-            text_buffer: self.text_pipeline.buffer_cache.cache.clone(),
-            image_buffer: self.raster_pipeline.buffer_cache.cache.clone(),
-            raster: self.raster_pipeline.texture_cache.raster_cache.clone(),
-            font: self.text_pipeline.font_cache.clone(),
-        }
-    }
-}
-
-impl WGPURenderer {
     fn do_resize(&mut self, size: PixelSize) -> bool {
         if size.width != self.context.surface_config.width
             || size.height != self.context.surface_config.height
@@ -1,6 +1,7 @@
 #version 450
 
 layout(location = 0) in vec2 v_TexPos;
+layout(location = 1) in vec4 v_Tint;
 
 layout(location = 0) out vec4 f_Color;
 
@@ -13,7 +14,7 @@ void main() {
     discard;
     // f_Color = vec4(1.0, 0.0, 1.0, 1.0);
   } else {
-    f_Color = value;
+    f_Color = value * v_Tint;
   }
 
 }
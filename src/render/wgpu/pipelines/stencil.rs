@@ -1,9 +1,12 @@
+use std::ops::Range;
+
 use bytemuck::{cast_slice, Pod, Zeroable};
 use log::info;
 use wgpu::{self, util::DeviceExt};
 
 use super::shared::{create_pipeline_depth_stencil, VBDesc};
 use crate::base_types::{Point, Pos, Scale, AABB};
+use crate::node::ClipFrame;
 use crate::render::next_power_of_2;
 use crate::render::wgpu::context;
 
@@ -72,8 +75,13 @@ pub struct StencilPipeline {
     instance_data: Vec<Instance>,
     instance_buffer: wgpu::Buffer,
     num_instances: usize,
+    /// The indices and base vertex of each instance. Scroll frames are drawn with the unit quad at the start of the buffers, while clip paths have their meshes appended after it.
+    draws: Vec<(Range<u32>, i32)>,
 }
 
+const QUAD_VERTICES: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+const QUAD_INDICES: [u16; 6] = [0, 1, 2, 2, 1, 3];
+
 impl StencilPipeline {
     pub fn alloc_instance_buffer<'a: 'b, 'b>(
         &'a mut self,
@@ -95,17 +103,67 @@ impl StencilPipeline {
         }
     }
 
-    pub fn fill_buffers<'a: 'b, 'b>(&'a mut self, aabbs: &[AABB], queue: &'b mut wgpu::Queue) {
+    pub fn fill_buffers<'a: 'b, 'b>(
+        &'a mut self,
+        frames: &[ClipFrame],
+        device: &'b wgpu::Device,
+        queue: &'b mut wgpu::Queue,
+    ) {
         self.instance_data.clear();
-        for aabb in aabbs {
-            self.instance_data.push((*aabb).into());
+        self.draws.clear();
+        let mut vertices: Vec<Vertex> = QUAD_VERTICES
+            .iter()
+            .map(|p| Vertex { pos: (*p).into() })
+            .collect();
+        let mut indices = QUAD_INDICES.to_vec();
+        for frame in frames {
+            match frame {
+                ClipFrame::Scroll(aabb) => {
+                    self.instance_data.push((*aabb).into());
+                    self.draws.push((0..QUAD_INDICES.len() as u32, 0));
+                }
+                ClipFrame::Path(mesh, pos) => {
+                    let start = indices.len() as u32;
+                    self.draws.push((
+                        start..(start + mesh.indices.len() as u32),
+                        vertices.len() as i32,
+                    ));
+                    vertices.extend(mesh.vertices.iter().map(|p| Vertex { pos: *p }));
+                    indices.extend_from_slice(&mesh.indices);
+                    self.instance_data.push(Instance {
+                        pos: *pos,
+                        scale: Scale::new(1.0, 1.0),
+                    });
+                }
+            }
+        }
+        if vertices.len() > QUAD_VERTICES.len() {
+            (self.vertex_buff, self.index_buff) = Self::create_buffers(device, &vertices, &indices);
         }
         queue.write_buffer(&self.instance_buffer, 0, cast_slice(&self.instance_data));
     }
 
+    fn create_buffers(
+        device: &wgpu::Device,
+        vertices: &[Vertex],
+        indices: &[u16],
+    ) -> (wgpu::Buffer, wgpu::Buffer) {
+        let vertex_buff = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buff = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+        (vertex_buff, index_buff)
+    }
+
     pub fn render<'a: 'b, 'b>(
         &'a mut self,
-        aabbs: &[AABB],
+        frames: &[ClipFrame],
         pass: &'b mut wgpu::RenderPass<'a>,
         instance_offset: usize,
         msaa: bool,
@@ -122,42 +180,22 @@ impl StencilPipeline {
                 .slice(((instance_offset * std::mem::size_of::<Instance>()) as u64)..),
         );
         pass.set_index_buffer(self.index_buff.slice(..), wgpu::IndexFormat::Uint16);
-        pass.draw_indexed(0..6_u32, 0, 0..(aabbs.len() as u32));
+        for i in 0..frames.len() {
+            let (indices, base_vertex) = self.draws[instance_offset + i].clone();
+            pass.draw_indexed(indices, base_vertex, (i as u32)..(i as u32 + 1));
+        }
     }
 
     pub fn new(
         context: &context::WGPUContext,
         uniform_bind_group_layout: &wgpu::BindGroupLayout,
     ) -> Self {
-        let vertex_data = vec![
-            Vertex {
-                pos: [0.0, 0.0].into(),
-            },
-            Vertex {
-                pos: [1.0, 0.0].into(),
-            },
-            Vertex {
-                pos: [0.0, 1.0].into(),
-            },
-            Vertex {
-                pos: [1.0, 1.0].into(),
-            },
-        ];
-        let index_data: [u16; 6] = [0, 1, 2, 2, 1, 3];
-        let vertex_buff = context
-            .device
-            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: None,
-                contents: cast_slice(&vertex_data),
-                usage: wgpu::BufferUsages::VERTEX,
-            });
-        let index_buff = context
-            .device
-            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: None,
-                contents: cast_slice(&index_data),
-                usage: wgpu::BufferUsages::INDEX,
-            });
+        let vertices: Vec<Vertex> = QUAD_VERTICES
+            .iter()
+            .map(|p| Vertex { pos: (*p).into() })
+            .collect();
+        let (vertex_buff, index_buff) =
+            Self::create_buffers(&context.device, &vertices, &QUAD_INDICES);
         let num_instances = 32; // Initial allocation
         let instance_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
@@ -209,6 +247,7 @@ impl StencilPipeline {
             instance_data: vec![],
             instance_buffer,
             num_instances,
+            draws: vec![],
             pipeline: create_pipeline_depth_stencil(
                 context,
                 layout,
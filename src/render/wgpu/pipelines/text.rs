@@ -259,7 +259,13 @@ impl TextPipeline {
         let mut cache_invalid = false;
         let mut cache_success = false;
         let mut cache_size = self.glyph_cache.size;
+        let prewarmed = self.font_cache.write().unwrap().take_prewarmed();
         while !cache_success {
+            for g in prewarmed.iter().cloned() {
+                self.glyph_cache
+                    .glyph_cache
+                    .queue_glyph(g.font_id.0, g.glyph);
+            }
             for (renderable, _) in renderables.iter() {
                 for g in renderable.glyphs.iter().cloned() {
                     self.glyph_cache
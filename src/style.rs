@@ -3,6 +3,7 @@
 #![doc = include_str!("../docs/styling.md")]
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Mutex, OnceLock};
 
 use crate::base_types::*;
@@ -49,6 +50,8 @@ pub enum StyleVal {
     Int(u32),
     Bool(bool),
     String(&'static str),
+    /// A named value, looked up in the current [`Theme`] when the style is read.
+    Token(&'static str),
 } // Impls below
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -125,28 +128,69 @@ impl Default for Style {
             // Button
             (
                 StyleKey::new("Button", "text_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("text"),
             ),
             (StyleKey::new("Button", "font_size", None), 12.0.into()),
             (
                 StyleKey::new("Button", "background_color", None),
-                Color::WHITE.into(),
+                StyleVal::Token("background"),
             ),
             (
-                StyleKey::new("Button", "highlight_color", None),
-                Color::LIGHT_GREY.into(),
+                StyleKey::new("Button", "background_color:hover", None),
+                StyleVal::Token("highlight"),
             ),
             (
-                StyleKey::new("Button", "active_color", None),
-                Color::MID_GREY.into(),
+                StyleKey::new("Button", "background_color:pressed", None),
+                StyleVal::Token("pressed"),
+            ),
+            (
+                StyleKey::new("Button", "background_color:disabled", None),
+                StyleVal::Token("surface"),
+            ),
+            (
+                StyleKey::new("Button", "text_color:disabled", None),
+                StyleVal::Token("text-muted"),
             ),
             (
                 StyleKey::new("Button", "border_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("border"),
+            ),
+            (
+                StyleKey::new("Button", "border_color:disabled", None),
+                StyleVal::Token("text-muted"),
             ),
             (StyleKey::new("Button", "border_width", None), 2.0.into()),
-            (StyleKey::new("Button", "radius", None), 4.0.into()),
-            (StyleKey::new("Button", "padding", None), 2.0.into()),
+            (
+                StyleKey::new("Button", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (
+                StyleKey::new("Button", "padding", None),
+                StyleVal::Token("spacing-xs"),
+            ),
+            // Markdown
+            (
+                StyleKey::new("Markdown", "text_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("Markdown", "link_color", None),
+                Color::BLUE.into(),
+            ),
+            (StyleKey::new("Markdown", "font_size", None), 12.0.into()),
+            (StyleKey::new("Markdown", "h1_size", None), 24.0.into()),
+            (StyleKey::new("Markdown", "h2_size", None), 18.0.into()),
+            (StyleKey::new("Markdown", "h3_size", None), 14.0.into()),
+            (
+                StyleKey::new("Markdown", "paragraph_spacing", None),
+                8.0.into(),
+            ),
+            (StyleKey::new("Markdown", "list_indent", None), 16.0.into()),
+            (
+                StyleKey::new("Markdown", "code_background_color", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (StyleKey::new("Markdown", "code_padding", None), 4.0.into()),
             // RadioButton
             (
                 StyleKey::new("RadioButton", "text_color", None),
@@ -178,93 +222,354 @@ impl Default for Style {
             ),
             (StyleKey::new("RadioButton", "radius", None), 4.0.into()),
             (StyleKey::new("RadioButton", "padding", None), 2.0.into()),
+            // Resizable
+            (StyleKey::new("Resizable", "handle_size", None), 8.0.into()),
+            (
+                StyleKey::new("Resizable", "handle_color", None),
+                Color::TRANSPARENT.into(),
+            ),
             // Select
             (
                 StyleKey::new("Select", "text_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("text"),
             ),
             (StyleKey::new("Select", "font_size", None), 12.0.into()),
             (
                 StyleKey::new("Select", "background_color", None),
-                Color::WHITE.into(),
+                StyleVal::Token("background"),
             ),
             (
-                StyleKey::new("Select", "highlight_color", None),
-                Color::LIGHT_GREY.into(),
+                StyleKey::new("Select", "background_color:hover", None),
+                StyleVal::Token("highlight"),
+            ),
+            (
+                StyleKey::new("Select", "background_color:pressed", None),
+                StyleVal::Token("pressed"),
             ),
             (
                 StyleKey::new("Select", "border_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("border"),
             ),
             (
                 StyleKey::new("Select", "caret_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("text"),
             ),
             (StyleKey::new("Select", "border_width", None), 2.0.into()),
-            (StyleKey::new("Select", "radius", None), 4.0.into()),
-            (StyleKey::new("Select", "padding", None), 2.0.into()),
+            (
+                StyleKey::new("Select", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (
+                StyleKey::new("Select", "padding", None),
+                StyleVal::Token("spacing-xs"),
+            ),
             (StyleKey::new("Select", "max_height", None), 250.0.into()),
+            // CodeView
+            (StyleKey::new("CodeView", "font_size", None), 12.0.into()),
+            (StyleKey::new("CodeView", "padding", None), 4.0.into()),
+            (
+                StyleKey::new("CodeView", "theme", None),
+                "InspiredGitHub".into(),
+            ),
+            (
+                StyleKey::new("CodeView", "line_number_color", None),
+                Color::MID_GREY.into(),
+            ),
+            // DatePicker
+            (
+                StyleKey::new("DatePicker", "text_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "outside_text_color", None),
+                Color::MID_GREY.into(),
+            ),
+            (StyleKey::new("DatePicker", "font_size", None), 12.0.into()),
+            (
+                StyleKey::new("DatePicker", "background_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "highlight_color", None),
+                Color::LIGHT_GREY.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "selected_color", None),
+                Color::DARK_GREY.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "selected_text_color", None),
+                Color::WHITE.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "today_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "border_color", None),
+                Color::BLACK.into(),
+            ),
+            (
+                StyleKey::new("DatePicker", "border_width", None),
+                1.0.into(),
+            ),
+            (StyleKey::new("DatePicker", "padding", None), 4.0.into()),
+            (StyleKey::new("DatePicker", "day_size", None), 28.0.into()),
             // Toggle
             (
                 StyleKey::new("Toggle", "background_color", None),
                 Color::LIGHT_GREY.into(),
             ),
             (
-                StyleKey::new("Toggle", "highlight_color", None),
+                StyleKey::new("Toggle", "background_color:pressed", None),
                 Color::DARK_GREY.into(),
             ),
             (
                 StyleKey::new("Toggle", "active_color", None),
                 Color::MID_GREY.into(),
             ),
+            (
+                StyleKey::new("Toggle", "active_color:pressed", None),
+                Color::DARK_GREY.into(),
+            ),
             (
                 StyleKey::new("Toggle", "border_color", None),
                 Color::BLACK.into(),
             ),
             (StyleKey::new("Toggle", "border_width", None), 2.0.into()),
+            // ToggleGroup
+            (
+                StyleKey::new("ToggleGroup", "text_color", None),
+                StyleVal::Token("text"),
+            ),
+            (StyleKey::new("ToggleGroup", "font_size", None), 12.0.into()),
+            (
+                StyleKey::new("ToggleGroup", "background_color", None),
+                StyleVal::Token("background"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "background_color:hover", None),
+                StyleVal::Token("highlight"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "background_color:pressed", None),
+                StyleVal::Token("pressed"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "active_color", None),
+                StyleVal::Token("accent"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "active_text_color", None),
+                StyleVal::Token("background"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "border_color", None),
+                StyleVal::Token("border"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "border_width", None),
+                1.0.into(),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (
+                StyleKey::new("ToggleGroup", "padding", None),
+                StyleVal::Token("spacing-sm"),
+            ),
             // ToolTip
             (
                 StyleKey::new("ToolTip", "text_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("text"),
             ),
             (StyleKey::new("ToolTip", "font_size", None), 12.0.into()),
             (
                 StyleKey::new("ToolTip", "background_color", None),
-                Color::WHITE.into(),
+                StyleVal::Token("background"),
             ),
             (
                 StyleKey::new("ToolTip", "border_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("border"),
             ),
             (StyleKey::new("ToolTip", "border_width", None), 2.0.into()),
-            (StyleKey::new("ToolTip", "padding", None), 4.0.into()),
+            (
+                StyleKey::new("ToolTip", "padding", None),
+                StyleVal::Token("spacing-sm"),
+            ),
+            // Badge
+            (
+                StyleKey::new("Badge", "background_color", None),
+                StyleVal::Token("accent"),
+            ),
+            (
+                StyleKey::new("Badge", "text_color", None),
+                Color::WHITE.into(),
+            ),
+            (StyleKey::new("Badge", "font_size", None), 10.0.into()),
+            (StyleKey::new("Badge", "size", None), 16.0.into()),
+            (StyleKey::new("Badge", "dot_size", None), 8.0.into()),
+            (StyleKey::new("Badge", "padding", None), 4.0.into()),
+            (StyleKey::new("Badge", "offset", None), 6.0.into()),
+            // FocusRing
+            (
+                StyleKey::new("FocusRing", "color", None),
+                StyleVal::Token("accent"),
+            ),
+            (StyleKey::new("FocusRing", "width", None), 2.0.into()),
+            (
+                StyleKey::new("FocusRing", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (StyleKey::new("FocusRing", "offset", None), 2.0.into()),
+            // LazyImage
+            (
+                StyleKey::new("LazyImage", "error_color", None),
+                StyleVal::Token("border"),
+            ),
+            // Skeleton
+            (
+                StyleKey::new("Skeleton", "color", None),
+                StyleVal::Token("highlight"),
+            ),
+            (
+                StyleKey::new("Skeleton", "highlight_color", None),
+                Color::new(1.0, 1.0, 1.0, 0.35).into(),
+            ),
+            (
+                StyleKey::new("Skeleton", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (
+                StyleKey::new("Skeleton", "shimmer_width", None),
+                80.0.into(),
+            ),
+            (StyleKey::new("Skeleton", "period", None), 1.5.into()),
+            // Spinner
+            (
+                StyleKey::new("Spinner", "color", None),
+                StyleVal::Token("accent"),
+            ),
+            (StyleKey::new("Spinner", "size", None), 24.0.into()),
+            (StyleKey::new("Spinner", "stroke_width", None), 3.0.into()),
+            (StyleKey::new("Spinner", "sweep", None), 0.75.into()),
+            (StyleKey::new("Spinner", "period", None), 1.0.into()),
+            // Toasts
+            (
+                StyleKey::new("Toasts", "h_position", None),
+                HorizontalPosition::Right.into(),
+            ),
+            (
+                StyleKey::new("Toasts", "v_position", None),
+                VerticalPosition::Bottom.into(),
+            ),
+            (
+                StyleKey::new("Toasts", "margin", None),
+                StyleVal::Token("spacing-lg"),
+            ),
+            (
+                StyleKey::new("Toasts", "spacing", None),
+                StyleVal::Token("spacing-md"),
+            ),
+            // Toast
+            (
+                StyleKey::new("Toast", "text_color", None),
+                Color::WHITE.into(),
+            ),
+            (StyleKey::new("Toast", "font_size", None), 12.0.into()),
+            (
+                StyleKey::new("Toast", "background_color", None),
+                Color::new(0.1, 0.1, 0.1, 0.9).into(),
+            ),
+            (
+                StyleKey::new("Toast", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (
+                StyleKey::new("Toast", "padding", None),
+                StyleVal::Token("spacing-md"),
+            ),
+            // PerfOverlay
+            (
+                StyleKey::new("PerfOverlay", "h_position", None),
+                HorizontalPosition::Left.into(),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "v_position", None),
+                VerticalPosition::Top.into(),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "margin", None),
+                StyleVal::Token("spacing-sm"),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "text_color", None),
+                Color::WHITE.into(),
+            ),
+            (StyleKey::new("PerfOverlay", "font_size", None), 11.0.into()),
+            (
+                StyleKey::new("PerfOverlay", "background_color", None),
+                Color::new(0.0, 0.0, 0.0, 0.75).into(),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "graph_color", None),
+                Color::new(0.3, 0.9, 0.4, 1.0).into(),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "graph_width", None),
+                120.0.into(),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "graph_height", None),
+                30.0.into(),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "radius", None),
+                StyleVal::Token("radius"),
+            ),
+            (
+                StyleKey::new("PerfOverlay", "padding", None),
+                StyleVal::Token("spacing-sm"),
+            ),
             // TextBox
             (StyleKey::new("TextBox", "font_size", None), 12.0.into()),
             (
                 StyleKey::new("TextBox", "text_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("text"),
             ),
             (
                 StyleKey::new("TextBox", "background_color", None),
-                Color::WHITE.into(),
+                StyleVal::Token("background"),
             ),
             (
                 StyleKey::new("TextBox", "selection_color", None),
-                Color::MID_GREY.into(),
+                StyleVal::Token("selection"),
             ),
             (
                 StyleKey::new("TextBox", "cursor_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("text"),
+            ),
+            (
+                StyleKey::new("TextBox", "cursor_blink_period", None),
+                500u32.into(),
             ),
             (
                 StyleKey::new("TextBox", "border_color", None),
-                Color::BLACK.into(),
+                StyleVal::Token("border"),
             ),
             (StyleKey::new("TextBox", "border_width", None), 1.0.into()),
+            (
+                StyleKey::new("TextBox", "border_width:focused", None),
+                2.0.into(),
+            ),
             (StyleKey::new("TextBox", "padding", None), 1.0.into()),
             // Text
             (StyleKey::new("Text", "size", None), 12.0.into()),
-            (StyleKey::new("Text", "color", None), Color::BLACK.into()),
+            (
+                StyleKey::new("Text", "color", None),
+                StyleVal::Token("text"),
+            ),
             (
                 StyleKey::new("Text", "h_alignment", None),
                 HorizontalPosition::Left.into(),
@@ -281,22 +586,29 @@ impl Default for Style {
                 HorizontalPosition::Right.into(),
             ),
             (StyleKey::new("Scroll", "bar_width", None), 12.0.into()),
+            (StyleKey::new("Scroll", "scroll_padding", None), 0.0.into()),
             (
                 StyleKey::new("Scroll", "bar_background_color", None),
-                Color::LIGHT_GREY.into(),
+                StyleVal::Token("scrollbar-track"),
             ),
             (
                 StyleKey::new("Scroll", "bar_color", None),
-                Into::<Color>::into(0.7).into(),
+                StyleVal::Token("scrollbar"),
             ),
             (
                 StyleKey::new("Scroll", "bar_highlight_color", None),
-                Into::<Color>::into(0.5).into(),
+                StyleVal::Token("scrollbar-highlight"),
             ),
             (
                 StyleKey::new("Scroll", "bar_active_color", None),
-                Color::DARK_GREY.into(),
+                StyleVal::Token("scrollbar-active"),
+            ),
+            (StyleKey::new("Scroll", "shadows", None), false.into()),
+            (
+                StyleKey::new("Scroll", "shadow_color", None),
+                Color::new(0.0, 0.0, 0.0, 0.2).into(),
             ),
+            (StyleKey::new("Scroll", "shadow_size", None), 8.0.into()),
         ]);
         Self(map)
     }
@@ -309,34 +621,277 @@ fn _current_style() -> &'static Mutex<Style> {
 
 pub fn set_current_style(s: Style) {
     *_current_style().lock().unwrap() = s;
+    STYLE_GENERATION.fetch_add(1, Ordering::Relaxed);
 }
 
 pub fn current_style(component: &'static str, parameter_name: &'static str) -> Option<StyleVal> {
-    _current_style()
+    let v = _current_style()
         .lock()
         .unwrap()
-        .style(component, parameter_name)
+        .style(component, parameter_name);
+    v.and_then(resolve_token)
 }
 
 fn get_current_style(k: StyleKey) -> Option<StyleVal> {
     _current_style().lock().unwrap().get(k)
 }
 
+/// Named classes of style parameters, that can be given to any [`Styled`] Component with [`Styled#with_class`], e.g. to avoid repeating the same styles on many widgets. Unlike the class-specific entries of a [`Style`], a class in a `StyleSheet` is not tied to one kind of Component: Each Component reads the parameters that it knows about.
+///
+/// ```ignore
+/// style::set_current_stylesheet(
+///     StyleSheet::new()
+///         .add("card", "background_color", Color::WHITE)
+///         .add("card", "border_color", Color::LIGHT_GREY)
+///         .add("card", "border_width", 1.0)
+///         .add("elevated", "border_width", 2.0),
+/// );
+/// node!(Div::new().with_classes(class!["card", "elevated"]))
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleSheet(HashMap<&'static str, StyleOverrideMap>);
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set `parameter` to `val` for Components with the given `class`.
+    pub fn add<V: Into<StyleVal>>(
+        mut self,
+        class: &'static str,
+        parameter: &'static str,
+        val: V,
+    ) -> Self {
+        self.0
+            .entry(class)
+            .or_default()
+            .insert(parameter, val.into());
+        self
+    }
+
+    pub fn get(&self, class: &'static str, parameter: &'static str) -> Option<StyleVal> {
+        self.0.get(class).and_then(|c| c.get(parameter)).cloned()
+    }
+}
+
+fn _current_stylesheet() -> &'static Mutex<StyleSheet> {
+    static CURRENT_STYLESHEET: OnceLock<Mutex<StyleSheet>> = OnceLock::new();
+    CURRENT_STYLESHEET.get_or_init(|| Mutex::new(StyleSheet::new()))
+}
+
+/// Replace the [`StyleSheet`] that classes are looked up in. Prefer [`UI#set_stylesheet`][crate::UI#method.set_stylesheet] when you have access to the UI, since it also redraws the app. Otherwise, the next draw will re-render everything.
+pub fn set_current_stylesheet(s: StyleSheet) {
+    *_current_stylesheet().lock().unwrap() = s;
+    STYLE_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+fn get_current_stylesheet(class: &'static str, parameter: &'static str) -> Option<StyleVal> {
+    _current_stylesheet().lock().unwrap().get(class, parameter)
+}
+
+/// A map between token names (e.g. `"background"`, `"accent"`, `"spacing-sm"`) and the values that they stand for. Styles refer to tokens with [`StyleVal::Token`], so swapping the current theme restyles every widget that uses them.
+///
+/// The default [`Style`] uses the following tokens, all of which are given by [`Theme::light`] and [`Theme::dark`]:
+/// - Colors: `background`, `surface`, `text`, `text-muted`, `accent`, `border`, `highlight`, `pressed`, `selection`, `scrollbar`, `scrollbar-highlight`, `scrollbar-active`, `scrollbar-track`
+/// - Sizes: `radius`, `spacing-xs`, `spacing-sm`, `spacing-md`, `spacing-lg`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme(HashMap<&'static str, StyleVal>);
+
+impl Theme {
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    pub fn add<V: Into<StyleVal>>(mut self, token: &'static str, v: V) -> Self {
+        self.0.insert(token, v.into());
+        self
+    }
+
+    pub fn get(&self, token: &'static str) -> Option<StyleVal> {
+        self.0.get(token).cloned()
+    }
+
+    /// Dark text on light backgrounds. This is the theme that is used by default.
+    pub fn light() -> Self {
+        Self::new()
+            .add("background", Color::WHITE)
+            .add("surface", Color::rgb(0.95, 0.95, 0.95))
+            .add("text", Color::BLACK)
+            .add("text-muted", Color::MID_GREY)
+            .add("accent", Color::rgb(0.2, 0.45, 0.85))
+            .add("border", Color::BLACK)
+            .add("highlight", Color::LIGHT_GREY)
+            .add("pressed", Color::MID_GREY)
+            .add("selection", Color::MID_GREY)
+            .add("scrollbar", Into::<Color>::into(0.7))
+            .add("scrollbar-highlight", Into::<Color>::into(0.5))
+            .add("scrollbar-active", Color::DARK_GREY)
+            .add("scrollbar-track", Color::LIGHT_GREY)
+            .with_sizes()
+    }
+
+    /// Light text on dark backgrounds.
+    pub fn dark() -> Self {
+        Self::new()
+            .add("background", Color::rgb(0.15, 0.15, 0.16))
+            .add("surface", Color::rgb(0.2, 0.2, 0.22))
+            .add("text", Color::rgb(0.92, 0.92, 0.92))
+            .add("text-muted", Color::rgb(0.6, 0.6, 0.6))
+            .add("accent", Color::rgb(0.4, 0.62, 1.0))
+            .add("border", Color::rgb(0.45, 0.45, 0.47))
+            .add("highlight", Color::rgb(0.27, 0.27, 0.29))
+            .add("pressed", Color::rgb(0.36, 0.36, 0.38))
+            .add("selection", Color::rgb(0.3, 0.4, 0.6))
+            .add("scrollbar", Color::rgb(0.4, 0.4, 0.42))
+            .add("scrollbar-highlight", Color::rgb(0.5, 0.5, 0.52))
+            .add("scrollbar-active", Color::rgb(0.62, 0.62, 0.64))
+            .add("scrollbar-track", Color::rgb(0.2, 0.2, 0.22))
+            .with_sizes()
+    }
+
+    fn with_sizes(self) -> Self {
+        self.add("radius", 4.0)
+            .add("spacing-xs", 2.0)
+            .add("spacing-sm", 4.0)
+            .add("spacing-md", 8.0)
+            .add("spacing-lg", 16.0)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+fn _current_theme() -> &'static Mutex<Theme> {
+    static CURRENT_THEME: OnceLock<Mutex<Theme>> = OnceLock::new();
+    CURRENT_THEME.get_or_init(|| Mutex::new(Theme::default()))
+}
+
+static STYLE_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Replace the [`Theme`] that tokens are resolved against. Prefer [`UI#set_theme`][crate::UI#method.set_theme] when you have access to the UI, since it also redraws the app. Otherwise, the next draw will re-render everything.
+pub fn set_current_theme(t: Theme) {
+    *_current_theme().lock().unwrap() = t;
+    STYLE_GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// The value of `token` in the current [`Theme`].
+pub fn current_theme_val(token: &'static str) -> Option<StyleVal> {
+    _current_theme().lock().unwrap().get(token)
+}
+
+/// Incremented every time the current [`Style`], [`StyleSheet`] or [`Theme`] is set.
+pub(crate) fn style_generation() -> usize {
+    STYLE_GENERATION.load(Ordering::Relaxed)
+}
+
+/// Tokens may refer to other tokens, up to this depth.
+const MAX_TOKEN_DEPTH: usize = 8;
+
+fn resolve_token(v: StyleVal) -> Option<StyleVal> {
+    let mut v = v;
+    for _ in 0..MAX_TOKEN_DEPTH {
+        match v {
+            StyleVal::Token(t) => v = current_theme_val(t)?,
+            v => return Some(v),
+        }
+    }
+    None
+}
+
+/// The interaction state of a widget, which selects between the variants of its style parameters. Any parameter, e.g. `background_color`, can be given a variant for each state by suffixing its name with `:hover`, `:pressed`, `:focused` or `:disabled`, e.g. `background_color:hover`. While the widget is in that state, the variant is used in place of the plain parameter. When more than one state applies, `:disabled` wins, then `:pressed`, `:focused`, and finally `:hover`.
+///
+/// Widgets keep an `Interaction` in their state, update it from their event handlers, and read their styles with [`Styled#interactive_style_val`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interaction {
+    pub hover: bool,
+    pub pressed: bool,
+    pub focused: bool,
+    pub disabled: bool,
+}
+
+impl Interaction {
+    /// Call from [`on_mouse_enter`][crate::Component#method.on_mouse_enter].
+    pub fn mouse_enter(&mut self) {
+        self.hover = true;
+    }
+
+    /// Call from [`on_mouse_leave`][crate::Component#method.on_mouse_leave]. A press is abandoned when the mouse leaves.
+    pub fn mouse_leave(&mut self) {
+        self.hover = false;
+        self.pressed = false;
+    }
+
+    /// Call from [`on_mouse_down`][crate::Component#method.on_mouse_down].
+    pub fn mouse_down(&mut self) {
+        self.pressed = true;
+    }
+
+    /// Call from [`on_mouse_up`][crate::Component#method.on_mouse_up].
+    pub fn mouse_up(&mut self) {
+        self.pressed = false;
+    }
+
+    /// Call from [`on_focus`][crate::Component#method.on_focus] and [`on_blur`][crate::Component#method.on_blur].
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Is the widget in none of the states?
+    pub fn is_idle(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The suffixes of the variants that apply, from the highest precedence to the lowest.
+    fn variants(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.disabled, "disabled"),
+            (self.pressed, "pressed"),
+            (self.focused, "focused"),
+            (self.hover, "hover"),
+        ]
+        .into_iter()
+        .filter_map(|(active, variant)| active.then_some(variant))
+    }
+}
+
+/// The name of the `variant` of `param`, e.g. `background_color:hover`. Style parameter names must be `'static`, so each name is allocated once and reused.
+fn variant_param(param: &'static str, variant: &'static str) -> &'static str {
+    static VARIANT_PARAMS: OnceLock<Mutex<HashMap<(&'static str, &'static str), &'static str>>> =
+        OnceLock::new();
+    VARIANT_PARAMS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry((param, variant))
+        .or_insert_with(|| Box::leak(format!("{}:{}", param, variant).into_boxed_str()))
+}
+
 /// Implemented by the [`component`][macro@crate::component] attribute macro, for "Styled" Components.
 pub trait Styled: Sized {
     #[doc(hidden)]
     fn name() -> &'static str;
     #[doc(hidden)]
-    fn class(&self) -> Option<&'static str>;
+    fn classes(&self) -> &[&'static str];
     #[doc(hidden)]
-    fn class_mut(&mut self) -> &mut Option<&'static str>;
+    fn classes_mut(&mut self) -> &mut Vec<&'static str>;
     #[doc(hidden)]
     fn style_overrides(&self) -> &StyleOverride;
     #[doc(hidden)]
     fn style_overrides_mut(&mut self) -> &mut StyleOverride;
 
+    /// Add a class to this Component. When a parameter is given by more than one class, the one added last wins.
     fn with_class(mut self, class: &'static str) -> Self {
-        *self.class_mut() = Some(class);
+        self.classes_mut().push(class);
+        self
+    }
+
+    /// Add each of `classes` to this Component, in order. See [`class!`][crate::class].
+    fn with_classes<I: IntoIterator<Item = &'static str>>(mut self, classes: I) -> Self {
+        self.classes_mut().extend(classes);
         self
     }
 
@@ -361,18 +916,33 @@ pub trait Styled: Sized {
         }
     }
 
+    /// Look up the value of `param`, from this Component's overrides, then its classes (last added first), then the default style. For each class, a class-specific entry in the current [`Style`] takes precedence over the current [`StyleSheet`]. [`StyleVal::Token`]s are resolved against the current [`Theme`].
     fn style_val(&self, param: &'static str) -> Option<StyleVal> {
-        if let Some(v) = self.style_overrides().0.get(param) {
+        let v = if let Some(v) = self.style_overrides().0.get(param) {
             Some(v.clone())
-        } else if let Some(c) = self.class() {
-            if let Some(v) = get_current_style(self.style_key(param, Some(c))) {
-                Some(v)
-            } else {
-                get_current_style(self.style_key(param, None))
-            }
         } else {
-            get_current_style(self.style_key(param, None))
-        }
+            self.classes()
+                .iter()
+                .rev()
+                .find_map(|c| {
+                    get_current_style(self.style_key(param, Some(c)))
+                        .or_else(|| get_current_stylesheet(c, param))
+                })
+                .or_else(|| get_current_style(self.style_key(param, None)))
+        };
+        v.and_then(resolve_token)
+    }
+
+    /// Like [`#style_val`][Styled#method.style_val], but first looks for the variants of `param` that match `interaction`, e.g. `background_color:hover`. See [`Interaction`].
+    fn interactive_style_val(
+        &self,
+        param: &'static str,
+        interaction: Interaction,
+    ) -> Option<StyleVal> {
+        interaction
+            .variants()
+            .find_map(|variant| self.style_val(variant_param(param, variant)))
+            .or_else(|| self.style_val(param))
     }
 }
 
@@ -413,6 +983,18 @@ macro_rules! style {
 
 }
 
+/// A list of class names, to be given to [`Styled#with_classes`]. Classes that come later take precedence over earlier ones.
+///
+/// ```ignore
+/// Div::new().with_classes(class!["card", "elevated"])
+/// ```
+#[macro_export]
+macro_rules! class {
+    ( $( $class:expr ),* $(,)? ) => (
+        [ $( $class ),* ]
+    );
+}
+
 // TODO we need some way to add more context to these errors, or otherwise prevent them from happening.
 // Right now, if you add the wrong type expected for a given style, the error message is terrible.
 
@@ -727,18 +1309,18 @@ mod tests {
 
     #[derive(Default)]
     struct Widget {
-        class: Option<&'static str>,
+        classes: Vec<&'static str>,
         style_overrides: StyleOverride,
     }
     impl Styled for Widget {
         fn name() -> &'static str {
             "Widget"
         }
-        fn class(&self) -> Option<&'static str> {
-            self.class
+        fn classes(&self) -> &[&'static str] {
+            &self.classes
         }
-        fn class_mut(&mut self) -> &mut Option<&'static str> {
-            &mut self.class
+        fn classes_mut(&mut self) -> &mut Vec<&'static str> {
+            &mut self.classes
         }
         fn style_overrides(&self) -> &StyleOverride {
             &self.style_overrides
@@ -757,9 +1339,64 @@ mod tests {
             )
     }
 
+    /// Tests run in parallel, so they all set the same style. It includes the default style, which other tests rely on.
+    fn full_test_style() -> Style {
+        let mut style = Style::default();
+        style.0.extend(test_style().0);
+        style
+            .add(
+                StyleKey::new("Widget", "token_color", None),
+                StyleVal::Token("test-color"),
+            )
+            .add(
+                StyleKey::new("Widget", "missing", None),
+                StyleVal::Token("test-missing"),
+            )
+            .add(
+                StyleKey::new("Widget", "color:hover", None),
+                Color::RED.into(),
+            )
+            .add(
+                StyleKey::new("Widget", "color:pressed", None),
+                Color::GREEN.into(),
+            )
+    }
+
+    /// Sets the current style to the [`full_test_style`]. The tests that change the current style, stylesheet or theme hold this while they run, so that they do not see each other's changes, and the previous ones are put back when it is dropped.
+    struct TestStyle {
+        style: Style,
+        stylesheet: StyleSheet,
+        theme: Theme,
+        _lock: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl TestStyle {
+        fn set() -> Self {
+            static LOCK: Mutex<()> = Mutex::new(());
+            // A failing test poisons the lock, but still restores the style
+            let lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let prev = Self {
+                style: _current_style().lock().unwrap().clone(),
+                stylesheet: _current_stylesheet().lock().unwrap().clone(),
+                theme: _current_theme().lock().unwrap().clone(),
+                _lock: lock,
+            };
+            set_current_style(full_test_style());
+            prev
+        }
+    }
+
+    impl Drop for TestStyle {
+        fn drop(&mut self) {
+            set_current_style(self.style.clone());
+            set_current_stylesheet(self.stylesheet.clone());
+            set_current_theme(self.theme.clone());
+        }
+    }
+
     #[test]
     fn test_base_style_val() {
-        set_current_style(test_style());
+        let _style = TestStyle::set();
 
         let w = Widget::default();
         let c: Color = w.style_val("color").into();
@@ -768,7 +1405,7 @@ mod tests {
 
     #[test]
     fn test_style_val_with_class() {
-        set_current_style(test_style());
+        let _style = TestStyle::set();
 
         let w = Widget::default().with_class("dark");
         let c: Color = w.style_val("color").into();
@@ -777,7 +1414,7 @@ mod tests {
 
     #[test]
     fn test_style_val_overrides() {
-        set_current_style(test_style());
+        let _style = TestStyle::set();
 
         let w = Widget::default().style("color", Color::BLUE);
         let c: Color = w.style_val("color").into();
@@ -790,6 +1427,97 @@ mod tests {
         assert_eq!(c, Color::BLUE);
     }
 
+    #[test]
+    fn test_theme_tokens() {
+        let _style = TestStyle::set();
+        set_current_theme(Theme::light().add("test-color", Color::WHITE));
+
+        let w = Widget::default();
+        let c: Color = w.style_val("token_color").into();
+        assert_eq!(c, Color::WHITE);
+        assert_eq!(w.style_val("missing"), None);
+
+        // Overrides take precedence over tokens, and may be tokens themselves
+        let w = Widget::default().style("token_color", Color::BLUE);
+        let c: Color = w.style_val("token_color").into();
+        assert_eq!(c, Color::BLUE);
+        let w = Widget::default().style("token_color", StyleVal::Token("text"));
+        let c: Color = w.style_val("token_color").into();
+        assert_eq!(c, Color::BLACK);
+
+        // Changing the theme changes the resolved value
+        set_current_theme(Theme::light().add("test-color", Color::BLACK));
+        let w = Widget::default();
+        let c: Color = w.style_val("token_color").into();
+        assert_eq!(c, Color::BLACK);
+    }
+
+    #[test]
+    fn test_stylesheet_precedence() {
+        let _style = TestStyle::set();
+        set_current_stylesheet(
+            StyleSheet::new()
+                .add("test-card", "color", Color::RED)
+                .add("test-card", "size", 1.0)
+                .add("test-elevated", "color", Color::GREEN),
+        );
+
+        // Default < class
+        let w = Widget::default();
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::WHITE);
+        let w = Widget::default().with_class("test-card");
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::RED);
+
+        // Class < later class
+        let w = Widget::default().with_classes(class!["test-card", "test-elevated"]);
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::GREEN);
+        // Parameters that the later class does not give come from the earlier one
+        assert_eq!(w.style_val("size"), Some(StyleVal::Float(1.0)));
+        let w = Widget::default().with_classes(class!["test-elevated", "test-card"]);
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::RED);
+
+        // Later class < inline
+        let w = Widget::default()
+            .with_classes(class!["test-card", "test-elevated"])
+            .style("color", Color::BLUE);
+        let c: Color = w.style_val("color").into();
+        assert_eq!(c, Color::BLUE);
+    }
+
+    #[test]
+    fn test_interactive_style_val() {
+        let _style = TestStyle::set();
+        let w = Widget::default();
+        let mut interaction = Interaction::default();
+        let c: Color = w.interactive_style_val("color", interaction).into();
+        assert_eq!(c, Color::WHITE);
+
+        interaction.mouse_enter();
+        let c: Color = w.interactive_style_val("color", interaction).into();
+        assert_eq!(c, Color::RED);
+        interaction.mouse_down();
+        let c: Color = w.interactive_style_val("color", interaction).into();
+        assert_eq!(c, Color::GREEN);
+        // There is no focused variant, so pressed still wins
+        interaction.set_focused(true);
+        let c: Color = w.interactive_style_val("color", interaction).into();
+        assert_eq!(c, Color::GREEN);
+
+        // Leaving abandons the press and the hover
+        interaction.mouse_leave();
+        let c: Color = w.interactive_style_val("color", interaction).into();
+        assert_eq!(c, Color::WHITE);
+
+        // Inline variants win too
+        let w = Widget::default().style("color:focused", Color::BLUE);
+        let c: Color = w.interactive_style_val("color", interaction).into();
+        assert_eq!(c, Color::BLUE);
+    }
+
     #[test]
     fn test_style_macro() {
         let s = style!(
@@ -1,20 +1,23 @@
 use std::cell::UnsafeCell;
+use std::collections::VecDeque;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::thread::{self, JoinHandle};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use log::info;
 
 use crate::base_types::*;
-use crate::component::Component;
+use crate::component::{Component, ScheduledQueue};
 use crate::event::{self, Event, EventCache, EventInput};
 use crate::input::*;
 use crate::instrumenting::*;
 use crate::layout::*;
-use crate::node::{Node, Registration};
-use crate::render::Renderer;
+use crate::node::{Node, NodeId, Registration};
+use crate::render::{RenderOptions, Renderer};
+use crate::style::{self, StyleSheet, Theme};
 use crate::window::Window;
 
 // This can become feature-dependant
@@ -45,7 +48,220 @@ pub struct UI<W: Window, A: Component + Default + Send + Sync> {
     physical_size: Arc<RwLock<PixelSize>>,
     logical_size: Arc<RwLock<PixelSize>>,
     event_cache: EventCache,
+    preferences: UiPreferences,
     node_dirty: Arc<RwLock<bool>>,
+    // Render every node on the next draw, regardless of their render hashes
+    full_render: Arc<RwLock<bool>>,
+    // The Node to focus once the first view is done
+    autofocus: Arc<RwLock<Option<u64>>>,
+    // Whether the accessibility tree needs to be sent to the window
+    #[cfg_attr(not(feature = "accesskit"), allow(dead_code))]
+    accessibility_dirty: Arc<RwLock<bool>>,
+    #[cfg(feature = "accesskit")]
+    window_focused: bool,
+    // Log focus changes and outline the focused Node
+    focus_debug: bool,
+    frame_timer: Arc<RwLock<FrameTimer>>,
+    // Whether the draw thread adds a PerfOverlay to the root Node
+    perf_overlay: Arc<RwLock<bool>>,
+    // Messages sent with UI#update, which are applied right before the next draw
+    update_queue: UpdateQueue,
+    redraw_request: RedrawRequest,
+}
+
+/// The messages sent to the root Node with [`UI#update`], since the last draw.
+#[derive(Default)]
+struct UpdateQueue {
+    messages: Vec<crate::Message>,
+}
+
+impl UpdateQueue {
+    fn push(&mut self, msg: crate::Message) {
+        self.messages.push(msg);
+    }
+
+    /// Apply the queued messages to `node`, in the order they were sent. Returns whether there were any, and so whether a draw is needed.
+    fn apply(&mut self, node: &mut Node, scheduled: &ScheduledQueue) -> bool {
+        if self.messages.is_empty() {
+            return false;
+        }
+        for msg in self.messages.drain(..) {
+            node.update(msg, scheduled);
+        }
+        true
+    }
+}
+
+/// Call `render` for each request from `receiver` that finds the frame dirty, at most `frame_cap` times a second. A request that comes too soon after the last render waits until the frame is due, rather than being dropped, since there may not be another request to render the frame.
+fn render_loop(
+    receiver: Receiver<()>,
+    frame_dirty: Arc<RwLock<bool>>,
+    frame_cap: Option<u32>,
+    mut render: impl FnMut(),
+) {
+    let min_frame_time = frame_cap.map(|fps| Duration::from_secs_f64(1.0 / fps.max(1) as f64));
+    let mut last_render: Option<Instant> = None;
+    for _ in receiver.iter() {
+        if *frame_dirty.read().unwrap() {
+            if let (Some(min), Some(last)) = (min_frame_time, last_render) {
+                thread::sleep(min.saturating_sub(last.elapsed()));
+            }
+            last_render = Some(Instant::now());
+            render();
+            *frame_dirty.write().unwrap() = false;
+        }
+    }
+}
+
+/// Deliver the messages in `scheduled` whose time has come to their Nodes, which are descendants of `node`. Returns whether any of those Nodes are now dirty.
+fn deliver_scheduled(node: &mut Node, scheduled: &ScheduledQueue) -> bool {
+    let mut dirty = false;
+    for (id, message) in scheduled.take_due() {
+        if let Some(stack) = node.get_target_stack(id) {
+            dirty |= node.send_messages(stack, &mut vec![message], scheduled);
+        }
+    }
+    dirty
+}
+
+/// Whether a redraw has been requested since the last draw, so that the backend is only asked to redraw once per frame. Shared with every [`UiWaker`].
+#[derive(Default, Clone)]
+struct RedrawRequest {
+    requested: Arc<AtomicBool>,
+}
+
+impl RedrawRequest {
+    /// Returns true for the first request of a frame, which is the one that should be passed on.
+    fn request(&self) -> bool {
+        !self.requested.swap(true, Ordering::SeqCst)
+    }
+
+    /// Start a new frame. Returns whether a redraw had been requested.
+    fn take(&self) -> bool {
+        self.requested.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// A handle that redraws the app from any thread, e.g. once an async task has changed something that the app views. Returned by [`UI#waker`].
+#[derive(Clone)]
+pub struct UiWaker {
+    node_dirty: Arc<RwLock<bool>>,
+    redraw_request: RedrawRequest,
+    window: Arc<RwLock<dyn Window>>,
+}
+
+impl UiWaker {
+    /// Mark the app as dirty and ask the [`Window`] to [redraw][Window#method.redraw]. Wakes made before the next [`UI#draw`] are coalesced into a single request.
+    pub fn wake(&self) {
+        *self.node_dirty.write().unwrap() = true;
+        if self.redraw_request.request() {
+            self.window.read().unwrap().redraw();
+        }
+    }
+}
+
+impl std::fmt::Debug for UiWaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("UiWaker").finish()
+    }
+}
+
+/// Timings and counts from the most recent frame, returned by [`UI#frame_stats`]. Times are wall-clock times in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FrameStats {
+    /// How long the last frame took from the start of its draw to the end of its render.
+    pub last_frame_ms: f32,
+    /// How long the last draw spent viewing and laying out Nodes.
+    pub layout_ms: f32,
+    /// How long the last render took to paint the frame.
+    pub render_ms: f32,
+    /// The number of frames rendered over the last second.
+    pub fps_1s_avg: f32,
+    /// The number of Nodes in the last frame, not counting the [`PerfOverlay`][crate::widgets::PerfOverlay].
+    pub node_count: usize,
+    /// The number of [`Renderable`][crate::renderables::Renderable]s in the last frame, not counting the [`PerfOverlay`][crate::widgets::PerfOverlay].
+    pub renderable_count: usize,
+}
+
+/// Collects the [`FrameStats`] from the draw and render threads.
+#[derive(Debug, Default)]
+pub(crate) struct FrameTimer {
+    stats: FrameStats,
+    draw_ms: f32,
+    /// When the frames of the last second were rendered.
+    rendered: VecDeque<Instant>,
+    /// The most recent frame times, oldest first.
+    history: VecDeque<f32>,
+}
+
+impl FrameTimer {
+    /// How many frame times are kept for [`PerfOverlay`][crate::widgets::PerfOverlay]'s graph.
+    const HISTORY: usize = 60;
+
+    fn drawn(
+        &mut self,
+        layout: Duration,
+        draw: Duration,
+        node_count: usize,
+        renderable_count: usize,
+    ) {
+        self.stats.layout_ms = layout.as_secs_f32() * 1000.0;
+        self.draw_ms = draw.as_secs_f32() * 1000.0;
+        self.stats.node_count = node_count;
+        self.stats.renderable_count = renderable_count;
+    }
+
+    fn rendered(&mut self, render: Duration) {
+        let now = Instant::now();
+        self.stats.render_ms = render.as_secs_f32() * 1000.0;
+        self.stats.last_frame_ms = self.draw_ms + self.stats.render_ms;
+        // Only the first render of a draw took the time to draw it
+        self.draw_ms = 0.0;
+
+        self.rendered.push_back(now);
+        while self
+            .rendered
+            .front()
+            .map_or(false, |t| now - *t > Duration::from_secs(1))
+        {
+            self.rendered.pop_front();
+        }
+        self.stats.fps_1s_avg = self.rendered.len() as f32;
+
+        self.history.push_back(self.stats.last_frame_ms);
+        if self.history.len() > Self::HISTORY {
+            self.history.pop_front();
+        }
+    }
+
+    pub(crate) fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    pub(crate) fn history(&self) -> Vec<f32> {
+        self.history.iter().copied().collect()
+    }
+}
+
+/// Preferences that change how the whole UI looks and behaves, for users who need them. Set with [`UI#set_preferences`], and read with [`UI#preferences`]. Each UI has its own, so the windows of an app may differ.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiPreferences {
+    /// Skip animations, jumping straight to where they end. Components see this as [`Event#reduce_motion`][Event#method.reduce_motion].
+    pub reduce_motion: bool,
+    /// Make the whole UI bigger or smaller. This multiplies the scale factor of the window's display, e.g. for an in-app "UI size" setting.
+    pub ui_scale: f32,
+    /// Prefer colors with more contrast. Apps may check this when choosing their [`Theme`].
+    pub high_contrast: bool,
+}
+
+impl Default for UiPreferences {
+    fn default() -> Self {
+        Self {
+            reduce_motion: false,
+            ui_scale: 1.0,
+            high_contrast: false,
+        }
+    }
 }
 
 thread_local!(
@@ -92,6 +308,21 @@ fn set_current_window(window: Arc<RwLock<dyn Window>>) {
     CURRENT_WINDOW.with(|r| unsafe { *r.get().as_mut().unwrap() = Some(window) })
 }
 
+/// What moved the focus, for [`UI#focus_debug`].
+#[derive(Debug, Clone, Copy)]
+enum FocusCause {
+    Tab,
+    Click,
+    Programmatic,
+}
+
+fn logical_size(physical_size: PixelSize, scale_factor: f32) -> PixelSize {
+    PixelSize {
+        width: (physical_size.width as f32 / scale_factor).round() as u32,
+        height: (physical_size.height as f32 / scale_factor).round() as u32,
+    }
+}
+
 impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W, A> {
     fn node_ref(&self) -> RwLockReadGuard<'_, Node> {
         self.node.read().unwrap()
@@ -107,10 +338,14 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
         node: Arc<RwLock<Node>>,
         physical_size: Arc<RwLock<PixelSize>>,
         frame_dirty: Arc<RwLock<bool>>,
+        frame_cap: Option<u32>,
+        frame_timer: Arc<RwLock<FrameTimer>>,
     ) -> JoinHandle<()> {
-        thread::spawn(move || {
-            for _ in receiver.iter() {
-                if *frame_dirty.read().unwrap() {
+        thread::Builder::new()
+            .name("lemna render".to_string())
+            .spawn(move || {
+                render_loop(receiver, frame_dirty, frame_cap, || {
+                    let start = Instant::now();
                     inst("UI::render");
                     // Pull out size so it gets pulled into the renderer lock
                     let size = *physical_size.read().unwrap();
@@ -120,12 +355,12 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                         .as_mut()
                         .unwrap()
                         .render(&node.read().unwrap(), size);
-                    *frame_dirty.write().unwrap() = false;
+                    frame_timer.write().unwrap().rendered(start.elapsed());
                     // println!("rendered");
                     inst_end();
-                }
-            }
-        })
+                })
+            })
+            .unwrap()
     }
 
     fn draw_thread(
@@ -136,14 +371,24 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
         scale_factor: Arc<RwLock<f32>>,
         frame_dirty: Arc<RwLock<bool>>,
         node_dirty: Arc<RwLock<bool>>,
+        full_render: Arc<RwLock<bool>>,
         registrations: Arc<RwLock<Vec<Registration>>>,
+        autofocus: Arc<RwLock<Option<u64>>>,
+        accessibility_dirty: Arc<RwLock<bool>>,
         window: Arc<RwLock<W>>,
+        frame_timer: Arc<RwLock<FrameTimer>>,
+        perf_overlay: Arc<RwLock<bool>>,
     ) -> JoinHandle<()> {
-        thread::spawn(move || {
+        thread::Builder::new()
+            .name("lemna draw".to_string())
+            .spawn(move || {
+            let mut style_generation = style::style_generation();
+            let mut first_view = true;
             for _ in receiver.iter() {
                 if *node_dirty.read().unwrap() {
                     // Set the node to clean right away so that concurrent events can reset it to dirty
                     *node_dirty.write().unwrap() = false;
+                    let start = Instant::now();
                     inst("UI::draw");
                     let logical_size = *logical_size.read().unwrap();
                     let scale_factor = *scale_factor.read().unwrap();
@@ -152,6 +397,12 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                         0,
                         lay!(size: size!(logical_size.width as f32, logical_size.height as f32)),
                     );
+                    new.toasts = true;
+                    if *perf_overlay.read().unwrap() {
+                        let timer = frame_timer.read().unwrap();
+                        new.perf_overlay =
+                            Some(crate::widgets::PerfOverlay::new(timer.stats(), timer.history()));
+                    }
 
                     {
                         // We need to lock the renderer while we modify the node, so that we don't try to render it while doing so
@@ -164,18 +415,43 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                         let mut new_registrations: Vec<Registration> = vec![];
                         new.view(Some(&mut old), &mut new_registrations);
                         *registrations.write().unwrap() = new_registrations;
+                        if std::mem::take(&mut first_view) {
+                            *autofocus.write().unwrap() = new.autofocus_target();
+                        }
                         inst_end();
 
                         let caches = renderer.as_mut().unwrap().caches();
                         inst("Node::layout");
                         new.layout(&old, &caches.font.read().unwrap(), scale_factor);
                         inst_end();
+                        let layout_time = start.elapsed();
 
                         inst("Node::render");
-                        let do_render = new.render(caches, Some(&mut old), scale_factor);
+                        // A new style, stylesheet or theme may have changed the look of any node
+                        let theme_changed =
+                            std::mem::replace(&mut style_generation, style::style_generation())
+                                != style_generation;
+                        let force =
+                            std::mem::take(&mut *full_render.write().unwrap()) || theme_changed;
+                        let do_render = new.render(caches, Some(&mut old), scale_factor, force);
                         inst_end();
 
+                        let (node_count, renderable_count) = new.frame_counts();
+                        frame_timer.write().unwrap().drawn(
+                            layout_time,
+                            start.elapsed(),
+                            node_count,
+                            renderable_count,
+                        );
+                        if capturing() {
+                            counters(
+                                "Draw",
+                                &[("nodes", node_count), ("renderables", renderable_count)],
+                            );
+                        }
+
                         *old = new;
+                        *accessibility_dirty.write().unwrap() = true;
 
                         if do_render {
                             window.write().unwrap().redraw();
@@ -187,14 +463,25 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 }
             }
         })
+        .unwrap()
     }
 
     /// Create a new `UI`, given a [`Window`].
     pub fn new(window: W) -> Self {
-        let scale_factor = Arc::new(RwLock::new(window.scale_factor()));
+        Self::with_render_options(window, RenderOptions::default())
+    }
+
+    /// Create a new `UI`, given a [`Window`] and the [`RenderOptions`] that control how (and how often) it presents frames.
+    pub fn with_render_options(window: W, options: RenderOptions) -> Self {
+        let preferences = window.preferences().unwrap_or_default();
+        let scale_factor = window.scale_factor() * preferences.ui_scale;
         // dbg!(scale_factor);
         let physical_size = Arc::new(RwLock::new(window.physical_size()));
-        let logical_size = Arc::new(RwLock::new(window.logical_size()));
+        let logical_size = Arc::new(RwLock::new(logical_size(
+            window.physical_size(),
+            scale_factor,
+        )));
+        let scale_factor = Arc::new(RwLock::new(scale_factor));
         info!(
             "New window with physical size {:?} client size {:?} and scale factor {:?}",
             physical_size, logical_size, scale_factor
@@ -203,8 +490,9 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
         let mut component = A::default();
         component.init();
 
-        let renderer = Arc::new(RwLock::new(Some(ActiveRenderer::new(&window))));
-        let event_cache = EventCache::new(window.scale_factor());
+        let renderer = Arc::new(RwLock::new(Some(ActiveRenderer::new(&window, &options))));
+        let mut event_cache = EventCache::new(*scale_factor.read().unwrap());
+        event_cache.reduce_motion = preferences.reduce_motion;
         let window = Arc::new(RwLock::new(window));
         set_current_window(window.clone());
 
@@ -216,7 +504,12 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
         )));
         let frame_dirty = Arc::new(RwLock::new(false));
         let node_dirty = Arc::new(RwLock::new(true));
+        let full_render = Arc::new(RwLock::new(false));
         let registrations: Arc<RwLock<Vec<Registration>>> = Default::default();
+        let autofocus: Arc<RwLock<Option<u64>>> = Default::default();
+        let accessibility_dirty = Arc::new(RwLock::new(false));
+        let frame_timer: Arc<RwLock<FrameTimer>> = Default::default();
+        let perf_overlay = Arc::new(RwLock::new(false));
 
         // Create a channel to speak to the renderer. Every time we send to this channel we want to trigger a render;
         let (render_channel, receiver) = unbounded::<()>();
@@ -226,6 +519,8 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
             node.clone(),
             physical_size.clone(),
             frame_dirty.clone(),
+            options.frame_cap,
+            frame_timer.clone(),
         );
 
         // Create a channel to speak to the drawer. Every time we send to this channel we want to trigger a draw;
@@ -238,8 +533,13 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
             scale_factor.clone(),
             frame_dirty,
             node_dirty.clone(),
+            full_render.clone(),
             registrations.clone(),
+            autofocus.clone(),
+            accessibility_dirty.clone(),
             window.clone(),
+            frame_timer.clone(),
+            perf_overlay.clone(),
         );
 
         let n = Self {
@@ -256,7 +556,18 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
             physical_size,
             logical_size,
             event_cache,
+            preferences,
             node_dirty,
+            full_render,
+            autofocus,
+            accessibility_dirty,
+            #[cfg(feature = "accesskit")]
+            window_focused: true,
+            focus_debug: false,
+            frame_timer,
+            perf_overlay,
+            update_queue: Default::default(),
+            redraw_request: Default::default(),
         };
         inst_end();
         n
@@ -274,6 +585,16 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
     ///
     /// A draw will only occur if an event was handled that resulted in [`state_mut`][crate::state_component_impl] being called.
     pub fn draw(&mut self) {
+        if self
+            .update_queue
+            .apply(&mut self.node.write().unwrap(), &self.event_cache.scheduled)
+        {
+            *self.node_dirty.write().unwrap() = true;
+        }
+        self.redraw_request.take();
+        self.take_autofocus();
+        #[cfg(feature = "accesskit")]
+        self.update_accessibility();
         self.draw_channel.send(()).unwrap();
     }
 
@@ -282,28 +603,308 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
     /// which it will do after drawing. This thread does not interact with the user-facing API,
     /// just the [`Renderable`][crate::renderables::Renderable]s generated during [`draw`][UI#method.draw].
     pub fn render(&mut self) {
+        #[cfg(feature = "accesskit")]
+        self.update_accessibility();
         self.render_channel.send(()).unwrap();
     }
 
-    fn blur(&mut self) {
-        let mut blur_event = Event::new(event::Blur, &self.event_cache);
+    /// Send the [`accessibility`][crate::accessibility] tree to the window, if it has changed since it was last sent.
+    #[cfg(feature = "accesskit")]
+    fn update_accessibility(&mut self) {
+        if std::mem::take(&mut *self.accessibility_dirty.write().unwrap()) {
+            let focus = self.window_focused.then_some(self.event_cache.focus);
+            let update = crate::accessibility::tree_update(&self.node_ref(), focus);
+            self.window.read().unwrap().accessibility_update(update);
+        }
+    }
+
+    /// Perform an action requested by an assistive technology.
+    #[cfg(feature = "accesskit")]
+    fn handle_accessibility(&mut self, request: &accesskit::ActionRequest) {
+        let id = crate::accessibility::lemna_id(request.target);
+        match request.action {
+            accesskit::Action::Focus => {
+                self.focus_node(id, true, FocusCause::Programmatic);
+                self.scroll_into_view(id);
+            }
+            accesskit::Action::Default => {
+                let aabb = self.node_ref().aabb_of(id);
+                if let Some(aabb) = aabb {
+                    // Click the middle of the Node, as the mouse would
+                    let mouse_position = self.event_cache.mouse_position;
+                    self.event_cache.mouse_position = Point::new(
+                        (aabb.pos.x + aabb.bottom_right.x) / 2.0,
+                        (aabb.pos.y + aabb.bottom_right.y) / 2.0,
+                    );
+                    let mut event = Event::new(event::Click(MouseButton::Left), &self.event_cache);
+                    self.handle_event(Node::click, &mut event, None);
+                    self.event_cache.mouse_position = mouse_position;
+                }
+            }
+            accesskit::Action::SetValue => {
+                if let Some(accesskit::ActionData::Value(value)) = &request.data {
+                    self.focus_node(id, true, FocusCause::Programmatic);
+                    let mut event =
+                        Event::new(event::SetValue(value.to_string()), &self.event_cache);
+                    self.handle_event(Node::set_value, &mut event, Some(id));
+                }
+            }
+            _ => (),
+        }
+        self.window.write().unwrap().redraw();
+    }
+
+    /// Change the [`UiPreferences`], redrawing the whole UI with them.
+    pub fn set_preferences(&mut self, preferences: UiPreferences) {
+        self.preferences = preferences;
+        self.event_cache.reduce_motion = preferences.reduce_motion;
+        self.update_scale_factor();
+        *self.full_render.write().unwrap() = true;
+        self.window.write().unwrap().redraw();
+    }
+
+    /// The [`UiPreferences`] that the UI is currently using.
+    pub fn preferences(&self) -> UiPreferences {
+        self.preferences
+    }
+
+    /// The timings and distances that turn mouse input into double clicks and drags.
+    pub fn gesture_thresholds(&self) -> event::GestureThresholds {
+        self.event_cache.gesture_thresholds
+    }
+
+    /// Change the timings and distances that turn mouse input into double clicks and drags, e.g. to match the settings of the OS.
+    pub fn set_gesture_thresholds(&mut self, thresholds: event::GestureThresholds) {
+        self.event_cache.gesture_thresholds = thresholds;
+    }
+
+    /// The scale factor of the UI is that of the window's display, multiplied by the [`ui_scale`][UiPreferences#structfield.ui_scale] preference.
+    fn update_scale_factor(&mut self) {
+        let scale_factor = self.window.read().unwrap().scale_factor() * self.preferences.ui_scale;
+        *self.logical_size.write().unwrap() =
+            logical_size(*self.physical_size.read().unwrap(), scale_factor);
+        *self.scale_factor.write().unwrap() = scale_factor;
+        self.event_cache.scale_factor = scale_factor;
+        *self.node_dirty.write().unwrap() = true;
+    }
+
+    /// Remove the focus from the focused Node, if any, sending it a [`Blur`][event::Blur] event. The root Node gets the focus instead.
+    pub fn blur(&mut self) {
+        self.send_blur(None);
+        let root = self.node.read().unwrap().id;
+        self.log_focus(root, FocusCause::Programmatic);
+        self.event_cache.focus_visible = false;
+        self.set_focus(root); // The root note gets focus
+        self.window.write().unwrap().redraw();
+    }
+
+    /// Move the focus to the Node with the given [`reference`][Node#method.reference], sending [`Blur`][event::Blur] and [`Focus`][event::Focus] events, and scroll it into view. Returns `false` if there is no such Node.
+    pub fn focus(&mut self, reference: &str) -> bool {
+        let id = self.node_ref().get_reference(reference);
+        if let Some(id) = id {
+            self.focus_reference(id);
+            self.window.write().unwrap().redraw();
+        }
+        id.is_some()
+    }
+
+    /// Focus the Node with the id `id`, found by its reference, and scroll it into view.
+    fn focus_reference(&mut self, id: u64) {
+        // Programmatic focus is only visible if the keyboard was last used to move it
+        self.focus_node(id, self.event_cache.focus_visible, FocusCause::Programmatic);
+        self.scroll_into_view(id);
+    }
+
+    /// Move the focus to the next [focusable][Component#method.focusable] Node, in tree order. Wraps around to the first one.
+    pub fn focus_next(&mut self) {
+        self.focus_adjacent(true);
+    }
+
+    /// Move the focus to the previous [focusable][Component#method.focusable] Node, in tree order. Wraps around to the last one.
+    pub fn focus_prev(&mut self) {
+        self.focus_adjacent(false);
+    }
+
+    /// Scroll the Node with the given [`reference`][Node#method.reference] into view, honoring its [`scroll_margin`][Node#method.scroll_margin] and the `scroll_padding` of the containers that scroll it. Returns `false` if there is no such Node.
+    pub fn ensure_visible(&mut self, reference: &str) -> bool {
+        let id = self.node_ref().get_reference(reference);
+        if let Some(id) = id {
+            self.scroll_into_view(id);
+            self.window.write().unwrap().redraw();
+        }
+        id.is_some()
+    }
+
+    fn scroll_into_view(&mut self, id: u64) {
+        let scale_factor = self.event_cache.scale_factor;
+        if self.node_mut().ensure_visible(id, scale_factor) {
+            *self.node_dirty.write().unwrap() = true;
+        }
+    }
+
+    /// Focus the [`autofocus`][Node#method.autofocus] Node, once the first view has found it.
+    fn take_autofocus(&mut self) {
+        let autofocus = self.autofocus.write().unwrap().take();
+        if let Some(id) = autofocus {
+            self.focus_node(id, self.event_cache.focus_visible, FocusCause::Programmatic);
+        }
+    }
+
+    fn focus_adjacent(&mut self, forward: bool) {
+        let next = self.node_ref().next_focus(self.event_cache.focus, forward);
+        if let Some(id) = next {
+            self.focus_node(id, true, FocusCause::Tab);
+            self.scroll_into_view(id);
+            self.window.write().unwrap().redraw();
+        }
+    }
+
+    /// Log focus changes, and outline the focused Node and its ancestors. The focused Node is outlined in red, and its ancestors in a fainter red.
+    pub fn focus_debug(&mut self, enabled: bool) {
+        self.focus_debug = enabled;
+        let focus = enabled.then_some(self.event_cache.focus);
+        if self.node_mut().set_focus_debug(focus) {
+            *self.node_dirty.write().unwrap() = true;
+            self.window.write().unwrap().redraw();
+        }
+    }
+
+    /// The ids of the Nodes from the root down to the focused one.
+    pub fn focus_path(&self) -> Vec<u64> {
+        self.node_ref()
+            .path_to(self.event_cache.focus)
+            .map(|path| path.iter().map(|n| n.id).collect())
+            .unwrap_or_default()
+    }
+
+    /// The [type names][Component#method.type_name] of the Components of the Nodes from the root down to the focused one.
+    pub fn focus_path_names(&self) -> Vec<&'static str> {
+        self.node_ref()
+            .path_to(self.event_cache.focus)
+            .map(|path| path.iter().map(|n| n.component.type_name()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Timings and counts from the most recent frame. These are collected for every frame, whether or not they are shown with [`#perf_overlay`][UI#method.perf_overlay].
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_timer.read().unwrap().stats()
+    }
+
+    /// Show the [`FrameStats`] and a graph of recent frame times over the app, with a [`PerfOverlay`][crate::widgets::PerfOverlay].
+    pub fn perf_overlay(&mut self, enabled: bool) {
+        if std::mem::replace(&mut *self.perf_overlay.write().unwrap(), enabled) != enabled {
+            *self.node_dirty.write().unwrap() = true;
+            self.window.write().unwrap().redraw();
+        }
+    }
+
+    fn log_focus(&self, to: u64, cause: FocusCause) {
+        if self.focus_debug && to != self.event_cache.focus {
+            let node = self.node_ref();
+            let name = |id| {
+                node.path_to(id)
+                    .and_then(|path| path.last().map(|n| n.component.type_name()))
+                    .unwrap_or("?")
+            };
+            focus_evt(&format!(
+                "Focus {} {} -> {} {} ({:?})",
+                self.event_cache.focus,
+                name(self.event_cache.focus),
+                to,
+                name(to),
+                cause
+            ));
+        }
+    }
+
+    /// Move the focus to the Node with the id `id`, showing its [`FocusRing`][crate::widgets::FocusRing] if `visible`. [Disabled][Node#method.disabled] Nodes can't be focused.
+    fn focus_node(&mut self, id: u64, visible: bool, cause: FocusCause) {
+        let disabled = self
+            .node_ref()
+            .path_to(id)
+            .map_or(false, |path| path.last().unwrap().disabled);
+        if id != self.event_cache.focus && !disabled {
+            self.log_focus(id, cause);
+            // The root Node has the focus when nothing else does
+            let root = self.node_ref().id;
+            let old_focus = Some(self.event_cache.focus).filter(|f| *f != root);
+            self.send_blur(Some(id));
+            self.event_cache.focus_visible = visible;
+            self.set_focus(id);
+            let (related_key_path, related_references) = old_focus
+                .and_then(|id| self.node_ref().key_path(id))
+                .unwrap_or_default();
+            let mut focus_event = Event::new(
+                event::Focus {
+                    related_target: old_focus,
+                    related_key_path,
+                    related_references,
+                },
+                &self.event_cache,
+            );
+            focus_event.target = Some(id);
+            self.node_mut().focus(&mut focus_event);
+            self.handle_dirty_event(&focus_event);
+        }
+    }
+
+    /// Send a [`Blur`][event::Blur] event to the focused Node, which is losing the focus to `new_focus`.
+    fn send_blur(&mut self, new_focus: Option<u64>) {
+        let (related_key_path, related_references) = new_focus
+            .and_then(|id| self.node_ref().key_path(id))
+            .unwrap_or_default();
+        let mut blur_event = Event::new(
+            event::Blur {
+                related_target: new_focus,
+                related_key_path,
+                related_references,
+            },
+            &self.event_cache,
+        );
         blur_event.target = Some(self.event_cache.focus);
         self.node_mut().blur(&mut blur_event);
         self.handle_dirty_event(&blur_event);
+    }
 
-        self.event_cache.focus = self.node.read().unwrap().id; // The root note gets focus
+    /// Move the focus to the Node with the id `focus`, letting the Nodes that the focus moves into or out of know.
+    fn set_focus(&mut self, focus: u64) {
+        let old_focus = std::mem::replace(&mut self.event_cache.focus, focus);
+        if old_focus != focus {
+            let mut node = self.node.write().unwrap();
+            let within_changed = node.focus_within_change(old_focus, focus, &self.event_cache);
+            let ring_changed = node.set_focus_visible(focus, self.event_cache.focus_visible);
+            let outline_changed = self.focus_debug && node.set_focus_debug(Some(focus));
+            if within_changed || ring_changed || outline_changed {
+                *self.node_dirty.write().unwrap() = true;
+            }
+            *self.accessibility_dirty.write().unwrap() = true;
+        }
+    }
+
+    /// Send a ContextMenu event if pressing or releasing `b` requests one on this platform.
+    fn context_menu(&mut self, b: MouseButton, pressed: bool) {
+        if self.event_cache.is_context_menu_gesture(b, pressed) {
+            let mut event = Event::new(
+                event::ContextMenu {
+                    position: self.event_cache.mouse_position,
+                },
+                &self.event_cache,
+            );
+            self.handle_event(Node::context_menu, &mut event, None);
+        }
     }
 
     fn handle_focus_or_blur<T: EventInput>(&mut self, event: &Event<T>) {
-        if event.focus.is_none() {
-            self.blur();
-        } else if event.focus != Some(self.event_cache.focus) {
+        if let Some(reference) = event.focus_reference.as_deref() {
+            let id = self.node_ref().get_reference(reference);
+            if let Some(id) = id {
+                self.focus_reference(id);
+            }
+        } else if event.focus.is_none() {
             self.blur();
-            self.event_cache.focus = event.focus.unwrap();
-            let mut focus_event = Event::new(event::Focus, &self.event_cache);
-            focus_event.target = Some(self.event_cache.focus);
-            self.node_mut().focus(&mut focus_event);
-            self.handle_dirty_event(&focus_event);
+        } else if let Some(focus) = event.focus {
+            // Focus that comes from the mouse isn't shown
+            self.focus_node(focus, false, FocusCause::Click);
         }
     }
 
@@ -344,6 +945,7 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
     /// Handle [`Input`]s coming from the [`Window`] backend.
     pub fn handle_input(&mut self, input: &Input) {
         inst("UI::handle_input");
+        self.take_autofocus();
         // if self.node.is_none() || self.renderer.is_none() {
         //     // If there is no node, the event has happened after exiting
         //     // For some reason checking for both works better, even though they're unset at the same time?
@@ -353,18 +955,14 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
             Input::Resize => {
                 let new_size = self.window.read().unwrap().physical_size();
                 if new_size.width != 0 && new_size.height != 0 {
-                    let scale_factor = self.window.read().unwrap().scale_factor();
                     *self.physical_size.write().unwrap() = new_size;
-                    *self.logical_size.write().unwrap() =
-                        self.window.read().unwrap().logical_size();
-                    *self.scale_factor.write().unwrap() = scale_factor;
-                    self.event_cache.scale_factor = scale_factor;
-                    *self.node_dirty.write().unwrap() = true;
+                    self.update_scale_factor();
                     self.window.write().unwrap().redraw(); // Always redraw after resizing
                 }
             }
             Input::Motion(Motion::Mouse { x, y }) => {
-                let pos = Point::new(*x, *y) * self.event_cache.scale_factor;
+                // Backends send positions that are logical to the window, not to the UI
+                let pos = Point::new(*x, *y) * self.window.read().unwrap().scale_factor();
 
                 if let Some(button) = self.event_cache.mouse_button_held() {
                     if self.event_cache.drag_started.is_none() {
@@ -374,8 +972,7 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                     let drag_start = self.event_cache.drag_started.unwrap();
 
                     if self.event_cache.drag_button.is_none()
-                        && ((drag_start.x - pos.x).abs() > event::DRAG_THRESHOLD
-                            || (drag_start.y - pos.y).abs() > event::DRAG_THRESHOLD)
+                        && self.event_cache.is_drag_start(drag_start, pos)
                     {
                         self.event_cache.drag_button = Some(button);
                         let mut drag_start_event =
@@ -421,40 +1018,66 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 }
             }
             Input::Motion(Motion::Scroll { x, y }) => {
+                let window_scale_factor = self.window.read().unwrap().scale_factor();
                 let mut event = Event::new(
                     event::Scroll {
-                        x: *x * self.event_cache.scale_factor,
-                        y: *y * self.event_cache.scale_factor,
+                        x: *x * window_scale_factor,
+                        y: *y * window_scale_factor,
                     },
                     &self.event_cache,
                 );
                 self.handle_event_without_focus(Node::scroll, &mut event, None);
             }
+            Input::Stylus {
+                x,
+                y,
+                pressure,
+                tilt,
+                phase,
+            } => {
+                let pos = Point::new(*x, *y) * self.window.read().unwrap().scale_factor();
+                let mut event = Event::new(
+                    event::Stylus {
+                        pressure: *pressure,
+                        tilt: *tilt,
+                        phase: *phase,
+                    },
+                    &self.event_cache,
+                );
+                event.mouse_position = pos;
+                self.handle_event_without_focus(Node::stylus, &mut event, None);
+
+                if event.bubbles {
+                    // Nothing handled the stylus, so it stands in for the mouse
+                    self.handle_input(&Input::Motion(Motion::Mouse { x: *x, y: *y }));
+                    match phase {
+                        StylusPhase::Down => {
+                            self.handle_input(&Input::Press(Button::Mouse(MouseButton::Left)))
+                        }
+                        StylusPhase::Up | StylusPhase::Cancel => {
+                            if self.event_cache.mouse_buttons_held.left {
+                                self.handle_input(&Input::Release(Button::Mouse(MouseButton::Left)))
+                            }
+                        }
+                        StylusPhase::Move => (),
+                    }
+                } else {
+                    self.event_cache.mouse_position = pos;
+                }
+            }
             Input::Press(Button::Mouse(b)) => {
                 self.event_cache.mouse_down(*b);
                 let mut event = Event::new(event::MouseDown(*b), &self.event_cache);
                 self.handle_event(Node::mouse_down, &mut event, None);
+                self.context_menu(*b, true);
             }
             Input::Release(Button::Mouse(b)) => {
                 let mut event = Event::new(event::MouseUp(*b), &self.event_cache);
                 self.handle_event(Node::mouse_up, &mut event, None);
+                self.context_menu(*b, false);
 
-                let mut is_double_click = false;
                 // Double clicking
-                if b == &MouseButton::Left {
-                    if self.event_cache.last_mouse_click.elapsed().as_millis()
-                        < event::DOUBLE_CLICK_INTERVAL_MS
-                        && self
-                            .event_cache
-                            .last_mouse_click_position
-                            .dist(self.event_cache.mouse_position)
-                            < event::DOUBLE_CLICK_MAX_DIST
-                    {
-                        is_double_click = true;
-                    }
-                    self.event_cache.last_mouse_click = Instant::now();
-                    self.event_cache.last_mouse_click_position = self.event_cache.mouse_position;
-                }
+                let is_double_click = b == &MouseButton::Left && self.event_cache.left_click();
 
                 // End drag
                 if Some(*b) == self.event_cache.drag_button {
@@ -471,14 +1094,13 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                         self.event_cache.drag_target,
                     );
 
-                    let drag_distance = self
+                    if self
                         .event_cache
-                        .drag_started
-                        .unwrap()
-                        .dist(self.event_cache.mouse_position);
-                    if drag_distance < event::DRAG_CLICK_MAX_DIST {
+                        .is_drag_click(self.event_cache.drag_started.unwrap())
+                    {
                         // Send a Click event if the drag was quite short
-                        let mut click_event = Event::new(event::Click(*b), &self.event_cache);
+                        let mut click_event =
+                            Event::new_from_press(event::Click(*b), &self.event_cache);
                         self.handle_event(Node::click, &mut click_event, None);
                     }
 
@@ -498,11 +1120,12 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                     // Resolve click
                     self.event_cache.mouse_up(*b);
                     let event_current_node_id = if is_double_click {
-                        let mut event = Event::new(event::DoubleClick(*b), &self.event_cache);
+                        let mut event =
+                            Event::new_from_press(event::DoubleClick(*b), &self.event_cache);
                         self.handle_event(Node::double_click, &mut event, None);
                         event.current_node_id
                     } else {
-                        let mut event = Event::new(event::Click(*b), &self.event_cache);
+                        let mut event = Event::new_from_press(event::Click(*b), &self.event_cache);
                         self.handle_event(Node::click, &mut event, None);
                         event.current_node_id
                     };
@@ -521,6 +1144,10 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 let mut event = Event::new(event::KeyDown(*k), &self.event_cache);
                 let focus = event.focus;
                 self.handle_event(Node::key_down, &mut event, focus);
+                // Tab navigation, unless the focused Node handled the key itself
+                if *k == Key::Tab && event.bubbles {
+                    self.focus_adjacent(!self.event_cache.modifiers_held.shift);
+                }
             }
             Input::Release(Button::Keyboard(k)) => {
                 if self.event_cache.key_held(*k) {
@@ -543,20 +1170,37 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 }
             }
             Input::Focus(false) => {
+                #[cfg(feature = "accesskit")]
+                {
+                    self.window_focused = false;
+                    *self.accessibility_dirty.write().unwrap() = true;
+                }
                 self.event_cache.clear();
-                let mut event = Event::new(event::Blur, &self.event_cache);
+                let mut event = Event::new(event::Blur::default(), &self.event_cache);
                 self.node_mut().component.on_blur(&mut event);
                 self.handle_dirty_event(&event);
             }
             Input::Focus(true) => {
-                let mut event = Event::new(event::Focus, &self.event_cache);
+                #[cfg(feature = "accesskit")]
+                {
+                    self.window_focused = true;
+                    *self.accessibility_dirty.write().unwrap() = true;
+                }
+                let mut event = Event::new(event::Focus::default(), &self.event_cache);
                 self.node_mut().component.on_focus(&mut event);
                 self.handle_dirty_event(&event);
             }
+            #[cfg(feature = "accesskit")]
+            Input::Accessibility(request) => self.handle_accessibility(request),
             Input::Timer => {
                 let mut event = Event::new(event::Tick, &self.event_cache);
                 self.node_mut().tick(&mut event);
                 self.handle_dirty_event(&event);
+
+                // Deliver the messages of any Command::After whose time has come
+                if deliver_scheduled(&mut self.node.write().unwrap(), &self.event_cache.scheduled) {
+                    *self.node_dirty.write().unwrap() = true;
+                }
             }
             Input::MouseLeaveWindow => {
                 if self.event_cache.mouse_over.is_some() {
@@ -585,6 +1229,7 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 self.event_cache.clear();
             }
             Input::MouseEnterWindow => (),
+            Input::Modifiers(modifiers) => self.event_cache.set_modifiers(*modifiers),
             Input::Drag(drag) => match drag {
                 Drag::Start(data) => {
                     self.event_cache.drag_data.push(data.clone());
@@ -638,9 +1283,18 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 }
             },
             Input::Exit => {
-                clear_current_window();
-                let renderer = self.renderer.write().unwrap().take().unwrap();
-                drop(renderer);
+                self.request_exit();
+            }
+            Input::ThemeChanged(scheme) => {
+                let mut event = Event::new(event::ThemeChange(*scheme), &self.event_cache);
+                self.node_mut().component.on_theme_change(&mut event);
+                for message in event.messages.drain(..) {
+                    self.update(message);
+                }
+                // Colors may have changed anywhere, so render everything
+                *self.full_render.write().unwrap() = true;
+                *self.node_dirty.write().unwrap() = true;
+                self.window.write().unwrap().redraw();
             }
             Input::Menu(id) => {
                 let current_focus = self.event_cache.focus;
@@ -661,10 +1315,11 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                             if let Some(stack) =
                                 self.node.read().unwrap().get_target_stack(current_focus)
                             {
-                                self.node
-                                    .write()
-                                    .unwrap()
-                                    .send_messages(stack, &mut menu_event.messages);
+                                self.node.write().unwrap().send_messages(
+                                    stack,
+                                    &mut menu_event.messages,
+                                    &menu_event.scheduled,
+                                );
                             }
                         }
                     }
@@ -698,10 +1353,125 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
             .add_font(name, bytes);
     }
 
+    /// Rasterize the glyphs of `text` ahead of time, so that there is no hitch when it first appears. See [`FontCache#prewarm`][crate::font_cache::FontCache#method.prewarm].
+    pub fn prewarm_text(&mut self, text: &str, font: Option<&str>, font_size: f32) {
+        let scale_factor = *self.scale_factor.read().unwrap();
+        self.renderer
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .caches()
+            .font
+            .write()
+            .unwrap()
+            .prewarm(text, font, font_size, scale_factor);
+    }
+
+    /// Replace the current [`Theme`], which the styles of the app's widgets are resolved against, and redraw every node with it.
+    pub fn set_theme(&mut self, theme: Theme) {
+        style::set_current_theme(theme);
+        *self.full_render.write().unwrap() = true;
+        *self.node_dirty.write().unwrap() = true;
+        self.window.write().unwrap().redraw();
+    }
+
+    /// Replace the current [`StyleSheet`], whose classes the app's widgets may use, and redraw every node with it.
+    pub fn set_stylesheet(&mut self, stylesheet: StyleSheet) {
+        style::set_current_stylesheet(stylesheet);
+        *self.full_render.write().unwrap() = true;
+        *self.node_dirty.write().unwrap() = true;
+        self.window.write().unwrap().redraw();
+    }
+
+    /// Replace the current [`Locale`][crate::locale::Locale], and view and redraw every node with it, so that their strings are translated again and their text runs in its direction.
+    pub fn set_locale(&mut self, locale: crate::locale::Locale) {
+        crate::locale::set_locale(locale);
+        *self.full_render.write().unwrap() = true;
+        *self.node_dirty.write().unwrap() = true;
+        self.window.write().unwrap().redraw();
+    }
+
+    /// The [`NodeId`] of the first Node, in tree order, with the given [`reference`][Node#method.reference], in the tree as of the last view. Returns `None` if there is no such Node.
+    pub fn node_id(&self, reference: &str) -> Option<NodeId> {
+        self.node_ref().get_reference(reference)
+    }
+
+    /// Every [`reference`][Node#method.reference] in the tree as of the last view, with the [`NodeId`] of its Node, in tree order.
+    pub fn references(&self) -> Vec<(String, NodeId)> {
+        self.node_ref().references()
+    }
+
+    /// The [`AABB`] of the Node with the id `id`, in physical pixels, as of the last layout. Returns `None` if there is no such Node.
+    pub fn node_aabb(&self, id: NodeId) -> Option<AABB> {
+        self.node_ref().aabb_of(id)
+    }
+
+    /// Paint the Node with the id `id`, and its descendants, into an [`Image`][crate::Image] the size of the Node, e.g. for a drag ghost or a thumbnail. This happens on the CPU, which paints rects and shapes but leaves out text and rasters. See [`#capture_texture`][UI#method.capture_texture] for a complete capture. Returns `None` if there is no such Node, or if the UI has exited.
+    pub fn capture(&self, id: NodeId) -> Option<crate::Image> {
+        let caches = self.renderer.read().unwrap().as_ref()?.caches();
+        let node = self.node_ref();
+        let path = node.path_to(id)?;
+        Some(crate::render::rasterize(path.last().unwrap(), &caches))
+    }
+
+    /// Render the Node with the id `id`, and its descendants, into a new [`wgpu::Texture`] the size of the Node. The texture is in the same format as the window's frames, and is transparent where the Node doesn't draw anything. Returns `None` if there is no such Node, or if the UI has exited.
+    pub fn capture_texture(&self, id: NodeId) -> Option<::wgpu::Texture> {
+        // Lock the renderer before the node, like the draw thread does
+        let mut renderer = self.renderer.write().unwrap();
+        let renderer = renderer.as_mut()?;
+        let node = self.node_ref();
+        let path = node.path_to(id)?;
+        Some(renderer.capture(path.last().unwrap()))
+    }
+
+    /// Show a transient notification with the text `message` for `duration`. See [`Toasts`][crate::widgets::Toasts].
+    pub fn toast(&mut self, message: impl Into<String>, duration: Duration) {
+        self.event_cache.toasts.push(message.into(), duration);
+        self.window.write().unwrap().redraw();
+    }
+
     /// Calls [`Component#update`][Component#method.update] with `msg` on the root Node of the application. This will always trigger a redraw.
+    ///
+    /// Messages are queued, and applied in the order they were sent right before the next [`#draw`][UI#method.draw]. A burst of messages, e.g. from parameter automation, thus only causes a single redraw.
     pub fn update(&mut self, msg: crate::Message) {
-        self.node_mut().component.update(msg);
-        *self.node_dirty.write().unwrap() = true;
+        self.update_queue.push(msg);
+        if self.redraw_request.request() {
+            self.window.write().unwrap().redraw();
+        }
+    }
+
+    /// Redraw the app on the next frame, e.g. when something that it views has changed without an input or message to say so. Calls made before the next [`#draw`][UI#method.draw] are coalesced into a single redraw.
+    pub fn request_redraw(&mut self) {
+        self.wake();
+    }
+
+    /// Like [`#request_redraw`][UI#method.request_redraw], but only needs a shared reference. Use [`#waker`][UI#method.waker] to redraw from other threads.
+    pub fn wake(&self) {
+        self.waker().wake();
+    }
+
+    /// A handle that can be sent to other threads, to [wake][UiWaker#method.wake] the app when something that it views has changed.
+    pub fn waker(&self) -> UiWaker {
+        UiWaker {
+            node_dirty: self.node_dirty.clone(),
+            redraw_request: self.redraw_request.clone(),
+            window: self.window.clone(),
+        }
+    }
+
+    /// Give every Component the chance to clean up with [`on_exit`][Component#method.on_exit], then tear the UI down, unless one of them [vetoed][Event#method.veto] it. Returns whether the UI was torn down. This is what [`Input::Exit`] does.
+    pub fn request_exit(&mut self) -> bool {
+        let mut event = Event::new(event::Exit::default(), &self.event_cache);
+        self.node_mut().exit(&mut event);
+        self.handle_dirty_event(&event);
+        if event.input.vetoed {
+            return false;
+        }
+        clear_current_window();
+        let renderer = self.renderer.write().unwrap().take();
+        drop(renderer);
+        true
     }
 
     /// Calls the equivalent of [`state_mut`][crate::state_component_impl] on the root Node of the application, and passes it as an arg to given closure `f`.
@@ -721,6 +1491,223 @@ impl<W: 'static + Window, A: 'static + Component + Default + Send + Sync> UI<W,
                 dirty = true;
             }
         }
-        *self.node_dirty.write().unwrap() = dirty;
+        if dirty {
+            self.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Counter {
+        count: Arc<AtomicUsize>,
+    }
+
+    impl Component for Counter {
+        fn update(&mut self, msg: crate::Message) -> Vec<crate::Message> {
+            if let Some(n) = msg.downcast_ref::<usize>() {
+                self.count.fetch_add(*n, Ordering::SeqCst);
+            }
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_update_queue() {
+        let count: Arc<AtomicUsize> = Default::default();
+        let mut node = Node::new(
+            Box::new(Counter {
+                count: count.clone(),
+            }),
+            0,
+            Layout::default(),
+        );
+        let mut queue = UpdateQueue::default();
+        let scheduled = ScheduledQueue::default();
+
+        // Messages sent before a frame are held until it is drawn
+        for _ in 0..10 {
+            queue.push(Box::new(1_usize));
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        // Then they are all applied, for a single draw
+        assert!(queue.apply(&mut node, &scheduled));
+        assert_eq!(count.load(Ordering::SeqCst), 10);
+        assert!(!queue.apply(&mut node, &scheduled));
+    }
+
+    /// Counts the messages that it sends to itself with a `Command::After`.
+    #[derive(Debug, Default)]
+    struct Delayed {
+        count: Arc<AtomicUsize>,
+    }
+
+    struct Later;
+
+    impl Component for Delayed {
+        fn update(&mut self, msg: crate::Message) -> Vec<crate::Message> {
+            if msg.is::<Later>() {
+                self.count.fetch_add(1, Ordering::SeqCst);
+                vec![]
+            } else {
+                vec![crate::Command::after(Duration::ZERO, Later)]
+            }
+        }
+    }
+
+    #[test]
+    fn test_scheduled_per_ui() {
+        // The root Node and EventCache of a UI
+        let ui = || {
+            let count: Arc<AtomicUsize> = Default::default();
+            let mut node = Node::new(
+                Box::new(Delayed {
+                    count: count.clone(),
+                }),
+                0,
+                Layout::default(),
+            );
+            node.view(None, &mut vec![]);
+            (node, EventCache::new(1.0), count)
+        };
+        let (mut node_a, cache_a, count_a) = ui();
+        let (mut node_b, cache_b, count_b) = ui();
+        node_a.update(Box::new(()), &cache_a.scheduled);
+        node_b.update(Box::new(()), &cache_b.scheduled);
+
+        // Each UI only delivers the messages that were scheduled by its own Nodes
+        deliver_scheduled(&mut node_a, &cache_a.scheduled);
+        assert_eq!(count_a.load(Ordering::SeqCst), 1);
+        assert_eq!(count_b.load(Ordering::SeqCst), 0);
+        deliver_scheduled(&mut node_b, &cache_b.scheduled);
+        assert_eq!(count_a.load(Ordering::SeqCst), 1);
+        assert_eq!(count_b.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_frame_cap() {
+        let (sender, receiver) = unbounded();
+        let frame_dirty = Arc::new(RwLock::new(false));
+        let renders: Arc<RwLock<Vec<Instant>>> = Default::default();
+        let handle = {
+            let frame_dirty = frame_dirty.clone();
+            let renders = renders.clone();
+            thread::spawn(move || {
+                render_loop(receiver, frame_dirty, Some(20), || {
+                    renders.write().unwrap().push(Instant::now())
+                })
+            })
+        };
+
+        // A dirty frame, which is rendered right away
+        *frame_dirty.write().unwrap() = true;
+        sender.send(()).unwrap();
+        while *frame_dirty.read().unwrap() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        // And a second, which is too soon for the cap
+        *frame_dirty.write().unwrap() = true;
+        sender.send(()).unwrap();
+        drop(sender);
+        handle.join().unwrap();
+
+        // The second is still rendered, once the frame is due, even though no request followed it
+        let renders = renders.read().unwrap();
+        assert_eq!(renders.len(), 2);
+        assert!(renders[1] - renders[0] >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_redraw_request() {
+        let request = RedrawRequest::default();
+        assert!(!request.take());
+
+        // Only the first request of a frame is passed on to the backend
+        assert!(request.request());
+        assert!(!request.request());
+        assert!(!request.request());
+
+        // Drawing the frame clears it
+        assert!(request.take());
+        assert!(!request.take());
+        assert!(request.request());
+    }
+
+    #[derive(Default)]
+    struct RedrawCounter {
+        redraws: AtomicUsize,
+    }
+
+    impl Window for RedrawCounter {
+        fn logical_size(&self) -> PixelSize {
+            PixelSize {
+                width: 100,
+                height: 100,
+            }
+        }
+
+        fn physical_size(&self) -> PixelSize {
+            self.logical_size()
+        }
+
+        fn scale_factor(&self) -> f32 {
+            1.0
+        }
+
+        fn redraw(&self) {
+            self.redraws.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    unsafe impl raw_window_handle::HasRawWindowHandle for RedrawCounter {
+        fn raw_window_handle(&self) -> raw_window_handle::RawWindowHandle {
+            panic!("Can't get windows handle in a test")
+        }
+    }
+
+    unsafe impl raw_window_handle::HasRawDisplayHandle for RedrawCounter {
+        fn raw_display_handle(&self) -> raw_window_handle::RawDisplayHandle {
+            panic!("Can't get windows handle in a test")
+        }
+    }
+
+    #[test]
+    fn test_waker() {
+        let window = Arc::new(RwLock::new(RedrawCounter::default()));
+        let redraws = || window.read().unwrap().redraws.load(Ordering::SeqCst);
+        let node_dirty = Arc::new(RwLock::new(false));
+        let redraw_request = RedrawRequest::default();
+        let waker = UiWaker {
+            node_dirty: node_dirty.clone(),
+            redraw_request: redraw_request.clone(),
+            window: window.clone(),
+        };
+
+        // Waking from other threads marks the app dirty, with a single redraw request
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let waker = waker.clone();
+                thread::spawn(move || waker.wake())
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert!(*node_dirty.read().unwrap());
+        assert_eq!(redraws(), 1);
+        waker.wake();
+        assert_eq!(redraws(), 1);
+
+        // Once the frame has been drawn, the next wake asks for another
+        assert!(redraw_request.take());
+        *node_dirty.write().unwrap() = false;
+        waker.wake();
+        assert!(*node_dirty.read().unwrap());
+        assert_eq!(redraws(), 2);
     }
 }
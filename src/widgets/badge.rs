@@ -0,0 +1,110 @@
+use crate::base_types::*;
+use crate::component::Component;
+use crate::layout::*;
+use crate::style::{HorizontalPosition, Styled};
+use crate::{node, txt, Node};
+use lemna_macros::component;
+
+/// A small count, like the number of unread messages, shown over the top right corner of another Node. Counts with more than one digit widen the badge into a pill, and a badge without a count is drawn as a dot.
+///
+/// Badges are usually attached with [`Node#badge`][Node#method.badge]:
+///
+/// ```ignore
+/// node!(Button::new(txt!("Inbox"))).badge(unread)
+/// ```
+#[component(Styled, Internal)]
+#[derive(Debug)]
+pub struct Badge {
+    pub count: Option<usize>,
+    pub show_zero: bool,
+}
+
+impl Badge {
+    pub fn new(count: usize) -> Self {
+        Self {
+            count: Some(count),
+            show_zero: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// A badge without a count.
+    pub fn dot() -> Self {
+        Self {
+            count: None,
+            ..Self::new(0)
+        }
+    }
+
+    /// Show the badge when its count is zero, instead of hiding it.
+    pub fn show_zero(mut self) -> Self {
+        self.show_zero = true;
+        self
+    }
+
+    /// Whether there is anything to show.
+    pub(crate) fn visible(&self) -> bool {
+        self.show_zero || self.count != Some(0)
+    }
+
+    /// The layout that places the badge over the corner of the Node it is attached to.
+    pub(crate) fn layout(&self) -> Layout {
+        let offset: f32 = self.style_val("offset").unwrap().f32();
+        Layout {
+            position_type: PositionType::Absolute,
+            z_index_increment: 100.0,
+            position: Rect {
+                top: Dimension::Px(-offset as f64),
+                right: Dimension::Px(-offset as f64),
+                ..Rect::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl From<usize> for Badge {
+    fn from(count: usize) -> Self {
+        Self::new(count)
+    }
+}
+
+impl Component for Badge {
+    fn view(&self) -> Option<Node> {
+        if !self.visible() {
+            return None;
+        }
+        let background_color: Color = self.style_val("background_color").into();
+        let count = match self.count {
+            Some(count) => count,
+            None => {
+                let size: f32 = self.style_val("dot_size").unwrap().f32();
+                return Some(node!(
+                    super::RoundedRect::new(background_color, size / 2.0),
+                    lay!(size: size!(size))
+                ));
+            }
+        };
+        let size: f32 = self.style_val("size").unwrap().f32();
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+
+        Some(
+            node!(
+                super::RoundedRect::new(background_color, size / 2.0),
+                lay!(
+                    size: size!(Auto, size),
+                    min_size: size!(size),
+                    padding: rect!(Auto, padding),
+                    cross_alignment: Alignment::Center,
+                    axis_alignment: Alignment::Center,
+                )
+            )
+            .push(node!(super::Text::new(txt!(count.to_string()))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", self.style_val("text_color").unwrap())
+                .style("h_alignment", HorizontalPosition::Center)
+                .maybe_style("font", self.style_val("font")))),
+        )
+    }
+}
@@ -1,21 +1,56 @@
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use super::ToolTip;
+use crate::accessibility::{AccessibilityInfo, Role};
 use crate::base_types::*;
 use crate::component::{Component, Message};
 use crate::event;
 use crate::font_cache::TextSegment;
-use crate::layout::*;
-use crate::style::{HorizontalPosition, Styled};
+use crate::style::{HorizontalPosition, Interaction, Styled};
 use crate::{node, Node};
 use lemna_macros::{component, state_component_impl};
 
 #[derive(Debug, Default)]
 struct ButtonState {
-    hover: bool,
-    pressed: bool,
-    tool_tip_open: Option<Point>,
-    hover_start: Option<Instant>,
+    interaction: Interaction,
+    pressed_at: Option<Instant>,
+    repeats: usize,
+}
+
+/// Settings for a [`Button`] that repeatedly fires its `on_click` while it is held down, e.g. for steppers. See [`Button#method.repeat`].
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonRepeat {
+    /// How long the button must be held before it starts repeating. This is also the interval between the first two repeats.
+    pub delay: Duration,
+    /// The shortest interval that repeats will accelerate to.
+    pub min_interval: Duration,
+}
+
+impl ButtonRepeat {
+    /// Each interval between repeats is this fraction of the last.
+    const ACCELERATION: f32 = 0.75;
+
+    /// The number of times the button will have repeated after being held for `held`, not counting the initial press.
+    fn repeats_within(&self, held: Duration) -> usize {
+        let min_interval = self.min_interval.max(Duration::from_millis(1));
+        let mut interval = self.delay.max(min_interval);
+        let mut t = interval;
+        let mut n = 0;
+        while t <= held {
+            n += 1;
+            interval = interval.mul_f32(Self::ACCELERATION).max(min_interval);
+            t += interval;
+        }
+        n
+    }
+}
+
+impl Default for ButtonRepeat {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            min_interval: Duration::from_millis(50),
+        }
+    }
 }
 
 #[component(State = "ButtonState", Styled, Internal)]
@@ -23,6 +58,7 @@ pub struct Button {
     pub label: Vec<TextSegment>,
     pub on_click: Option<Box<dyn Fn() -> Message + Send + Sync>>,
     pub tool_tip: Option<String>,
+    pub repeat: Option<ButtonRepeat>,
 }
 
 impl std::fmt::Debug for Button {
@@ -39,9 +75,10 @@ impl Button {
             label,
             on_click: None,
             tool_tip: None,
+            repeat: None,
             state: Some(ButtonState::default()),
             dirty: false,
-            class: Default::default(),
+            classes: Default::default(),
             style_overrides: Default::default(),
         }
     }
@@ -51,32 +88,58 @@ impl Button {
         self
     }
 
-    pub fn tool_tip(mut self, t: String) -> Self {
-        self.tool_tip = Some(t);
+    /// Show a [`ToolTip`][super::ToolTip] with the text `t` when the Button is hovered over or focused with the keyboard.
+    pub fn tooltip(mut self, t: impl Into<String>) -> Self {
+        self.tool_tip = Some(t.into());
         self
     }
+
+    /// An alias of [`Button#method.tooltip`].
+    pub fn tool_tip(self, t: String) -> Self {
+        self.tooltip(t)
+    }
+
+    /// Fire `on_click` as soon as the button is pressed, then again at an accelerating interval for as long as it is held down.
+    pub fn repeat(mut self, repeat: ButtonRepeat) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+
+    /// The interaction state, which is disabled when the Button is within a [disabled][Node#method.disabled] Node.
+    fn interaction(&self) -> Interaction {
+        Interaction {
+            disabled: crate::subtree_disabled(),
+            ..self.state_ref().interaction
+        }
+    }
+
+    fn background_color(&self) -> Color {
+        self.interactive_style_val("background_color", self.interaction())
+            .into()
+    }
 }
 
 #[state_component_impl(ButtonState)]
 impl Component for Button {
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Button).name(AccessibilityInfo::text(&self.label)))
+    }
+
     fn view(&self) -> Option<Node> {
         let radius: f32 = self.style_val("radius").unwrap().f32();
         let padding: f64 = self.style_val("padding").unwrap().into();
-        let active_color: Color = self.style_val("active_color").into();
-        let highlight_color: Color = self.style_val("highlight_color").into();
-        let background_color: Color = self.style_val("background_color").into();
-        let border_color: Color = self.style_val("border_color").into();
-        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let interaction = self.interaction();
+        let border_color: Color = self
+            .interactive_style_val("border_color", interaction)
+            .into();
+        let border_width: f32 = self
+            .interactive_style_val("border_width", interaction)
+            .unwrap()
+            .f32();
 
-        let mut base = node!(
+        let base = node!(
             super::RoundedRect {
-                background_color: if self.state_ref().pressed {
-                    active_color
-                } else if self.state_ref().hover {
-                    highlight_color
-                } else {
-                    background_color
-                },
+                background_color: self.background_color(),
                 border_color,
                 border_width,
                 radius: (radius, radius, radius, radius),
@@ -91,35 +154,29 @@ impl Component for Button {
         )
         .push(node!(super::Text::new(self.label.clone())
             .style("size", self.style_val("font_size").unwrap())
-            .style("color", self.style_val("text_color").unwrap())
+            .style(
+                "color",
+                self.interactive_style_val("text_color", interaction)
+                    .unwrap()
+            )
             .style("h_alignment", HorizontalPosition::Center)
             .maybe_style("font", self.style_val("font"))));
 
-        if let (Some(p), Some(tt)) = (self.state_ref().tool_tip_open, self.tool_tip.as_ref()) {
-            base = base.push(node!(
-                ToolTip::new(tt.clone()),
-                lay!(position_type: PositionType::Absolute,
-                     z_index_increment: 1000.0,
-                     position: (p + ToolTip::MOUSE_OFFSET).into(),
-                ),
-            ));
-        }
-
         Some(base)
     }
 
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tool_tip.as_deref()
+    }
+
     fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
-        let dirty = self.dirty;
-        self.state_mut().hover_start = Some(Instant::now());
-        // This state mutation should not trigger a redraw. We use whatever value was previously set.
-        self.dirty = dirty;
         event.stop_bubbling();
     }
 
     fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
-        self.state_mut().hover = true;
+        self.state_mut().interaction.mouse_enter();
         if let Some(w) = crate::current_window() {
-            w.set_cursor("PointingHand");
+            w.set_cursor(crate::Cursor::PointingHand);
         }
     }
 
@@ -131,29 +188,124 @@ impl Component for Button {
     }
 
     fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
-        if self.state_ref().hover_start.is_some()
-            && self
-                .state_ref()
-                .hover_start
-                .map(|s| s.elapsed().as_millis() > ToolTip::DELAY)
-                .unwrap_or(false)
-            && self.state_ref().tool_tip_open.is_none()
+        if let (Some(repeat), Some(pressed_at), Some(f)) =
+            (self.repeat, self.state_ref().pressed_at, &self.on_click)
         {
-            self.state_mut().tool_tip_open = Some(event.relative_logical_position());
+            let repeats = repeat.repeats_within(pressed_at.elapsed());
+            for _ in self.state_ref().repeats..repeats {
+                event.emit(f());
+            }
+            let dirty = self.dirty;
+            self.state_mut().repeats = repeats;
+            // Repeating should not trigger a redraw by itself
+            self.dirty = dirty;
         }
     }
 
-    fn on_mouse_down(&mut self, _event: &mut event::Event<event::MouseDown>) {
-        self.state_mut().pressed = true;
+    fn on_mouse_down(&mut self, event: &mut event::Event<event::MouseDown>) {
+        self.state_mut().interaction.mouse_down();
+        if let (Some(_), Some(f)) = (self.repeat, &self.on_click) {
+            event.emit(f());
+            self.state_mut().pressed_at = Some(Instant::now());
+            self.state_mut().repeats = 0;
+        }
     }
 
     fn on_mouse_up(&mut self, _event: &mut event::Event<event::MouseUp>) {
-        self.state_mut().pressed = false;
+        self.state_mut().interaction.mouse_up();
+        self.state_mut().pressed_at = None;
     }
 
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        // Repeating buttons fire on press instead
+        if self.repeat.is_some() {
+            return;
+        }
         if let Some(f) = &self.on_click {
             event.emit(f());
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::MouseButton;
+
+    #[test]
+    fn test_hover_background() {
+        let mut button = Button::new(vec!["Button".into()]);
+        assert_eq!(
+            button.background_color(),
+            button.style_val("background_color").into()
+        );
+
+        let mut event = event::Event::new(event::MouseEnter, &event::EventCache::new(1.0));
+        button.on_mouse_enter(&mut event);
+        let hover: Color = button.style_val("background_color:hover").into();
+        assert_eq!(button.background_color(), hover);
+        assert_ne!(hover, button.style_val("background_color").into());
+    }
+
+    #[test]
+    fn test_repeat_accelerates() {
+        let repeat = ButtonRepeat {
+            delay: Duration::from_millis(400),
+            min_interval: Duration::from_millis(100),
+        };
+        assert_eq!(repeat.repeats_within(Duration::from_millis(399)), 0);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(400)), 1);
+
+        // Find the times at which each repeat fires
+        let fire_times: Vec<u64> = (0..2000)
+            .filter(|ms| {
+                repeat.repeats_within(Duration::from_millis(*ms))
+                    > repeat.repeats_within(Duration::from_millis(ms.saturating_sub(1)))
+            })
+            .collect();
+        assert!(fire_times.len() > 3);
+        let intervals: Vec<u64> = fire_times.windows(2).map(|w| w[1] - w[0]).collect();
+        assert!(intervals[0] < 400);
+        assert!(intervals.windows(2).all(|w| w[1] <= w[0]));
+        assert!(intervals.iter().all(|i| *i >= 100));
+        assert_eq!(*intervals.last().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_repeat_while_held() {
+        let cache = event::EventCache::new(1.0);
+        let mut button = Button::new(vec!["+".into()])
+            .on_click(Box::new(|| msg!(())))
+            .repeat(ButtonRepeat {
+                delay: Duration::from_millis(400),
+                min_interval: Duration::from_millis(100),
+            });
+        let hold_for = |button: &mut Button, ms: u64| {
+            *button.state_mut().pressed_at.as_mut().unwrap() -= Duration::from_millis(ms);
+            let mut event = event::Event::new(event::Tick, &cache);
+            button.on_tick(&mut event);
+            event.messages.len()
+        };
+
+        // Pressing fires once
+        let mut event = event::Event::new(event::MouseDown(MouseButton::Left), &cache);
+        button.on_mouse_down(&mut event);
+        assert_eq!(event.messages.len(), 1);
+
+        // Nothing more until the delay has passed
+        assert_eq!(hold_for(&mut button, 300), 0);
+        assert_eq!(hold_for(&mut button, 100), 1);
+        // Then it keeps firing, only for the repeats it has not already fired
+        assert_eq!(hold_for(&mut button, 0), 0);
+        assert!(hold_for(&mut button, 1000) > 1);
+
+        // Releasing stops it
+        button.on_mouse_up(&mut event::Event::new(
+            event::MouseUp(MouseButton::Left),
+            &cache,
+        ));
+        let mut event = event::Event::new(event::Tick, &cache);
+        button.on_tick(&mut event);
+        assert!(event.messages.is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+use std::hash::Hash;
+use std::sync::OnceLock;
+
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher};
+use crate::font_cache::TextSegment;
+use crate::layout::*;
+use crate::style::{HorizontalPosition, Styled};
+use crate::{node, Node};
+use lemna_macros::{component, state_component_impl};
+
+/// The theme used when the `theme` style parameter does not name one of syntect's default themes.
+const DEFAULT_THEME: &str = "InspiredGitHub";
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
+fn color(c: syntect::highlighting::Color) -> Color {
+    Color::new(
+        c.r as f32 / 255.0,
+        c.g as f32 / 255.0,
+        c.b as f32 / 255.0,
+        c.a as f32 / 255.0,
+    )
+}
+
+/// A run of source with a single color.
+#[derive(Debug, Clone, PartialEq)]
+struct Span {
+    text: String,
+    color: Color,
+}
+
+#[derive(Debug, Default)]
+struct Highlighted {
+    lines: Vec<Vec<Span>>,
+    background: Option<Color>,
+}
+
+/// Split `source` into lines of colored spans. `language` is a name or file extension that syntect knows, e.g. `"rs"` or `"Rust"`. Unknown languages are shown as plain text.
+fn highlight(source: &str, language: &str, theme: &str) -> Highlighted {
+    let syntax_set = syntax_set();
+    let syntax = syntax_set
+        .find_syntax_by_token(language)
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+    let themes = &theme_set().themes;
+    let theme = themes.get(theme).unwrap_or(&themes[DEFAULT_THEME]);
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let lines = LinesWithEndings::from(source)
+        .map(|line| {
+            highlighter
+                .highlight_line(line, syntax_set)
+                .unwrap_or_default()
+                .drain(..)
+                .filter_map(|(style, text)| {
+                    let text = text.trim_end_matches(['\n', '\r']);
+                    (!text.is_empty()).then(|| Span {
+                        text: text.to_string(),
+                        color: color(style.foreground),
+                    })
+                })
+                .collect()
+        })
+        .collect();
+
+    Highlighted {
+        lines,
+        background: theme.settings.background.map(color),
+    }
+}
+
+#[derive(Debug, Default)]
+struct CodeViewState {
+    highlighted: Highlighted,
+}
+
+/// Read-only, syntax highlighted source code, with optional line numbers. Lines are not wrapped: The view scrolls horizontally instead.
+///
+/// Highlighting is performed by [syntect](https://docs.rs/syntect), using the theme named by the `theme` style parameter (one of syntect's default themes). The background is the theme's, unless one is given with the `background_color` style parameter. Set `font` to the name of a monospace font that has been added to the UI.
+#[component(State = "CodeViewState", Styled, Internal)]
+#[derive(Debug)]
+pub struct CodeView {
+    pub source: String,
+    /// A name or file extension that syntect knows, e.g. `"rs"` or `"Rust"`.
+    pub language: String,
+    pub line_numbers: bool,
+}
+
+impl CodeView {
+    pub fn new(source: impl Into<String>, language: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            language: language.into(),
+            line_numbers: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+            state: Some(CodeViewState::default()),
+            dirty: false,
+        }
+    }
+
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    fn highlight(&mut self) {
+        let theme = self.style_val("theme").unwrap();
+        self.state_mut().highlighted = highlight(&self.source, &self.language, theme.str());
+    }
+}
+
+#[state_component_impl(CodeViewState)]
+impl Component for CodeView {
+    fn init(&mut self) {
+        self.highlight();
+    }
+
+    fn new_props(&mut self) {
+        self.highlight();
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.source.hash(hasher);
+        self.language.hash(hasher);
+        self.style_val("theme")
+            .map(|t| t.str().to_string())
+            .hash(hasher);
+    }
+
+    fn view(&self) -> Option<Node> {
+        let font_size: f32 = self.style_val("font_size").unwrap().f32();
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let highlighted = &self.state_ref().highlighted;
+        let background: Color = self
+            .style_val("background_color")
+            .map(|c| c.color())
+            .or(highlighted.background)
+            .unwrap_or(Color::WHITE);
+        let font = self.style_val("font");
+        // Wide enough for the digits of the last line number
+        let gutter_width =
+            highlighted.lines.len().to_string().len() as f32 * font_size * 0.6 + padding;
+
+        let mut lines = node!(
+            super::Div::new(),
+            lay!(direction: Column, padding: rect!(padding))
+        );
+        for (i, line) in highlighted.lines.iter().enumerate() {
+            let mut line_node = node!(
+                super::Div::new(),
+                lay!(direction: Row, cross_alignment: Baseline)
+            )
+            .key(i as u64);
+            if self.line_numbers {
+                line_node = line_node.push(node!(
+                    super::Text::new(vec![TextSegment::from((i + 1).to_string())])
+                        .style("size", font_size as f64)
+                        .style("color", self.style_val("line_number_color").unwrap())
+                        .style("h_alignment", HorizontalPosition::Right)
+                        .maybe_style("font", font.clone()),
+                    lay!(
+                        size: size!(gutter_width, Auto),
+                        margin: rect!(0.0, 0.0, 0.0, padding),
+                    ),
+                    0
+                ));
+            }
+            if line.is_empty() {
+                // Keep empty lines as tall as the others
+                line_node = line_node.push(node!(
+                    super::Text::new(vec![" ".into()])
+                        .style("size", font_size as f64)
+                        .maybe_style("font", font.clone()),
+                    Layout::default(),
+                    1
+                ));
+            }
+            for (j, span) in line.iter().enumerate() {
+                line_node = line_node.push(node!(
+                    super::Text::new(vec![span.text.clone().into()])
+                        .style("size", font_size as f64)
+                        .style("color", span.color)
+                        .maybe_style("font", font.clone()),
+                    Layout::default(),
+                    j as u64 + 2
+                ));
+            }
+            lines = lines.push(line_node);
+        }
+
+        Some(
+            node!(
+                super::Div::new().bg(background).scroll_x(),
+                lay!(size: size_pct!(100.0, Auto))
+            )
+            .push(lines),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_keywords() {
+        let highlighted = highlight("fn main() {\n    let x = 1;\n}\n", "rs", DEFAULT_THEME);
+        assert_eq!(highlighted.lines.len(), 3);
+
+        let color_of = |text: &str| {
+            highlighted
+                .lines
+                .iter()
+                .flatten()
+                .find(|s| s.text.trim() == text)
+                .unwrap_or_else(|| panic!("No span {:?} in {:?}", text, highlighted.lines))
+                .color
+        };
+        // Keywords are colored differently from identifiers
+        assert_ne!(color_of("let"), color_of("x"));
+        assert_ne!(color_of("fn"), color_of("x"));
+        // Spans cover the whole line
+        let second_line: String = highlighted.lines[1]
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect();
+        assert_eq!(second_line, "    let x = 1;");
+    }
+}
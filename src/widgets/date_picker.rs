@@ -0,0 +1,468 @@
+use std::hash::Hash;
+
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, Message};
+use crate::event;
+use crate::input::Key;
+use crate::layout::*;
+use crate::style::{HorizontalPosition, Styled};
+use crate::{node, txt, Node};
+use lemna_macros::{component, state_component_impl};
+
+/// The format used to display and parse dates in the text field of a [`DatePicker`].
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug)]
+enum DatePickerMessage {
+    OpenClose,
+    Close,
+    Select(NaiveDate),
+    Entered(String),
+}
+
+#[derive(Debug)]
+enum CalendarMessage {
+    /// Show the month this many months away
+    Month(i32),
+    Highlight(NaiveDate),
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+//
+// DatePicker
+// The top-level, public component
+#[derive(Debug, Default)]
+struct DatePickerState {
+    open: bool,
+    selected: Option<NaiveDate>,
+}
+
+/// A text field for entering dates, that opens a calendar to pick them from.
+///
+/// Dates can be typed into the field as `YYYY-MM-DD`, or picked from the calendar with the mouse. Once the calendar has been clicked on, the arrow keys move the highlighted day, PageUp and PageDown move by a month, Enter picks the highlighted day, and Escape closes the calendar.
+#[component(State = "DatePickerState", Styled, Internal)]
+pub struct DatePicker {
+    pub selected: Option<NaiveDate>,
+    on_change: Option<Box<dyn Fn(NaiveDate) -> Message + Send + Sync>>,
+}
+
+impl std::fmt::Debug for DatePicker {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DatePicker")
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+impl DatePicker {
+    pub fn new(selected: Option<NaiveDate>) -> Self {
+        Self {
+            selected,
+            on_change: None,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+            state: Some(DatePickerState::default()),
+            dirty: false,
+        }
+    }
+
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(NaiveDate) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    fn select(&mut self, date: NaiveDate) -> Vec<Message> {
+        self.state_mut().selected = Some(date);
+        self.state_mut().open = false;
+        self.on_change.iter().map(|f| f(date)).collect()
+    }
+}
+
+#[state_component_impl(DatePickerState)]
+impl Component for DatePicker {
+    fn view(&self) -> Option<Node> {
+        let selected = self.state_ref().selected;
+
+        let mut text_box = super::TextBox::new(selected.map(|d| d.format(DATE_FORMAT).to_string()))
+            .on_commit(Box::new(|s| {
+                Box::new(DatePickerMessage::Entered(s.to_string()))
+            }));
+        let mut button = super::Button::new(txt!(tr!("...")))
+            .on_click(Box::new(|| Box::new(DatePickerMessage::OpenClose)));
+        *text_box.classes_mut() = self.classes.clone();
+        *button.classes_mut() = self.classes.clone();
+
+        let mut base = node!(super::Div::new(), lay!(direction: Direction::Column)).push(
+            node!(
+                super::Div::new(),
+                lay!(size: size_pct!(100.0), direction: Direction::Row)
+            )
+            .push(node!(text_box, lay!(size: size_pct!(80.0, 100.0))))
+            .push(node!(button, lay!(size: size_pct!(20.0, 100.0)))),
+        );
+        if self.state_ref().open {
+            let today = Local::now().date_naive();
+            base = base.push(node!(
+                Calendar {
+                    selected,
+                    today,
+                    style_overrides: self.style_overrides.clone(),
+                    classes: self.classes.clone(),
+                    state: Some(CalendarState::new(selected.unwrap_or(today))),
+                    dirty: false,
+                },
+                lay!(position_type: PositionType::Absolute, z_index_increment: 1000.0),
+                1
+            ));
+        }
+        Some(base)
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.selected.hash(hasher);
+    }
+
+    fn init(&mut self) {
+        self.state_mut().selected = self.selected;
+    }
+
+    fn new_props(&mut self) {
+        self.state_mut().selected = self.selected;
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<DatePickerMessage>() {
+            Some(DatePickerMessage::OpenClose) => {
+                self.state_mut().open = !self.state_ref().open;
+                vec![]
+            }
+            Some(DatePickerMessage::Close) => {
+                self.state_mut().open = false;
+                vec![]
+            }
+            Some(DatePickerMessage::Select(date)) => self.select(*date),
+            Some(DatePickerMessage::Entered(s)) => {
+                match NaiveDate::parse_from_str(s.trim(), DATE_FORMAT) {
+                    Ok(date) => self.select(date),
+                    Err(_) => vec![],
+                }
+            }
+            None => vec![message],
+        }
+    }
+}
+
+//
+// Calendar
+// Visible after opening: A grid of the days of a month
+#[derive(Debug)]
+struct CalendarState {
+    /// The first day of the month being shown
+    month: NaiveDate,
+    highlighted: NaiveDate,
+}
+
+impl CalendarState {
+    fn new(date: NaiveDate) -> Self {
+        Self {
+            month: first_of_month(date),
+            highlighted: date,
+        }
+    }
+
+    /// Highlight `date`, showing its month.
+    fn highlight(&mut self, date: NaiveDate) {
+        self.month = first_of_month(date);
+        self.highlighted = date;
+    }
+
+    fn move_days(&mut self, days: i64) {
+        if let Some(date) = self.highlighted.checked_add_signed(Duration::days(days)) {
+            self.highlight(date);
+        }
+    }
+
+    /// Move to the same day, `months` away. Days that don't exist in the new month are moved to its last day.
+    fn move_months(&mut self, months: i32) {
+        let date = if months < 0 {
+            self.highlighted
+                .checked_sub_months(Months::new(months.unsigned_abs()))
+        } else {
+            self.highlighted
+                .checked_add_months(Months::new(months as u32))
+        };
+        if let Some(date) = date {
+            self.highlight(date);
+        }
+    }
+
+    /// The days shown in the grid: Six full weeks, starting on the Monday on or before the first of the month.
+    fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let offset = self.month.weekday().num_days_from_monday() as i64;
+        let start = self.month - Duration::days(offset);
+        (0..42).map(move |i| start + Duration::days(i))
+    }
+}
+
+#[component(State = "CalendarState", Styled = "DatePicker", Internal)]
+#[derive(Debug)]
+struct Calendar {
+    selected: Option<NaiveDate>,
+    today: NaiveDate,
+}
+
+#[state_component_impl(CalendarState)]
+impl Component for Calendar {
+    fn view(&self) -> Option<Node> {
+        let background_color: Color = self.style_val("background_color").into();
+        let border_color: Color = self.style_val("border_color").into();
+        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let padding: f64 = self.style_val("padding").unwrap().into();
+        let day_size: f64 = self.style_val("day_size").unwrap().into();
+        let month = self.state_ref().month;
+
+        let text = |s: String, color: Color| {
+            super::Text::new(txt!(s))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", color)
+                .style("h_alignment", HorizontalPosition::Center)
+                .maybe_style("font", self.style_val("font"))
+        };
+        let text_color: Color = self.style_val("text_color").into();
+
+        let mut header = node!(
+            super::Div::new(),
+            lay!(
+                size: size!(day_size * 7.0, Auto),
+                direction: Direction::Row,
+                axis_alignment: Alignment::Center,
+                cross_alignment: Alignment::Center,
+            )
+        );
+        for (label, months) in [("<", -1), (">", 1)] {
+            let button = node!(
+                super::Button::new(txt!(label))
+                    .on_click(Box::new(move || Box::new(CalendarMessage::Month(months)))),
+                lay!(size: size!(day_size)),
+                months as u64
+            );
+            if months > 0 {
+                header = header.push(node!(
+                    text(month.format("%B %Y").to_string(), text_color),
+                    lay!(size: size!(day_size * 5.0, Auto)),
+                ));
+            }
+            header = header.push(button);
+        }
+
+        let mut grid = node!(
+            super::Div::new(),
+            lay!(size: size!(day_size * 7.0, Auto), direction: Direction::Row, wrap: true)
+        );
+        for (i, day) in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+            .iter()
+            .enumerate()
+        {
+            grid = grid.push(node!(
+                super::Div::new(),
+                lay!(size: size!(day_size), axis_alignment: Alignment::Center, cross_alignment: Alignment::Center),
+                i as u64
+            ).push(node!(text(tr!(day), text_color))));
+        }
+        for date in self.state_ref().days() {
+            grid = grid.push(node!(
+                DayCell {
+                    date,
+                    in_month: date.month() == month.month(),
+                    selected: Some(date) == self.selected,
+                    highlighted: date == self.state_ref().highlighted,
+                    today: date == self.today,
+                    style_overrides: self.style_overrides.clone(),
+                    classes: self.classes.clone(),
+                },
+                lay!(size: size!(day_size)),
+                date.num_days_from_ce() as u64
+            ));
+        }
+
+        Some(
+            node!(
+                super::Div::new()
+                    .bg(background_color)
+                    .border(border_color, border_width),
+                lay!(padding: rect!(padding), direction: Direction::Column)
+            )
+            .push(header)
+            .push(grid),
+        )
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<CalendarMessage>() {
+            Some(CalendarMessage::Month(months)) => {
+                self.state_mut().move_months(*months);
+                vec![]
+            }
+            Some(CalendarMessage::Highlight(date)) => {
+                self.state_mut().highlighted = *date;
+                vec![]
+            }
+            None => vec![message],
+        }
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.focus();
+        event.stop_bubbling();
+    }
+
+    fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
+        event.stop_bubbling();
+    }
+
+    fn on_blur(&mut self, event: &mut event::Event<event::Blur>) {
+        event.emit(Box::new(DatePickerMessage::Close));
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        match event.input.0 {
+            Key::Left => self.state_mut().move_days(-1),
+            Key::Right => self.state_mut().move_days(1),
+            Key::Up => self.state_mut().move_days(-7),
+            Key::Down => self.state_mut().move_days(7),
+            Key::PageUp => self.state_mut().move_months(-1),
+            Key::PageDown => self.state_mut().move_months(1),
+            Key::Return | Key::NumPadEnter => event.emit(Box::new(DatePickerMessage::Select(
+                self.state_ref().highlighted,
+            ))),
+            Key::Escape => event.emit(Box::new(DatePickerMessage::Close)),
+            _ => (),
+        }
+    }
+}
+
+//
+// DayCell
+// An individual day within a Calendar
+#[component(Styled = "DatePicker", Internal)]
+#[derive(Debug)]
+struct DayCell {
+    date: NaiveDate,
+    in_month: bool,
+    selected: bool,
+    highlighted: bool,
+    today: bool,
+}
+
+impl Component for DayCell {
+    fn view(&self) -> Option<Node> {
+        let mut div = super::Div::new();
+        if self.selected {
+            div = div.bg(self.style_val("selected_color"));
+        } else if self.highlighted {
+            div = div.bg(self.style_val("highlight_color"));
+        }
+        if self.today {
+            div = div.border(self.style_val("today_color"), 1.0);
+        }
+        let text_color = if self.selected {
+            self.style_val("selected_text_color")
+        } else if self.in_month {
+            self.style_val("text_color")
+        } else {
+            self.style_val("outside_text_color")
+        };
+
+        Some(
+            node!(
+                div,
+                lay!(
+                    size: size_pct!(100.0),
+                    axis_alignment: Alignment::Center,
+                    cross_alignment: Alignment::Center
+                )
+            )
+            .push(node!(super::Text::new(txt!(self.date.day().to_string()))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", text_color.unwrap())
+                .style("h_alignment", HorizontalPosition::Center)
+                .maybe_style("font", self.style_val("font")))),
+        )
+    }
+
+    fn on_mouse_enter(&mut self, event: &mut event::Event<event::MouseEnter>) {
+        event.emit(Box::new(CalendarMessage::Highlight(self.date)));
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.emit(Box::new(DatePickerMessage::Select(self.date)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_select_date() {
+        let mut picker = DatePicker::new(Some(date(2024, 1, 31))).on_change(Box::new(|d| msg!(d)));
+        picker.init();
+        picker.update(Box::new(DatePickerMessage::OpenClose));
+        assert!(picker.state_ref().open);
+
+        // Navigate to March
+        let mut calendar = CalendarState::new(picker.state_ref().selected.unwrap());
+        calendar.move_months(1);
+        assert_eq!(calendar.highlighted, date(2024, 2, 29));
+        calendar.move_months(1);
+        assert_eq!(calendar.month, date(2024, 3, 1));
+        let day = calendar.days().find(|d| *d == date(2024, 3, 15)).unwrap();
+
+        let messages = picker.update(Box::new(DatePickerMessage::Select(day)));
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].downcast_ref::<NaiveDate>(),
+            Some(&date(2024, 3, 15))
+        );
+        assert!(!picker.state_ref().open);
+
+        // Typed dates are parsed
+        let messages = picker.update(Box::new(DatePickerMessage::Entered("2023-12-25".into())));
+        assert_eq!(
+            messages[0].downcast_ref::<NaiveDate>(),
+            Some(&date(2023, 12, 25))
+        );
+        assert!(picker
+            .update(Box::new(DatePickerMessage::Entered("soon".into())))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_keyboard_navigation() {
+        let mut calendar = CalendarState::new(date(2024, 1, 31));
+        // Right arrow crosses into the next month
+        calendar.move_days(1);
+        assert_eq!(calendar.highlighted, date(2024, 2, 1));
+        assert_eq!(calendar.month, date(2024, 2, 1));
+        // Up arrow moves back a week, into the previous month
+        calendar.move_days(-7);
+        assert_eq!(calendar.highlighted, date(2024, 1, 25));
+        assert_eq!(calendar.month, date(2024, 1, 1));
+
+        // The grid starts on the Monday before the first
+        let days: Vec<NaiveDate> = calendar.days().collect();
+        assert_eq!(days.len(), 42);
+        assert_eq!(days[0], date(2024, 1, 1));
+        let feb = CalendarState::new(date(2024, 2, 10));
+        assert_eq!(feb.days().next(), Some(date(2024, 1, 29)));
+    }
+}
@@ -1,15 +1,43 @@
-use std::hash::Hash;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 use crate::base_types::*;
 use crate::component::{Component, ComponentHasher, RenderContext};
 use crate::event;
 use crate::layout::*;
-use crate::render::{renderables::Rect, Renderable};
+use crate::render::{
+    renderables::{Raster, RasterData, RasterQuad, Rect},
+    Renderable,
+};
 use crate::style::{HorizontalPosition, StyleVal, Styled, VerticalPosition};
 
 use lemna_macros::{component, state_component_impl};
 
 const MIN_BAR_SIZE: f32 = 10.0;
+/// Number of bands used to draw the gradient of a scroll shadow
+const SHADOW_STEPS: usize = 4;
+/// The most quads that a background image is drawn with. Smaller tiles are drawn at a multiple of their size.
+const MAX_TILES: usize = 4096;
+
+/// Which edges of a scrollable [`Div`] have content clipped beyond them.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+struct ScrollShadows {
+    top: bool,
+    right: bool,
+    bottom: bool,
+    left: bool,
+}
+
+impl ScrollShadows {
+    fn new(scroll_position: Point, max_position: Scale, x: bool, y: bool) -> Self {
+        Self {
+            top: y && scroll_position.y > 0.0,
+            bottom: y && scroll_position.y < max_position.height,
+            left: x && scroll_position.x > 0.0,
+            right: x && scroll_position.x < max_position.width,
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct DivState {
@@ -24,12 +52,153 @@ pub struct DivState {
     scaled_scroll_bar_width: f32,
 }
 
+/// How the background image of a [`Div`] fills it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFill {
+    /// Tile the image across and down, from the top left corner.
+    #[default]
+    Repeat,
+    /// Tile the image across the top.
+    RepeatX,
+    /// Tile the image down the left side.
+    RepeatY,
+    /// Draw the image once, in the top left corner.
+    NoRepeat,
+    /// Scale the image to cover the Div, keeping its aspect ratio, and crop what doesn't fit.
+    Cover,
+    /// Scale the image to fit in the Div, keeping its aspect ratio, and center it.
+    Contain,
+}
+
+/// An image drawn behind the children of a [`Div`]. See [`Div#bg_image`][Div#method.bg_image].
+#[derive(Debug)]
+pub struct BackgroundImage {
+    data: Option<RasterData>,
+    size: PixelSize,
+    // The hash of the image's data, so that it is only uploaded when it changes
+    content_hash: u64,
+    pub fill: ImageFill,
+    pub tint: Option<Color>,
+}
+
+impl BackgroundImage {
+    pub fn new<D: Into<RasterData>>(data: D, size: PixelSize) -> Self {
+        let data = data.into();
+        let mut hasher = DefaultHasher::new();
+        <&[u8]>::from(&data).hash(&mut hasher);
+        Self {
+            data: Some(data),
+            size,
+            content_hash: hasher.finish(),
+            fill: ImageFill::default(),
+            tint: None,
+        }
+    }
+}
+
+/// The quads that draw an image of `image` physical size over an `area`, as `fill` describes.
+pub(super) fn image_quads(fill: ImageFill, image: Scale, area: Scale) -> Vec<RasterQuad> {
+    if image.width <= 0.0 || image.height <= 0.0 || area.width <= 0.0 || area.height <= 0.0 {
+        return vec![];
+    }
+    let (repeat_x, repeat_y) = match fill {
+        ImageFill::Cover | ImageFill::Contain => {
+            let (x_scale, y_scale) = (area.width / image.width, area.height / image.height);
+            let scale = if fill == ImageFill::Cover {
+                x_scale.max(y_scale)
+            } else {
+                x_scale.min(y_scale)
+            };
+            let drawn = Scale::new(image.width * scale, image.height * scale);
+            let pos = Point::new(
+                (area.width - drawn.width) / 2.0,
+                (area.height - drawn.height) / 2.0,
+            );
+            return clipped_quad(pos, drawn, area).into_iter().collect();
+        }
+        ImageFill::Repeat => (true, true),
+        ImageFill::RepeatX => (true, false),
+        ImageFill::RepeatY => (false, true),
+        ImageFill::NoRepeat => (false, false),
+    };
+    let count = |tile: Scale| {
+        (
+            if repeat_x {
+                (area.width / tile.width).ceil() as usize
+            } else {
+                1
+            },
+            if repeat_y {
+                (area.height / tile.height).ceil() as usize
+            } else {
+                1
+            },
+        )
+    };
+    let mut tile = image;
+    let (mut cols, mut rows) = count(tile);
+    while cols * rows > MAX_TILES {
+        tile = Scale::new(tile.width * 2.0, tile.height * 2.0);
+        (cols, rows) = count(tile);
+    }
+    let mut quads = vec![];
+    for row in 0..rows {
+        for col in 0..cols {
+            let pos = Point::new(col as f32 * tile.width, row as f32 * tile.height);
+            quads.extend(clipped_quad(pos, tile, area));
+        }
+    }
+    quads
+}
+
+/// A quad drawing a whole image at `pos` with `size`, clipped to an `area` at the origin.
+fn clipped_quad(pos: Point, size: Scale, area: Scale) -> Option<RasterQuad> {
+    let (x0, y0) = (pos.x.max(0.0), pos.y.max(0.0));
+    let (x1, y1) = (
+        (pos.x + size.width).min(area.width),
+        (pos.y + size.height).min(area.height),
+    );
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+    Some(RasterQuad {
+        dest: (Point::new(x0, y0), Point::new(x1, y1)),
+        source: (
+            Point::new((x0 - pos.x) / size.width, (y0 - pos.y) / size.height),
+            Point::new((x1 - pos.x) / size.width, (y1 - pos.y) / size.height),
+        ),
+    })
+}
+
+/// A border along one side of a [`Div`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Border {
+    pub color: Color,
+    pub width: f32,
+}
+
+/// The borders of each side of a [`Div`]. Sides that are `None` have no border of their own.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Borders {
+    pub top: Option<Border>,
+    pub right: Option<Border>,
+    pub bottom: Option<Border>,
+    pub left: Option<Border>,
+}
+
+/// A container, with an optional background and border, that may scroll its children. The background and border can also be given by the `background_color`, `border_color` and `border_width` style parameters, e.g. through a [`StyleSheet`][crate::StyleSheet] class.
+///
+/// Each side can also have a border of its own, e.g. a line under each row of a table, with [`#border_top`][Div#method.border_top] and friends, or the `border_<side>_color` and `border_<side>_width` style parameters. These are drawn over the background, inside the edges of the Div, so they do not change its layout.
+///
+/// A Div can also have a background image, e.g. a texture, with [`#bg_image`][Div#method.bg_image]. It is drawn over the background color, and under the borders and children.
 #[component(State = "DivState", Styled = "Scroll", Internal)]
 #[derive(Debug, Default)]
 pub struct Div {
     pub background: Option<Color>,
     pub border_color: Option<Color>,
     pub border_width: Option<f32>,
+    pub borders: Borders,
+    pub background_image: Option<BackgroundImage>,
 }
 
 impl Div {
@@ -42,12 +211,68 @@ impl Div {
         self
     }
 
+    /// Draw the 8 bit rgba image `data`, `size` pixels large, behind the children of the Div. Each pixel of the image covers a logical pixel. It is tiled over the Div, unless it is given another [`ImageFill`] with [`#bg_image_fill`][Div#method.bg_image_fill].
+    ///
+    /// The image is only uploaded to the GPU when its data changes, so it is fine to give the same image to the Div every time it is viewed. It is clipped to the rectangle inside the Div's border, but not to rounded corners. Like other rasters, it is not drawn by [`UI#capture`][crate::UI#method.capture].
+    pub fn bg_image<D: Into<RasterData>>(mut self, data: D, size: PixelSize) -> Self {
+        self.background_image = Some(BackgroundImage::new(data, size));
+        self
+    }
+
+    /// How the background image fills the Div. Does nothing without a [`#bg_image`][Div#method.bg_image].
+    pub fn bg_image_fill(mut self, fill: ImageFill) -> Self {
+        if let Some(image) = &mut self.background_image {
+            image.fill = fill;
+        }
+        self
+    }
+
+    /// Multiply the color of each pixel of the background image with `tint`. Does nothing without a [`#bg_image`][Div#method.bg_image].
+    pub fn bg_image_tint<C: Into<Color>>(mut self, tint: C) -> Self {
+        if let Some(image) = &mut self.background_image {
+            image.tint = Some(tint.into());
+        }
+        self
+    }
+
     pub fn border<C: Into<Color>>(mut self, color: C, width: f32) -> Self {
         self.border_color = Some(color.into());
         self.border_width = Some(width);
         self
     }
 
+    pub fn border_top<C: Into<Color>>(mut self, color: C, width: f32) -> Self {
+        self.borders.top = Some(Border {
+            color: color.into(),
+            width,
+        });
+        self
+    }
+
+    pub fn border_right<C: Into<Color>>(mut self, color: C, width: f32) -> Self {
+        self.borders.right = Some(Border {
+            color: color.into(),
+            width,
+        });
+        self
+    }
+
+    pub fn border_bottom<C: Into<Color>>(mut self, color: C, width: f32) -> Self {
+        self.borders.bottom = Some(Border {
+            color: color.into(),
+            width,
+        });
+        self
+    }
+
+    pub fn border_left<C: Into<Color>>(mut self, color: C, width: f32) -> Self {
+        self.borders.left = Some(Border {
+            color: color.into(),
+            width,
+        });
+        self
+    }
+
     pub fn scroll_x(mut self) -> Self {
         self = self.style("x", true);
         self.state = Some(DivState::default());
@@ -60,6 +285,11 @@ impl Div {
         self
     }
 
+    /// Leave `padding` logical pixels between the edges of the Div and the Nodes that are scrolled into view, e.g. so that they are not hidden by a sticky header.
+    pub fn scroll_padding(self, padding: f32) -> Self {
+        self.style("scroll_padding", padding as f64)
+    }
+
     fn x_scrollable(&self) -> bool {
         self.style_val("x").unwrap().into()
     }
@@ -71,6 +301,169 @@ impl Div {
     fn scrollable(&self) -> bool {
         self.x_scrollable() || self.y_scrollable()
     }
+
+    /// The background color, from [`#bg`][Div#method.bg] or else the `background_color` style parameter, e.g. from a class.
+    fn background_color(&self) -> Option<Color> {
+        self.background
+            .or_else(|| self.style_val("background_color").map(|c| c.color()))
+    }
+
+    /// The border color and width, from [`#border`][Div#method.border] or else the `border_color` and `border_width` style parameters.
+    fn border_style(&self) -> Option<(Color, f32)> {
+        let color = self
+            .border_color
+            .or_else(|| self.style_val("border_color").map(|c| c.color()))?;
+        let width = self
+            .border_width
+            .or_else(|| self.style_val("border_width").map(|w| w.f32()))?;
+        Some((color, width))
+    }
+
+    /// The border of one `side`, from [`#borders`][Div#structfield.borders] or else the `border_<side>_color` and `border_<side>_width` style parameters.
+    fn side_border(
+        &self,
+        border: Option<Border>,
+        color_param: &'static str,
+        width_param: &'static str,
+    ) -> Option<Border> {
+        border.or_else(|| {
+            let color = self.style_val(color_param)?.color();
+            let width = self.style_val(width_param)?.f32();
+            Some(Border { color, width })
+        })
+    }
+
+    /// The (top, right, bottom, left) borders.
+    fn side_borders(&self) -> [Option<Border>; 4] {
+        [
+            self.side_border(self.borders.top, "border_top_color", "border_top_width"),
+            self.side_border(
+                self.borders.right,
+                "border_right_color",
+                "border_right_width",
+            ),
+            self.side_border(
+                self.borders.bottom,
+                "border_bottom_color",
+                "border_bottom_width",
+            ),
+            self.side_border(self.borders.left, "border_left_color", "border_left_width"),
+        ]
+    }
+
+    /// A thin rect inside each edge of a Div of `size` that has a border.
+    fn side_border_renderables(&self, size: Scale, scale_factor: f32) -> Vec<Renderable> {
+        let [top, right, bottom, left] = self.side_borders();
+        let mut rs = vec![];
+        let mut edge = |border: Option<Border>, rect: &dyn Fn(f32) -> (f32, f32, Scale)| {
+            if let Some(Border { color, width }) = border {
+                let (x, y, scale) = rect((width * scale_factor.floor()).round());
+                rs.push(Renderable::Rect(Rect::new(
+                    Pos { x, y, z: 0.15 }, // above background
+                    scale,
+                    color,
+                )))
+            }
+        };
+        edge(top, &|w| (0.0, 0.0, Scale::new(size.width, w)));
+        edge(right, &|w| {
+            (size.width - w, 0.0, Scale::new(w, size.height))
+        });
+        edge(bottom, &|w| {
+            (0.0, size.height - w, Scale::new(size.width, w))
+        });
+        edge(left, &|w| (0.0, 0.0, Scale::new(w, size.height)));
+        rs
+    }
+
+    /// The background image, tiled or scaled over the `size` of the Div, inset by `inset` from each edge.
+    fn background_image_renderable(
+        &mut self,
+        size: Scale,
+        inset: f32,
+        scale_factor: f32,
+        prev: Option<Raster>,
+        caches: &crate::render::Caches,
+    ) -> Option<Renderable> {
+        let image = self.background_image.as_mut()?;
+        let data = image.data.take()?;
+        let tile = Scale::new(
+            image.size.width as f32 * scale_factor,
+            image.size.height as f32 * scale_factor,
+        );
+        let area = size - Scale::new(inset * 2.0, inset * 2.0);
+        let mut quads = image_quads(image.fill, tile, area);
+        if quads.is_empty() {
+            return None;
+        }
+        for quad in quads.iter_mut() {
+            for p in [&mut quad.dest.0, &mut quad.dest.1] {
+                p.x += inset;
+                p.y += inset;
+            }
+        }
+        let raster = Raster::quads(
+            data,
+            image.size,
+            image.content_hash,
+            quads,
+            &mut caches.image_buffer.write().unwrap(),
+            &mut caches.raster.write().unwrap(),
+            prev.as_ref().map(|r| r.buffer_id),
+            prev.as_ref().map(|r| r.raster_cache_id),
+        )
+        .tint(image.tint.unwrap_or(Color::WHITE))
+        .z(0.11); // above background
+        Some(Renderable::Raster(raster))
+    }
+
+    /// Draw a gradient along each edge in `shadows`, fading in towards the edge of `frame`
+    fn shadow_renderables(
+        &self,
+        shadows: ScrollShadows,
+        frame: AABB,
+        scale_factor: f32,
+    ) -> Vec<Renderable> {
+        let mut rs = vec![];
+        let color: Color = self.style_val("shadow_color").into();
+        let shadow_size = self.style_val("shadow_size").unwrap().f32() * scale_factor;
+        let step = shadow_size / SHADOW_STEPS as f32;
+        let width = frame.width();
+        let height = frame.height();
+        let z = 100.0; // above the content
+
+        for i in 0..SHADOW_STEPS {
+            let color = Color {
+                a: color.a * (1.0 - i as f32 / SHADOW_STEPS as f32),
+                ..color
+            };
+            let offset = step * i as f32;
+            let mut edge = |x: f32, y: f32, width: f32, height: f32| {
+                rs.push(Renderable::Rect(Rect::new(
+                    Pos {
+                        x: frame.pos.x + x,
+                        y: frame.pos.y + y,
+                        z,
+                    },
+                    Scale { width, height },
+                    color,
+                )))
+            };
+            if shadows.top {
+                edge(0.0, offset, width, step);
+            }
+            if shadows.bottom {
+                edge(0.0, height - offset - step, width, step);
+            }
+            if shadows.left {
+                edge(offset, 0.0, step, height);
+            }
+            if shadows.right {
+                edge(width - offset - step, 0.0, step, height);
+            }
+        }
+        rs
+    }
 }
 
 #[state_component_impl(DivState)]
@@ -83,8 +476,23 @@ impl Component for Div {
             self.state_ref().y_bar_pressed.hash(hasher);
             self.state_ref().x_bar_pressed.hash(hasher);
         }
-        if let Some(color) = self.background {
+        if let Some(color) = self.background_color() {
+            color.hash(hasher);
+        }
+        if let Some((color, width)) = self.border_style() {
             color.hash(hasher);
+            width.to_bits().hash(hasher);
+        }
+        if let Some(image) = &self.background_image {
+            image.content_hash.hash(hasher);
+            image.size.width.hash(hasher);
+            image.size.height.hash(hasher);
+            image.fill.hash(hasher);
+            image.tint.hash(hasher);
+        }
+        for border in self.side_borders().into_iter().flatten() {
+            border.color.hash(hasher);
+            border.width.to_bits().hash(hasher);
         }
         // Maybe TODO: Should hash scroll_descriptor
     }
@@ -233,6 +641,16 @@ impl Component for Div {
         }
     }
 
+    fn scroll_to(&mut self, position: Point) {
+        if self.scrollable() {
+            self.state_mut().scroll_position = position;
+        }
+    }
+
+    fn scroll_padding(&self) -> f32 {
+        self.style_val("scroll_padding").map_or(0.0, |p| p.f32())
+    }
+
     fn frame_bounds(&self, aabb: AABB, inner_scale: Option<Scale>) -> AABB {
         let mut aabb = aabb;
         if self.scrollable() {
@@ -267,11 +685,10 @@ impl Component for Div {
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
         let mut rs = vec![];
-        let border_width = self
-            .border_width
-            .map_or(0.0, |x| (x * context.scale_factor.floor()).round());
+        let border = self.border_style();
+        let border_width = border.map_or(0.0, |(_, x)| (x * context.scale_factor.floor()).round());
 
-        if let Some(bg) = self.background {
+        if let Some(bg) = self.background_color() {
             rs.push(Renderable::Rect(Rect::new(
                 Pos {
                     x: border_width,
@@ -283,7 +700,7 @@ impl Component for Div {
             )))
         }
 
-        if let (Some(color), Some(_width)) = (self.border_color, self.border_width) {
+        if let Some((color, _width)) = border {
             rs.push(Renderable::Rect(Rect::new(
                 Pos::default(),
                 context.aabb.size(),
@@ -291,6 +708,24 @@ impl Component for Div {
             )))
         }
 
+        let prev_raster = context.prev_state.and_then(|prev| {
+            prev.into_iter().find_map(|r| match r {
+                Renderable::Raster(r) => Some(r),
+                _ => None,
+            })
+        });
+        if let Some(image) = self.background_image_renderable(
+            context.aabb.size(),
+            border_width,
+            context.scale_factor,
+            prev_raster,
+            &context.caches,
+        ) {
+            rs.push(image);
+        }
+
+        rs.append(&mut self.side_border_renderables(context.aabb.size(), context.scale_factor));
+
         if self.scrollable() {
             let scroll_position = self.state_ref().scroll_position;
             let inner_scale = context.inner_scale.unwrap();
@@ -326,7 +761,7 @@ impl Component for Div {
                         Pos {
                             x,
                             y: bar_y_offset,
-                            z: 0.1, // above background
+                            z: 0.12, // above background image
                         },
                         Scale {
                             width: scaled_width,
@@ -397,7 +832,7 @@ impl Component for Div {
                         Pos {
                             x: bar_x_offset,
                             y,
-                            z: 0.1, // above background
+                            z: 0.12, // above background image
                         },
                         Scale {
                             width: bar_background_width,
@@ -441,8 +876,105 @@ impl Component for Div {
                     self.state_mut().x_scroll_bar = None;
                 }
             }
+
+            if self.style_val("shadows").unwrap().into() {
+                let shadows = ScrollShadows::new(
+                    scroll_position,
+                    max_position,
+                    self.x_scrollable(),
+                    self.y_scrollable(),
+                );
+                let frame = self.frame_bounds(AABB::new(Pos::default(), size), Some(inner_scale));
+                rs.append(&mut self.shadow_renderables(shadows, frame, context.scale_factor));
+            }
         }
 
         Some(rs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_shadows() {
+        let max_position = Scale::new(100.0, 200.0);
+        let shadows = ScrollShadows::new(Point::new(0.0, 0.0), max_position, false, true);
+        assert!(!shadows.top);
+        assert!(shadows.bottom);
+        assert!(!shadows.left && !shadows.right);
+
+        let shadows = ScrollShadows::new(Point::new(0.0, 50.0), max_position, false, true);
+        assert!(shadows.top);
+        assert!(shadows.bottom);
+
+        let shadows = ScrollShadows::new(Point::new(100.0, 200.0), max_position, true, true);
+        assert!(shadows.top && shadows.left);
+        assert!(!shadows.bottom && !shadows.right);
+    }
+
+    #[test]
+    fn test_side_borders() {
+        let size = Scale::new(100.0, 20.0);
+        let div = Div::new().border_bottom(Color::BLACK, 1.0);
+        let rs = div.side_border_renderables(size, 2.0);
+        assert_eq!(rs.len(), 1);
+        match &rs[0] {
+            Renderable::Rect(r) => {
+                let i = r.render(&AABB::new(Pos::default(), size));
+                assert_eq!((i.pos.x, i.pos.y), (0.0, 18.0));
+                assert_eq!(i.scale, Scale::new(100.0, 2.0));
+            }
+            r => panic!("Expected a rect, got {:?}", r),
+        }
+
+        let div = Div::new()
+            .border_bottom(Color::BLACK, 1.0)
+            .border_left(Color::WHITE, 3.0);
+        assert_eq!(div.side_border_renderables(size, 1.0).len(), 2);
+        assert!(Div::new().side_border_renderables(size, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_image_quads() {
+        let p = Point::new;
+        let image = Scale::new(10.0, 10.0);
+        let area = Scale::new(25.0, 10.0);
+
+        let quads = image_quads(ImageFill::Repeat, image, area);
+        assert_eq!(quads.len(), 3);
+        // The last tile is cut off at the edge of the area
+        assert_eq!(quads[2].dest, (p(20.0, 0.0), p(25.0, 10.0)));
+        assert_eq!(quads[2].source, (p(0.0, 0.0), p(0.5, 1.0)));
+
+        assert_eq!(image_quads(ImageFill::NoRepeat, image, area).len(), 1);
+        let area = Scale::new(25.0, 25.0);
+        let quads = image_quads(ImageFill::RepeatX, image, area);
+        assert_eq!(quads.len(), 3);
+        assert!(quads.iter().all(|q| q.dest.0.y == 0.0));
+        assert_eq!(image_quads(ImageFill::RepeatY, image, area).len(), 3);
+
+        // Scaled to cover, and cropped evenly top and bottom
+        let tall = Scale::new(10.0, 20.0);
+        let area = Scale::new(20.0, 20.0);
+        let quads = image_quads(ImageFill::Cover, tall, area);
+        assert_eq!(quads.len(), 1);
+        assert_eq!(quads[0].dest, (p(0.0, 0.0), p(20.0, 20.0)));
+        assert_eq!(quads[0].source, (p(0.0, 0.25), p(1.0, 0.75)));
+
+        // Scaled to fit, and centered
+        let quads = image_quads(ImageFill::Contain, tall, area);
+        assert_eq!(quads[0].dest, (p(5.0, 0.0), p(15.0, 20.0)));
+        assert_eq!(quads[0].source, (p(0.0, 0.0), p(1.0, 1.0)));
+
+        // Tiny tiles are drawn larger, to keep the number of quads down
+        let quads = image_quads(
+            ImageFill::Repeat,
+            Scale::new(1.0, 1.0),
+            Scale::new(1000.0, 1000.0),
+        );
+        assert!(quads.len() <= MAX_TILES);
+        assert!(image_quads(ImageFill::Repeat, image, Scale::new(0.0, 10.0)).is_empty());
+    }
+}
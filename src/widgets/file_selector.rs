@@ -1,10 +1,10 @@
 use std::path::PathBuf;
 
-use crate::component::{Component, Message};
+use crate::component::Message;
 use crate::{node, txt, Node, Styled};
 use lemna_macros::component;
 
-#[component(Styled, Internal)]
+#[component(Styled, Composed, Internal)]
 pub struct FileSelector {
     pub title: String,
     pub default_path: Option<PathBuf>,
@@ -29,7 +29,7 @@ impl FileSelector {
             default_path: None,
             filter: None,
             on_select: None,
-            class: Default::default(),
+            classes: Default::default(),
             style_overrides: Default::default(),
         }
     }
@@ -50,7 +50,8 @@ impl FileSelector {
         self
     }
 
-    fn select(&self) -> Option<PathBuf> {
+    /// Show the file dialog right away, returning the chosen file. Useful for opening a file from somewhere other than the selector's button, e.g. a menu.
+    pub fn select(&self) -> Option<PathBuf> {
         let path = self
             .default_path
             .as_ref()
@@ -70,20 +71,16 @@ impl FileSelector {
         );
         f.map(|s| s.into())
     }
-}
 
-impl Component for FileSelector {
-    fn view(&self) -> Option<Node> {
-        let mut b = super::Button::new(txt!("...")); // TODO Style override
+    fn body(&self) -> Node {
+        let mut b = super::Button::new(txt!(tr!("..."))); // TODO Style override
         *b.style_overrides_mut() = self.style_overrides.clone();
-        if let Some(class) = self.class {
-            b = b.with_class(class);
-        }
+        *b.classes_mut() = self.classes.clone();
         let this: &'static Self = unsafe { std::mem::transmute(self) };
         if let Some(f) = &this.on_select {
             b = b.on_click(Box::new(|| f(this.select())));
         }
 
-        Some(node!(b, lay!(size: size_pct!(100.0))))
+        node!(b, lay!(size: size_pct!(100.0)))
     }
 }
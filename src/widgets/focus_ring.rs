@@ -0,0 +1,137 @@
+use std::hash::Hash;
+
+use lyon::tessellation;
+use lyon::tessellation::basic_shapes;
+use lyon::tessellation::math as lyon_math;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::render::{
+    renderables::shape::{self, Shape},
+    Renderable,
+};
+use crate::style::Styled;
+use lemna_macros::component;
+
+/// The outline that is drawn around a Node that was focused with the keyboard, e.g. by pressing Tab. It is drawn `offset` logical pixels outside of the focused Node, above its content.
+///
+/// Components choose their ring with [`Component#focus_ring`][Component#method.focus_ring], so a widget can give it its own style, or opt out by returning `None`:
+///
+/// ```ignore
+/// fn focus_ring(&self) -> Option<FocusRing> {
+///     Some(FocusRing::new().style("radius", 0.0))
+/// }
+/// ```
+#[component(Styled, Internal)]
+#[derive(Debug)]
+pub struct FocusRing {}
+
+impl FocusRing {
+    pub fn new() -> Self {
+        Self {
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// Color, width, radius and offset.
+    fn params(&self) -> (Color, f32, f32, f32) {
+        (
+            self.style_val("color").into(),
+            self.style_val("width").unwrap().f32(),
+            self.style_val("radius").unwrap().f32(),
+            self.style_val("offset").unwrap().f32(),
+        )
+    }
+}
+
+impl Default for FocusRing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for FocusRing {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        let (color, width, radius, offset) = self.params();
+        color.hash(hasher);
+        width.to_bits().hash(hasher);
+        radius.to_bits().hash(hasher);
+        offset.to_bits().hash(hasher);
+    }
+
+    fn full_control(&self) -> bool {
+        true
+    }
+
+    fn set_aabb(
+        &mut self,
+        aabb: &mut AABB,
+        parent_aabb: AABB,
+        _children: Vec<(&mut AABB, Option<Scale>, Option<Point>)>,
+        _frame: AABB,
+        scale_factor: f32,
+    ) {
+        // Surround the focused Node
+        let (_, width, _, offset) = self.params();
+        let outset = ((width + offset) * scale_factor).round();
+        aabb.pos.x = parent_aabb.pos.x - outset;
+        aabb.pos.y = parent_aabb.pos.y - outset;
+        aabb.bottom_right = parent_aabb.bottom_right + Point::new(outset, outset);
+    }
+
+    // The ring should never get in the way of the mouse
+    fn is_mouse_over(&self, _mouse_position: Point, _aabb: AABB) -> bool {
+        false
+    }
+
+    fn is_mouse_maybe_over(&self, _mouse_position: Point, _aabb: AABB) -> bool {
+        false
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let (color, width, radius, _) = self.params();
+        let width = (width * context.scale_factor).round();
+        if width <= 0.0 {
+            return None;
+        }
+        let radius = radius * context.scale_factor;
+        let mut geometry = shape::ShapeGeometry::new();
+        // The stroke is centered on the rectangle, so inset it to stay inside of the AABB
+        let inset = width / 2.0;
+        basic_shapes::stroke_rounded_rectangle(
+            &lyon_math::rect(
+                inset,
+                inset,
+                context.aabb.width() - width,
+                context.aabb.height() - width,
+            ),
+            &basic_shapes::BorderRadii {
+                top_left: radius,
+                top_right: radius,
+                bottom_right: radius,
+                bottom_left: radius,
+            },
+            &tessellation::StrokeOptions::tolerance(shape::TOLERANCE).dont_apply_line_width(),
+            &mut tessellation::BuffersBuilder::new(
+                &mut geometry,
+                shape::Vertex::stroke_vertex_constructor,
+            ),
+        )
+        .unwrap();
+
+        Some(vec![Renderable::Shape(Shape::new(
+            geometry,
+            0,
+            color,
+            color,
+            width * 0.5,
+            0.0,
+            &mut context.caches.shape_buffer.write().unwrap(),
+            context.prev_state.as_ref().and_then(|v| match v.get(0) {
+                Some(Renderable::Shape(r)) => Some(r.buffer_id),
+                _ => None,
+            }),
+        ))])
+    }
+}
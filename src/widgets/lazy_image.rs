@@ -0,0 +1,410 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::div::image_quads;
+use super::ImageFill;
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::event;
+use crate::render::{
+    renderables::{Raster, RasterData},
+    Image, Renderable,
+};
+use crate::style::Styled;
+use crate::{node, Node};
+use lemna_macros::{component, state_component_impl};
+
+type Loader = Box<dyn FnOnce() -> Result<Image, String> + Send + Sync>;
+
+/// How far along the image with a given key is.
+enum Entry {
+    /// Being loaded, for this many [`LazyImage`]s. Loads that nothing is waiting for are cancelled.
+    Loading(usize),
+    Loaded(Result<Arc<Image>, String>),
+}
+
+/// The images that have been loaded, or are loading, by key. Shared by every [`LazyImage`], so that an image is only decoded once.
+fn loaded() -> &'static Mutex<HashMap<String, Entry>> {
+    static LOADED: OnceLock<Mutex<HashMap<String, Entry>>> = OnceLock::new();
+    LOADED.get_or_init(Default::default)
+}
+
+/// Run `loader` and store what it returns under `key`, unless nothing is waiting for it anymore.
+fn load(key: String, loader: Loader) {
+    let cancelled = |key: &String| {
+        let mut loaded = loaded().lock().unwrap();
+        let cancelled = matches!(loaded.get(key), Some(Entry::Loading(0)));
+        if cancelled {
+            loaded.remove(key);
+        }
+        cancelled
+    };
+    if cancelled(&key) {
+        return;
+    }
+    // A panic would otherwise take down the thread pool
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(loader))
+        .unwrap_or_else(|_| Err("The image loader panicked".to_string()));
+    let result = result.and_then(|image| {
+        if image.data.len() == image.width as usize * image.height as usize * 4 {
+            Ok(Arc::new(image))
+        } else {
+            Err(format!(
+                "Expected {} bytes of RGBA data for a {}x{} image, got {}",
+                image.width * image.height * 4,
+                image.width,
+                image.height,
+                image.data.len()
+            ))
+        }
+    });
+    if !cancelled(&key) {
+        loaded().lock().unwrap().insert(key, Entry::Loaded(result));
+    }
+}
+
+/// A [`LazyImage`]'s interest in the image with `key`. Dropping it, e.g. because the LazyImage was unmounted, cancels the load if nothing else is waiting for it.
+#[derive(Debug)]
+struct Request {
+    key: String,
+}
+
+impl Request {
+    /// Start loading `key` with `loader` in the background, unless it is already loaded or loading.
+    fn new(key: &str, loader: Loader) -> Self {
+        let mut loaded = loaded().lock().unwrap();
+        match loaded.get_mut(key) {
+            Some(Entry::Loading(waiting)) => *waiting += 1,
+            Some(Entry::Loaded(_)) => (),
+            None => {
+                loaded.insert(key.to_string(), Entry::Loading(1));
+                let key = key.to_string();
+                rayon::spawn(move || load(key, loader));
+            }
+        }
+        Self {
+            key: key.to_string(),
+        }
+    }
+
+    /// The loaded image, or the error that loading it ran into, once it is done.
+    fn poll(&self) -> Option<Result<Arc<Image>, String>> {
+        match loaded().lock().unwrap().get(&self.key) {
+            Some(Entry::Loaded(result)) => Some(result.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Drop for Request {
+    fn drop(&mut self) {
+        if let Some(Entry::Loading(waiting)) = loaded().lock().unwrap().get_mut(&self.key) {
+            *waiting = waiting.saturating_sub(1);
+        }
+    }
+}
+
+#[derive(Default)]
+struct LazyImageState {
+    /// Set while the image is loading.
+    request: Option<Request>,
+    image: Option<Result<Arc<Image>, String>>,
+}
+
+impl fmt::Debug for LazyImageState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyImageState")
+            .field("request", &self.request)
+            .field("loaded", &self.image.as_ref().map(|i| i.is_ok()))
+            .finish()
+    }
+}
+
+/// An image that is loaded in the background, so that decoding it doesn't hold up the UI. A placeholder is shown until it is loaded: a [`Skeleton`][super::Skeleton], unless it is given another with [`#placeholder`][LazyImage#method.placeholder]. If it fails to load, a box of the `error_color` style parameter is shown, or whatever [`#error`][LazyImage#method.error] returns:
+///
+/// ```ignore
+/// node!(
+///     LazyImage::file(&cover_path, decode_png)
+///         .fill(ImageFill::Cover)
+///         .placeholder(Box::new(|| node!(Spinner::new()))),
+///     lay!(size: size!(120.0))
+/// )
+/// ```
+///
+/// Loaded images are kept, by key, for every LazyImage to share, so an image that is scrolled out of view and back again isn't loaded again. A load is cancelled if every LazyImage waiting for it is unmounted before it starts. The backend needs to send [`Input::Timer`][crate::input::Input::Timer] for a loaded image to be shown. LazyImages take their size from their layout, and are drawn like the [background image][super::Div#method.bg_image] of a [`Div`][super::Div].
+#[component(State = "LazyImageState", Styled, Internal)]
+pub struct LazyImage {
+    key: String,
+    loader: Option<Loader>,
+    fill: ImageFill,
+    placeholder: Option<Box<dyn Fn() -> Node + Send + Sync>>,
+    error: Option<Box<dyn Fn(&str) -> Node + Send + Sync>>,
+}
+
+impl fmt::Debug for LazyImage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyImage")
+            .field("key", &self.key)
+            .field("fill", &self.fill)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl LazyImage {
+    /// The image returned by `loader`, which is called in the background. `key` identifies the image: `loader` is only called if no image with the same key has been loaded before.
+    pub fn new<F>(key: impl Into<String>, loader: F) -> Self
+    where
+        F: FnOnce() -> Result<Image, String> + Send + Sync + 'static,
+    {
+        Self {
+            key: key.into(),
+            loader: Some(Box::new(loader)),
+            fill: ImageFill::Contain,
+            placeholder: None,
+            error: None,
+            state: Some(LazyImageState::default()),
+            dirty: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// The file at `path`, read and decoded with `decode` in the background. It is keyed by its path and modification time, so it is loaded again if the file changes.
+    pub fn file<P: AsRef<std::path::Path>>(
+        path: P,
+        decode: fn(&[u8]) -> Result<Image, String>,
+    ) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let key = format!("{}@{:?}", path.display(), modified);
+        Self::new(key, move || {
+            let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
+            decode(&bytes)
+        })
+    }
+
+    /// How the image fills the LazyImage. Defaults to [`ImageFill::Contain`].
+    pub fn fill(mut self, fill: ImageFill) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Show the Node that `placeholder` returns while the image is loading.
+    pub fn placeholder(mut self, placeholder: Box<dyn Fn() -> Node + Send + Sync>) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
+    /// Show the Node that `error` returns, given the error, if the image fails to load.
+    pub fn error(mut self, error: Box<dyn Fn(&str) -> Node + Send + Sync>) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    fn start_loading(&mut self) {
+        if let Some(loader) = self.loader.take() {
+            let request = Request::new(&self.key, loader);
+            let state = self.state_mut();
+            state.image = request.poll();
+            state.request = if state.image.is_none() {
+                Some(request)
+            } else {
+                None
+            };
+        }
+    }
+}
+
+#[state_component_impl(LazyImageState)]
+impl Component for LazyImage {
+    fn init(&mut self) {
+        self.start_loading();
+    }
+
+    fn new_props(&mut self) {
+        self.start_loading();
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.key.hash(hasher);
+    }
+
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.key.hash(hasher);
+        self.fill.hash(hasher);
+        self.state_ref()
+            .image
+            .as_ref()
+            .map(|i| i.is_ok())
+            .hash(hasher);
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if let Some(image) = self.state_ref().request.as_ref().and_then(Request::poll) {
+            let state = self.state_mut();
+            state.image = Some(image);
+            state.request = None;
+        }
+    }
+
+    fn view(&self) -> Option<Node> {
+        match &self.state_ref().image {
+            Some(Ok(_)) => None,
+            Some(Err(e)) => Some(if let Some(error) = &self.error {
+                error(e)
+            } else {
+                let error_color: Color = self.style_val("error_color").into();
+                node!(
+                    super::Div::new().bg(error_color),
+                    lay!(size: size_pct!(100.0))
+                )
+            }),
+            None => Some(if let Some(placeholder) = &self.placeholder {
+                placeholder()
+            } else {
+                node!(super::Skeleton::new(), lay!(size: size_pct!(100.0)))
+            }),
+        }
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let image = self.state_ref().image.clone()?.ok()?;
+        let quads = image_quads(
+            self.fill,
+            Scale::new(
+                image.width as f32 * context.scale_factor,
+                image.height as f32 * context.scale_factor,
+            ),
+            context.aabb.size(),
+        );
+        if quads.is_empty() {
+            return None;
+        }
+        let mut hasher = DefaultHasher::new();
+        self.key.hash(&mut hasher);
+        let prev = context.prev_state.and_then(|prev| {
+            prev.into_iter().find_map(|r| match r {
+                Renderable::Raster(r) => Some(r),
+                _ => None,
+            })
+        });
+        Some(vec![Renderable::Raster(Raster::quads(
+            RasterData::Vec(image.data.clone()),
+            PixelSize::new(image.width, image.height),
+            hasher.finish(),
+            quads,
+            &mut context.caches.image_buffer.write().unwrap(),
+            &mut context.caches.raster.write().unwrap(),
+            prev.as_ref().map(|r| r.buffer_id),
+            prev.as_ref().map(|r| r.raster_cache_id),
+        ))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::txt;
+    use std::time::Duration;
+
+    fn pixels() -> Image {
+        Image {
+            width: 2,
+            height: 1,
+            data: vec![255; 8],
+        }
+    }
+
+    /// Tick `image` until it is done loading.
+    fn wait(image: &mut LazyImage) {
+        let cache = event::EventCache::new(1.0);
+        for _ in 0..200 {
+            image.on_tick(&mut event::Event::new(event::Tick, &cache));
+            if image.state_ref().request.is_none() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("The image never loaded");
+    }
+
+    #[test]
+    fn test_load() {
+        let mut image = LazyImage::new("test_load", || {
+            std::thread::sleep(Duration::from_millis(20));
+            Ok(pixels())
+        });
+        image.init();
+        // The placeholder is shown until the image arrives
+        let placeholder = image.view().unwrap();
+        assert!(format!("{:?}", placeholder.component).contains("Skeleton"));
+
+        wait(&mut image);
+        assert!(image.view().is_none());
+        assert_eq!(
+            image.state_ref().image.as_ref().unwrap().as_deref(),
+            Ok(&pixels())
+        );
+
+        // The same image is not loaded twice
+        let mut again = LazyImage::new("test_load", || panic!("Loaded twice"));
+        again.init();
+        assert!(again.state_ref().request.is_none());
+        assert!(again.view().is_none());
+    }
+
+    #[test]
+    fn test_error() {
+        let mut image =
+            LazyImage::new("test_error", || Err("Not an image".to_string())).error(Box::new(|e| {
+                node!(crate::widgets::Text::new(txt!(e.to_string())))
+            }));
+        image.init();
+        wait(&mut image);
+        assert!(format!("{:?}", image.view().unwrap().component).contains("Not an image"));
+
+        // Data of the wrong size is an error too
+        let mut image = LazyImage::new("test_error_size", || {
+            Ok(Image {
+                width: 2,
+                height: 2,
+                data: vec![255; 8],
+            })
+        });
+        image.init();
+        wait(&mut image);
+        assert!(matches!(image.state_ref().image, Some(Err(_))));
+    }
+
+    #[test]
+    fn test_cancel() {
+        // Nothing is waiting for the image anymore by the time the load starts
+        loaded()
+            .lock()
+            .unwrap()
+            .insert("test_cancel".to_string(), Entry::Loading(0));
+        load(
+            "test_cancel".to_string(),
+            Box::new(|| panic!("Cancelled loads aren't run")),
+        );
+        assert!(loaded().lock().unwrap().get("test_cancel").is_none());
+
+        // Unmounting the last LazyImage waiting for a load cancels it
+        let request = Request {
+            key: "test_cancel".to_string(),
+        };
+        loaded()
+            .lock()
+            .unwrap()
+            .insert("test_cancel".to_string(), Entry::Loading(1));
+        drop(request);
+        assert!(matches!(
+            loaded().lock().unwrap().get("test_cancel"),
+            Some(Entry::Loading(0))
+        ));
+    }
+}
@@ -0,0 +1,565 @@
+use std::hash::Hash;
+
+use pulldown_cmark as md;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, Message};
+use crate::event;
+use crate::font_cache::TextSegment;
+use crate::style::Styled;
+use crate::{node, Node};
+use lemna_macros::component;
+
+#[derive(Debug)]
+enum MarkdownMessage {
+    Link(String),
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct SpanStyle {
+    strong: bool,
+    emphasis: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+/// A run of text with a single style. Hard line breaks are represented by a span of `"\n"`.
+#[derive(Debug, Clone, PartialEq)]
+struct Span {
+    text: String,
+    style: SpanStyle,
+}
+
+#[derive(Debug, PartialEq)]
+enum Block {
+    Heading(u8, Vec<Span>),
+    Paragraph(Vec<Span>),
+    /// The text of a tight list item, which is not spaced like a paragraph.
+    Plain(Vec<Span>),
+    List {
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Code(String),
+}
+
+/// Parse the supported subset of Markdown into blocks. Anything unsupported is either dropped, or reduced to its text.
+fn parse(source: &str) -> Vec<Block> {
+    // The blocks of the document, followed by those of any list items that are open
+    let mut blocks: Vec<Vec<Block>> = vec![vec![]];
+    let mut lists: Vec<(Option<u64>, Vec<Vec<Block>>)> = vec![];
+    let mut spans: Vec<Span> = vec![];
+    let mut style = SpanStyle::default();
+    let mut code_block: Option<String> = None;
+
+    let push_plain = |blocks: &mut Vec<Vec<Block>>, spans: &mut Vec<Span>| {
+        if !spans.is_empty() {
+            blocks
+                .last_mut()
+                .unwrap()
+                .push(Block::Plain(std::mem::take(spans)));
+        }
+    };
+
+    for event in md::Parser::new(source) {
+        match event {
+            md::Event::Start(md::Tag::Emphasis) => style.emphasis = true,
+            md::Event::End(md::Tag::Emphasis) => style.emphasis = false,
+            md::Event::Start(md::Tag::Strong) => style.strong = true,
+            md::Event::End(md::Tag::Strong) => style.strong = false,
+            md::Event::Start(md::Tag::Link(_, url, _)) => style.link = Some(url.to_string()),
+            md::Event::End(md::Tag::Link(..)) => style.link = None,
+            md::Event::Start(md::Tag::CodeBlock(_)) => code_block = Some(String::new()),
+            md::Event::End(md::Tag::CodeBlock(_)) => {
+                let code = code_block.take().unwrap_or_default();
+                blocks
+                    .last_mut()
+                    .unwrap()
+                    .push(Block::Code(code.trim_end_matches('\n').to_string()));
+            }
+            md::Event::Text(text) => {
+                if let Some(code) = code_block.as_mut() {
+                    code.push_str(&text);
+                } else {
+                    spans.push(Span {
+                        text: text.to_string(),
+                        style: style.clone(),
+                    });
+                }
+            }
+            md::Event::Code(text) => spans.push(Span {
+                text: text.to_string(),
+                style: SpanStyle {
+                    code: true,
+                    ..style.clone()
+                },
+            }),
+            md::Event::SoftBreak => spans.push(Span {
+                text: " ".to_string(),
+                style: style.clone(),
+            }),
+            md::Event::HardBreak => spans.push(Span {
+                text: "\n".to_string(),
+                style: SpanStyle::default(),
+            }),
+            md::Event::End(md::Tag::Paragraph) => blocks
+                .last_mut()
+                .unwrap()
+                .push(Block::Paragraph(std::mem::take(&mut spans))),
+            md::Event::End(md::Tag::Heading(level, ..)) => blocks
+                .last_mut()
+                .unwrap()
+                .push(Block::Heading(level as u8, std::mem::take(&mut spans))),
+            md::Event::Start(md::Tag::List(start)) => {
+                // A list nested in a tight list item follows the item's text
+                push_plain(&mut blocks, &mut spans);
+                lists.push((start, vec![]));
+            }
+            md::Event::End(md::Tag::List(_)) => {
+                let (start, items) = lists.pop().unwrap();
+                blocks
+                    .last_mut()
+                    .unwrap()
+                    .push(Block::List { start, items });
+            }
+            md::Event::Start(md::Tag::Item) => blocks.push(vec![]),
+            md::Event::End(md::Tag::Item) => {
+                push_plain(&mut blocks, &mut spans);
+                let item = blocks.pop().unwrap();
+                lists.last_mut().unwrap().1.push(item);
+            }
+            _ => (),
+        }
+    }
+
+    blocks.pop().unwrap()
+}
+
+/// A piece of text that is laid out as a unit: Lines are only wrapped between words.
+struct Word {
+    segments: Vec<TextSegment>,
+    link: Option<String>,
+}
+
+/// Renders a subset of [Markdown](https://commonmark.org/) as [`Text`][super::Text] nodes.
+///
+/// The supported subset is:
+/// - Paragraphs, and hard line breaks
+/// - Headings. Levels 3 through 6 are all the same size
+/// - Bold (`**bold**`) and italic (`*italic*`) text
+/// - Unordered and ordered lists, which may be nested
+/// - Links, which emit the message returned by [`#on_link`][Markdown#method.on_link] when clicked
+/// - Code spans and code blocks
+///
+/// Block quotes are shown as regular paragraphs, and the alt text of images is shown in their place. Other Markdown, like HTML and tables, is not shown.
+///
+/// Bold, italic, and code text are set in the fonts given by the `bold_font`, `italic_font`, `bold_italic_font`, and `code_font` style parameters, which are unset by default. Set them to the names of fonts that have been added to the UI, or that text will be shown in the regular `font`.
+#[component(Styled, Internal)]
+pub struct Markdown {
+    source: String,
+    blocks: Vec<Block>,
+    on_link: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+}
+
+impl std::fmt::Debug for Markdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Markdown")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl Markdown {
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        Self {
+            blocks: parse(&source),
+            source,
+            on_link: None,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// Called with the URL of a link when it is clicked.
+    pub fn on_link(mut self, link_fn: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
+        self.on_link = Some(link_fn);
+        self
+    }
+
+    fn font(&self, style: &SpanStyle, heading: bool) -> Option<String> {
+        let bold = style.strong || heading;
+        let font = if style.code {
+            self.style_val("code_font")
+        } else if bold && style.emphasis {
+            self.style_val("bold_italic_font")
+                .or_else(|| self.style_val("bold_font"))
+        } else if bold {
+            self.style_val("bold_font")
+        } else if style.emphasis {
+            self.style_val("italic_font")
+        } else {
+            None
+        };
+        font.or_else(|| self.style_val("font"))
+            .map(|f| f.str().to_string())
+    }
+
+    fn heading_size(&self, level: u8) -> f32 {
+        self.style_val(match level {
+            1 => "h1_size",
+            2 => "h2_size",
+            _ => "h3_size",
+        })
+        .unwrap()
+        .f32()
+    }
+
+    /// Split a line of spans into words, keeping each word's trailing whitespace.
+    fn words(&self, spans: &[Span], size: f32, heading: bool) -> Vec<Word> {
+        let mut words = vec![];
+        let mut current = Word {
+            segments: vec![],
+            link: None,
+        };
+        for span in spans.iter() {
+            for piece in span.text.split_inclusive(char::is_whitespace) {
+                if !current.segments.is_empty() && current.link != span.style.link {
+                    words.push(std::mem::replace(
+                        &mut current,
+                        Word {
+                            segments: vec![],
+                            link: None,
+                        },
+                    ));
+                }
+                current.link = span.style.link.clone();
+                current.segments.push(TextSegment {
+                    text: piece.to_string(),
+                    size: Some(size),
+                    font: self.font(&span.style, heading),
+                    link: None,
+                });
+                if piece.ends_with(char::is_whitespace) {
+                    words.push(std::mem::replace(
+                        &mut current,
+                        Word {
+                            segments: vec![],
+                            link: None,
+                        },
+                    ));
+                }
+            }
+        }
+        if !current.segments.is_empty() {
+            words.push(current);
+        }
+        words
+    }
+
+    fn inline_node(&self, spans: &[Span], size: f32, heading: bool, spacing: f32) -> Node {
+        let text_color: Color = self.style_val("text_color").into();
+        let link_color: Color = self.style_val("link_color").into();
+
+        let mut base = node!(
+            super::Div::new(),
+            lay!(
+                direction: Column,
+                size: size_pct!(100.0, Auto),
+                margin: rect!(0.0, 0.0, spacing, 0.0),
+            )
+        );
+        for (i, line) in spans.split(|s| s.text == "\n").enumerate() {
+            let mut line_node = node!(
+                super::Div::new(),
+                lay!(
+                    direction: Row,
+                    wrap: true,
+                    cross_alignment: Baseline,
+                    size: size_pct!(100.0, Auto),
+                )
+            )
+            .key(i as u64);
+            for (j, word) in self.words(line, size, heading).drain(..).enumerate() {
+                let text = node!(super::Text::new(word.segments)
+                    .style("size", size as f64)
+                    .style(
+                        "color",
+                        if word.link.is_some() {
+                            link_color
+                        } else {
+                            text_color
+                        }
+                    )
+                    .maybe_style("font", self.style_val("font")));
+                line_node = line_node.push(if let Some(url) = word.link {
+                    node!(Link { url }).push(text).key(j as u64)
+                } else {
+                    text.key(j as u64)
+                });
+            }
+            base = base.push(line_node);
+        }
+        base
+    }
+
+    fn block_node(&self, block: &Block) -> Node {
+        let font_size: f32 = self.style_val("font_size").unwrap().f32();
+        let spacing: f32 = self.style_val("paragraph_spacing").unwrap().f32();
+
+        match block {
+            Block::Heading(level, spans) => {
+                self.inline_node(spans, self.heading_size(*level), true, spacing)
+            }
+            Block::Paragraph(spans) => self.inline_node(spans, font_size, false, spacing),
+            Block::Plain(spans) => self.inline_node(spans, font_size, false, 0.0),
+            Block::List { start, items } => {
+                let indent: f32 = self.style_val("list_indent").unwrap().f32();
+                let mut list = node!(
+                    super::Div::new(),
+                    lay!(
+                        direction: Column,
+                        size: size_pct!(100.0, Auto),
+                        margin: rect!(0.0, 0.0, spacing, 0.0),
+                    )
+                );
+                for (i, item) in items.iter().enumerate() {
+                    let marker = match start {
+                        Some(n) => format!("{}.", n + i as u64),
+                        None => "•".to_string(),
+                    };
+                    let mut item_node = node!(
+                        super::Div::new(),
+                        lay!(
+                            direction: Column,
+                            size: size_pct!(100.0, Auto),
+                            padding: rect!(0.0, indent, 0.0, 0.0),
+                        )
+                    )
+                    .key(i as u64)
+                    .push(
+                        node!(
+                            super::Text::new(vec![TextSegment {
+                                text: marker,
+                                size: Some(font_size),
+                                font: self.font(&SpanStyle::default(), false),
+                                link: None,
+                            }])
+                            .style("size", font_size as f64)
+                            .style("color", self.style_val("text_color").unwrap()),
+                            lay!(
+                                position_type: Absolute,
+                                position: rect!(0.0, 0.0, Auto, Auto),
+                            )
+                        )
+                        .key(0),
+                    );
+                    for (j, block) in item.iter().enumerate() {
+                        item_node = item_node.push(self.block_node(block).key(j as u64 + 1));
+                    }
+                    list = list.push(item_node);
+                }
+                list
+            }
+            Block::Code(code) => {
+                let padding: f32 = self.style_val("code_padding").unwrap().f32();
+                let background_color: Color = self.style_val("code_background_color").into();
+                node!(
+                    super::Div::new().bg(background_color),
+                    lay!(
+                        size: size_pct!(100.0, Auto),
+                        padding: rect!(padding),
+                        margin: rect!(0.0, 0.0, spacing, 0.0),
+                    )
+                )
+                .push(node!(super::Text::new(vec![TextSegment {
+                    text: code.clone(),
+                    size: Some(font_size),
+                    font: self.font(
+                        &SpanStyle {
+                            code: true,
+                            ..Default::default()
+                        },
+                        false
+                    ),
+                    link: None,
+                }])
+                .style("size", font_size as f64)
+                .style("color", self.style_val("text_color").unwrap())))
+            }
+        }
+    }
+}
+
+impl Component for Markdown {
+    fn view(&self) -> Option<Node> {
+        let mut base = node!(
+            super::Div::new(),
+            lay!(direction: Column, size: size_pct!(100.0, Auto))
+        );
+        for (i, block) in self.blocks.iter().enumerate() {
+            base = base.push(self.block_node(block).key(i as u64));
+        }
+        Some(base)
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.source.hash(hasher);
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<MarkdownMessage>() {
+            Some(MarkdownMessage::Link(url)) => self.on_link.iter().map(|f| f(url)).collect(),
+            None => vec![message],
+        }
+    }
+}
+
+//
+// Link
+// Wraps the Text of a linked word
+#[derive(Debug)]
+struct Link {
+    url: String,
+}
+
+impl Component for Link {
+    fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
+        if let Some(w) = crate::current_window() {
+            w.set_cursor(crate::Cursor::PointingHand);
+        }
+    }
+
+    fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        if let Some(w) = crate::current_window() {
+            w.unset_cursor();
+        }
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.emit(Box::new(MarkdownMessage::Link(self.url.clone())));
+        event.stop_bubbling();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(node: &Node) -> &super::super::Text {
+        node.component
+            .as_any()
+            .and_then(|c| c.downcast_ref::<super::super::Text>())
+            .unwrap_or_else(|| panic!("Expected a Text, got {:?}", node.component))
+    }
+
+    /// The text, size and font of each segment of a Text Node.
+    fn segments(node: &Node) -> Vec<(&str, Option<f32>, Option<&str>)> {
+        text(node)
+            .text
+            .iter()
+            .map(|s| (s.text.as_str(), s.size, s.font.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_heading_and_bold() {
+        let markdown =
+            Markdown::new("# Title\n\nSome **bold** text").style("bold_font", "noto sans bold");
+        assert_eq!(
+            markdown.blocks[1],
+            Block::Paragraph(vec![
+                Span {
+                    text: "Some ".to_string(),
+                    style: SpanStyle::default()
+                },
+                Span {
+                    text: "bold".to_string(),
+                    style: SpanStyle {
+                        strong: true,
+                        ..Default::default()
+                    }
+                },
+                Span {
+                    text: " text".to_string(),
+                    style: SpanStyle::default()
+                },
+            ])
+        );
+
+        // One Node per block, each holding a Node per line, each holding a Text per word
+        let view = markdown.view().unwrap();
+        assert_eq!(view.children.len(), 2);
+        let heading = &view.children[0].children;
+        assert_eq!(heading.len(), 1);
+        assert_eq!(heading[0].children.len(), 1);
+        let paragraph = &view.children[1].children;
+        assert_eq!(paragraph.len(), 1);
+        let words = &paragraph[0].children;
+        assert_eq!(words.len(), 3);
+
+        // Headings are large and bold
+        let title = &heading[0].children[0];
+        assert_eq!(
+            segments(title),
+            vec![("Title", Some(24.0), Some("noto sans bold"))]
+        );
+        assert_eq!(text(title).style_val("size").unwrap().f32(), 24.0);
+        assert_eq!(segments(&words[0]), vec![("Some ", Some(12.0), None)]);
+        // The bold run and the space after it form one word
+        assert_eq!(
+            segments(&words[1]),
+            vec![
+                ("bold", Some(12.0), Some("noto sans bold")),
+                (" ", Some(12.0), None)
+            ]
+        );
+        assert_eq!(segments(&words[2]), vec![("text", Some(12.0), None)]);
+        for word in words.iter() {
+            let style = |param| text(word).style_val(param);
+            assert_eq!(style("size").unwrap().f32(), 12.0);
+            assert_eq!(
+                Color::from(style("color")),
+                Color::from(markdown.style_val("text_color"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_links_and_lists() {
+        let blocks = parse("- [one](https://example.com) two\n- three");
+        assert_eq!(blocks.len(), 1);
+        if let Block::List { start, items } = &blocks[0] {
+            assert_eq!(*start, None);
+            assert_eq!(items.len(), 2);
+            assert_eq!(
+                items[0][0],
+                Block::Plain(vec![
+                    Span {
+                        text: "one".to_string(),
+                        style: SpanStyle {
+                            link: Some("https://example.com".to_string()),
+                            ..Default::default()
+                        }
+                    },
+                    Span {
+                        text: " two".to_string(),
+                        style: SpanStyle::default()
+                    },
+                ])
+            );
+        } else {
+            panic!("Expected a list, got {:?}", blocks[0]);
+        }
+
+        let mut markdown = Markdown::new("[one](https://example.com)")
+            .on_link(Box::new(|url| msg!(url.to_string())));
+        let messages = markdown.update(Box::new(MarkdownMessage::Link(
+            "https://example.com".to_string(),
+        )));
+        assert_eq!(
+            messages[0].downcast_ref::<String>(),
+            Some(&"https://example.com".to_string())
+        );
+    }
+}
@@ -1,35 +1,91 @@
 //! Built-in Components.
 
+mod badge;
+pub use badge::Badge;
+
 mod button;
-pub use button::Button;
+pub use button::{Button, ButtonRepeat};
 
 mod canvas;
 pub use canvas::Canvas;
 
+#[cfg(feature = "code-view")]
+mod code_view;
+#[cfg(feature = "code-view")]
+pub use code_view::CodeView;
+
+#[cfg(feature = "date-picker")]
+mod date_picker;
+#[cfg(feature = "date-picker")]
+pub use date_picker::DatePicker;
+
 mod div;
-pub use div::Div;
+pub use div::{BackgroundImage, Border, Borders, Div, ImageFill};
 
 #[cfg(feature = "file-dialogs")]
 mod file_selector;
 pub use file_selector::*;
 
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "markdown")]
+pub use markdown::Markdown;
+
+mod focus_ring;
+pub use focus_ring::FocusRing;
+
+mod lazy_image;
+pub use lazy_image::LazyImage;
+
+mod nine_slice;
+pub use nine_slice::NineSlice;
+
+mod path;
+pub(crate) use path::ClipMesh;
+pub use path::{ClipPath, Path, PathCommand};
+
+mod perf_overlay;
+pub use perf_overlay::PerfOverlay;
+
+mod popup;
+pub use popup::{Popover, PopoverAlign, PopoverSide};
+
 mod radio_buttons;
 pub use radio_buttons::*;
 
+mod resizable;
+pub use resizable::{Resizable, ResizeEdges};
+
 mod rounded_rect;
 pub use rounded_rect::RoundedRect;
 
+mod scroll_view;
+pub use scroll_view::ScrollView;
+
 mod select;
 pub use select::*;
 
+mod skeleton;
+pub use skeleton::Skeleton;
+
+mod spinner;
+pub use spinner::Spinner;
+
 mod text;
 pub use text::Text;
 
 mod textbox;
-pub use textbox::{TextBox, TextBoxAction};
+pub use textbox::{InputMask, TextBox, TextBoxAction};
+
+mod toast;
+pub(crate) use toast::ToastQueue;
+pub use toast::{Toast, Toasts};
 
 mod toggle;
 pub use toggle::*;
 
+mod toggle_group;
+pub use toggle_group::ToggleGroup;
+
 mod tool_tip;
 pub use tool_tip::*;
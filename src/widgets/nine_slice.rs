@@ -0,0 +1,96 @@
+use std::hash::Hash;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::font_cache::FontCache;
+use crate::render::{
+    renderables::{
+        raster::{Insets, NineSliceInsets, Raster},
+        RasterData,
+    },
+    Renderable,
+};
+
+/// Draws an 8 bit rgba image that stretches to fill its box, while keeping its corners a fixed size. The edges stretch along one axis, and the center stretches along both. Useful for skinned panels, bubbles, and decorative borders.
+///
+/// When no size is given by the layout, the `NineSlice` will be sized to its image.
+///
+/// The `render_hash` of a `NineSlice` only covers its size and insets, so give it a new key if you change its image.
+pub struct NineSlice {
+    data: Option<RasterData>,
+    size: PixelSize,
+    insets: Insets,
+}
+
+impl std::fmt::Debug for NineSlice {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("NineSlice")
+            .field("size", &self.size)
+            .field("insets", &self.insets)
+            .finish()
+    }
+}
+
+impl NineSlice {
+    /// `insets` are measured in pixels of the source image, and are drawn at that many logical pixels.
+    pub fn new<D: Into<RasterData>>(data: D, size: PixelSize, insets: Insets) -> Self {
+        Self {
+            data: Some(data.into()),
+            size,
+            insets,
+        }
+    }
+}
+
+impl Component for NineSlice {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.size.width.hash(hasher);
+        self.size.height.hash(hasher);
+        (self.insets.top as u32).hash(hasher);
+        (self.insets.right as u32).hash(hasher);
+        (self.insets.bottom as u32).hash(hasher);
+        (self.insets.left as u32).hash(hasher);
+    }
+
+    fn fill_bounds(
+        &mut self,
+        width: Option<f32>,
+        height: Option<f32>,
+        _max_width: Option<f32>,
+        _max_height: Option<f32>,
+        _font_cache: &FontCache,
+        _scale_factor: f32,
+    ) -> (Option<f32>, Option<f32>) {
+        (
+            Some(width.unwrap_or(self.size.width as f32)),
+            Some(height.unwrap_or(self.size.height as f32)),
+        )
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let prev = context.prev_state.and_then(|mut v| match v.pop() {
+            Some(Renderable::Raster(r)) => Some(r),
+            _ => None,
+        });
+        let data = self.data.take()?;
+        let s = context.scale_factor;
+
+        Some(vec![Renderable::Raster(Raster::nine_slice(
+            data,
+            self.size,
+            NineSliceInsets {
+                source: self.insets,
+                dest: Insets::new(
+                    (self.insets.top * s).round(),
+                    (self.insets.right * s).round(),
+                    (self.insets.bottom * s).round(),
+                    (self.insets.left * s).round(),
+                ),
+            },
+            &mut context.caches.image_buffer.write().unwrap(),
+            &mut context.caches.raster.write().unwrap(),
+            prev.as_ref().map(|r| r.buffer_id),
+            prev.as_ref().map(|r| r.raster_cache_id),
+        ))])
+    }
+}
@@ -0,0 +1,446 @@
+use std::hash::{Hash, Hasher};
+
+use lyon::path::Path as LyonPath;
+use lyon::tessellation::math as lyon_math;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::render::{
+    renderables::shape::{Shape, StrokeStyle},
+    Renderable,
+};
+
+/// One step of a [`Path`]. Points are relative to the top left of the [`Path`]'s view box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    /// Start a new sub-path.
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo {
+        ctrl: Point,
+        to: Point,
+    },
+    CubicTo {
+        ctrl1: Point,
+        ctrl2: Point,
+        to: Point,
+    },
+    /// A circular arc around `center`, starting at the current point. `sweep` is in radians, positive values going clockwise.
+    Arc {
+        center: Point,
+        sweep: f32,
+    },
+    /// Connect the current point back to the start of the sub-path.
+    Close,
+}
+
+impl Hash for PathCommand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let hash_point = |p: &Point, state: &mut H| {
+            p.x.to_bits().hash(state);
+            p.y.to_bits().hash(state);
+        };
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::MoveTo(p) | Self::LineTo(p) => hash_point(p, state),
+            Self::QuadTo { ctrl, to } => {
+                hash_point(ctrl, state);
+                hash_point(to, state);
+            }
+            Self::CubicTo { ctrl1, ctrl2, to } => {
+                hash_point(ctrl1, state);
+                hash_point(ctrl2, state);
+                hash_point(to, state);
+            }
+            Self::Arc { center, sweep } => {
+                hash_point(center, state);
+                sweep.to_bits().hash(state);
+            }
+            Self::Close => (),
+        }
+    }
+}
+
+/// Static vector graphics, built from [`PathCommand`]s. The path is filled and/or stroked, and drawn in logical pixels from the top left of the node. When a [`#view_box`][Path#method.view_box] is given, the path is instead scaled so that the view box fills the node.
+///
+/// ```ignore
+/// node!(
+///     widgets::Path::new()
+///         .move_to(0.0, 10.0)
+///         .line_to(5.0, 0.0)
+///         .line_to(10.0, 10.0)
+///         .close()
+///         .view_box(10.0, 10.0)
+///         .fill(Color::BLACK),
+///     lay!(size: size!(20.0))
+/// )
+/// ```
+#[derive(Debug, Default)]
+pub struct Path {
+    pub commands: Vec<PathCommand>,
+    pub view_box: Option<Scale>,
+    pub fill: Option<Color>,
+    pub stroke: Option<(Color, f32)>,
+    pub stroke_style: StrokeStyle,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn command(mut self, command: PathCommand) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    pub fn move_to(self, x: f32, y: f32) -> Self {
+        self.command(PathCommand::MoveTo(Point::new(x, y)))
+    }
+
+    pub fn line_to(self, x: f32, y: f32) -> Self {
+        self.command(PathCommand::LineTo(Point::new(x, y)))
+    }
+
+    pub fn quad_to(self, ctrl: Point, to: Point) -> Self {
+        self.command(PathCommand::QuadTo { ctrl, to })
+    }
+
+    pub fn cubic_to(self, ctrl1: Point, ctrl2: Point, to: Point) -> Self {
+        self.command(PathCommand::CubicTo { ctrl1, ctrl2, to })
+    }
+
+    pub fn arc(self, center: Point, sweep: f32) -> Self {
+        self.command(PathCommand::Arc { center, sweep })
+    }
+
+    pub fn close(self) -> Self {
+        self.command(PathCommand::Close)
+    }
+
+    /// The size of the area that the commands are drawn in. It is stretched to the size of the node.
+    pub fn view_box(mut self, width: f32, height: f32) -> Self {
+        self.view_box = Some(Scale::new(width, height));
+        self
+    }
+
+    pub fn fill<C: Into<Color>>(mut self, color: C) -> Self {
+        self.fill = Some(color.into());
+        self
+    }
+
+    /// Stroke the path with the given `color`, `width` logical pixels wide.
+    pub fn stroke<C: Into<Color>>(mut self, color: C, width: f32) -> Self {
+        self.stroke = Some((color.into(), width));
+        self
+    }
+
+    pub fn stroke_style(mut self, style: StrokeStyle) -> Self {
+        self.stroke_style = style;
+        self
+    }
+}
+
+/// A shape that the children of a [`Node`][crate::Node] are clipped to, set with [`Node#clip_path`][crate::Node#method.clip_path]. Like a [`Path`], its commands are in logical pixels from the top left of the node, unless a [`#view_box`][ClipPath#method.view_box] is given.
+///
+/// ```ignore
+/// node!(Div::new(), lay!(size: size!(48.0)))
+///     .clip_path(ClipPath::ellipse())
+///     .push(node!(widgets::Image::new(avatar), lay!(size: size_pct!(100.0))))
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClipPath {
+    pub commands: Vec<PathCommand>,
+    pub view_box: Option<Scale>,
+}
+
+impl ClipPath {
+    pub fn new(commands: Vec<PathCommand>) -> Self {
+        Self {
+            commands,
+            view_box: None,
+        }
+    }
+
+    /// The ellipse that fills the node, e.g. a circle for a square node.
+    pub fn ellipse() -> Self {
+        Self::new(vec![
+            PathCommand::MoveTo(Point::new(1.0, 0.5)),
+            PathCommand::Arc {
+                center: Point::new(0.5, 0.5),
+                sweep: std::f32::consts::TAU,
+            },
+            PathCommand::Close,
+        ])
+        .view_box(1.0, 1.0)
+    }
+
+    /// The size of the area that the commands are drawn in. It is stretched to the size of the node.
+    pub fn view_box(mut self, width: f32, height: f32) -> Self {
+        self.view_box = Some(Scale::new(width, height));
+        self
+    }
+
+    /// Tessellate the path for a node of physical `size`.
+    pub(crate) fn mesh(&self, size: Scale, scale_factor: f32) -> ClipMesh {
+        let path = build_path(
+            &self.commands,
+            path_scale(self.view_box, size, scale_factor),
+        );
+        let (geometry, _) = Shape::path_to_shape_geometry(path, true, false);
+        ClipMesh {
+            vertices: geometry.vertices.iter().map(|v| v.pos).collect(),
+            indices: geometry.indices,
+        }
+    }
+}
+
+impl Hash for ClipPath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.commands.hash(state);
+        self.view_box
+            .map(|s| (s.width.to_bits(), s.height.to_bits()))
+            .hash(state);
+    }
+}
+
+impl From<Path> for ClipPath {
+    fn from(path: Path) -> Self {
+        Self {
+            commands: path.commands,
+            view_box: path.view_box,
+        }
+    }
+}
+
+/// The triangles that fill a [`ClipPath`], in physical pixels relative to the top left of the clipping node.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct ClipMesh {
+    pub vertices: Vec<Point>,
+    pub indices: Vec<u16>,
+}
+
+impl ClipMesh {
+    pub fn triangles(&self) -> impl Iterator<Item = [Point; 3]> + '_ {
+        self.indices.chunks(3).map(|t| {
+            [
+                self.vertices[t[0] as usize],
+                self.vertices[t[1] as usize],
+                self.vertices[t[2] as usize],
+            ]
+        })
+    }
+
+    /// Is `p`, relative to the top left of the clipping node, inside of the mesh?
+    pub fn contains(&self, p: Point) -> bool {
+        let edge =
+            |a: Point, b: Point, p: Point| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+        self.triangles().any(|[a, b, c]| {
+            let w = [edge(a, b, p), edge(b, c, p), edge(c, a, p)];
+            w.iter().all(|w| *w >= 0.0) || w.iter().all(|w| *w <= 0.0)
+        })
+    }
+}
+
+/// How much to scale the points of a path, drawn in a node of physical `size`. See [`Path#view_box`][Path#method.view_box].
+fn path_scale(view_box: Option<Scale>, size: Scale, scale_factor: f32) -> (f32, f32) {
+    match view_box {
+        Some(view_box) if view_box.width > 0.0 && view_box.height > 0.0 => {
+            (size.width / view_box.width, size.height / view_box.height)
+        }
+        _ => (scale_factor, scale_factor),
+    }
+}
+
+/// Build the lyon path for `commands`, with each point scaled by `scale`. Sub-paths that are not closed are left open.
+fn build_path(commands: &[PathCommand], scale: (f32, f32)) -> LyonPath {
+    let point = |p: &Point| lyon_math::point(p.x * scale.0, p.y * scale.1);
+    let mut builder = LyonPath::builder();
+    // The current and starting points of the sub-path, before scaling
+    let mut start = Point::default();
+    let mut current = start;
+    for command in commands.iter() {
+        match command {
+            PathCommand::MoveTo(p) => {
+                start = *p;
+                current = *p;
+                builder.move_to(point(p));
+            }
+            PathCommand::LineTo(p) => {
+                current = *p;
+                builder.line_to(point(p));
+            }
+            PathCommand::QuadTo { ctrl, to } => {
+                current = *to;
+                builder.quadratic_bezier_to(point(ctrl), point(to));
+            }
+            PathCommand::CubicTo { ctrl1, ctrl2, to } => {
+                current = *to;
+                builder.cubic_bezier_to(point(ctrl1), point(ctrl2), point(to));
+            }
+            PathCommand::Arc { center, sweep } => {
+                let (dx, dy) = (current.x - center.x, current.y - center.y);
+                let radius = (dx * dx + dy * dy).sqrt();
+                // A circle is stretched into an ellipse when the scale is not uniform
+                builder.arc(
+                    point(center),
+                    lyon_math::vector(radius * scale.0, radius * scale.1),
+                    lyon_math::Angle::radians(*sweep),
+                    lyon_math::Angle::radians(0.0),
+                );
+                let angle = dy.atan2(dx) + sweep;
+                current = Point::new(
+                    center.x + radius * angle.cos(),
+                    center.y + radius * angle.sin(),
+                );
+            }
+            PathCommand::Close => {
+                builder.close();
+                current = start;
+            }
+        }
+    }
+    builder.build()
+}
+
+impl Component for Path {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        self.commands.hash(hasher);
+        self.view_box
+            .map(|s| (s.width.to_bits(), s.height.to_bits()))
+            .hash(hasher);
+        self.fill.hash(hasher);
+        self.stroke.map(|(c, w)| (c, w.to_bits())).hash(hasher);
+        (self.stroke_style.line_join as u8).hash(hasher);
+        (self.stroke_style.line_cap as u8).hash(hasher);
+        self.stroke_style
+            .dash
+            .as_ref()
+            .map(|d| {
+                (
+                    d.pattern.iter().map(|l| l.to_bits()).collect::<Vec<_>>(),
+                    d.offset.to_bits(),
+                )
+            })
+            .hash(hasher);
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        if self.commands.is_empty() || (self.fill.is_none() && self.stroke.is_none()) {
+            return None;
+        }
+        let path = build_path(
+            &self.commands,
+            path_scale(self.view_box, context.aabb.size(), context.scale_factor),
+        );
+
+        let (stroke_color, stroke_width) = self.stroke.unwrap_or((Color::BLACK, 0.0));
+        let (geometry, fill_count) = if self.stroke.is_some() {
+            Shape::path_to_styled_shape_geometry(
+                path,
+                self.fill.is_some(),
+                &self.stroke_style,
+                stroke_width * context.scale_factor,
+                context.scale_factor,
+            )
+        } else {
+            Shape::path_to_shape_geometry(path, true, false)
+        };
+        if geometry.vertices.is_empty() {
+            return None;
+        }
+
+        Some(vec![Renderable::Shape(Shape::new(
+            geometry,
+            fill_count,
+            self.fill.unwrap_or(Color::BLACK),
+            stroke_color,
+            stroke_width * context.scale_factor * 0.5,
+            0.0,
+            &mut context.caches.shape_buffer.write().unwrap(),
+            context.prev_state.as_ref().and_then(|v| match v.get(0) {
+                Some(Renderable::Shape(r)) => Some(r.buffer_id),
+                _ => None,
+            }),
+        ))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyon::path::PathEvent;
+
+    #[test]
+    fn test_triangle() {
+        let triangle = Path::new()
+            .move_to(0.0, 0.0)
+            .line_to(10.0, 0.0)
+            .line_to(0.0, 10.0)
+            .close();
+        let path = build_path(&triangle.commands, (2.0, 2.0));
+
+        let (geometry, fill_count) = Shape::path_to_shape_geometry(path.clone(), true, false);
+        // Every vertex is inside of the scaled triangle
+        for v in geometry.vertices.iter() {
+            assert!(v.pos.x >= 0.0 && v.pos.y >= 0.0 && v.pos.x + v.pos.y <= 20.0);
+        }
+        // And together they cover it
+        let area: f32 = geometry.indices[..fill_count as usize]
+            .chunks(3)
+            .map(|t| {
+                let (a, b, c) = (
+                    geometry.vertices[t[0] as usize].pos,
+                    geometry.vertices[t[1] as usize].pos,
+                    geometry.vertices[t[2] as usize].pos,
+                );
+                ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.0
+            })
+            .sum();
+        assert!((area - 200.0).abs() < 0.01, "{}", area);
+
+        // Closing the path connects the last point to the first
+        let end = path.iter().find_map(|e| match e {
+            PathEvent::End { last, first, close } => Some((last, first, close)),
+            _ => None,
+        });
+        assert_eq!(
+            end,
+            Some((
+                lyon_math::point(0.0, 20.0),
+                lyon_math::point(0.0, 0.0),
+                true
+            ))
+        );
+
+        // While an unclosed path is left open
+        let open = build_path(&triangle.commands[..3], (1.0, 1.0));
+        assert!(open
+            .iter()
+            .any(|e| matches!(e, PathEvent::End { close: false, .. })));
+    }
+
+    #[test]
+    fn test_clip_ellipse() {
+        let mesh = ClipPath::ellipse().mesh(Scale::new(40.0, 20.0), 2.0);
+        // The view box is stretched over the node, whatever the scale factor
+        assert!(mesh.contains(Point::new(20.0, 10.0)));
+        assert!(mesh.contains(Point::new(1.0, 10.0)));
+        assert!(mesh.contains(Point::new(20.0, 1.0)));
+        // But the corners are cut off
+        assert!(!mesh.contains(Point::new(2.0, 2.0)));
+        assert!(!mesh.contains(Point::new(38.0, 18.0)));
+        assert!(!mesh.contains(Point::new(41.0, 10.0)));
+
+        // Without a view box, commands are in logical pixels
+        let triangle: ClipPath = Path::new()
+            .move_to(0.0, 0.0)
+            .line_to(10.0, 0.0)
+            .line_to(0.0, 10.0)
+            .close()
+            .into();
+        let mesh = triangle.mesh(Scale::new(100.0, 100.0), 2.0);
+        assert!(mesh.contains(Point::new(9.0, 9.0)));
+        assert!(!mesh.contains(Point::new(11.0, 11.0)));
+    }
+}
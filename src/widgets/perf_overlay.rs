@@ -0,0 +1,175 @@
+use std::time::{Duration, Instant};
+
+use crate::base_types::*;
+use crate::component::Component;
+use crate::event;
+use crate::layout::*;
+use crate::style::{HorizontalPosition, Styled, VerticalPosition};
+use crate::{node, txt, FrameStats, Node};
+use lemna_macros::{component, state_component_impl};
+
+#[derive(Debug)]
+struct PerfOverlayState {
+    refreshed: Instant,
+}
+
+impl Default for PerfOverlayState {
+    fn default() -> Self {
+        Self {
+            refreshed: Instant::now(),
+        }
+    }
+}
+
+/// The [`FrameStats`] of the app, and a graph of its recent frame times, shown over the top of the app by [`UI#perf_overlay`][crate::UI#method.perf_overlay]. It sits in the corner given by its `h_position` and `v_position` styles.
+///
+/// The overlay refreshes its numbers a few times a second with [`Tick`][event::Tick] events, so the backend needs to send [`Input::Timer`][crate::input::Input::Timer] while it is shown. Each refresh redraws the app.
+#[component(State = "PerfOverlayState", Styled, Internal)]
+#[derive(Debug)]
+pub struct PerfOverlay {
+    pub stats: FrameStats,
+    /// Recent frame times in milliseconds, oldest first.
+    pub history: Vec<f32>,
+}
+
+impl PerfOverlay {
+    /// How often the numbers are refreshed.
+    const REFRESH: Duration = Duration::from_millis(500);
+    /// The frame time that the graph is scaled to fit, unless a frame took longer.
+    const GRAPH_MIN_MS: f32 = 1000.0 / 30.0;
+
+    pub(crate) fn new(stats: FrameStats, history: Vec<f32>) -> Self {
+        Self {
+            stats,
+            history,
+            state: Some(PerfOverlayState::default()),
+            dirty: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// The layout that places the overlay in its corner of the window.
+    pub(crate) fn layout(&self) -> Layout {
+        let margin = Dimension::Px(self.style_val("margin").unwrap().f32() as f64);
+        let mut position = Rect::default();
+        match self.style_val("h_position").unwrap().horizontal_position() {
+            HorizontalPosition::Right => position.right = margin,
+            _ => position.left = margin,
+        }
+        match self.style_val("v_position").unwrap().vertical_position() {
+            VerticalPosition::Bottom => position.bottom = margin,
+            _ => position.top = margin,
+        }
+        Layout {
+            position_type: PositionType::Absolute,
+            z_index_increment: 3000.0,
+            position,
+            ..Default::default()
+        }
+    }
+
+    fn graph(&self) -> Option<Node> {
+        if self.history.len() < 2 {
+            return None;
+        }
+        let width: f32 = self.style_val("graph_width").unwrap().f32();
+        let height: f32 = self.style_val("graph_height").unwrap().f32();
+        let max = self
+            .history
+            .iter()
+            .copied()
+            .fold(Self::GRAPH_MIN_MS, f32::max);
+        let step = width / (self.history.len() - 1) as f32;
+        let mut path = super::Path::new();
+        for (i, ms) in self.history.iter().enumerate() {
+            let (x, y) = (i as f32 * step, height - ms / max * height);
+            path = if i == 0 {
+                path.move_to(x, y)
+            } else {
+                path.line_to(x, y)
+            };
+        }
+        let color: Color = self.style_val("graph_color").into();
+        Some(node!(
+            path.stroke(color, 1.0),
+            lay!(size: size!(width, height), margin: rect!(4.0, 0.0, 0.0, 0.0))
+        ))
+    }
+}
+
+#[state_component_impl(PerfOverlayState)]
+impl Component for PerfOverlay {
+    fn view(&self) -> Option<Node> {
+        let background_color: Color = self.style_val("background_color").into();
+        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+        let stats = &self.stats;
+        let lines = [
+            format!("{:.1} ms  {:.0} fps", stats.last_frame_ms, stats.fps_1s_avg),
+            format!(
+                "layout {:.1} ms  render {:.1} ms",
+                stats.layout_ms, stats.render_ms
+            ),
+            format!(
+                "{} nodes  {} renderables",
+                stats.node_count, stats.renderable_count
+            ),
+        ];
+
+        let graph_key = lines.len() as u64;
+        let mut overlay = node!(
+            super::RoundedRect::new(background_color, radius),
+            lay!(direction: Direction::Column, padding: rect!(padding))
+        );
+        for (i, line) in lines.into_iter().enumerate() {
+            overlay = overlay.push(node!(
+                super::Text::new(txt!(line))
+                    .style("size", self.style_val("font_size").unwrap())
+                    .style("color", self.style_val("text_color").unwrap())
+                    .style("h_alignment", HorizontalPosition::Left)
+                    .maybe_style("font", self.style_val("font")),
+                lay!(),
+                i as u64
+            ));
+        }
+        if let Some(graph) = self.graph() {
+            overlay = overlay.push(graph.key(graph_key));
+        }
+        Some(overlay)
+    }
+
+    fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
+        if self.state_ref().refreshed.elapsed() >= Self::REFRESH {
+            self.state_mut().refreshed = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perf_overlay() {
+        let stats = FrameStats {
+            last_frame_ms: 4.0,
+            node_count: 12,
+            ..Default::default()
+        };
+        let mut overlay = PerfOverlay::new(stats, vec![]);
+        // No graph until there are frames to plot
+        assert_eq!(overlay.view().unwrap().children.len(), 3);
+        overlay.history = vec![4.0, 8.0, 5.0];
+        assert_eq!(overlay.view().unwrap().children.len(), 4);
+
+        // Ticks only redraw once it's time to refresh
+        let cache = event::EventCache::new(1.0);
+        overlay.on_tick(&mut event::Event::new(event::Tick, &cache));
+        assert!(!overlay.is_dirty());
+        overlay.state_mut().refreshed -= PerfOverlay::REFRESH;
+        overlay.dirty = false;
+        overlay.on_tick(&mut event::Event::new(event::Tick, &cache));
+        assert!(overlay.is_dirty());
+    }
+}
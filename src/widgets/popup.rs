@@ -0,0 +1,195 @@
+//! Placement of popups, like the list of a [`Select`][super::Select] or a [`ToolTip`][super::ToolTip], which open next to whatever they belong to.
+use crate::base_types::*;
+
+/// The side of its anchor that a [`Popover`] prefers to be on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PopoverSide {
+    Top,
+    #[default]
+    Bottom,
+    Left,
+    Right,
+}
+
+/// How a [`Popover`] lines up with its anchor, along the side that it is on: with the anchor's top or left edge, its center, or its bottom or right edge.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PopoverAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+/// Positions a floating element, like a menu or a dropdown list, next to the anchor that it belongs to, while keeping it in the window. Meant for the [`set_aabb`][crate::Component#method.set_aabb] of a [`full_control`][crate::Component#method.full_control] Component, with the AABB of the anchor as the parent AABB:
+///
+/// ```ignore
+/// fn set_aabb(&mut self, aabb: &mut AABB, parent_aabb: AABB, _children: Vec<(&mut AABB, Option<Scale>, Option<Point>)>, frame: AABB, _scale_factor: f32) {
+///     let position = Popover::new(PopoverSide::Bottom, PopoverAlign::Center)
+///         .position(parent_aabb, aabb.size(), Popover::bounds(frame));
+///     aabb.translate_mut(position.pos.x - aabb.pos.x, position.pos.y - aabb.pos.y);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Popover {
+    pub side: PopoverSide,
+    pub align: PopoverAlign,
+}
+
+impl Popover {
+    pub fn new(side: PopoverSide, align: PopoverAlign) -> Self {
+        Self { side, align }
+    }
+
+    /// The bounds that popovers should stay in, given the `frame` passed to [`set_aabb`][crate::Component#method.set_aabb]: the whole window, since popovers can overflow their scrollable ancestors.
+    pub fn bounds(frame: AABB) -> AABB {
+        popup_bounds(frame)
+    }
+
+    /// Where a popover of `size` goes next to `anchor`, within `bounds`. It goes on the preferred side if there is room for it there, otherwise on the opposite side. When there is room on neither, it takes the side with more room, and is shortened to fit. Along the side, it is aligned with the anchor, then shifted to stay within `bounds`.
+    pub fn position(&self, anchor: AABB, size: Scale, bounds: AABB) -> AABB {
+        let vertical = matches!(self.side, PopoverSide::Top | PopoverSide::Bottom);
+        // (start, end) of the anchor and bounds along the main axis, then the cross axis
+        let (anchor_main, anchor_cross, bounds_main, bounds_cross, length, breadth) = if vertical {
+            (
+                (anchor.pos.y, anchor.bottom_right.y),
+                (anchor.pos.x, anchor.bottom_right.x),
+                (bounds.pos.y, bounds.bottom_right.y),
+                (bounds.pos.x, bounds.bottom_right.x),
+                size.height,
+                size.width,
+            )
+        } else {
+            (
+                (anchor.pos.x, anchor.bottom_right.x),
+                (anchor.pos.y, anchor.bottom_right.y),
+                (bounds.pos.x, bounds.bottom_right.x),
+                (bounds.pos.y, bounds.bottom_right.y),
+                size.width,
+                size.height,
+            )
+        };
+
+        let (main, length) = if matches!(self.side, PopoverSide::Bottom | PopoverSide::Right) {
+            popup_span(
+                anchor_main.0,
+                anchor_main.1,
+                length,
+                bounds_main.0,
+                bounds_main.1,
+            )
+        } else {
+            // Mirror the axis, so that the side before the anchor is preferred
+            let (start, length) = popup_span(
+                -anchor_main.1,
+                -anchor_main.0,
+                length,
+                -bounds_main.1,
+                -bounds_main.0,
+            );
+            (-(start + length), length)
+        };
+
+        let cross = match self.align {
+            PopoverAlign::Start => anchor_cross.0,
+            PopoverAlign::Center => (anchor_cross.0 + anchor_cross.1 - breadth) / 2.0,
+            PopoverAlign::End => anchor_cross.1 - breadth,
+        };
+        let cross = cross.min(bounds_cross.1 - breadth).max(bounds_cross.0);
+
+        if vertical {
+            AABB::new(
+                Pos::new(cross, main, anchor.pos.z),
+                Scale::new(breadth, length),
+            )
+        } else {
+            AABB::new(
+                Pos::new(main, cross, anchor.pos.z),
+                Scale::new(length, breadth),
+            )
+        }
+    }
+}
+
+/// The area that popups must fit in: the window being laid out, rather than the `frame` of the nearest scrollable ancestor, which popups can overflow. `frame` is used outside of layout.
+pub(crate) fn popup_bounds(frame: AABB) -> AABB {
+    crate::node::layout_window().unwrap_or(frame)
+}
+
+/// Place a popup that is `length` long along one axis, next to the span from `start` to `end` that it belongs to, within the span from `bounds_start` to `bounds_end`. Returns the start and length of the popup.
+///
+/// The popup goes after the span if there is room, otherwise before it. When there is room on neither side it goes on the side with more room, shortened to fit.
+pub(crate) fn popup_span(
+    start: f32,
+    end: f32,
+    length: f32,
+    bounds_start: f32,
+    bounds_end: f32,
+) -> (f32, f32) {
+    let after = (bounds_end - end).max(0.0);
+    let before = (start - bounds_start).max(0.0);
+    if length <= after || (length > before && after >= before) {
+        (end, length.min(after))
+    } else {
+        let length = length.min(before);
+        (start - length, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_popover() {
+        let bounds = AABB::new(Pos::default(), Scale::new(400.0, 300.0));
+        let anchor = AABB::new(Pos::new(100.0, 100.0, 0.0), Scale::new(80.0, 20.0));
+        let size = Scale::new(40.0, 50.0);
+        let place = |side, align, anchor| {
+            let aabb = Popover::new(side, align).position(anchor, size, bounds);
+            (aabb.pos.x, aabb.pos.y, aabb.width(), aabb.height())
+        };
+        use PopoverAlign::*;
+        use PopoverSide::*;
+
+        // Each side, with room to spare
+        assert_eq!(place(Bottom, Start, anchor), (100.0, 120.0, 40.0, 50.0));
+        assert_eq!(place(Top, Start, anchor), (100.0, 50.0, 40.0, 50.0));
+        assert_eq!(place(Right, Start, anchor), (180.0, 100.0, 40.0, 50.0));
+        assert_eq!(place(Left, Start, anchor), (60.0, 100.0, 40.0, 50.0));
+        // Each alignment
+        assert_eq!(place(Bottom, Center, anchor), (120.0, 120.0, 40.0, 50.0));
+        assert_eq!(place(Bottom, End, anchor), (140.0, 120.0, 40.0, 50.0));
+        assert_eq!(place(Right, Center, anchor), (180.0, 85.0, 40.0, 50.0));
+        assert_eq!(place(Right, End, anchor), (180.0, 70.0, 40.0, 50.0));
+
+        // Flips to the other side when the preferred one lacks room
+        let bottom_right = AABB::new(Pos::new(340.0, 260.0, 0.0), Scale::new(60.0, 20.0));
+        assert_eq!(place(Bottom, Start, bottom_right).1, 210.0);
+        assert_eq!(place(Right, Start, bottom_right).0, 300.0);
+        let top_left = AABB::new(Pos::new(0.0, 10.0, 0.0), Scale::new(20.0, 20.0));
+        assert_eq!(place(Top, Start, top_left).1, 30.0);
+        assert_eq!(place(Left, Start, top_left).0, 20.0);
+
+        // And shifts along the side to stay in bounds
+        assert_eq!(place(Bottom, End, top_left).0, 0.0);
+        assert_eq!(place(Left, Start, bottom_right), (300.0, 250.0, 40.0, 50.0));
+
+        // Without room on either side, it takes the larger one and shrinks, whichever side it prefers
+        let tall = Scale::new(40.0, 250.0);
+        for side in [Bottom, Top] {
+            let aabb = Popover::new(side, Start).position(anchor, tall, bounds);
+            assert_eq!((aabb.pos.y, aabb.height()), (120.0, 180.0));
+        }
+    }
+
+    #[test]
+    fn test_popup_span() {
+        // Room after
+        assert_eq!(popup_span(10.0, 20.0, 50.0, 0.0, 100.0), (20.0, 50.0));
+        // Only room before
+        assert_eq!(popup_span(70.0, 80.0, 50.0, 0.0, 100.0), (20.0, 50.0));
+        // Room on neither side: The side with more room is used, and the popup shrinks
+        assert_eq!(popup_span(70.0, 80.0, 90.0, 0.0, 100.0), (0.0, 70.0));
+        assert_eq!(popup_span(20.0, 30.0, 90.0, 0.0, 100.0), (30.0, 70.0));
+    }
+}
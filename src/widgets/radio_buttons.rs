@@ -1,8 +1,6 @@
 use std::fmt;
 use std::hash::Hash;
-use std::time::Instant;
 
-use super::ToolTip;
 use crate::base_types::*;
 use crate::component::{Component, ComponentHasher, Message};
 use crate::event;
@@ -16,6 +14,7 @@ use lemna_macros::{component, state_component_impl};
 pub struct RadioButtons {
     buttons: Vec<Vec<TextSegment>>,
     tool_tips: Option<Vec<String>>,
+    tool_tip: Option<String>,
     selected: Vec<usize>,
     direction: Direction,
     max_rows: Option<usize>,
@@ -45,6 +44,7 @@ impl RadioButtons {
         Self {
             buttons,
             tool_tips: None,
+            tool_tip: None,
             selected,
             direction: Direction::Row,
             max_rows: None,
@@ -52,7 +52,7 @@ impl RadioButtons {
             on_change: None,
             multi_select: false,
             nullable: false,
-            class: Default::default(),
+            classes: Default::default(),
             style_overrides: Default::default(),
         }
     }
@@ -98,6 +98,12 @@ impl RadioButtons {
         self.tool_tips = Some(t);
         self
     }
+
+    /// Show a [`ToolTip`][super::ToolTip] with the text `t` over every button that has not been given its own with [`RadioButtons#method.tool_tips`].
+    pub fn tooltip(mut self, t: impl Into<String>) -> Self {
+        self.tool_tip = Some(t.into());
+        self
+    }
 }
 
 impl Component for RadioButtons {
@@ -168,7 +174,11 @@ impl Component for RadioButtons {
             container = container.push(
                 node!(RadioButton {
                     label: b.clone(),
-                    tool_tip: self.tool_tips.as_ref().map(|tt| tt[position].clone()),
+                    tool_tip: self
+                        .tool_tips
+                        .as_ref()
+                        .map(|tt| tt[position].clone())
+                        .or_else(|| self.tool_tip.clone()),
                     position,
                     selected,
                     radius: (
@@ -187,7 +197,7 @@ impl Component for RadioButtons {
                     ),
                     state: Some(Default::default()),
                     dirty: false,
-                    class: self.class,
+                    classes: self.classes.clone(),
                     style_overrides: self.style_overrides.clone(),
                 })
                 .key(j as u64),
@@ -229,8 +239,6 @@ impl Component for RadioButtons {
 #[derive(Debug, Default)]
 struct RadioButtonState {
     hover: bool,
-    tool_tip_open: Option<Point>,
-    hover_start: Option<Instant>,
 }
 
 #[component(State = "RadioButtonState", Styled, Internal)]
@@ -257,7 +265,7 @@ impl Component for RadioButton {
         let border_color: Color = self.style_val("border_color").into();
         let border_width: f32 = self.style_val("border_width").unwrap().f32();
 
-        let mut base = node!(
+        let base = node!(
             super::RoundedRect {
                 background_color: if self.selected {
                     active_color
@@ -283,23 +291,14 @@ impl Component for RadioButton {
             .style("h_alignment", HorizontalPosition::Center)
             .maybe_style("font", self.style_val("font"))));
 
-        if let (Some(p), Some(tt)) = (self.state_ref().tool_tip_open, self.tool_tip.as_ref()) {
-            base = base.push(node!(
-                ToolTip::new(tt.clone()),
-                lay!(position_type: PositionType::Absolute,
-                     z_index_increment: 1000.0,
-                     position: (p + ToolTip::MOUSE_OFFSET).into(),
-                )
-            ));
-        }
-
         Some(base)
     }
 
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tool_tip.as_deref()
+    }
+
     fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
-        self.state_mut().hover_start = Some(Instant::now());
-        // This state mutation should not trigger a redraw
-        self.dirty = false;
         event.stop_bubbling();
     }
 
@@ -311,19 +310,6 @@ impl Component for RadioButton {
         *self.state_mut() = RadioButtonState::default();
     }
 
-    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
-        if self.state_ref().hover_start.is_some()
-            && self
-                .state_ref()
-                .hover_start
-                .map(|s| s.elapsed().as_millis() > super::ToolTip::DELAY)
-                .unwrap_or(false)
-            && self.state_ref().tool_tip_open.is_none()
-        {
-            self.state_mut().tool_tip_open = Some(event.relative_logical_position());
-        }
-    }
-
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
         event.stop_bubbling();
         event.emit(msg!(RadioButtonMsg::Clicked(self.position)));
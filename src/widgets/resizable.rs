@@ -0,0 +1,448 @@
+use std::any::Any;
+use std::hash::Hash;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::event;
+use crate::layout::*;
+use crate::render::{renderables::Rect, Renderable};
+use crate::style::Styled;
+use crate::window::Cursor;
+use crate::{node, Node};
+use lemna_macros::{component, state_component_impl};
+
+/// Which edges of a [`Resizable`] can be dragged. Corners can be dragged when both of their edges can.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResizeEdges {
+    pub top: bool,
+    pub right: bool,
+    pub bottom: bool,
+    pub left: bool,
+}
+
+impl Default for ResizeEdges {
+    /// The right and bottom edges.
+    fn default() -> Self {
+        Self {
+            top: false,
+            right: true,
+            bottom: true,
+            left: false,
+        }
+    }
+}
+
+impl ResizeEdges {
+    pub fn all() -> Self {
+        Self {
+            top: true,
+            right: true,
+            bottom: true,
+            left: true,
+        }
+    }
+
+    /// The handles for these edges and their corners.
+    fn handles(&self) -> Vec<Handle> {
+        let mut handles = vec![];
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let h = Handle { x, y };
+                if h != (Handle { x: 0, y: 0 })
+                    && (x == 0 || self.x_enabled(x))
+                    && (y == 0 || self.y_enabled(y))
+                {
+                    handles.push(h);
+                }
+            }
+        }
+        handles
+    }
+
+    fn x_enabled(&self, x: i8) -> bool {
+        (x < 0 && self.left) || (x > 0 && self.right)
+    }
+
+    fn y_enabled(&self, y: i8) -> bool {
+        (y < 0 && self.top) || (y > 0 && self.bottom)
+    }
+}
+
+/// A drag handle: `x` is -1 for the left edge, 1 for the right, and 0 for neither; `y` is -1 for the top, 1 for the bottom, and 0 for neither.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Handle {
+    x: i8,
+    y: i8,
+}
+
+impl Handle {
+    fn cursor(&self) -> Cursor {
+        match (self.x, self.y) {
+            (0, _) => Cursor::SizeNS,
+            (_, 0) => Cursor::SizeWE,
+            (x, y) if x == y => Cursor::SizeNWSE,
+            _ => Cursor::SizeNESW,
+        }
+    }
+}
+
+/// The size that results from dragging `handle` by `delta`, starting from `start`. When `keep_aspect` is true, the ratio of width to height of `start` is preserved.
+fn resized(
+    start: Scale,
+    handle: Handle,
+    delta: Point,
+    min: Scale,
+    max: Scale,
+    keep_aspect: bool,
+) -> Scale {
+    let mut width = start.width + delta.x * handle.x as f32;
+    let mut height = start.height + delta.y * handle.y as f32;
+
+    if keep_aspect && start.width > 0.0 && start.height > 0.0 {
+        let ratio = start.width / start.height;
+        let x_change = (width - start.width).abs() / start.width;
+        let y_change = (height - start.height).abs() / start.height;
+        if handle.y == 0 || (handle.x != 0 && x_change >= y_change) {
+            height = width / ratio;
+        } else {
+            width = height * ratio;
+        }
+    }
+
+    Scale {
+        width: width.clamp(min.width, max.width),
+        height: height.clamp(min.height, max.height),
+    }
+}
+
+#[derive(Debug)]
+enum ResizeMessage {
+    Start,
+    Resize {
+        handle: Handle,
+        delta: Point,
+        keep_aspect: bool,
+    },
+}
+
+#[derive(Debug, Default)]
+struct ResizableState {
+    size: Scale,
+    drag_start_size: Scale,
+}
+
+/// A container whose size can be changed by dragging its edges and corners. Its children are pushed into a node of the current size, so they should usually be sized with percentages.
+///
+/// Holding shift while dragging preserves the aspect ratio.
+#[component(State = "ResizableState", Styled, Internal)]
+pub struct Resizable {
+    /// The initial size, in logical pixels.
+    pub size: Scale,
+    pub min_size: Scale,
+    pub max_size: Scale,
+    pub edges: ResizeEdges,
+    on_resize: Option<Box<dyn Fn(Scale, Point) -> Message + Send + Sync>>,
+}
+
+impl std::fmt::Debug for Resizable {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Resizable")
+            .field("size", &self.size)
+            .field("min_size", &self.min_size)
+            .field("max_size", &self.max_size)
+            .field("edges", &self.edges)
+            .finish()
+    }
+}
+
+impl Resizable {
+    pub fn new(size: Scale) -> Self {
+        Self {
+            size,
+            min_size: Scale::default(),
+            max_size: Scale::new(f32::INFINITY, f32::INFINITY),
+            edges: ResizeEdges::default(),
+            on_resize: None,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+            state: Some(ResizableState {
+                size,
+                drag_start_size: size,
+            }),
+            dirty: false,
+        }
+    }
+
+    pub fn min_size(mut self, min_size: Scale) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    pub fn max_size(mut self, max_size: Scale) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn edges(mut self, edges: ResizeEdges) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    /// Called with the new size, in logical pixels, as the user drags. Dragging the left or top edge also moves the origin, so that the opposite edge stays in place: the second argument is how far it moved since the last call, to be added to the Resizable's position.
+    pub fn on_resize(
+        mut self,
+        resize_fn: Box<dyn Fn(Scale, Point) -> Message + Send + Sync>,
+    ) -> Self {
+        self.on_resize = Some(resize_fn);
+        self
+    }
+}
+
+#[state_component_impl(ResizableState)]
+impl Component for Resizable {
+    fn view(&self) -> Option<Node> {
+        let size = self.state_ref().size;
+        let handle_size: f32 = self.style_val("handle_size").unwrap().f32();
+
+        let mut base = node!(
+            super::Div::new(),
+            lay!(size: size!(size.width, size.height))
+        );
+        for handle in self.edges.handles() {
+            // Center the handle on the edge it belongs to
+            let (left, width) = match handle.x {
+                -1 => (-handle_size / 2.0, handle_size),
+                1 => (size.width - handle_size / 2.0, handle_size),
+                _ => (handle_size / 2.0, size.width - handle_size),
+            };
+            let (top, height) = match handle.y {
+                -1 => (-handle_size / 2.0, handle_size),
+                1 => (size.height - handle_size / 2.0, handle_size),
+                _ => (handle_size / 2.0, size.height - handle_size),
+            };
+            base = base.push(node!(
+                ResizeHandle {
+                    handle,
+                    color: self.style_val("handle_color").into(),
+                },
+                lay!(
+                    position_type: PositionType::Absolute,
+                    position: rect!(top, left, Auto, Auto),
+                    size: size!(width.max(0.0), height.max(0.0)),
+                    z_index_increment: 100.0,
+                ),
+                ((handle.x + 1) * 3 + handle.y + 1) as u64
+            ));
+        }
+        Some(base)
+    }
+
+    fn container(&self) -> Option<Vec<usize>> {
+        Some(vec![0])
+    }
+
+    fn new_props(&mut self) {
+        self.state_mut().size = self.size;
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        (self.size.width as u32).hash(hasher);
+        (self.size.height as u32).hash(hasher);
+    }
+
+    // The hash only sees whole pixels, so compare the sizes exactly
+    fn props_eq(&self, prev: &dyn Any) -> Option<bool> {
+        prev.downcast_ref::<Self>()
+            .map(|prev| self.size == prev.size)
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<ResizeMessage>() {
+            Some(ResizeMessage::Start) => {
+                self.state_mut().drag_start_size = self.state_ref().size;
+                vec![]
+            }
+            Some(ResizeMessage::Resize {
+                handle,
+                delta,
+                keep_aspect,
+            }) => {
+                let size = resized(
+                    self.state_ref().drag_start_size,
+                    *handle,
+                    *delta,
+                    self.min_size,
+                    self.max_size,
+                    *keep_aspect,
+                );
+                let prev = self.state_ref().size;
+                if size == prev {
+                    return vec![];
+                }
+                self.state_mut().size = size;
+                let origin_delta = Point::new(
+                    if handle.x < 0 {
+                        prev.width - size.width
+                    } else {
+                        0.0
+                    },
+                    if handle.y < 0 {
+                        prev.height - size.height
+                    } else {
+                        0.0
+                    },
+                );
+                self.on_resize
+                    .iter()
+                    .map(|f| f(size, origin_delta))
+                    .collect()
+            }
+            None => vec![message],
+        }
+    }
+}
+
+//
+// ResizeHandle
+// The invisible (unless styled otherwise) areas that can be dragged
+#[derive(Debug)]
+struct ResizeHandle {
+    handle: Handle,
+    color: Color,
+}
+
+impl Component for ResizeHandle {
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        if self.color == Color::TRANSPARENT {
+            return None;
+        }
+        Some(vec![Renderable::Rect(Rect::new(
+            Pos::default(),
+            context.aabb.size(),
+            self.color,
+        ))])
+    }
+
+    fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
+        if let Some(w) = crate::current_window() {
+            w.set_cursor(self.handle.cursor());
+        }
+    }
+
+    fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        if let Some(w) = crate::current_window() {
+            w.unset_cursor();
+        }
+    }
+
+    fn on_drag_start(&mut self, event: &mut event::Event<event::DragStart>) {
+        event.stop_bubbling();
+        event.emit(Box::new(ResizeMessage::Start));
+    }
+
+    fn on_drag(&mut self, event: &mut event::Event<event::Drag>) {
+        event.emit(Box::new(ResizeMessage::Resize {
+            handle: self.handle,
+            delta: event.logical_delta(),
+            keep_aspect: event.modifiers_held.shift,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_corner() {
+        let mut resizable = Resizable::new(Scale::new(100.0, 100.0))
+            .max_size(Scale::new(120.0, 200.0))
+            .on_resize(Box::new(|s, _| msg!(s)));
+        let corner = Handle { x: 1, y: 1 };
+
+        resizable.update(Box::new(ResizeMessage::Start));
+        let messages = resizable.update(Box::new(ResizeMessage::Resize {
+            handle: corner,
+            delta: Point::new(10.0, 20.0),
+            keep_aspect: false,
+        }));
+        assert_eq!(
+            messages[0].downcast_ref::<Scale>(),
+            Some(&Scale::new(110.0, 120.0))
+        );
+        // Deltas are relative to the start of the drag, and clamped to the max size
+        resizable.update(Box::new(ResizeMessage::Resize {
+            handle: corner,
+            delta: Point::new(30.0, 20.0),
+            keep_aspect: false,
+        }));
+        assert_eq!(resizable.state_ref().size, Scale::new(120.0, 120.0));
+
+        // The child is laid out at the new size
+        let mut n = Node::new(Box::new(resizable), 0, Layout::default()).push(node!(
+            super::super::Div::new(),
+            lay!(size: size_pct!(100.0))
+        ));
+        n.view(None, &mut vec![]);
+        n.calculate_layout(&crate::font_cache::FontCache::default(), 1.0);
+        let child = n.children[0].children.last().unwrap();
+        assert_eq!(child.layout_result.size, size!(120.0, 120.0));
+
+        // Keeping the aspect ratio
+        assert_eq!(
+            resized(
+                Scale::new(100.0, 50.0),
+                Handle { x: 1, y: 0 },
+                Point::new(100.0, 0.0),
+                Scale::default(),
+                Scale::new(1000.0, 1000.0),
+                true
+            ),
+            Scale::new(200.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn test_resize_top_left() {
+        let mut resizable = Resizable::new(Scale::new(100.0, 100.0))
+            .min_size(Scale::new(80.0, 0.0))
+            .edges(ResizeEdges::all())
+            .on_resize(Box::new(|s, d| msg!((s, d))));
+        let corner = Handle { x: -1, y: -1 };
+
+        // Dragging up and to the left grows the size, and moves the origin by as much
+        resizable.update(Box::new(ResizeMessage::Start));
+        let messages = resizable.update(Box::new(ResizeMessage::Resize {
+            handle: corner,
+            delta: Point::new(-10.0, -20.0),
+            keep_aspect: false,
+        }));
+        assert_eq!(
+            messages[0].downcast_ref::<(Scale, Point)>(),
+            Some(&(Scale::new(110.0, 120.0), Point::new(-10.0, -20.0)))
+        );
+
+        // The origin delta is relative to the last message, and stops where the size is clamped
+        let messages = resizable.update(Box::new(ResizeMessage::Resize {
+            handle: corner,
+            delta: Point::new(30.0, 10.0),
+            keep_aspect: false,
+        }));
+        assert_eq!(
+            messages[0].downcast_ref::<(Scale, Point)>(),
+            Some(&(Scale::new(80.0, 90.0), Point::new(30.0, 30.0)))
+        );
+
+        // The right and bottom edges leave the origin in place
+        resizable.update(Box::new(ResizeMessage::Start));
+        let messages = resizable.update(Box::new(ResizeMessage::Resize {
+            handle: Handle { x: 1, y: 1 },
+            delta: Point::new(10.0, 10.0),
+            keep_aspect: false,
+        }));
+        assert_eq!(
+            messages[0].downcast_ref::<(Scale, Point)>(),
+            Some(&(Scale::new(90.0, 100.0), Point::new(0.0, 0.0)))
+        );
+    }
+}
@@ -46,6 +46,26 @@ impl RoundedRect {
     }
 }
 
+/// The rectangle to stroke, and the width of the stroke. When `pixel_snap` is set, the stroke is a whole number of device pixels wide and is drawn just inside the edges of the rectangle, so that it does not straddle two rows of pixels.
+fn border_geometry(
+    width: f32,
+    height: f32,
+    border_width: f32,
+    scale_factor: f32,
+    pixel_snap: bool,
+) -> (lyon_math::Rect, f32) {
+    if pixel_snap {
+        let border_width = (border_width * scale_factor.floor()).round().max(1.0);
+        let inset = border_width / 2.0;
+        (
+            lyon_math::rect(inset, inset, width - border_width, height - border_width),
+            border_width,
+        )
+    } else {
+        (lyon_math::rect(0.0, 0.0, width, height), border_width)
+    }
+}
+
 impl Component for RoundedRect {
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.background_color.hash(hasher);
@@ -59,7 +79,13 @@ impl Component for RoundedRect {
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
         let mut geometry = shape::ShapeGeometry::new();
-        let rect = lyon_math::rect(0.0, 0.0, context.aabb.width(), context.aabb.height());
+        let (rect, border_width) = border_geometry(
+            context.aabb.width(),
+            context.aabb.height(),
+            self.border_width,
+            context.scale_factor,
+            context.pixel_snap && self.border_width > 0.0,
+        );
         let radii = basic_shapes::BorderRadii {
             top_left: self.radius.0,
             top_right: self.radius.1,
@@ -96,7 +122,7 @@ impl Component for RoundedRect {
             fill_count.indices,
             self.background_color,
             self.border_color,
-            self.border_width * 0.5,
+            border_width * 0.5,
             0.0,
             &mut context.caches.shape_buffer.write().unwrap(),
             context.prev_state.as_ref().and_then(|v| match v.get(0) {
@@ -106,3 +132,37 @@ impl Component for RoundedRect {
         ))])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pixel_snapped_border() {
+        let scale_factor = 1.5;
+        // A 15 device pixel tall node, e.g. a 10px tall node at a scale factor of 1.5
+        let (rect, border_width) = border_geometry(30.0, 15.0, 1.0, scale_factor, true);
+        assert_eq!(border_width, 1.0);
+        // The top and bottom strokes each cover exactly one row of device pixels
+        let top = (
+            rect.min().y - border_width / 2.0,
+            rect.min().y + border_width / 2.0,
+        );
+        let bottom = (
+            rect.max().y - border_width / 2.0,
+            rect.max().y + border_width / 2.0,
+        );
+        assert_eq!(top, (0.0, 1.0));
+        assert_eq!(bottom, (14.0, 15.0));
+
+        // Without snapping, the stroke straddles the edge of the node
+        let (rect, border_width) = border_geometry(30.0, 15.0, 1.0, scale_factor, false);
+        assert_eq!(rect.min().y - border_width / 2.0, -0.5);
+
+        // Only a snapped stroke is scaled to whole device pixels
+        let (_, snapped) = border_geometry(30.0, 15.0, 1.0, 2.0, true);
+        let (_, unsnapped) = border_geometry(30.0, 15.0, 1.0, 2.0, false);
+        assert_eq!(snapped, 2.0);
+        assert_eq!(unsnapped, 1.0);
+    }
+}
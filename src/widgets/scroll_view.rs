@@ -0,0 +1,33 @@
+use crate::component::Component;
+use crate::{node, Node, Styled};
+use lemna_macros::component;
+
+use super::Div;
+
+/// A container that scrolls its children along both axes, with scroll bars and with shadows drawn on each edge that has content clipped beyond it.
+///
+/// `ScrollView` is styled with the same `"Scroll"` parameters as a scrolling [`Div`], and its children are pushed into that `Div`. The scroll shadows can be styled with `shadow_color` and `shadow_size`.
+#[component(Styled = "Scroll", Internal)]
+#[derive(Debug, Default)]
+pub struct ScrollView {}
+
+impl ScrollView {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Component for ScrollView {
+    fn view(&self) -> Option<Node> {
+        let mut div = Div::new();
+        *div.style_overrides_mut() = self.style_overrides.clone();
+        *div.classes_mut() = self.classes.clone();
+        let div = div.scroll_x().scroll_y().style("shadows", true);
+
+        Some(node!(div, lay!(size: size_pct!(100.0))))
+    }
+
+    fn container(&self) -> Option<Vec<usize>> {
+        Some(vec![0])
+    }
+}
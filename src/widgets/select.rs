@@ -1,11 +1,14 @@
 use std::hash::Hash;
 
+use super::popup::{Popover, PopoverAlign, PopoverSide};
+use crate::accessibility::{AccessibilityInfo, Role};
 use crate::base_types::*;
 use crate::component::{Component, ComponentHasher, Message, RenderContext};
 use crate::event;
+use crate::input::Key;
 use crate::layout::*;
 use crate::render::{renderables::shape::Shape, Renderable};
-use crate::style::{current_style, HorizontalPosition, Styled};
+use crate::style::{current_style, HorizontalPosition, Interaction, Styled};
 use crate::{node, txt, Node};
 use lemna_macros::{component, state_component_impl};
 
@@ -14,7 +17,75 @@ enum SelectMessage {
     OpenClose,
     Close,
     Hover(usize),
+    /// Move the highlight by this many entries, or open the list if it is closed
+    MoveHighlight(isize),
     Select(usize),
+    /// Choose the highlighted entry if the list is open, otherwise open it
+    Choose,
+    /// Highlight the next entry that starts with this character
+    TypeAhead(char),
+}
+
+/// The character typed with `key`, for the keys that type-ahead responds to.
+fn key_char(key: Key) -> Option<char> {
+    use Key::*;
+    let c = match key {
+        A => 'a',
+        B => 'b',
+        C => 'c',
+        D => 'd',
+        E => 'e',
+        F => 'f',
+        G => 'g',
+        H => 'h',
+        I => 'i',
+        J => 'j',
+        K => 'k',
+        L => 'l',
+        M => 'm',
+        N => 'n',
+        O => 'o',
+        P => 'p',
+        Q => 'q',
+        R => 'r',
+        S => 's',
+        T => 't',
+        U => 'u',
+        V => 'v',
+        W => 'w',
+        X => 'x',
+        Y => 'y',
+        Z => 'z',
+        D0 => '0',
+        D1 => '1',
+        D2 => '2',
+        D3 => '3',
+        D4 => '4',
+        D5 => '5',
+        D6 => '6',
+        D7 => '7',
+        D8 => '8',
+        D9 => '9',
+        _ => return None,
+    };
+    Some(c)
+}
+
+/// The index of the first of `options` after `from` that starts with `c`, ignoring case, wrapping around to the start.
+fn type_ahead<M: ToString>(options: &[M], from: usize, c: char) -> Option<usize> {
+    let starts_with = |i: &usize| {
+        options[*i]
+            .to_string()
+            .chars()
+            .next()
+            .map_or(false, |first| first.to_lowercase().eq(c.to_lowercase()))
+    };
+    if options.is_empty() {
+        return None;
+    }
+    (from + 1..options.len())
+        .chain(0..=from.min(options.len() - 1))
+        .find(starts_with)
 }
 
 //
@@ -35,6 +106,7 @@ where
     pub selection: Vec<M>,
     pub selected: usize,
     on_change: Option<Box<dyn Fn(usize, &M) -> Message + Send + Sync>>,
+    tool_tip: Option<String>,
 }
 
 impl<M: std::fmt::Debug + Send + Sync> std::fmt::Debug for Select<M> {
@@ -51,7 +123,8 @@ impl<M: ToString + Send + Sync> Select<M> {
             selection,
             selected,
             on_change: None,
-            class: Default::default(),
+            tool_tip: None,
+            classes: Default::default(),
             style_overrides: Default::default(),
             state: Some(SelectState::default()),
             dirty: false,
@@ -62,6 +135,28 @@ impl<M: ToString + Send + Sync> Select<M> {
         self.on_change = Some(change_fn);
         self
     }
+
+    /// Show a [`ToolTip`][super::ToolTip] with the text `t` when the Select is hovered over or focused with the keyboard.
+    pub fn tooltip(mut self, t: impl Into<String>) -> Self {
+        self.tool_tip = Some(t.into());
+        self
+    }
+
+    /// Open the list, highlighting the selected entry. Does nothing if it is already open.
+    fn open(&mut self) {
+        if !self.state_ref().open {
+            self.state_mut().hovering = self.state_ref().selected;
+            self.state_mut().open = true;
+        }
+    }
+
+    fn select(&mut self, i: usize) -> Vec<Message> {
+        self.state_mut().selected = i;
+        match &self.on_change {
+            Some(change_fn) => vec![change_fn(i, &self.selection[i])],
+            None => vec![],
+        }
+    }
 }
 
 #[state_component_impl(SelectState)]
@@ -73,7 +168,10 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + std::fmt::Display + Send
             node!(super::Div::new(), lay!(direction: Direction::Column)).push(node!(SelectBox {
                 selection: self.selection.get(self.state_ref().selected).cloned(),
                 style_overrides: self.style_overrides.clone(),
-                class: self.class,
+                classes: self.classes.clone(),
+                open: self.state_ref().open,
+                state: Some(SelectBoxState::default()),
+                dirty: false,
             }));
         if self.state_ref().open {
             base = base.push(node!(
@@ -81,7 +179,7 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + std::fmt::Display + Send
                     selections: self.selection.clone(),
                     hovering: self.state_ref().hovering,
                     style_overrides: self.style_overrides.clone(),
-                    class: self.class,
+                    classes: self.classes.clone(),
                 },
                 lay!(position_type: PositionType::Absolute, z_index_increment: 1000.0),
                 1
@@ -94,6 +192,10 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + std::fmt::Display + Send
         self.selected.hash(hasher);
     }
 
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tool_tip.as_deref()
+    }
+
     fn init(&mut self) {
         self.state_mut().selected = self.selected;
     }
@@ -115,13 +217,34 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + std::fmt::Display + Send
                 self.state_mut().open = !self.state_ref().open;
             }
             Some(SelectMessage::Close) => self.state_mut().open = false,
-            Some(SelectMessage::Select(i)) => {
-                self.state_mut().selected = *i;
-                if let Some(change_fn) = &self.on_change {
-                    m.push(change_fn(*i, &self.selection[*i]))
+            Some(SelectMessage::Select(i)) => m.append(&mut self.select(*i)),
+            Some(SelectMessage::Hover(i)) => self.state_mut().hovering = *i,
+            Some(SelectMessage::MoveHighlight(d)) => {
+                if !self.state_ref().open {
+                    self.open();
+                } else if !self.selection.is_empty() {
+                    let last = self.selection.len() as isize - 1;
+                    self.state_mut().hovering =
+                        (self.state_ref().hovering as isize + d).clamp(0, last) as usize;
+                }
+            }
+            Some(SelectMessage::Choose) => {
+                if self.state_ref().open {
+                    let i = self.state_ref().hovering;
+                    if i < self.selection.len() {
+                        m.append(&mut self.select(i));
+                    }
+                    self.state_mut().open = false;
+                } else {
+                    self.open();
+                }
+            }
+            Some(SelectMessage::TypeAhead(c)) => {
+                self.open();
+                if let Some(i) = type_ahead(&self.selection, self.state_ref().hovering, *c) {
+                    self.state_mut().hovering = i;
                 }
             }
-            Some(SelectMessage::Hover(i)) => self.state_mut().hovering = *i,
             _ => panic!(),
         }
         m
@@ -131,21 +254,51 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + std::fmt::Display + Send
 //
 // SelectBox
 // The base component you interact with. A button that shows selection state
-#[component(Styled = "Select", Internal)]
+#[derive(Debug, Default)]
+struct SelectBoxState {
+    interaction: Interaction,
+}
+
+#[component(State = "SelectBoxState", Styled = "Select", Internal)]
 #[derive(Debug)]
 struct SelectBox<M> {
     selection: Option<M>,
+    /// Is the list open?
+    open: bool,
 }
 
+#[state_component_impl(SelectBoxState)]
 impl<M: 'static + std::fmt::Debug + Clone + ToString> Component for SelectBox<M> {
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        let info = AccessibilityInfo::new(Role::PopupButton);
+        Some(match &self.selection {
+            Some(s) => info.value(s.to_string()),
+            None => info,
+        })
+    }
+
     fn view(&self) -> Option<Node> {
+        let interaction = self.state_ref().interaction;
         let padding: f64 = self.style_val("padding").unwrap().into();
         let radius: f32 = self.style_val("radius").unwrap().f32();
         let font_size: f32 = self.style_val("font_size").unwrap().f32();
-        let background_color: Color = self.style_val("background_color").into();
-        let border_color: Color = self.style_val("border_color").into();
-        let caret_color: Color = self.style_val("caret_color").into();
-        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let background_color: Color = self
+            .interactive_style_val("background_color", interaction)
+            .into();
+        let border_color: Color = self
+            .interactive_style_val("border_color", interaction)
+            .into();
+        let caret_color: Color = self
+            .interactive_style_val("caret_color", interaction)
+            .into();
+        let border_width: f32 = self
+            .interactive_style_val("border_width", interaction)
+            .unwrap()
+            .f32();
 
         let mut base = node!(
             super::RoundedRect {
@@ -166,7 +319,11 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString> Component for SelectBox<M>
             base = base
                 .push(node!(super::Text::new(txt!(selection.to_string()))
                     .style("size", self.style_val("font_size").unwrap())
-                    .style("color", self.style_val("text_color").unwrap())
+                    .style(
+                        "color",
+                        self.interactive_style_val("text_color", interaction)
+                            .unwrap()
+                    )
                     .style("h_alignment", HorizontalPosition::Center)
                     .maybe_style("font", self.style_val("font"))))
                 .push(node!(
@@ -185,15 +342,55 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString> Component for SelectBox<M>
         event.stop_bubbling();
     }
 
+    fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
+        self.state_mut().interaction.mouse_enter();
+    }
+
+    fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        self.state_mut().interaction.mouse_leave();
+    }
+
+    fn on_mouse_down(&mut self, _event: &mut event::Event<event::MouseDown>) {
+        self.state_mut().interaction.mouse_down();
+    }
+
+    fn on_mouse_up(&mut self, _event: &mut event::Event<event::MouseUp>) {
+        self.state_mut().interaction.mouse_up();
+    }
+
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
         event.focus();
         event.stop_bubbling();
         event.emit(Box::new(SelectMessage::OpenClose));
     }
 
+    fn on_focus(&mut self, _event: &mut event::Event<event::Focus>) {
+        self.state_mut().interaction.set_focused(true);
+    }
+
     fn on_blur(&mut self, event: &mut event::Event<event::Blur>) {
+        self.state_mut().interaction.set_focused(false);
         event.emit(Box::new(SelectMessage::Close));
     }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        let message = match event.input.0 {
+            Key::Up => SelectMessage::MoveHighlight(-1),
+            Key::Down => SelectMessage::MoveHighlight(1),
+            Key::Space | Key::Return => SelectMessage::Choose,
+            // Escape only belongs to the Select while its list is open
+            Key::Escape if self.open => SelectMessage::Close,
+            key => {
+                let m = event.modifiers_held;
+                match key_char(key) {
+                    Some(c) if !(m.ctrl || m.alt || m.meta) => SelectMessage::TypeAhead(c),
+                    _ => return,
+                }
+            }
+        };
+        event.stop_bubbling();
+        event.emit(Box::new(message));
+    }
 }
 
 #[derive(Debug)]
@@ -259,7 +456,9 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + Send + Sync> Component fo
                     id: i,
                     selected: i == self.hovering,
                     style_overrides: self.style_overrides.clone(),
-                    class: self.class,
+                    classes: self.classes.clone(),
+                    state: Some(SelectEntryState::default()),
+                    dirty: false,
                 })
                 .key(i as u64),
             );
@@ -282,37 +481,37 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + Send + Sync> Component fo
         if let Some((child_aabb, Some(inner_scale), _)) = children.first_mut() {
             let max_height: f32 = self.style_val("max_height").unwrap().f32();
             let bar_width: f32 = current_style("Scroll", "bar_width").unwrap().f32();
-            // Set size based on list elements and max_height
-            let mut h = inner_scale.height;
+            // Open below the Select if the list fits, otherwise above it, or wherever there is more room, scrolling if it is too long
+            let position = Popover::new(PopoverSide::Bottom, PopoverAlign::Start).position(
+                parent_aabb,
+                Scale::new(
+                    inner_scale.width,
+                    inner_scale.height.min(max_height * scale_factor),
+                ),
+                Popover::bounds(frame),
+            );
+            let h = position.height();
             let mut w = inner_scale.width;
-            if h > max_height * scale_factor {
-                h = max_height * scale_factor;
-                w = inner_scale.width + bar_width * scale_factor;
-            }
-
-            // Shrink if there isn't enough room
-            let room_above = parent_aabb.pos.y - frame.pos.y;
-            let room_bellow = frame.bottom_right.y - parent_aabb.bottom_right.y;
-            if h > room_bellow && h > room_above {
-                h = room_bellow.max(room_above);
-                w = inner_scale.width + bar_width * scale_factor;
+            if h < inner_scale.height {
+                w += bar_width * scale_factor;
             }
 
+            aabb.translate_mut(position.pos.x - aabb.pos.x, position.pos.y - aabb.pos.y);
             aabb.set_scale_mut(w, h);
             child_aabb.set_scale_mut(w, h);
         }
-
-        if aabb.bottom_right.y > frame.bottom_right.y {
-            // Flip up if there isn't enough room underneath
-            aabb.translate_mut(0.0, -parent_aabb.height() - aabb.height());
-        }
     }
 }
 
 //
 // SelectEntry
 // An individual entry within a SelectList
-#[component(Styled = "Select", Internal)]
+#[derive(Debug, Default)]
+struct SelectEntryState {
+    interaction: Interaction,
+}
+
+#[component(State = "SelectEntryState", Styled = "Select", Internal)]
 #[derive(Debug)]
 struct SelectEntry<M>
 where
@@ -320,24 +519,51 @@ where
 {
     selection: M,
     id: usize,
+    /// Highlighted, either by the mouse or the keyboard
     selected: bool,
 }
 
+impl<M> SelectEntry<M>
+where
+    M: Send + Sync,
+{
+    /// Entries are hovered when they are highlighted, whether by the mouse or the keyboard
+    fn interaction(&self) -> Interaction {
+        Interaction {
+            hover: self.selected,
+            ..self.state_ref().interaction
+        }
+    }
+
+    /// Entries are only given a background while they are interacted with, otherwise the list's shows through
+    fn background_color(&self) -> Option<Color> {
+        let interaction = self.interaction();
+        (!interaction.is_idle()).then(|| {
+            self.interactive_style_val("background_color", interaction)
+                .into()
+        })
+    }
+}
+
+#[state_component_impl(SelectEntryState)]
 impl<M: 'static + std::fmt::Debug + Clone + ToString + Send + Sync> Component for SelectEntry<M> {
     fn view(&self) -> Option<Node> {
         let padding: f64 = self.style_val("padding").unwrap().into();
-        let highlight_color: Color = self.style_val("highlight_color").into();
 
         let mut div = super::Div::new();
-        if self.selected {
-            div = div.bg(highlight_color)
+        if let Some(color) = self.background_color() {
+            div = div.bg(color)
         }
 
         Some(
             node!(div, lay!(size: size_pct!(100.0), padding: rect!(padding))).push(node!(
                 super::Text::new(txt!(self.selection.to_string()))
                     .style("size", self.style_val("font_size").unwrap())
-                    .style("color", self.style_val("text_color").unwrap())
+                    .style(
+                        "color",
+                        self.interactive_style_val("text_color", self.interaction())
+                            .unwrap()
+                    )
                     .style("h_alignment", HorizontalPosition::Center)
                     .maybe_style("font", self.style_val("font"))
             )),
@@ -345,6 +571,10 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + Send + Sync> Component fo
     }
 
     fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
+        // Moving the mouse takes the highlight back from the keyboard
+        if !self.selected {
+            event.emit(Box::new(SelectMessage::Hover(self.id)));
+        }
         event.stop_bubbling();
     }
 
@@ -352,9 +582,190 @@ impl<M: 'static + std::fmt::Debug + Clone + ToString + Send + Sync> Component fo
         event.emit(Box::new(SelectMessage::Hover(self.id)));
     }
 
+    fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        self.state_mut().interaction.mouse_leave();
+    }
+
+    fn on_mouse_down(&mut self, _event: &mut event::Event<event::MouseDown>) {
+        self.state_mut().interaction.mouse_down();
+    }
+
+    fn on_mouse_up(&mut self, _event: &mut event::Event<event::MouseUp>) {
+        self.state_mut().interaction.mouse_up();
+    }
+
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
         event.stop_bubbling();
         event.emit(Box::new(SelectMessage::Select(self.id)));
         event.emit(Box::new(SelectMessage::Close));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        let mut select = Select::new(vec!["a", "b", "c"], 0);
+        select.update(Box::new(SelectMessage::OpenClose));
+        assert_eq!(select.state_ref().hovering, 0);
+
+        // Hovering an option highlights it
+        select.update(Box::new(SelectMessage::Hover(2)));
+        assert_eq!(select.state_ref().hovering, 2);
+
+        // The keyboard moves the highlight from wherever it is
+        select.update(Box::new(SelectMessage::MoveHighlight(-1)));
+        assert_eq!(select.state_ref().hovering, 1);
+        select.update(Box::new(SelectMessage::MoveHighlight(-1)));
+        select.update(Box::new(SelectMessage::MoveHighlight(-1)));
+        assert_eq!(select.state_ref().hovering, 0);
+
+        // And the mouse takes it back
+        select.update(Box::new(SelectMessage::Hover(1)));
+        assert_eq!(select.state_ref().hovering, 1);
+        // The selection is unchanged
+        assert_eq!(select.state_ref().selected, 0);
+
+        let mut entry = SelectEntry {
+            selection: "b",
+            id: 1,
+            selected: true,
+            style_overrides: Default::default(),
+            classes: vec![],
+            state: Some(SelectEntryState::default()),
+            dirty: false,
+        };
+        assert_eq!(
+            entry.background_color(),
+            Some(entry.style_val("background_color:hover").into())
+        );
+        entry.state_mut().interaction.mouse_down();
+        assert_eq!(
+            entry.background_color(),
+            Some(entry.style_val("background_color:pressed").into())
+        );
+        entry.selected = false;
+        entry.state_mut().interaction.mouse_up();
+        assert_eq!(entry.background_color(), None);
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let mut select = Select::new(vec!["apple", "Banana", "cherry", "blueberry"], 0);
+        let cache = event::EventCache::new(1.0);
+        let mut select_box = SelectBox {
+            selection: Some("apple"),
+            open: false,
+            style_overrides: Default::default(),
+            classes: vec![],
+            state: Some(SelectBoxState::default()),
+            dirty: false,
+        };
+        let mut press = |select: &mut Select<&'static str>, key: Key| {
+            select_box.open = select.state_ref().open;
+            let mut event = event::Event::new(event::KeyDown(key), &cache);
+            select_box.on_key_down(&mut event);
+            event
+                .messages
+                .drain(..)
+                .flat_map(|m| select.update(m))
+                .count()
+        };
+
+        // Typing a letter opens the list and highlights the next option starting with it
+        press(&mut select, Key::B);
+        assert!(select.state_ref().open);
+        assert_eq!(select.state_ref().hovering, 1);
+        press(&mut select, Key::B);
+        assert_eq!(select.state_ref().hovering, 3);
+        // Wrapping around
+        press(&mut select, Key::B);
+        assert_eq!(select.state_ref().hovering, 1);
+        // Letters without a match leave the highlight where it is
+        press(&mut select, Key::Z);
+        assert_eq!(select.state_ref().hovering, 1);
+
+        // Escape closes it without changing the selection
+        press(&mut select, Key::Escape);
+        assert!(!select.state_ref().open);
+        assert_eq!(select.state_ref().selected, 0);
+
+        // Down opens it on the selection, and Return chooses the highlighted option
+        press(&mut select, Key::Down);
+        assert!(select.state_ref().open);
+        assert_eq!(select.state_ref().hovering, 0);
+        press(&mut select, Key::Down);
+        press(&mut select, Key::Down);
+        press(&mut select, Key::Up);
+        assert_eq!(select.state_ref().hovering, 1);
+        press(&mut select, Key::Return);
+        assert!(!select.state_ref().open);
+        assert_eq!(select.state_ref().selected, 1);
+
+        // Space opens it too
+        press(&mut select, Key::Space);
+        assert!(select.state_ref().open);
+        assert_eq!(select.state_ref().hovering, 1);
+    }
+
+    #[test]
+    fn test_flip() {
+        let mut list = SelectList {
+            selections: vec!["a", "b", "c"],
+            hovering: 0,
+            style_overrides: Default::default(),
+            classes: vec![],
+        };
+        let frame = AABB::new(Pos::default(), Scale::new(400.0, 400.0));
+        let list_height = 100.0;
+        let mut child = AABB::new(Pos::default(), Scale::new(80.0, list_height));
+        let set_aabb = |list: &mut SelectList<&'static str>, parent: AABB, child: &mut AABB| {
+            // The list starts out right under the Select
+            let mut aabb = AABB::new(
+                Pos::new(parent.pos.x, parent.bottom_right.y, 0.0),
+                Scale::new(80.0, list_height),
+            );
+            list.set_aabb(
+                &mut aabb,
+                parent,
+                vec![(child, Some(Scale::new(80.0, list_height)), None)],
+                frame,
+                1.0,
+            );
+            aabb
+        };
+
+        // With room underneath, the list opens downward
+        let select = AABB::new(Pos::new(10.0, 10.0, 0.0), Scale::new(80.0, 20.0));
+        let aabb = set_aabb(&mut list, select, &mut child);
+        assert_eq!(aabb.pos.y, select.bottom_right.y);
+        assert_eq!(aabb.height(), list_height);
+
+        // At the bottom of the window, it opens upward
+        let select = AABB::new(Pos::new(10.0, 370.0, 0.0), Scale::new(80.0, 20.0));
+        let aabb = set_aabb(&mut list, select, &mut child);
+        assert!(aabb.pos.y < select.pos.y);
+        assert_eq!(aabb.bottom_right.y, select.pos.y);
+        assert_eq!(aabb.height(), list_height);
+
+        // When it fits on neither side, it takes the larger one and scrolls
+        let frame_height = list_height;
+        let select = AABB::new(Pos::new(10.0, 20.0, 0.0), Scale::new(80.0, 20.0));
+        let mut aabb = AABB::new(
+            Pos::new(select.pos.x, select.bottom_right.y, 0.0),
+            Scale::new(80.0, list_height),
+        );
+        list.set_aabb(
+            &mut aabb,
+            select,
+            vec![(&mut child, Some(Scale::new(80.0, list_height)), None)],
+            AABB::new(Pos::default(), Scale::new(400.0, frame_height)),
+            1.0,
+        );
+        assert_eq!(aabb.pos.y, select.bottom_right.y);
+        assert_eq!(aabb.bottom_right.y, frame_height);
+        assert!(aabb.width() > 80.0);
+    }
+}
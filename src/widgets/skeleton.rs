@@ -0,0 +1,204 @@
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use lyon::tessellation;
+use lyon::tessellation::basic_shapes;
+use lyon::tessellation::math as lyon_math;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::event;
+use crate::render::{
+    renderables::{
+        shape::{self, Shape},
+        Rect,
+    },
+    Renderable,
+};
+use crate::style::Styled;
+use lemna_macros::{component, state_component_impl};
+
+/// Number of bands on either side of the middle of the shimmer, used to draw its gradient
+const SHIMMER_STEPS: usize = 4;
+
+#[derive(Debug)]
+struct SkeletonState {
+    started: Instant,
+    /// How far the shimmer has moved across the skeleton, from 0 to 1. `None` when the shimmer is not shown.
+    highlight: Option<f32>,
+}
+
+impl Default for SkeletonState {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            highlight: None,
+        }
+    }
+}
+
+/// A placeholder for content that is still loading: a rounded rectangle with a shimmer that sweeps across it. It takes its size from its layout, so it can stand in for the content that will replace it:
+///
+/// ```ignore
+/// node!(Skeleton::new(), lay!(size: size!(200.0, 14.0)))
+/// ```
+///
+/// The shimmer is not animated when the user prefers reduced motion.
+#[component(State = "SkeletonState", Styled, Internal)]
+#[derive(Debug)]
+pub struct Skeleton {}
+
+impl Skeleton {
+    pub fn new() -> Self {
+        Self {
+            state: Some(SkeletonState::default()),
+            dirty: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// Where the shimmer is at `elapsed` into the animation, from 0 to 1.
+    fn highlight_at(&self, elapsed: Duration) -> f32 {
+        let period = self.style_val("period").unwrap().f32().max(0.01);
+        (elapsed.as_secs_f32() % period) / period
+    }
+}
+
+impl Default for Skeleton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[state_component_impl(SkeletonState)]
+impl Component for Skeleton {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        let color: Color = self.style_val("color").into();
+        let highlight_color: Color = self.style_val("highlight_color").into();
+        color.hash(hasher);
+        highlight_color.hash(hasher);
+        self.style_val("radius")
+            .unwrap()
+            .f32()
+            .to_bits()
+            .hash(hasher);
+        self.style_val("shimmer_width")
+            .unwrap()
+            .f32()
+            .to_bits()
+            .hash(hasher);
+        self.state_ref().highlight.map(f32::to_bits).hash(hasher);
+    }
+
+    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
+        let highlight = if event.reduce_motion() {
+            None
+        } else {
+            Some(self.highlight_at(self.state_ref().started.elapsed()))
+        };
+        if highlight != self.state_ref().highlight {
+            self.state_mut().highlight = highlight;
+        }
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let color: Color = self.style_val("color").into();
+        let radius = self.style_val("radius").unwrap().f32() * context.scale_factor;
+        let width = context.aabb.width();
+        let height = context.aabb.height();
+
+        let mut geometry = shape::ShapeGeometry::new();
+        let fill_count = basic_shapes::fill_rounded_rectangle(
+            &lyon_math::rect(0.0, 0.0, width, height),
+            &basic_shapes::BorderRadii {
+                top_left: radius,
+                top_right: radius,
+                bottom_right: radius,
+                bottom_left: radius,
+            },
+            &tessellation::FillOptions::tolerance(shape::TOLERANCE),
+            &mut tessellation::BuffersBuilder::new(
+                &mut geometry,
+                shape::Vertex::basic_vertex_constructor,
+            ),
+        )
+        .unwrap();
+        let mut rs = vec![Renderable::Shape(Shape::new(
+            geometry,
+            fill_count.indices,
+            color,
+            color,
+            0.0,
+            0.0,
+            &mut context.caches.shape_buffer.write().unwrap(),
+            context.prev_state.as_ref().and_then(|v| match v.get(0) {
+                Some(Renderable::Shape(r)) => Some(r.buffer_id),
+                _ => None,
+            }),
+        ))];
+
+        if let Some(highlight) = self.state_ref().highlight {
+            // Sweep from just before the left edge to just past the right one, fading out from the middle
+            let highlight_color: Color = self.style_val("highlight_color").into();
+            let shimmer_width =
+                self.style_val("shimmer_width").unwrap().f32() * context.scale_factor;
+            let step = shimmer_width / (SHIMMER_STEPS * 2) as f32;
+            let middle = highlight * (width + shimmer_width) - shimmer_width / 2.0;
+            for i in 0..SHIMMER_STEPS {
+                let color = Color {
+                    a: highlight_color.a * (1.0 - i as f32 / SHIMMER_STEPS as f32),
+                    ..highlight_color
+                };
+                let offset = step * i as f32;
+                for x in [middle + offset, middle - offset - step] {
+                    let start = x.max(0.0);
+                    let end = (x + step).min(width);
+                    if end > start {
+                        rs.push(Renderable::Rect(Rect::new(
+                            Pos {
+                                x: start,
+                                y: 0.0,
+                                z: 0.1,
+                            },
+                            Scale {
+                                width: end - start,
+                                height,
+                            },
+                            color,
+                        )));
+                    }
+                }
+            }
+        }
+
+        Some(rs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shimmer() {
+        let mut skeleton = Skeleton::new();
+        let mut cache = event::EventCache::new(1.0);
+        let tick_after = |skeleton: &mut Skeleton, cache: &event::EventCache, ms: u64| {
+            skeleton.state_mut().started = Instant::now() - Duration::from_millis(ms);
+            skeleton.on_tick(&mut event::Event::new(event::Tick, cache));
+            skeleton.state_ref().highlight
+        };
+
+        let period = (skeleton.style_val("period").unwrap().f32() * 1000.0) as u64;
+        let first = tick_after(&mut skeleton, &cache, 0).unwrap();
+        let second = tick_after(&mut skeleton, &cache, period / 4).unwrap();
+        let third = tick_after(&mut skeleton, &cache, period / 2).unwrap();
+        assert!(first < second && second < third);
+
+        // The shimmer stands still when motion is reduced
+        cache.reduce_motion = true;
+        assert_eq!(tick_after(&mut skeleton, &cache, 0), None);
+        assert_eq!(tick_after(&mut skeleton, &cache, period / 2), None);
+    }
+}
@@ -0,0 +1,174 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use lyon::path::Path;
+use lyon::tessellation::math as lyon_math;
+
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, RenderContext};
+use crate::event;
+use crate::font_cache::FontCache;
+use crate::render::{
+    renderables::shape::{LineCap, Shape, StrokeStyle},
+    Renderable,
+};
+use crate::style::Styled;
+use lemna_macros::{component, state_component_impl};
+
+#[derive(Debug)]
+struct SpinnerState {
+    started: Instant,
+    /// How far the arc has turned, in radians clockwise from the top.
+    angle: f32,
+}
+
+impl Default for SpinnerState {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            angle: 0.0,
+        }
+    }
+}
+
+/// An indicator for work of unknown length: a partial arc that spins around its center. It is `size` logical pixels across, unless its layout says otherwise, and makes a full turn every `period` seconds:
+///
+/// ```ignore
+/// node!(Spinner::new().style("size", 32.0).style("period", 0.8))
+/// ```
+///
+/// The arc stands still when the user prefers reduced motion.
+#[component(State = "SpinnerState", Styled, Internal)]
+#[derive(Debug)]
+pub struct Spinner {}
+
+impl Spinner {
+    pub fn new() -> Self {
+        Self {
+            state: Some(SpinnerState::default()),
+            dirty: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// How far the arc has turned `elapsed` into the animation, from 0 to a full turn.
+    fn angle_at(&self, elapsed: Duration) -> f32 {
+        let period = self.style_val("period").unwrap().f32().max(0.01);
+        (elapsed.as_secs_f32() % period) / period * TAU
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[state_component_impl(SpinnerState)]
+impl Component for Spinner {
+    fn render_hash(&self, hasher: &mut ComponentHasher) {
+        let color: Color = self.style_val("color").into();
+        color.hash(hasher);
+        for param in ["stroke_width", "sweep"] {
+            self.style_val(param).unwrap().f32().to_bits().hash(hasher);
+        }
+        self.state_ref().angle.to_bits().hash(hasher);
+    }
+
+    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
+        let angle = if event.reduce_motion() {
+            0.0
+        } else {
+            self.angle_at(self.state_ref().started.elapsed())
+        };
+        if angle != self.state_ref().angle {
+            self.state_mut().angle = angle;
+        }
+    }
+
+    fn fill_bounds(
+        &mut self,
+        width: Option<f32>,
+        height: Option<f32>,
+        _max_width: Option<f32>,
+        _max_height: Option<f32>,
+        _font_cache: &FontCache,
+        _scale_factor: f32,
+    ) -> (Option<f32>, Option<f32>) {
+        let size = self.style_val("size").unwrap().f32();
+        (Some(width.unwrap_or(size)), Some(height.unwrap_or(size)))
+    }
+
+    fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
+        let color: Color = self.style_val("color").into();
+        let stroke_width = self.style_val("stroke_width").unwrap().f32() * context.scale_factor;
+        // The fraction of a full turn that the arc covers
+        let sweep = self.style_val("sweep").unwrap().f32().clamp(0.01, 1.0) * TAU;
+        let center = lyon_math::point(context.aabb.width() / 2.0, context.aabb.height() / 2.0);
+        let radius = (center.x.min(center.y) - stroke_width / 2.0).max(0.0);
+        if radius <= 0.0 {
+            return None;
+        }
+
+        let start = self.state_ref().angle - FRAC_PI_2;
+        let mut builder = Path::builder();
+        builder.move_to(center + lyon_math::vector(start.cos(), start.sin()) * radius);
+        builder.arc(
+            center,
+            lyon_math::vector(radius, radius),
+            lyon_math::Angle::radians(sweep),
+            lyon_math::Angle::radians(0.0),
+        );
+        let (geometry, fill_count) = Shape::path_to_styled_shape_geometry(
+            builder.build(),
+            false,
+            &StrokeStyle::default().line_cap(LineCap::Round),
+            stroke_width,
+            context.scale_factor,
+        );
+
+        Some(vec![Renderable::Shape(Shape::new(
+            geometry,
+            fill_count,
+            color,
+            color,
+            stroke_width * 0.5,
+            0.0,
+            &mut context.caches.shape_buffer.write().unwrap(),
+            context.prev_state.as_ref().and_then(|v| match v.get(0) {
+                Some(Renderable::Shape(r)) => Some(r.buffer_id),
+                _ => None,
+            }),
+        ))])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spin() {
+        let mut spinner = Spinner::new();
+        let mut cache = event::EventCache::new(1.0);
+        let tick_after = |spinner: &mut Spinner, cache: &event::EventCache, ms: u64| {
+            spinner.state_mut().started = Instant::now() - Duration::from_millis(ms);
+            spinner.on_tick(&mut event::Event::new(event::Tick, cache));
+            spinner.state_ref().angle
+        };
+
+        let period = (spinner.style_val("period").unwrap().f32() * 1000.0) as u64;
+        let first = tick_after(&mut spinner, &cache, 0);
+        let second = tick_after(&mut spinner, &cache, period / 4);
+        let third = tick_after(&mut spinner, &cache, period / 2);
+        assert!(first < second && second < third);
+        assert!((third - std::f32::consts::PI).abs() < 0.1, "{}", third);
+
+        // The arc stands still when motion is reduced
+        cache.reduce_motion = true;
+        assert_eq!(tick_after(&mut spinner, &cache, period / 4), 0.0);
+        assert_eq!(tick_after(&mut spinner, &cache, period / 2), 0.0);
+    }
+}
@@ -1,8 +1,10 @@
 use std::hash::Hash;
 
+use crate::accessibility::{AccessibilityInfo, Role};
 use crate::base_types::*;
-use crate::component::{Component, ComponentHasher, RenderContext};
-use crate::font_cache::{FontCache, TextSegment};
+use crate::component::{Component, ComponentHasher, Message, RenderContext};
+use crate::event;
+use crate::font_cache::{FontCache, SectionGlyph, TextSegment};
 use crate::render::{renderables::text, Renderable};
 use crate::style::{HorizontalPosition, Styled};
 use lemna_macros::{component, state_component_impl};
@@ -14,37 +16,146 @@ struct BoundsCache {
     max_width: Option<f32>,
     max_height: Option<f32>,
     output: Option<(Option<f32>, Option<f32>)>,
+    baseline: Option<f32>,
 }
 
 #[derive(Debug, Default)]
 pub struct TextState {
     bounds_cache: BoundsCache,
+    /// The physical area covered by each run of linked text, relative to the Node, and its URL.
+    links: Vec<(AABB, String)>,
+    over_link: bool,
 }
 
+/// Displays [`TextSegment`]s. Segments with a [`link`][TextSegment#method.link] can be clicked, which emits the message returned by [`#on_link`][Text#method.on_link]:
+///
+/// ```ignore
+/// Text::new(vec!["See the ".into(), TextSegment::from("docs").link("https://docs.rs/lemna")])
+///     .on_link(Box::new(|url| msg!(AppMsg::Open(url.to_string()))))
+/// ```
 #[component(State = "TextState", Styled, Internal)]
-#[derive(Debug)]
 pub struct Text {
     pub text: Vec<TextSegment>,
+    pub on_link: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+}
+
+impl std::fmt::Debug for Text {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Text").field("text", &self.text).finish()
+    }
 }
 
 impl Text {
     pub fn new(text: Vec<TextSegment>) -> Self {
         Self {
             text,
-            class: Default::default(),
+            on_link: None,
+            classes: Default::default(),
             style_overrides: Default::default(),
             state: Some(TextState::default()),
             dirty: false,
         }
     }
+
+    /// Called with the URL of a linked segment when it is clicked.
+    pub fn on_link(mut self, link_fn: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
+        self.on_link = Some(link_fn);
+        self
+    }
+
+    /// The area covered by each run of linked glyphs, one per line that the run is on, with its URL.
+    fn link_regions(&self, glyphs: &[SectionGlyph], font_cache: &FontCache) -> Vec<(AABB, String)> {
+        let mut regions: Vec<(AABB, String)> = vec![];
+        let mut last_section = None;
+        for g in glyphs.iter() {
+            let link = match self.text.get(g.section_index).and_then(|s| s.link.as_ref()) {
+                Some(link) => link,
+                None => {
+                    last_section = None;
+                    continue;
+                }
+            };
+            let bounds = font_cache.glyph_bounds(g);
+            match regions.last_mut() {
+                // Extend the run, unless it has wrapped onto a new line
+                Some((region, _))
+                    if last_section == Some(g.section_index) && region.pos.y == bounds.pos.y =>
+                {
+                    region.bottom_right.x = bounds.bottom_right.x;
+                }
+                _ => regions.push((bounds, link.clone())),
+            }
+            last_section = Some(g.section_index);
+        }
+        regions
+    }
+
+    /// The URL of the link at the physical `position`, relative to the Node.
+    /// The `h_alignment` style, mirrored when the current locale is right-to-left.
+    fn h_alignment(&self) -> HorizontalPosition {
+        crate::locale::text_direction()
+            .align(self.style_val("h_alignment").unwrap().horizontal_position())
+    }
+
+    fn link_at(&self, position: Point) -> Option<&str> {
+        self.state_ref()
+            .links
+            .iter()
+            .find(|(region, _)| region.is_under(position))
+            .map(|(_, link)| link.as_str())
+    }
 }
 
 #[state_component_impl(TextState)]
 impl Component for Text {
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::StaticText).name(AccessibilityInfo::text(&self.text)))
+    }
+
     fn new_props(&mut self) {
         self.state = Some(TextState::default());
     }
 
+    fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
+        if self.state_ref().links.is_empty() {
+            return;
+        }
+        let over_link = self.link_at(event.relative_physical_position()).is_some();
+        if over_link != self.state_ref().over_link {
+            if let Some(w) = crate::current_window() {
+                if over_link {
+                    w.set_cursor(crate::Cursor::PointingHand);
+                } else {
+                    w.unset_cursor();
+                }
+            }
+            // Hovering a link should not trigger a redraw
+            let dirty = self.dirty;
+            self.state_mut().over_link = over_link;
+            self.dirty = dirty;
+        }
+    }
+
+    fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        if self.state_ref().over_link {
+            if let Some(w) = crate::current_window() {
+                w.unset_cursor();
+            }
+            let dirty = self.dirty;
+            self.state_mut().over_link = false;
+            self.dirty = dirty;
+        }
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        if let Some(f) = &self.on_link {
+            if let Some(url) = self.link_at(event.relative_physical_position()) {
+                event.emit(f(url));
+                event.stop_bubbling();
+            }
+        }
+    }
+
     fn props_hash(&self, hasher: &mut ComponentHasher) {
         self.text.hash(hasher);
     }
@@ -54,7 +165,7 @@ impl Component for Text {
         (self.style_val("size").unwrap().f32() as u32).hash(hasher);
         (self.style_val("color").unwrap().color()).hash(hasher);
         (self.style_val("font").map(|p| p.str().to_string())).hash(hasher);
-        (self.style_val("h_alignment").unwrap().horizontal_position()).hash(hasher);
+        self.h_alignment().hash(hasher);
     }
 
     fn fill_bounds(
@@ -112,25 +223,32 @@ impl Component for Text {
         } else {
             (None, None)
         };
+        // Glyphs are positioned by their baseline
+        let baseline = glyphs.first().map(|g| g.glyph.position.y / scale);
         self.state_mut().bounds_cache = BoundsCache {
             width,
             height,
             max_width,
             max_height,
             output: Some(output),
+            baseline,
         };
         output
     }
 
+    fn baseline(&self) -> Option<f32> {
+        self.state_ref().bounds_cache.baseline
+    }
+
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
-        let h_alignment: HorizontalPosition =
-            self.style_val("h_alignment").unwrap().horizontal_position();
+        let h_alignment = self.h_alignment();
         let font = self.style_val("font").map(|p| p.str().to_string());
         let color: Color = self.style_val("color").into();
         let bounds = context.aabb.size();
         let size: f32 = self.style_val("size").unwrap().f32();
 
-        let glyphs = context.caches.font.read().unwrap().layout_text(
+        let font_cache = context.caches.font.read().unwrap();
+        let glyphs = font_cache.layout_text(
             &self.text,
             font.as_deref(),
             size,
@@ -138,6 +256,14 @@ impl Component for Text {
             h_alignment,
             (bounds.width, bounds.height),
         );
+        if self.text.iter().any(|s| s.link.is_some()) {
+            let links = self.link_regions(&glyphs, &font_cache);
+            // Finding the links should not trigger a redraw
+            let dirty = self.dirty;
+            self.state_mut().links = links;
+            self.dirty = dirty;
+        }
+        drop(font_cache);
 
         if glyphs.is_empty() {
             Some(vec![])
@@ -155,3 +281,67 @@ impl Component for Text {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::MouseButton;
+
+    #[test]
+    fn test_link_click() {
+        let mut font_cache = FontCache::default();
+        font_cache.add_font("noto sans regular".to_string(), ttf_noto_sans::REGULAR);
+        let mut text = Text::new(vec![
+            "Read the ".into(),
+            TextSegment::from("docs").link("https://example.com"),
+            " first".into(),
+        ])
+        .on_link(Box::new(|url| Box::new(url.to_string())));
+
+        let glyphs = font_cache.layout_text(
+            &text.text,
+            None,
+            12.0,
+            1.0,
+            HorizontalPosition::Left,
+            (f32::MAX, f32::MAX),
+        );
+        let links = text.link_regions(&glyphs, &font_cache);
+        assert_eq!(links.len(), 1);
+        let (region, url) = links[0].clone();
+        assert_eq!(url, "https://example.com");
+        text.state_mut().links = links;
+
+        let click = |text: &mut Text, position: Point| {
+            let mut event = event::Event::new(
+                event::Click(MouseButton::Left),
+                &event::EventCache::new(1.0),
+            );
+            event.current_aabb = Some(AABB::default());
+            event.mouse_position = position;
+            text.on_click(&mut event);
+            event
+                .messages
+                .pop()
+                .map(|m| *m.downcast::<String>().unwrap())
+        };
+        let middle = Point::new(
+            (region.pos.x + region.bottom_right.x) / 2.0,
+            (region.pos.y + region.bottom_right.y) / 2.0,
+        );
+        assert_eq!(
+            click(&mut text, middle),
+            Some("https://example.com".to_string())
+        );
+        // Over "Read", before the link
+        assert_eq!(click(&mut text, Point::new(1.0, middle.y)), None);
+        // Over " first", after the link
+        assert_eq!(
+            click(
+                &mut text,
+                Point::new(region.bottom_right.x + 10.0, middle.y)
+            ),
+            None
+        );
+    }
+}
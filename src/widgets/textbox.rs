@@ -2,6 +2,9 @@ use std::cmp::Ordering;
 use std::hash::Hash;
 use std::time::Instant;
 
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::accessibility::{AccessibilityInfo, Role};
 use crate::base_types::*;
 use crate::component::{Component, ComponentHasher, Message, RenderContext};
 use crate::event;
@@ -12,11 +15,152 @@ use crate::render::{
     renderables::{Rect, Text},
     Renderable,
 };
-use crate::style::{HorizontalPosition, Styled};
+use crate::style::{HorizontalPosition, Interaction, Styled};
 use crate::{node, Node};
 use lemna_macros::{component, state_component_impl};
 
-const CURSOR_BLINK_PERIOD: u128 = 500; // millis
+/// Is the cursor visible `elapsed` millis after it was last moved? It stays visible for the first `period`, then toggles every `period`. A `period` of 0 disables blinking.
+fn cursor_blink_visible(elapsed: u128, period: u128) -> bool {
+    period == 0 || (elapsed / period) % 2 == 0
+}
+
+/// A pattern that a [`TextBox`] formats its text with, as it is typed.
+///
+/// In the pattern, `#` accepts a digit, `?` accepts a letter, and `*` accepts any alphanumeric character. Any other character is a separator that is inserted automatically, e.g. `"(###) ###-####"`. Typed characters that don't fit the next slot are dropped, and nothing beyond the last slot is accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputMask {
+    pattern: Vec<char>,
+}
+
+impl InputMask {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.chars().collect(),
+        }
+    }
+
+    fn accepts(slot: char, c: char) -> Option<bool> {
+        match slot {
+            '#' => Some(c.is_ascii_digit()),
+            '?' => Some(c.is_alphabetic()),
+            '*' => Some(c.is_alphanumeric()),
+            _ => None,
+        }
+    }
+
+    fn is_slot(c: char) -> bool {
+        Self::accepts(c, c).is_some()
+    }
+
+    /// The characters of `formatted` text that fill the mask's slots, i.e. without separators.
+    pub fn raw(&self, formatted: &str) -> String {
+        formatted
+            .chars()
+            .zip(self.pattern.iter())
+            .filter(|(_, p)| Self::is_slot(**p))
+            .map(|(c, _)| c)
+            .collect()
+    }
+
+    /// Format `raw` according to the mask, dropping characters that don't fit. Separators are only inserted before a slot that is filled.
+    pub fn format(&self, raw: &str) -> String {
+        let mut chars = raw.chars();
+        let mut formatted = String::new();
+        let mut pending = String::new();
+        for &p in self.pattern.iter() {
+            if Self::is_slot(p) {
+                match chars.find(|c| Self::accepts(p, *c) == Some(true)) {
+                    Some(c) => {
+                        formatted.push_str(&pending);
+                        pending.clear();
+                        formatted.push(c);
+                    }
+                    None => break,
+                }
+            } else {
+                pending.push(p);
+            }
+        }
+        formatted
+    }
+
+    /// How many slots are filled before the byte offset `pos` of the `formatted` text.
+    fn raw_position(&self, formatted: &str, pos: usize) -> usize {
+        formatted
+            .char_indices()
+            .take_while(|(i, _)| *i < pos)
+            .zip(self.pattern.iter())
+            .filter(|(_, p)| Self::is_slot(**p))
+            .count()
+    }
+
+    /// The byte offset in `formatted` text just after the `raw_pos`th filled slot.
+    fn formatted_position(&self, formatted: &str, raw_pos: usize) -> usize {
+        if raw_pos == 0 {
+            return 0;
+        }
+        let mut filled = 0;
+        for ((i, c), p) in formatted.char_indices().zip(self.pattern.iter()) {
+            if Self::is_slot(*p) {
+                filled += 1;
+                if filled == raw_pos {
+                    return i + c.len_utf8();
+                }
+            }
+        }
+        formatted.len()
+    }
+}
+
+/// The start of the grapheme cluster that the byte offset `pos` of `text` falls in.
+fn grapheme_floor(text: &str, pos: usize) -> usize {
+    text.grapheme_indices(true)
+        .map(|(i, _)| i)
+        .take_while(|i| *i <= pos)
+        .last()
+        .unwrap_or(0)
+}
+
+/// The grapheme boundary before the byte offset `pos` of `text`.
+fn prev_grapheme(text: &str, pos: usize) -> usize {
+    text.grapheme_indices(true)
+        .map(|(i, _)| i)
+        .take_while(|i| *i < pos)
+        .last()
+        .unwrap_or(0)
+}
+
+/// The grapheme boundary after the byte offset `pos` of `text`.
+fn next_grapheme(text: &str, pos: usize) -> usize {
+    text.grapheme_indices(true)
+        .map(|(i, g)| i + g.len())
+        .find(|end| *end > pos)
+        .unwrap_or(text.len())
+}
+
+/// The start of the word before the byte offset `pos` of `text`, skipping over any spaces and punctuation in between.
+fn prev_word(text: &str, pos: usize) -> usize {
+    text.unicode_word_indices()
+        .map(|(i, _)| i)
+        .take_while(|i| *i < pos)
+        .last()
+        .unwrap_or(0)
+}
+
+/// The end of the word after the byte offset `pos` of `text`, skipping over any spaces and punctuation in between.
+fn next_word(text: &str, pos: usize) -> usize {
+    text.unicode_word_indices()
+        .map(|(i, w)| i + w.len())
+        .find(|end| *end > pos)
+        .unwrap_or(text.len())
+}
+
+/// The start and end of the word that the byte offset `pos` of `text` is in, or at the edge of.
+fn word_at(text: &str, pos: usize) -> Option<(usize, usize)> {
+    text.unicode_word_indices()
+        .map(|(i, w)| (i, i + w.len()))
+        .find(|(start, end)| *start <= pos && pos <= *end)
+}
 
 #[derive(Debug)]
 enum TextBoxMessage {
@@ -35,7 +179,7 @@ pub enum TextBoxAction {
 
 #[derive(Debug, Default)]
 struct TextBoxState {
-    focused: bool,
+    interaction: Interaction,
 }
 
 #[component(State = "TextBoxState", Styled, Internal)]
@@ -44,6 +188,10 @@ pub struct TextBox {
     on_change: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
     on_commit: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
     on_focus: Option<Box<dyn Fn() -> Message + Send + Sync>>,
+    on_raw_change: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+    on_raw_commit: Option<Box<dyn Fn(&str) -> Message + Send + Sync>>,
+    mask: Option<InputMask>,
+    tool_tip: Option<String>,
 }
 
 impl std::fmt::Debug for TextBox {
@@ -59,18 +207,24 @@ impl TextBox {
             on_change: None,
             on_commit: None,
             on_focus: None,
+            on_raw_change: None,
+            on_raw_commit: None,
+            mask: None,
+            tool_tip: None,
             state: Some(TextBoxState::default()),
             dirty: false,
-            class: Default::default(),
+            classes: Default::default(),
             style_overrides: Default::default(),
         }
     }
 
+    /// Called with the text after every edit.
     pub fn on_change(mut self, change_fn: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
         self.on_change = Some(change_fn);
         self
     }
 
+    /// Called with the text once editing is finished, when the TextBox loses focus, e.g. because Enter was pressed.
     pub fn on_commit(mut self, commit_fn: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
         self.on_commit = Some(commit_fn);
         self
@@ -80,29 +234,66 @@ impl TextBox {
         self.on_focus = Some(focus_fn);
         self
     }
+
+    /// Format the text with `mask` as it is entered. [`#on_change`][TextBox#method.on_change] and [`#on_commit`][TextBox#method.on_commit] receive the formatted text, while [`#on_raw_change`][TextBox#method.on_raw_change] and [`#on_raw_commit`][TextBox#method.on_raw_commit] receive it without separators.
+    pub fn mask(mut self, mask: InputMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn on_raw_change(mut self, change_fn: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
+        self.on_raw_change = Some(change_fn);
+        self
+    }
+
+    pub fn on_raw_commit(mut self, commit_fn: Box<dyn Fn(&str) -> Message + Send + Sync>) -> Self {
+        self.on_raw_commit = Some(commit_fn);
+        self
+    }
+
+    /// Show a [`ToolTip`][super::ToolTip] with the text `t` when the TextBox is hovered over or focused with the keyboard.
+    pub fn tooltip(mut self, t: impl Into<String>) -> Self {
+        self.tool_tip = Some(t.into());
+        self
+    }
+
+    fn raw(&self, text: &str) -> String {
+        self.mask
+            .as_ref()
+            .map_or_else(|| text.to_string(), |mask| mask.raw(text))
+    }
 }
 
 #[state_component_impl(TextBoxState)]
 impl Component for TextBox {
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tool_tip.as_deref()
+    }
+
     fn view(&self) -> Option<Node> {
-        let background_color: Color = self.style_val("background_color").into();
-        let border_color: Color = self.style_val("border_color").into();
-        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let interaction = self.state_ref().interaction;
+        let background_color: Color = self
+            .interactive_style_val("background_color", interaction)
+            .into();
+        let border_color: Color = self
+            .interactive_style_val("border_color", interaction)
+            .into();
+        let border_width: f32 = self
+            .interactive_style_val("border_width", interaction)
+            .unwrap()
+            .f32();
 
         Some(
             node!(
-                TextBoxContainer::new(
-                    background_color,
-                    border_color,
-                    border_width * if self.state_ref().focused { 2.0 } else { 1.0 },
-                ),
+                TextBoxContainer::new(background_color, border_color, border_width),
                 lay!(size: size_pct!(100.0),)
             )
             .push(node!(
                 TextBoxText {
                     default_text: self.text.clone().unwrap_or_default(),
+                    mask: self.mask.clone(),
                     style_overrides: self.style_overrides.clone(),
-                    class: self.class,
+                    classes: self.classes.clone(),
                     state: None,
                     dirty: false,
                 },
@@ -115,21 +306,27 @@ impl Component for TextBox {
         let mut m: Vec<Message> = vec![];
         match message.downcast_ref::<TextBoxMessage>() {
             Some(TextBoxMessage::Open) => {
-                self.state_mut().focused = true;
+                self.state_mut().interaction.set_focused(true);
                 if let Some(focus_fn) = &self.on_focus {
                     m.push(focus_fn())
                 }
             }
-            Some(TextBoxMessage::Close) => self.state_mut().focused = false,
+            Some(TextBoxMessage::Close) => self.state_mut().interaction.set_focused(false),
             Some(TextBoxMessage::Change(s)) => {
                 if let Some(change_fn) = &self.on_change {
                     m.push(change_fn(s))
                 }
+                if let Some(change_fn) = &self.on_raw_change {
+                    m.push(change_fn(&self.raw(s)))
+                }
             }
             Some(TextBoxMessage::Commit(s)) => {
                 if let Some(commit_fn) = &self.on_commit {
                     m.push(commit_fn(s))
                 }
+                if let Some(commit_fn) = &self.on_raw_commit {
+                    m.push(commit_fn(&self.raw(s)))
+                }
             }
             _ => m.push(message),
         }
@@ -283,13 +480,14 @@ struct TextBoxTextState {
 #[derive(Debug)]
 pub struct TextBoxText {
     pub default_text: String,
+    mask: Option<InputMask>,
 }
 
 impl TextBoxText {
     fn reset_state(&mut self) {
         self.state = Some(TextBoxTextState {
             focused: false,
-            text: self.default_text.clone(),
+            text: self.masked(&self.default_text),
             cursor_pos: 0,
             selection_from: None,
             activated_at: Instant::now(),
@@ -303,6 +501,12 @@ impl TextBoxText {
         });
     }
 
+    fn masked(&self, text: &str) -> String {
+        self.mask
+            .as_ref()
+            .map_or_else(|| text.to_string(), |mask| mask.format(text))
+    }
+
     fn selection(&self) -> Option<(usize, usize)> {
         let pos = self.state_ref().cursor_pos;
         self.state_ref()
@@ -314,15 +518,16 @@ impl TextBoxText {
             })
     }
 
+    /// The cursor position, as a byte offset into the text, that is closest to `x`. It is always at the start of a grapheme cluster.
     fn position(&self, x: f32) -> usize {
-        if let Some(i) = self
+        if let Some(g) = self
             .state_ref()
             .glyphs
             .iter()
-            .position(|g| x < g.glyph.position.x + 4.0)
+            .find(|g| x < g.glyph.position.x + 4.0)
         // This should really be checking against the glyph center
         {
-            i
+            grapheme_floor(&self.state_ref().text, g.byte_index)
         } else {
             self.state_ref().text.len()
         }
@@ -330,33 +535,57 @@ impl TextBoxText {
 
     // Returns whether or not there was a word to select
     fn select_word(&mut self) -> bool {
-        let pos = self.state_ref().cursor_pos;
-        let text = &self.state_ref().text;
-        let end_pos = pos
-            + text
-                .chars()
-                .skip(pos)
-                .position(|x| !x.is_alphanumeric())
-                .unwrap_or(text.len() - pos);
-        let start_pos = pos
-            - text
-                .chars()
-                .rev()
-                .skip(text.len() - pos)
-                .position(|x| !x.is_alphanumeric())
-                .unwrap_or(pos);
-
-        if start_pos != end_pos {
-            self.state_mut().selection_from = Some(start_pos);
-            self.state_mut().cursor_pos = end_pos;
+        if let Some((start, end)) = word_at(&self.state_ref().text, self.state_ref().cursor_pos) {
+            self.state_mut().selection_from = Some(start);
+            self.state_mut().cursor_pos = end;
             true
         } else {
             false
         }
     }
 
+    /// Move the cursor one grapheme cluster, or one word when `by_word`, forward or back. When `select` is set, the selection is extended to the new position, otherwise an existing selection is dropped, without moving the cursor.
+    fn move_cursor(&mut self, forward: bool, by_word: bool, select: bool) {
+        let pos = self.state_ref().cursor_pos;
+        let text = &self.state_ref().text;
+        let to = match (forward, by_word) {
+            (false, false) => prev_grapheme(text, pos),
+            (false, true) => prev_word(text, pos),
+            (true, false) => next_grapheme(text, pos),
+            (true, true) => next_word(text, pos),
+        };
+        if select {
+            let from = self.state_ref().selection_from.unwrap_or(pos);
+            self.state_mut().selection_from = (from != to).then_some(from);
+            self.state_mut().cursor_pos = to;
+        } else if self.state_ref().selection_from.is_some() {
+            self.state_mut().selection_from = None;
+        } else {
+            self.state_mut().cursor_pos = to;
+        }
+    }
+
     fn insert_text(&mut self, text: &str) {
-        if let Some((a, b)) = self.selection() {
+        if let Some(mask) = self.mask.clone() {
+            let pos = self.state_ref().cursor_pos;
+            let (a, b) = self.selection().unwrap_or((pos, pos));
+            let current = &self.state_ref().text;
+            let before = mask.raw(&current[..a]);
+            let after: String = mask
+                .raw(current)
+                .chars()
+                .skip(mask.raw_position(current, b))
+                .collect();
+            // Only count what the mask accepts towards the new cursor position
+            let inserted = mask
+                .raw(&mask.format(&format!("{before}{text}")))
+                .chars()
+                .count();
+            let formatted = mask.format(&format!("{before}{text}{after}"));
+            self.state_mut().cursor_pos = mask.formatted_position(&formatted, inserted);
+            self.state_mut().text = formatted;
+            self.state_mut().selection_from = None;
+        } else if let Some((a, b)) = self.selection() {
             self.state_mut().text.replace_range(a..b, text);
             self.state_mut().cursor_pos = a + text.len();
             self.state_mut().selection_from = None;
@@ -368,24 +597,85 @@ impl TextBoxText {
         self.state_mut().dirty = true;
     }
 
+    /// Delete the selection, or else the grapheme cluster (or word, when `by_word`) before the cursor, or after it when `forward`. Returns whether anything was deleted.
+    fn delete(&mut self, forward: bool, by_word: bool) -> bool {
+        let pos = self.state_ref().cursor_pos;
+        if self.selection().is_some() {
+            self.insert_text("");
+            true
+        } else if let Some(mask) = self.mask.clone() {
+            // Remove the character of the slot next to the cursor, which takes any separators that would trail it along with it
+            let text = &self.state_ref().text;
+            let mut raw = mask.raw(text);
+            let raw_pos = mask.raw_position(text, pos);
+            let removed = if forward {
+                raw_pos
+            } else if raw_pos > 0 {
+                raw_pos - 1
+            } else {
+                return false;
+            };
+            match raw.char_indices().nth(removed) {
+                Some((i, _)) => {
+                    raw.remove(i);
+                }
+                None => return false,
+            }
+            let formatted = mask.format(&raw);
+            self.state_mut().cursor_pos = mask.formatted_position(&formatted, removed);
+            self.state_mut().text = formatted;
+            self.state_mut().dirty = true;
+            true
+        } else {
+            let text = &self.state_ref().text;
+            let (a, b) = match (forward, by_word) {
+                (false, false) => (prev_grapheme(text, pos), pos),
+                (false, true) => (prev_word(text, pos), pos),
+                (true, false) => (pos, next_grapheme(text, pos)),
+                (true, true) => (pos, next_word(text, pos)),
+            };
+            if a == b {
+                return false;
+            }
+            self.state_mut().text.replace_range(a..b, "");
+            self.state_mut().cursor_pos = a;
+            self.state_mut().dirty = true;
+            true
+        }
+    }
+
     fn activate(&mut self) {
+        self.reset_cursor_blink();
+        self.state_mut().selection_from = None;
+    }
+
+    /// Make the cursor solid, and restart its blink period
+    fn reset_cursor_blink(&mut self) {
         self.state_mut().activated_at = Instant::now();
         self.state_mut().cursor_visible = true;
-        self.state_mut().selection_from = None;
     }
 
+    fn update_cursor_blink(&mut self) {
+        let period: u32 = self.style_val("cursor_blink_period").unwrap().into();
+        let visible = cursor_blink_visible(
+            self.state_ref().activated_at.elapsed().as_millis(),
+            period.into(),
+        );
+        if visible != self.state_ref().cursor_visible {
+            self.state_mut().cursor_visible = visible;
+        }
+    }
+
+    /// The x position of the cursor at the byte offset `pos` of the text.
     fn cursor_position_px(&self, pos: usize) -> f32 {
-        let len = self.state_ref().text.len();
         let glyphs = &self.state_ref().glyphs;
-        (if pos < len {
-            let g = &glyphs[pos].glyph;
-            g.position.x
-        } else if pos == 0 {
-            0.0
+        (if let Some(g) = glyphs.iter().find(|g| g.byte_index >= pos) {
+            g.glyph.position.x
+        } else if let Some(g) = glyphs.last() {
+            // Past the last glyph, need to add the advance
+            g.glyph.position.x + self.state_ref().glyph_widths.last().map_or(0.0, |w| *w)
         } else {
-            // Last glyph, need to add the advance
-            let g = &glyphs[pos - 1].glyph;
-            g.position.x + self.state_ref().glyph_widths.last().map_or(0.0, |w| *w)
+            0.0
         }) + self.state_ref().padding_offset_px
     }
 
@@ -451,8 +741,17 @@ impl Component for TextBoxText {
         self.reset_state();
     }
 
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::TextField).value(self.state_ref().text.clone()))
+    }
+
     fn props_hash(&self, hasher: &mut ComponentHasher) {
         self.default_text.hash(hasher);
+        self.mask.hash(hasher);
     }
 
     fn new_props(&mut self) {
@@ -473,7 +772,7 @@ impl Component for TextBoxText {
 
     fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
         if let Some(w) = crate::current_window() {
-            w.set_cursor("Ibeam")
+            w.set_cursor(crate::Cursor::Text)
         }
     }
 
@@ -485,12 +784,7 @@ impl Component for TextBoxText {
 
     fn on_tick(&mut self, _event: &mut event::Event<event::Tick>) {
         if self.state_ref().focused {
-            let visible =
-                (self.state_ref().activated_at.elapsed().as_millis() / CURSOR_BLINK_PERIOD) % 2
-                    == 0;
-            if visible != self.state_ref().cursor_visible {
-                self.state_mut().cursor_visible = visible;
-            }
+            self.update_cursor_blink();
         }
     }
 
@@ -565,63 +859,22 @@ impl Component for TextBoxText {
     }
 
     fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        self.reset_cursor_blink();
         let pos = self.state_ref().cursor_pos;
         let len = self.state_ref().text.len();
+        // Option is used to move by words on macOS
+        let by_word = event.modifiers_held.ctrl || event.modifiers_held.alt;
         let mut changed = false;
         match event.input.0 {
-            Key::Backspace => {
-                if let Some((a, b)) = self.selection() {
-                    self.state_mut().text.replace_range(a..b, "");
-                    self.state_mut().cursor_pos = a;
-                    self.state_mut().selection_from = None;
-                    changed = true;
-                } else if pos > 0 {
-                    self.state_mut().text.remove(pos - 1);
-                    self.state_mut().cursor_pos -= 1;
-                    changed = true;
-                }
-            }
-            Key::Left => {
-                // TODO more modifiers
-                if pos > 0 {
-                    if event.modifiers_held.shift {
-                        if let Some(s) = self.state_ref().selection_from {
-                            if pos == s + 1 {
-                                self.state_mut().selection_from = None;
-                            }
-                        } else {
-                            self.state_mut().selection_from = Some(pos);
-                        }
-                        self.state_mut().cursor_pos -= 1;
-                    } else if self.state_ref().selection_from.is_some() {
-                        self.state_mut().selection_from = None;
-                    } else {
-                        self.state_mut().cursor_pos -= 1;
-                    }
-                } else if !event.modifiers_held.shift && self.state_ref().selection_from.is_some() {
-                    self.state_mut().selection_from = None;
-                }
+            Key::Backspace | Key::Delete => {
+                changed = self.delete(event.input.0 == Key::Delete, by_word);
             }
-            Key::Right => {
-                // TODO more modifiers
-                if pos < len {
-                    if event.modifiers_held.shift {
-                        if let Some(s) = self.state_ref().selection_from {
-                            if pos + 1 == s {
-                                self.state_mut().selection_from = None;
-                            }
-                        } else {
-                            self.state_mut().selection_from = Some(pos);
-                        }
-                        self.state_mut().cursor_pos += 1;
-                    } else if self.state_ref().selection_from.is_some() {
-                        self.state_mut().selection_from = None;
-                    } else {
-                        self.state_mut().cursor_pos += 1;
-                    }
-                } else if !event.modifiers_held.shift && self.state_ref().selection_from.is_some() {
-                    self.state_mut().selection_from = None;
-                }
+            Key::Left | Key::Right => {
+                self.move_cursor(
+                    event.input.0 == Key::Right,
+                    by_word,
+                    event.modifiers_held.shift,
+                );
             }
             Key::Up => {
                 // TODO more modifiers
@@ -677,6 +930,7 @@ impl Component for TextBoxText {
     }
 
     fn on_text_entry(&mut self, event: &mut event::Event<event::TextEntry>) {
+        self.reset_cursor_blink();
         self.insert_text(&event.input.0);
         self.state_mut().dirty = true;
         event.stop_bubbling();
@@ -685,6 +939,20 @@ impl Component for TextBoxText {
         )));
     }
 
+    fn on_set_value(&mut self, event: &mut event::Event<event::SetValue>) {
+        self.reset_cursor_blink();
+        let text = self.masked(&event.input.0);
+        let len = text.len();
+        self.state_mut().text = text;
+        self.state_mut().cursor_pos = len;
+        self.state_mut().selection_from = None;
+        self.state_mut().dirty = true;
+        event.stop_bubbling();
+        event.emit(Box::new(TextBoxMessage::Change(
+            self.state_ref().text.clone(),
+        )));
+    }
+
     fn on_drag_start(&mut self, event: &mut event::Event<event::DragStart>) {
         self.activate();
         self.state_mut().selection_from = Some(self.position(event.relative_physical_position().x));
@@ -745,6 +1013,7 @@ impl Component for TextBoxText {
                     text: self.state_ref().text.clone(),
                     size: font_size.into(),
                     font: font.clone(),
+                    link: None,
                 }],
                 font.as_deref(),
                 font_size,
@@ -840,3 +1109,246 @@ impl Component for TextBoxText {
         Some(renderables)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_cursor_blink() {
+        assert!(cursor_blink_visible(0, 500));
+        assert!(cursor_blink_visible(499, 500));
+        assert!(!cursor_blink_visible(500, 500));
+        assert!(!cursor_blink_visible(999, 500));
+        assert!(cursor_blink_visible(1000, 500));
+        assert!(!cursor_blink_visible(300, 200));
+        assert!(cursor_blink_visible(123456, 0));
+
+        let mut text = TextBoxText {
+            default_text: "".to_string(),
+            mask: None,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+            state: None,
+            dirty: false,
+        }
+        .style("cursor_blink_period", 200u32);
+        text.reset_state();
+        text.state_mut().focused = true;
+        text.reset_cursor_blink();
+
+        text.state_mut().activated_at = Instant::now() - Duration::from_millis(250);
+        text.update_cursor_blink();
+        assert!(!text.state_ref().cursor_visible);
+        text.state_mut().activated_at = Instant::now() - Duration::from_millis(450);
+        text.update_cursor_blink();
+        assert!(text.state_ref().cursor_visible);
+        text.state_mut().activated_at = Instant::now() - Duration::from_millis(650);
+        text.update_cursor_blink();
+        assert!(!text.state_ref().cursor_visible);
+
+        // A keystroke makes the cursor solid again
+        text.reset_cursor_blink();
+        text.update_cursor_blink();
+        assert!(text.state_ref().cursor_visible);
+    }
+
+    fn text_box(default_text: &str, mask: Option<&str>) -> TextBoxText {
+        let mut text = TextBoxText {
+            default_text: default_text.to_string(),
+            mask: mask.map(InputMask::new),
+            classes: Default::default(),
+            style_overrides: Default::default(),
+            state: None,
+            dirty: false,
+        };
+        text.reset_state();
+        text
+    }
+
+    #[test]
+    fn test_change_and_commit() {
+        let mut textbox = TextBox::new(None)
+            .on_change(Box::new(|s| Box::new(format!("change {s}"))))
+            .on_commit(Box::new(|s| Box::new(format!("commit {s}"))));
+        let mut text = text_box("", None);
+        let cache = event::EventCache::new(1.0);
+        let mut messages = vec![];
+
+        // Every edit is a change
+        for c in ["a", "b", "c"] {
+            let mut event = event::Event::new(event::TextEntry(c.to_string()), &cache);
+            text.on_text_entry(&mut event);
+            messages.append(&mut event.messages);
+        }
+        // Leaving the TextBox commits it, once
+        let mut event = event::Event::new(event::Blur::default(), &cache);
+        text.on_blur(&mut event);
+        messages.append(&mut event.messages);
+
+        let messages: Vec<String> = messages
+            .into_iter()
+            .flat_map(|m| textbox.update(m))
+            .map(|m| *m.downcast::<String>().unwrap())
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["change a", "change ab", "change abc", "commit abc"]
+        );
+    }
+
+    #[test]
+    fn test_input_mask() {
+        let mut text = text_box("", Some("###-###"));
+        for c in ["1", "2", "3"] {
+            text.insert_text(c);
+        }
+        // The separator is only inserted once there is something after it
+        assert_eq!(text.state_ref().text, "123");
+        text.insert_text("a");
+        assert_eq!(text.state_ref().text, "123");
+        text.insert_text("4");
+        assert_eq!(text.state_ref().text, "123-4");
+        assert_eq!(text.state_ref().cursor_pos, 5);
+        text.insert_text("5678");
+        assert_eq!(text.state_ref().text, "123-456");
+        assert_eq!(text.mask.as_ref().unwrap().raw("123-456"), "123456");
+
+        // Backspace removes digits, skipping over separators
+        text.state_mut().cursor_pos = 5;
+        assert!(text.delete(false, false));
+        assert_eq!(text.state_ref().text, "123-56");
+        assert_eq!(text.state_ref().cursor_pos, 3);
+        assert!(text.delete(false, false));
+        assert_eq!(text.state_ref().text, "125-6");
+        assert_eq!(text.state_ref().cursor_pos, 2);
+        text.state_mut().cursor_pos = 5;
+        assert!(text.delete(false, false));
+        assert_eq!(text.state_ref().text, "125");
+        assert_eq!(text.state_ref().cursor_pos, 3);
+
+        // Inserting in the middle moves the cursor past inserted separators
+        text.state_mut().cursor_pos = 1;
+        text.insert_text("9");
+        assert_eq!(text.state_ref().text, "192-5");
+        assert_eq!(text.state_ref().cursor_pos, 2);
+
+        // Pasted text that is already formatted is accepted
+        text.state_mut().selection_from = Some(0);
+        text.state_mut().cursor_pos = 5;
+        text.insert_text("987-654");
+        assert_eq!(text.state_ref().text, "987-654");
+        assert_eq!(text.state_ref().cursor_pos, 7);
+
+        let mut text = text_box("", Some("(###) ###"));
+        text.insert_text("1");
+        assert_eq!(text.state_ref().text, "(1");
+        assert_eq!(text.state_ref().cursor_pos, 2);
+        text.insert_text("2345");
+        assert_eq!(text.state_ref().text, "(123) 45");
+        assert!(text.delete(false, false));
+        assert!(text.delete(false, false));
+        assert_eq!(text.state_ref().text, "(123");
+        assert_eq!(text.state_ref().cursor_pos, 4);
+    }
+
+    #[test]
+    fn test_graphemes() {
+        let cursor = |text: &TextBoxText| text.state_ref().cursor_pos;
+
+        // A combining accent moves and is deleted along with its letter
+        let mut text = text_box("nai\u{308}ve", None);
+        text.move_cursor(true, false, false);
+        text.move_cursor(true, false, false);
+        assert_eq!(cursor(&text), 2);
+        text.move_cursor(true, false, false);
+        assert_eq!(cursor(&text), 5);
+        text.move_cursor(false, false, false);
+        assert_eq!(cursor(&text), 2);
+        text.state_mut().cursor_pos = 5;
+        assert!(text.delete(false, false));
+        assert_eq!(text.state_ref().text, "nave");
+        assert_eq!(cursor(&text), 2);
+
+        // As does an emoji ZWJ sequence
+        let mut text = text_box("a\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}b", None);
+        text.state_mut().cursor_pos = 1;
+        text.move_cursor(true, false, false);
+        assert_eq!(cursor(&text), 19);
+        text.move_cursor(false, false, false);
+        assert_eq!(cursor(&text), 1);
+        assert!(text.delete(true, false));
+        assert_eq!(text.state_ref().text, "ab");
+        assert_eq!(cursor(&text), 1);
+
+        // And an emoji with a skin tone
+        let mut text = text_box("\u{1F44D}\u{1F3FD}!", None);
+        text.state_mut().cursor_pos = 8;
+        assert!(text.delete(false, false));
+        assert_eq!(text.state_ref().text, "!");
+        assert_eq!(cursor(&text), 0);
+        assert!(!text.delete(false, false));
+    }
+
+    #[test]
+    fn test_words() {
+        let cursor = |text: &TextBoxText| text.state_ref().cursor_pos;
+
+        let mut text = text_box("naïve café", None);
+        text.move_cursor(true, true, false);
+        assert_eq!(cursor(&text), 6);
+        text.move_cursor(true, true, false);
+        assert_eq!(cursor(&text), 12);
+        text.move_cursor(false, true, false);
+        assert_eq!(cursor(&text), 7);
+        text.move_cursor(false, true, false);
+        assert_eq!(cursor(&text), 0);
+
+        // Selecting by word, then by letter
+        text.move_cursor(true, true, true);
+        assert_eq!(text.selection(), Some((0, 6)));
+        text.move_cursor(false, false, true);
+        assert_eq!(text.selection(), Some((0, 5)));
+        // Moving without shift drops the selection
+        text.move_cursor(false, false, false);
+        assert_eq!(text.selection(), None);
+        assert_eq!(cursor(&text), 5);
+
+        // Deleting a word at a time
+        text.state_mut().cursor_pos = 12;
+        assert!(text.delete(false, true));
+        assert_eq!(text.state_ref().text, "naïve ");
+        assert_eq!(cursor(&text), 7);
+        text.state_mut().cursor_pos = 0;
+        assert!(text.delete(true, true));
+        assert_eq!(text.state_ref().text, " ");
+        assert_eq!(cursor(&text), 0);
+
+        // Double clicks select the same words
+        let mut text = text_box("naïve café", None);
+        text.state_mut().cursor_pos = 1;
+        assert!(text.select_word());
+        assert_eq!(text.selection(), Some((0, 6)));
+        text.state_mut().selection_from = None;
+        text.state_mut().cursor_pos = 12;
+        assert!(text.select_word());
+        assert_eq!(text.selection(), Some((7, 12)));
+
+        // Each ideograph is a word of its own
+        let mut text = text_box("中文 abc", None);
+        text.move_cursor(true, false, false);
+        assert_eq!(cursor(&text), 3);
+        text.move_cursor(true, true, false);
+        assert_eq!(cursor(&text), 6);
+        text.move_cursor(true, true, false);
+        assert_eq!(cursor(&text), 10);
+        text.move_cursor(false, true, false);
+        assert_eq!(cursor(&text), 7);
+        text.move_cursor(false, true, false);
+        assert_eq!(cursor(&text), 3);
+        text.state_mut().cursor_pos = 8;
+        assert!(text.select_word());
+        assert_eq!(text.selection(), Some((7, 10)));
+    }
+}
@@ -0,0 +1,312 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::base_types::*;
+use crate::component::{Component, Message};
+use crate::event;
+use crate::layout::*;
+use crate::style::{HorizontalPosition, Styled, VerticalPosition};
+use crate::{node, txt, Node};
+use lemna_macros::{component, state_component_impl};
+
+#[derive(Debug)]
+struct QueuedToast {
+    message: String,
+    duration: Duration,
+}
+
+/// The notifications that have been sent, but not yet picked up by [`Toasts`]. Each UI has its own, shared by the Events it sends.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ToastQueue(Arc<Mutex<Vec<QueuedToast>>>);
+
+impl ToastQueue {
+    /// Queue a notification that shows `message` for `duration`. It is picked up by [`Toasts`] on the next tick.
+    pub(crate) fn push(&self, message: String, duration: Duration) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(QueuedToast { message, duration });
+    }
+
+    fn take(&self) -> Vec<QueuedToast> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+#[derive(Debug)]
+enum ToastMessage {
+    Hover(u64, bool),
+}
+
+#[derive(Debug)]
+struct ActiveToast {
+    key: u64,
+    message: String,
+    duration: Duration,
+    /// How long the toast has been shown for, not counting the time it was hovered.
+    elapsed: Duration,
+    /// How visible the toast is, from 0 to 1. This follows whether the toast has expired over the course of a transition.
+    shown: f32,
+    hovered: bool,
+}
+
+#[derive(Debug, Default)]
+struct ToastsState {
+    toasts: Vec<ActiveToast>,
+    next_key: u64,
+    last_tick: Option<Instant>,
+}
+
+/// The stack of transient notifications sent with [`UI#toast`][crate::UI#method.toast] or [`Event#toast`][crate::Event#method.toast]. The UI puts this over the top of the app, in the corner given by its `h_position` and `v_position` styles. Each toast fades in, and fades out once its duration is up. Hovering over a toast pauses its countdown.
+///
+/// Toasts count down with [`Tick`][event::Tick] events, so the backend needs to send [`Input::Timer`][crate::input::Input::Timer] while they are shown.
+#[component(State = "ToastsState", Styled, Internal)]
+#[derive(Debug)]
+pub struct Toasts {}
+
+impl Toasts {
+    /// How long it takes a toast to fade in or out.
+    const TRANSITION: Duration = Duration::from_millis(200);
+
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Some(ToastsState::default()),
+            dirty: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// The layout that places the toasts in their corner of the window.
+    pub(crate) fn layout(&self) -> Layout {
+        let margin = Dimension::Px(self.style_val("margin").unwrap().f32() as f64);
+        let mut position = Rect::default();
+        match self.style_val("h_position").unwrap().horizontal_position() {
+            HorizontalPosition::Left => position.left = margin,
+            _ => position.right = margin,
+        }
+        match self.style_val("v_position").unwrap().vertical_position() {
+            VerticalPosition::Top => position.top = margin,
+            _ => position.bottom = margin,
+        }
+        Layout {
+            position_type: PositionType::Absolute,
+            z_index_increment: 2000.0,
+            position,
+            ..Default::default()
+        }
+    }
+}
+
+#[state_component_impl(ToastsState)]
+impl Component for Toasts {
+    fn view(&self) -> Option<Node> {
+        if self.state_ref().toasts.is_empty() {
+            return None;
+        }
+        let spacing: f32 = self.style_val("spacing").unwrap().f32();
+        let cross_alignment = match self.style_val("h_position").unwrap().horizontal_position() {
+            HorizontalPosition::Left => Alignment::Start,
+            _ => Alignment::End,
+        };
+        let mut stack = node!(
+            super::Div::new(),
+            lay!(direction: Direction::Column, cross_alignment: cross_alignment)
+        );
+        // The newest toast is the one closest to the edge of the window
+        let top =
+            self.style_val("v_position").unwrap().vertical_position() == VerticalPosition::Top;
+        let mut toasts: Vec<&ActiveToast> = self.state_ref().toasts.iter().collect();
+        if top {
+            toasts.reverse();
+        }
+        for (i, toast) in toasts.into_iter().enumerate() {
+            let margin = if i == 0 { 0.0 } else { spacing };
+            stack = stack.push(node!(
+                Toast::new(toast.key, toast.message.clone(), toast.shown),
+                lay!(margin: rect!(margin, 0.0, 0.0, 0.0)),
+                toast.key
+            ));
+        }
+        Some(stack)
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<ToastMessage>() {
+            Some(ToastMessage::Hover(key, hovered)) => {
+                // Pausing doesn't change how the toasts look
+                let dirty = self.dirty;
+                if let Some(toast) = self.state_mut().toasts.iter_mut().find(|t| t.key == *key) {
+                    toast.hovered = *hovered;
+                }
+                self.dirty = dirty;
+                vec![]
+            }
+            None => vec![message],
+        }
+    }
+
+    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
+        let queued = event.toasts.take();
+        if queued.is_empty() && self.state_ref().toasts.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let dt = self
+            .state_ref()
+            .last_tick
+            .map_or(Duration::ZERO, |t| now - t);
+        let step = dt.as_secs_f32() / Self::TRANSITION.as_secs_f32();
+        let reduce_motion = event.reduce_motion();
+
+        // Only changes to what is shown need a redraw
+        let dirty = self.dirty;
+        let mut changed = !queued.is_empty();
+        let state = self.state_mut();
+        for q in queued {
+            state.toasts.push(ActiveToast {
+                key: state.next_key,
+                message: q.message,
+                duration: q.duration,
+                elapsed: Duration::ZERO,
+                shown: if reduce_motion { 1.0 } else { 0.0 },
+                hovered: false,
+            });
+            state.next_key += 1;
+        }
+        for toast in state.toasts.iter_mut() {
+            if !toast.hovered {
+                toast.elapsed += dt;
+            }
+            let target = if toast.elapsed < toast.duration {
+                1.0
+            } else {
+                0.0
+            };
+            let shown = if reduce_motion {
+                target
+            } else if toast.shown < target {
+                (toast.shown + step).min(target)
+            } else {
+                (toast.shown - step).max(target)
+            };
+            if shown != toast.shown {
+                toast.shown = shown;
+                changed = true;
+            }
+        }
+        let count = state.toasts.len();
+        state
+            .toasts
+            .retain(|t| t.elapsed < t.duration || t.shown > 0.0);
+        changed |= count != state.toasts.len();
+        state.last_tick = if state.toasts.is_empty() {
+            None
+        } else {
+            Some(now)
+        };
+        self.dirty = dirty || changed;
+    }
+}
+
+/// A single notification in [`Toasts`].
+#[component(Styled, Internal)]
+#[derive(Debug)]
+pub struct Toast {
+    key: u64,
+    pub message: String,
+    /// How visible the toast is, from 0 to 1.
+    shown: f32,
+}
+
+impl Toast {
+    const MAX_WIDTH: f32 = 400.0;
+
+    fn new(key: u64, message: String, shown: f32) -> Self {
+        Self {
+            key,
+            message,
+            shown,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    fn fade(&self, color: Color) -> Color {
+        Color {
+            a: color.a * self.shown,
+            ..color
+        }
+    }
+}
+
+impl Component for Toast {
+    fn view(&self) -> Option<Node> {
+        let background_color = self.fade(self.style_val("background_color").into());
+        let text_color = self.fade(self.style_val("text_color").into());
+        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let padding: f32 = self.style_val("padding").unwrap().f32();
+
+        Some(
+            node!(
+                super::RoundedRect::new(background_color, radius),
+                lay!(
+                    padding: rect!(padding),
+                    max_size: size!(Toast::MAX_WIDTH, Auto),
+                )
+            )
+            .push(node!(super::Text::new(txt!(self.message.clone()))
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", text_color)
+                .style("h_alignment", HorizontalPosition::Left)
+                .maybe_style("font", self.style_val("font")))),
+        )
+    }
+
+    fn on_mouse_enter(&mut self, event: &mut event::Event<event::MouseEnter>) {
+        event.emit(Box::new(ToastMessage::Hover(self.key, true)));
+    }
+
+    fn on_mouse_leave(&mut self, event: &mut event::Event<event::MouseLeave>) {
+        event.emit(Box::new(ToastMessage::Hover(self.key, false)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts() {
+        let mut toasts = Toasts::new();
+        let cache = event::EventCache::new(1.0);
+        let tick_after = |toasts: &mut Toasts, elapsed: Duration| {
+            if let Some(last_tick) = toasts.state_mut().last_tick.as_mut() {
+                *last_tick -= elapsed;
+            }
+            toasts.on_tick(&mut event::Event::new(event::Tick, &cache));
+        };
+        assert!(toasts.view().is_none());
+
+        // A queued toast is shown on the next tick
+        let duration = Duration::from_secs(3);
+        cache.toasts.push("Saved".to_string(), duration);
+        tick_after(&mut toasts, Duration::ZERO);
+        assert!(toasts.is_dirty());
+        let view = toasts.view().unwrap();
+        assert_eq!(view.children.len(), 1);
+        tick_after(&mut toasts, Toasts::TRANSITION);
+        assert_eq!(toasts.state_ref().toasts[0].shown, 1.0);
+
+        // Hovering pauses the countdown
+        toasts.update(Box::new(ToastMessage::Hover(0, true)));
+        tick_after(&mut toasts, duration);
+        assert_eq!(toasts.state_ref().toasts.len(), 1);
+        toasts.update(Box::new(ToastMessage::Hover(0, false)));
+
+        // Once its time is up, it is removed
+        tick_after(&mut toasts, duration);
+        assert!(toasts.state_ref().toasts.is_empty());
+        assert!(toasts.view().is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::hash::Hash;
+use std::time::{Duration, Instant};
 
+use crate::accessibility::{AccessibilityInfo, Role};
 use crate::base_types::*;
 use crate::component::{Component, ComponentHasher, Message, RenderContext};
 use crate::event;
@@ -8,21 +10,24 @@ use crate::render::{
     renderables::shape::{self, Shape},
     Renderable,
 };
-use crate::style::Styled;
+use crate::style::{Interaction, Styled};
 use lemna_macros::{component, state_component_impl};
 
-// TODO Make a tooltip
 // TODO Font icons
 
 #[derive(Debug, Default)]
 struct ToggleState {
-    pressed: bool,
+    interaction: Interaction,
+    /// How active the toggle looks, from 0 to 1. This follows `active` over the course of a transition.
+    shown: f32,
+    last_tick: Option<Instant>,
 }
 
 #[component(State = "ToggleState", Styled, Internal)]
 pub struct Toggle {
     active: bool,
     on_change: Option<Box<dyn Fn(bool) -> Message + Send + Sync>>,
+    tool_tip: Option<String>,
 }
 
 impl fmt::Debug for Toggle {
@@ -34,13 +39,20 @@ impl fmt::Debug for Toggle {
 }
 
 impl Toggle {
+    /// How long it takes to switch between looking inactive and active.
+    const TRANSITION: Duration = Duration::from_millis(150);
+
     pub fn new(active: bool) -> Self {
         Self {
             active,
             on_change: None,
-            state: Some(ToggleState::default()),
+            tool_tip: None,
+            state: Some(ToggleState {
+                shown: if active { 1.0 } else { 0.0 },
+                ..Default::default()
+            }),
             dirty: false,
-            class: Default::default(),
+            classes: Default::default(),
             style_overrides: Default::default(),
         }
     }
@@ -49,20 +61,38 @@ impl Toggle {
         self.on_change = Some(change_fn);
         self
     }
+
+    /// Show a [`ToolTip`][super::ToolTip] with the text `t` when the Toggle is hovered over or focused with the keyboard.
+    pub fn tooltip(mut self, t: impl Into<String>) -> Self {
+        self.tool_tip = Some(t.into());
+        self
+    }
 }
 
 #[state_component_impl(ToggleState)]
 impl Component for Toggle {
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Switch).checked(self.active))
+    }
+
+    fn tooltip_text(&self) -> Option<&str> {
+        self.tool_tip.as_deref()
+    }
+
+    fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
+        self.state_mut().interaction.mouse_enter();
+    }
+
     fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
-        self.state_mut().pressed = false;
+        self.state_mut().interaction.mouse_leave();
     }
 
     fn on_mouse_down(&mut self, _event: &mut event::Event<event::MouseDown>) {
-        self.state_mut().pressed = true;
+        self.state_mut().interaction.mouse_down();
     }
 
     fn on_mouse_up(&mut self, _event: &mut event::Event<event::MouseUp>) {
-        self.state_mut().pressed = false;
+        self.state_mut().interaction.mouse_up();
     }
 
     fn on_click(&mut self, event: &mut event::Event<event::Click>) {
@@ -78,20 +108,57 @@ impl Component for Toggle {
         }
     }
 
+    fn on_tick(&mut self, event: &mut event::Event<event::Tick>) {
+        let target = if self.active { 1.0 } else { 0.0 };
+        let shown = self.state_ref().shown;
+        if shown == target {
+            return;
+        }
+        let now = Instant::now();
+        let shown = if event.reduce_motion() {
+            target
+        } else {
+            // Start moving on the next tick
+            let elapsed = self
+                .state_ref()
+                .last_tick
+                .map_or(0.0, |t| (now - t).as_secs_f32());
+            let step = elapsed / Self::TRANSITION.as_secs_f32();
+            if shown < target {
+                (shown + step).min(target)
+            } else {
+                (shown - step).max(target)
+            }
+        };
+        self.state_mut().shown = shown;
+        self.state_mut().last_tick = if shown == target { None } else { Some(now) };
+    }
+
     fn render_hash(&self, hasher: &mut ComponentHasher) {
         self.active.hash(hasher);
-        self.state_ref().pressed.hash(hasher);
+        self.state_ref().interaction.hash(hasher);
+        self.state_ref().shown.to_bits().hash(hasher);
     }
 
     fn render(&mut self, context: RenderContext) -> Option<Vec<Renderable>> {
         use lyon::tessellation::math as lyon_math;
         use lyon::tessellation::{self, basic_shapes};
 
-        let background_color: Color = self.style_val("background_color").into();
-        let active_color: Color = self.style_val("active_color").into();
-        let border_color: Color = self.style_val("border_color").into();
-        let highlight_color: Color = self.style_val("highlight_color").into();
-        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let interaction = self.state_ref().interaction;
+        let background_color: Color = self
+            .interactive_style_val("background_color", interaction)
+            .into();
+        let active_color: Color = self
+            .interactive_style_val("active_color", interaction)
+            .into();
+        let fill_color = background_color.mix(active_color, self.state_ref().shown);
+        let border_color: Color = self
+            .interactive_style_val("border_color", interaction)
+            .into();
+        let border_width: f32 = self
+            .interactive_style_val("border_width", interaction)
+            .unwrap()
+            .f32();
 
         let mut geometry = shape::ShapeGeometry::new();
         let center = lyon_math::point(context.aabb.width() / 2.0, context.aabb.height() / 2.0);
@@ -124,13 +191,7 @@ impl Component for Toggle {
         Some(vec![Renderable::Shape(Shape::new(
             geometry,
             fill_count,
-            if self.state_ref().pressed {
-                highlight_color
-            } else if self.active {
-                active_color
-            } else {
-                background_color
-            },
+            fill_color,
             border_color,
             border_width * 0.5,
             0.0,
@@ -142,3 +203,29 @@ impl Component for Toggle {
         ))])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduce_motion() {
+        let mut cache = event::EventCache::new(1.0);
+        let mut toggle = Toggle::new(false);
+        toggle.active = true;
+        toggle.on_tick(&mut event::Event::new(event::Tick, &cache));
+        // The transition takes a few frames
+        assert!(toggle.state_ref().shown < 1.0);
+        std::thread::sleep(Duration::from_millis(10));
+        toggle.on_tick(&mut event::Event::new(event::Tick, &cache));
+        let shown = toggle.state_ref().shown;
+        assert!(shown > 0.0 && shown < 1.0);
+
+        // But it settles right away when motion is reduced
+        cache.reduce_motion = true;
+        let mut toggle = Toggle::new(false);
+        toggle.active = true;
+        toggle.on_tick(&mut event::Event::new(event::Tick, &cache));
+        assert_eq!(toggle.state_ref().shown, 1.0);
+    }
+}
@@ -0,0 +1,462 @@
+use std::fmt;
+use std::hash::Hash;
+
+use crate::accessibility::{AccessibilityInfo, Role};
+use crate::base_types::*;
+use crate::component::{Component, ComponentHasher, Message};
+use crate::event;
+use crate::font_cache::TextSegment;
+use crate::input::Key;
+use crate::layout::*;
+use crate::style::{HorizontalPosition, Interaction, Styled};
+use crate::{node, Node};
+use lemna_macros::{component, state_component_impl};
+
+/// Which segments of a [`ToggleGroup`] are active.
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Selection {
+    /// Always sorted, and never more than one long unless `multi`.
+    active: Vec<usize>,
+    multi: bool,
+}
+
+impl Selection {
+    fn new(mut active: Vec<usize>, multi: bool) -> Self {
+        active.sort_unstable();
+        active.dedup();
+        if !multi {
+            active.truncate(1);
+        }
+        Self { active, multi }
+    }
+
+    fn is_active(&self, i: usize) -> bool {
+        self.active.contains(&i)
+    }
+
+    /// Toggle segment `i`, as a click does. In a single selection group, this makes `i` the only active segment, and clicking the active segment does nothing. Returns whether the selection changed.
+    fn toggle(&mut self, i: usize) -> bool {
+        if !self.multi {
+            if self.active == [i] {
+                return false;
+            }
+            self.active = vec![i];
+        } else if let Some(p) = self.active.iter().position(|a| *a == i) {
+            self.active.remove(p);
+        } else {
+            self.active.push(i);
+            self.active.sort_unstable();
+        }
+        true
+    }
+}
+
+#[derive(Debug, Default)]
+struct ToggleGroupState {
+    selection: Selection,
+    /// The segment that the keyboard moves from.
+    cursor: usize,
+    focused: bool,
+}
+
+#[derive(Debug)]
+enum ToggleGroupMessage {
+    Clicked(usize),
+}
+
+/// A row of segments that toggle on and off, drawn as one control with shared borders, e.g. solo, mute and arm buttons. Only one segment is active at a time, unless the group is made with [`#multi_select`][ToggleGroup#method.multi_select].
+///
+/// The group keeps track of its own selection, so that quick successive clicks each act on the result of the last, and it only takes on `selected` again when that changes. When focused, Left and Right move between segments, selecting them as they go; in a `multi_select` group they move without selecting, and Space or Return toggles the segment.
+///
+/// Active segments are drawn with the `active_color` and `active_text_color` style parameters.
+#[doc(alias = "SegmentedControl")]
+#[component(State = "ToggleGroupState", Styled, Internal)]
+pub struct ToggleGroup {
+    labels: Vec<Vec<TextSegment>>,
+    selected: Vec<usize>,
+    multi_select: bool,
+    on_change: Option<Box<dyn Fn(usize) -> Message + Send + Sync>>,
+}
+
+impl fmt::Debug for ToggleGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ToggleGroup")
+            .field("labels", &self.labels)
+            .field("selected", &self.selected)
+            .field("multi_select", &self.multi_select)
+            .finish()
+    }
+}
+
+impl ToggleGroup {
+    pub fn new(labels: Vec<Vec<TextSegment>>, selected: Option<usize>) -> Self {
+        let selected: Vec<usize> = selected.into_iter().collect();
+        Self {
+            labels,
+            state: Some(ToggleGroupState {
+                selection: Selection::new(selected.clone(), false),
+                cursor: selected.first().copied().unwrap_or(0),
+                focused: false,
+            }),
+            selected,
+            multi_select: false,
+            on_change: None,
+            dirty: false,
+            classes: Default::default(),
+            style_overrides: Default::default(),
+        }
+    }
+
+    /// Let any number of segments be active at once, starting with `selected`.
+    pub fn multi_select(mut self, selected: Vec<usize>) -> Self {
+        self.multi_select = true;
+        self.state.as_mut().unwrap().selection = Selection::new(selected.clone(), true);
+        self.selected = selected;
+        self
+    }
+
+    /// Called with the index of a segment when it is selected, or, in a [`#multi_select`][ToggleGroup#method.multi_select] group, whenever it is toggled on or off.
+    pub fn on_change(mut self, change_fn: Box<dyn Fn(usize) -> Message + Send + Sync>) -> Self {
+        self.on_change = Some(change_fn);
+        self
+    }
+
+    /// Toggle segment `i`, returning the message to emit if that changed anything.
+    fn toggle(&mut self, i: usize) -> Option<Message> {
+        if i >= self.labels.len() || !self.state_mut().selection.toggle(i) {
+            return None;
+        }
+        self.state_mut().cursor = i;
+        self.on_change.as_ref().map(|f| f(i))
+    }
+
+    /// Move the keyboard cursor by `delta` segments, returning the message to emit if that changed the selection.
+    fn move_cursor(&mut self, delta: isize) -> Option<Message> {
+        let last = self.labels.len().checked_sub(1)?;
+        let cursor = self.state_ref().cursor as isize + delta;
+        let cursor = cursor.clamp(0, last as isize) as usize;
+        self.state_mut().cursor = cursor;
+        if self.multi_select {
+            None
+        } else {
+            self.toggle(cursor)
+        }
+    }
+}
+
+#[state_component_impl(ToggleGroupState)]
+impl Component for ToggleGroup {
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        Some(AccessibilityInfo::new(Role::Group))
+    }
+
+    fn new_props(&mut self) {
+        let selection = Selection::new(self.selected.clone(), self.multi_select);
+        self.state_mut().selection = selection;
+    }
+
+    fn props_hash(&self, hasher: &mut ComponentHasher) {
+        self.selected.hash(hasher);
+        self.multi_select.hash(hasher);
+    }
+
+    fn view(&self) -> Option<Node> {
+        let radius: f32 = self.style_val("radius").unwrap().f32();
+        let border_width: f32 = self.style_val("border_width").unwrap().f32();
+        let len = self.labels.len();
+        let state = self.state_ref();
+
+        let mut base = node!(
+            super::Div::new(),
+            lay!(direction: Direction::Row, cross_alignment: Alignment::Stretch)
+        );
+        for (position, label) in self.labels.iter().enumerate() {
+            let (first, last) = (position == 0, position + 1 == len);
+            base = base.push(
+                node!(
+                    ToggleSegment {
+                        label: label.clone(),
+                        position,
+                        active: state.selection.is_active(position),
+                        multi_select: self.multi_select,
+                        cursor: state.focused && self.multi_select && state.cursor == position,
+                        // Only the outer corners are rounded
+                        radius: (
+                            if first { radius } else { 0.0 },
+                            if last { radius } else { 0.0 },
+                            if last { radius } else { 0.0 },
+                            if first { radius } else { 0.0 },
+                        ),
+                        state: Some(Default::default()),
+                        dirty: false,
+                        classes: self.classes.clone(),
+                        style_overrides: self.style_overrides.clone(),
+                    },
+                    lay!(
+                        // Overlap the border of the previous segment, so that they share it
+                        margin: Rect {
+                            left: Dimension::Px(if first { 0.0 } else { -border_width as f64 }),
+                            right: Dimension::Px(0.0),
+                            top: Dimension::Px(0.0),
+                            bottom: Dimension::Px(0.0),
+                        }
+                    )
+                )
+                .key(position as u64),
+            );
+        }
+        Some(base)
+    }
+
+    fn update(&mut self, message: Message) -> Vec<Message> {
+        match message.downcast_ref::<ToggleGroupMessage>() {
+            Some(ToggleGroupMessage::Clicked(i)) => self.toggle(*i).into_iter().collect(),
+            None => vec![message],
+        }
+    }
+
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.focus();
+        event.stop_bubbling();
+    }
+
+    fn on_focus(&mut self, _event: &mut event::Event<event::Focus>) {
+        self.state_mut().focused = true;
+    }
+
+    fn on_blur(&mut self, _event: &mut event::Event<event::Blur>) {
+        self.state_mut().focused = false;
+    }
+
+    fn on_key_down(&mut self, event: &mut event::Event<event::KeyDown>) {
+        let message = match event.input.0 {
+            Key::Left => self.move_cursor(-1),
+            Key::Right => self.move_cursor(1),
+            Key::Space | Key::Return if self.multi_select => {
+                let cursor = self.state_ref().cursor;
+                self.toggle(cursor)
+            }
+            _ => return,
+        };
+        event.stop_bubbling();
+        if let Some(message) = message {
+            event.emit(message);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ToggleSegmentState {
+    interaction: Interaction,
+}
+
+#[component(State = "ToggleSegmentState", Styled = "ToggleGroup", Internal)]
+#[derive(Debug)]
+struct ToggleSegment {
+    label: Vec<TextSegment>,
+    position: usize,
+    active: bool,
+    multi_select: bool,
+    /// Is the keyboard cursor of a `multi_select` group on this segment?
+    cursor: bool,
+    radius: (f32, f32, f32, f32),
+}
+
+#[state_component_impl(ToggleSegmentState)]
+impl Component for ToggleSegment {
+    fn accessibility(&self) -> Option<AccessibilityInfo> {
+        let role = if self.multi_select {
+            Role::CheckBox
+        } else {
+            Role::RadioButton
+        };
+        Some(
+            AccessibilityInfo::new(role)
+                .name(AccessibilityInfo::text(&self.label))
+                .checked(self.active),
+        )
+    }
+
+    fn view(&self) -> Option<Node> {
+        let interaction = Interaction {
+            hover: self.state_ref().interaction.hover || self.cursor,
+            disabled: crate::subtree_disabled(),
+            ..self.state_ref().interaction
+        };
+        let padding: f64 = self.style_val("padding").unwrap().into();
+        let (background_color, text_color): (Color, Color) = if self.active {
+            (
+                self.interactive_style_val("active_color", interaction)
+                    .into(),
+                self.interactive_style_val("active_text_color", interaction)
+                    .into(),
+            )
+        } else {
+            (
+                self.interactive_style_val("background_color", interaction)
+                    .into(),
+                self.interactive_style_val("text_color", interaction).into(),
+            )
+        };
+
+        Some(
+            node!(
+                super::RoundedRect {
+                    background_color,
+                    border_color: self
+                        .interactive_style_val("border_color", interaction)
+                        .into(),
+                    border_width: self.style_val("border_width").unwrap().f32(),
+                    radius: self.radius,
+                },
+                lay!(
+                    size: size_pct!(100.0),
+                    padding: rect!(padding),
+                    cross_alignment: Alignment::Center,
+                    axis_alignment: Alignment::Center
+                )
+            )
+            .push(node!(super::Text::new(self.label.clone())
+                .style("size", self.style_val("font_size").unwrap())
+                .style("color", text_color)
+                .style("h_alignment", HorizontalPosition::Center)
+                .maybe_style("font", self.style_val("font")))),
+        )
+    }
+
+    fn on_mouse_motion(&mut self, event: &mut event::Event<event::MouseMotion>) {
+        event.stop_bubbling();
+    }
+
+    fn on_mouse_enter(&mut self, _event: &mut event::Event<event::MouseEnter>) {
+        self.state_mut().interaction.mouse_enter();
+    }
+
+    fn on_mouse_leave(&mut self, _event: &mut event::Event<event::MouseLeave>) {
+        self.state_mut().interaction.mouse_leave();
+    }
+
+    fn on_mouse_down(&mut self, _event: &mut event::Event<event::MouseDown>) {
+        self.state_mut().interaction.mouse_down();
+    }
+
+    fn on_mouse_up(&mut self, _event: &mut event::Event<event::MouseUp>) {
+        self.state_mut().interaction.mouse_up();
+    }
+
+    // Bubbles up to the group, which takes the focus
+    fn on_click(&mut self, event: &mut event::Event<event::Click>) {
+        event.emit(msg!(ToggleGroupMessage::Clicked(self.position)));
+    }
+
+    // Same as on_click
+    fn on_double_click(&mut self, event: &mut event::Event<event::DoubleClick>) {
+        event.emit(msg!(ToggleGroupMessage::Clicked(self.position)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::txt;
+
+    fn group() -> ToggleGroup {
+        ToggleGroup::new(vec![txt!("Solo"), txt!("Mute"), txt!("Arm")], Some(0))
+            .on_change(Box::new(|i| msg!(i)))
+    }
+
+    fn segment(node: &Node) -> &ToggleSegment {
+        node.component
+            .as_any()
+            .and_then(|c| c.downcast_ref::<ToggleSegment>())
+            .unwrap_or_else(|| panic!("Expected a ToggleSegment, got {:?}", node.component))
+    }
+
+    fn click(group: &mut ToggleGroup, i: usize) -> Vec<usize> {
+        group
+            .update(msg!(ToggleGroupMessage::Clicked(i)))
+            .into_iter()
+            .map(|m| *m.downcast::<usize>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_exclusive() {
+        let mut group = group();
+        // Rapid clicks, before the parent has viewed the group again
+        let mut changes = vec![];
+        for i in [1, 2, 2, 0, 1, 1, 2] {
+            changes.extend(click(&mut group, i));
+            assert_eq!(group.state_ref().selection.active, vec![i]);
+        }
+        // Clicking the active segment changes nothing
+        assert_eq!(changes, vec![1, 2, 0, 1, 2]);
+        assert!(click(&mut group, 5).is_empty());
+
+        // Keys move the selection, and stop at the ends
+        let cache = event::EventCache::new(1.0);
+        let mut left = event::Event::new(event::KeyDown(Key::Left), &cache);
+        group.on_key_down(&mut left);
+        assert_eq!(group.state_ref().selection.active, vec![1]);
+        assert_eq!(*left.messages[0].downcast_ref::<usize>().unwrap(), 1);
+        for _ in 0..3 {
+            group.on_key_down(&mut event::Event::new(event::KeyDown(Key::Right), &cache));
+        }
+        assert_eq!(group.state_ref().selection.active, vec![2]);
+    }
+
+    #[test]
+    fn test_shared_borders() {
+        let mut group = group();
+        let border_width: f32 = group.style_val("border_width").unwrap().f32();
+        let radius: f32 = group.style_val("radius").unwrap().f32();
+        let base = group.view().unwrap();
+        let segments = &base.children;
+        assert_eq!(segments.len(), 3);
+        // Each segment after the first is drawn over the right border of the one before it
+        assert_eq!(segments[0].layout.margin.left, Dimension::Px(0.0));
+        for segment in &segments[1..] {
+            assert_eq!(
+                segment.layout.margin.left,
+                Dimension::Px(-border_width as f64)
+            );
+        }
+        // And only the outer corners are rounded
+        let radii: Vec<(f32, f32, f32, f32)> = segments.iter().map(|s| segment(s).radius).collect();
+        assert_eq!(
+            radii,
+            vec![
+                (radius, 0.0, 0.0, radius),
+                (0.0, 0.0, 0.0, 0.0),
+                (0.0, radius, radius, 0.0)
+            ]
+        );
+
+        // Clicking a segment selects it, and the view shows it
+        assert_eq!(click(&mut group, 2), vec![2]);
+        let base = group.view().unwrap();
+        let active: Vec<bool> = base.children.iter().map(|s| segment(s).active).collect();
+        assert_eq!(active, vec![false, false, true]);
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let mut group = group().multi_select(vec![2, 0, 2]);
+        assert_eq!(group.state_ref().selection.active, vec![0, 2]);
+        assert_eq!(click(&mut group, 1), vec![1]);
+        assert_eq!(click(&mut group, 0), vec![0]);
+        assert_eq!(group.state_ref().selection.active, vec![1, 2]);
+
+        // Keys move without selecting, until Space
+        let cache = event::EventCache::new(1.0);
+        let mut left = event::Event::new(event::KeyDown(Key::Left), &cache);
+        group.on_key_down(&mut left);
+        assert!(left.messages.is_empty());
+        group.on_key_down(&mut event::Event::new(event::KeyDown(Key::Space), &cache));
+        assert_eq!(group.state_ref().selection.active, vec![0, 1, 2]);
+    }
+}
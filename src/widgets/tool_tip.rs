@@ -1,3 +1,4 @@
+use super::popup::{popup_bounds, popup_span};
 use crate::base_types::*;
 use crate::component::Component;
 use crate::style::{HorizontalPosition, Styled};
@@ -8,20 +9,29 @@ use lemna_macros::component;
 #[derive(Debug)]
 pub struct ToolTip {
     pub tool_tip: String,
+    /// How far above the ToolTip's position the thing it belongs to starts, in logical pixels. The ToolTip flips up over it.
+    anchor_height: f32,
 }
 
 impl ToolTip {
     const MAX_WIDTH: f32 = 300.0;
     pub(crate) const MOUSE_OFFSET: Point = Point { x: 14.0, y: 0.0 };
     pub(crate) const DELAY: u128 = 1000; // millis
+    pub(crate) const FOCUS_DELAY: u128 = 500; // millis
 
     pub fn new(tool_tip: String) -> Self {
         Self {
             tool_tip,
-            class: Default::default(),
+            anchor_height: 0.0,
+            classes: Default::default(),
             style_overrides: Default::default(),
         }
     }
+
+    pub(crate) fn anchor_height(mut self, height: f32) -> Self {
+        self.anchor_height = height;
+        self
+    }
 }
 
 impl Component for ToolTip {
@@ -59,16 +69,25 @@ impl Component for ToolTip {
         _parent_aabb: AABB,
         _children: Vec<(&mut AABB, Option<Scale>, Option<Point>)>,
         frame: AABB,
-        _scale_factor: f32,
+        scale_factor: f32,
     ) {
-        if aabb.bottom_right.y > frame.bottom_right.y {
-            // Flip up if there isn't enough room underneath
-            aabb.translate_mut(0.0, -aabb.height());
-        }
-
-        if aabb.bottom_right.x > frame.bottom_right.x {
-            // Flip left if there isn't enough room to the right
-            aabb.translate_mut(-aabb.width() - Self::MOUSE_OFFSET.x * 2.0, 0.0);
-        }
+        let bounds = popup_bounds(frame);
+        // Flip up if there isn't enough room underneath
+        let (top, _) = popup_span(
+            aabb.pos.y - self.anchor_height * scale_factor,
+            aabb.pos.y,
+            aabb.height(),
+            bounds.pos.y,
+            bounds.bottom_right.y,
+        );
+        // Flip left, to the other side of the mouse, if there isn't enough room to the right
+        let (left, _) = popup_span(
+            aabb.pos.x - Self::MOUSE_OFFSET.x * 2.0,
+            aabb.pos.x,
+            aabb.width(),
+            bounds.pos.x,
+            bounds.bottom_right.x,
+        );
+        aabb.translate_mut(left - aabb.pos.x, top - aabb.pos.y);
     }
 }
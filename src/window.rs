@@ -1,6 +1,204 @@
-use crate::base_types::{Data, PixelSize};
+use crate::base_types::{Data, DataKind, PixelSize};
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// The mouse cursor shown over a [`Window`]. See [`Window#method.set_cursor`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Cursor {
+    #[default]
+    Arrow,
+    Hidden,
+    Text,
+    PointingHand,
+    Hand,
+    HandGrabbing,
+    NoEntry,
+    Cross,
+    Move,
+    SizeNWSE,
+    SizeNS,
+    SizeNESW,
+    SizeWE,
+    /// An RGBA image. Backends that cannot show custom cursors show its [`fallback`][CursorImage#structfield.fallback] instead.
+    Custom(CursorImage),
+}
+
+impl Cursor {
+    /// The cursor to show on a backend that cannot display custom images: `self`, unless it is [`Cursor::Custom`].
+    pub fn named(&self) -> &Cursor {
+        match self {
+            Cursor::Custom(image) => &image.fallback,
+            c => c,
+        }
+    }
+}
+
+/// A custom cursor image, for [`Cursor::Custom`].
+///
+/// Images are identified by a hash of their contents, which is computed once on creation. Backends use it to cache the platform cursor, so setting the same image repeatedly is cheap.
+#[derive(Debug, Clone)]
+pub struct CursorImage {
+    pub width: u32,
+    pub height: u32,
+    /// Non-premultiplied RGBA8 pixels, row by row. `width * height * 4` bytes.
+    pub rgba: Arc<[u8]>,
+    /// The point of the image, from its top left, that is at the mouse position.
+    pub hotspot: (u32, u32),
+    /// The cursor used when the backend cannot display custom images. Defaults to [`Cursor::Arrow`].
+    pub fallback: Box<Cursor>,
+    id: u64,
+}
+
+impl CursorImage {
+    /// Panics if `rgba` is not `width * height * 4` bytes long, or if `hotspot` is outside of the image.
+    pub fn new(width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) -> Self {
+        assert_eq!(
+            rgba.len(),
+            (width * height * 4) as usize,
+            "Cursor image data should be width * height RGBA pixels"
+        );
+        assert!(
+            hotspot.0 < width && hotspot.1 < height,
+            "Cursor hotspot should be within the image"
+        );
+        let mut hasher = DefaultHasher::new();
+        (width, height, rgba, hotspot).hash(&mut hasher);
+        Self {
+            width,
+            height,
+            rgba: rgba.into(),
+            hotspot,
+            fallback: Box::new(Cursor::Arrow),
+            id: hasher.finish(),
+        }
+    }
+
+    /// Set the cursor shown by backends that cannot display custom images.
+    pub fn fallback(mut self, cursor: Cursor) -> Self {
+        self.fallback = Box::new(cursor);
+        self
+    }
+
+    /// A hash of the image's contents, suitable as a cache key.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl PartialEq for CursorImage {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.fallback == other.fallback
+    }
+}
+
+impl Eq for CursorImage {}
+
+/// A menu bar, made up of top level [`Menu`]s, to be shown with [`Window#method.set_menu`]. When the user chooses one of its items, the item's `id` is sent to the app in an [`event::MenuSelect`][crate::event::MenuSelect]. It goes to the focused Component first, then to the root Component.
+///
+/// ```
+/// use lemna::{Menu, MenuDescription, MenuItem};
+/// const OPEN: i32 = 1;
+/// const QUIT: i32 = 2;
+///
+/// let menu = MenuDescription::new().menu(
+///     Menu::new("&File")
+///         .item(MenuItem::new(OPEN, "&Open").accelerator("Ctrl+O"))
+///         .item(MenuItem::new(QUIT, "&Quit")),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MenuDescription {
+    pub menus: Vec<Menu>,
+}
+
+impl MenuDescription {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    /// The item with the given `id`, if there is one.
+    pub fn get_item(&self, id: i32) -> Option<&MenuItem> {
+        self.menus
+            .iter()
+            .flat_map(|m| m.items.iter())
+            .find(|i| i.id == id)
+    }
+}
+
+/// A top level menu of a [`MenuDescription`]. An `&` in the label marks the following letter as its mnemonic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Menu {
+    pub label: String,
+    pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            items: vec![],
+        }
+    }
+
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// An entry in a [`Menu`]. The `id` is chosen by the app, and is what it receives in an [`event::MenuSelect`][crate::event::MenuSelect].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuItem {
+    pub id: i32,
+    pub label: String,
+    /// A keyboard shortcut, shown next to the label, e.g. `"Ctrl+O"`.
+    pub accelerator: Option<String>,
+    pub enabled: bool,
+    /// `Some` for items that can be checked.
+    pub checked: Option<bool>,
+}
+
+impl MenuItem {
+    pub fn new(id: i32, label: impl Into<String>) -> Self {
+        Self {
+            id,
+            label: label.into(),
+            accelerator: None,
+            enabled: true,
+            checked: None,
+        }
+    }
+
+    pub fn accelerator(mut self, accelerator: impl Into<String>) -> Self {
+        self.accelerator = Some(accelerator.into());
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+}
+
+/// Whether the OS prefers light or dark colors. See [`Window#method.color_scheme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
 
 /// The trait that backends must implement. An instance is returned by [`current_window`][crate::current_window] so that an app may interact with the OS's windowing system.
 pub trait Window: HasRawWindowHandle + HasRawDisplayHandle + Send + Sync + Any {
@@ -16,39 +214,111 @@ pub trait Window: HasRawWindowHandle + HasRawDisplayHandle + Send + Sync + Any {
     /// For internal use only.
     fn redraw(&self) {}
 
-    /// Set the current cursor. Cursor names are backend-specific, but they should support the following:
-    /// - "Arrow"
-    /// - "None"
-    /// - "Hidden"
-    /// - "Ibeam"
-    /// - "Text"
-    /// - "PointingHand"
-    /// - "Hand"
-    /// - "HandGrabbing"
-    /// - "NoEntry"
-    /// - "Cross"
-    /// - "Size"
-    /// - "Move"
-    /// - "SizeNWSE"
-    /// - "SizeNS"
-    /// - "SizeNESW"
-    /// - "SizeWE"
-    fn set_cursor(&self, _cursor_type: &str) {}
+    /// Set the current cursor. Backends that cannot display a [`Cursor::Custom`] image show its fallback.
+    fn set_cursor(&self, _cursor: Cursor) {}
+
+    /// Set the current cursor to an RGBA image, `width` by `height` pixels, with the mouse position at `hotspot`. Shorthand for [`#set_cursor`][Window#method.set_cursor] with a [`Cursor::Custom`]. Backends that cannot display custom cursors show an arrow.
+    fn set_cursor_image(&self, width: u32, height: u32, rgba: &[u8], hotspot: (u32, u32)) {
+        self.set_cursor(Cursor::Custom(CursorImage::new(
+            width, height, rgba, hotspot,
+        )));
+    }
 
     /// Reset the cursor to the default pointer.
     fn unset_cursor(&self) {}
 
-    /// Put the [`Data`] on the clipboard.
+    /// Hand an update of the [`accessibility`][crate::accessibility] tree to the OS. Backends that support assistive technologies should send the [`Input::Accessibility`][crate::input::Input::Accessibility] action requests that they receive back to the UI.
+    #[cfg(feature = "accesskit")]
+    fn accessibility_update(&self, _update: accesskit::TreeUpdate) {}
+
+    /// Put the [`Data`] on the clipboard. Kinds of data that the backend cannot put on the clipboard are skipped, with a warning logged.
     fn put_on_clipboard(&self, _data: &Data) {}
 
-    /// Get the current [`Data`] that is on the clipboard, if any.
+    /// Get the current [`Data`] that is on the clipboard, if any. When the clipboard holds more than one kind of data, text is preferred.
     fn get_from_clipboard(&self) -> Option<Data> {
         None
     }
 
+    /// The kinds of [`Data`] currently on the clipboard that the backend can read.
+    fn clipboard_kinds(&self) -> Vec<DataKind> {
+        self.get_from_clipboard()
+            .map(|d| vec![d.kind()])
+            .unwrap_or_default()
+    }
+
+    /// Get the first of `kinds` that is on the clipboard, if any. List the kinds in order of preference, e.g. `&[DataKind::Image, DataKind::String]` to get an image if there is one, and text otherwise.
+    fn get_from_clipboard_as(&self, kinds: &[DataKind]) -> Option<Data> {
+        self.get_from_clipboard()
+            .filter(|d| kinds.contains(&d.kind()))
+    }
+
     /// Start a Drag and Drop with the given [`Data`].
     fn start_drag(&self, _data: Data) {}
 
     /// When responding to a Drag and Drop action, tell the window of origin whether the mouse is currently over a valid drop target.
     fn set_drop_target_valid(&self, _valid: bool) {}
+
+    /// Replace the window's menu bar with `menu`. Can be called at any time, e.g. to enable or check items. Only the wx-rs backend supports native menus.
+    fn set_menu(&self, _menu: &MenuDescription) {}
+
+    /// The OS's current light or dark preference, if the backend knows it. Backends that do send an [`Input::ThemeChanged`][crate::input::Input::ThemeChanged] when it changes, which the root Component receives as an [`event::ThemeChange`][crate::event::ThemeChange].
+    fn color_scheme(&self) -> Option<ColorScheme> {
+        None
+    }
+
+    /// The user's [`UiPreferences`][crate::UiPreferences] from the OS, e.g. whether they have asked for reduced motion, if the backend knows them. These are used until the app calls [`UI#set_preferences`][crate::UI#method.set_preferences].
+    fn preferences(&self) -> Option<crate::UiPreferences> {
+        None
+    }
+
+    /// Set the title of the window. Not all backends support this.
+    fn set_title(&self, _title: &str) {}
+
+    /// Ask for the window to be resized to the given logical size. The resize may happen after some delay, it may be clamped to the window's minimum and maximum sizes, and it may be refused entirely (e.g. by a plugin host). An [`Input::Resize`][crate::input::Input::Resize] is sent once the window has actually been resized.
+    fn request_resize(&self, _size: PixelSize) {}
+
+    /// Set the minimum logical size of the window, or remove it with `None`.
+    fn set_min_size(&self, _size: Option<PixelSize>) {}
+
+    /// Set the maximum logical size of the window, or remove it with `None`.
+    fn set_max_size(&self, _size: Option<PixelSize>) {}
+
+    /// Make the window fullscreen, or return it to its previous size. An [`Input::Resize`][crate::input::Input::Resize] is sent once the window has changed size. Not supported by the baseview backend, or in plugins.
+    fn set_fullscreen(&self, _fullscreen: bool) {}
+
+    /// Is the window currently fullscreen?
+    fn is_fullscreen(&self) -> bool {
+        false
+    }
+
+    /// Maximize the window, or restore it. Not supported by the baseview backend, or in plugins.
+    fn set_maximized(&self, _maximized: bool) {}
+
+    /// Minimize the window, or restore it. Not supported by the baseview backend, or in plugins.
+    fn set_minimized(&self, _minimized: bool) {}
+
+    /// Keep the window above all others. Not supported by the baseview backend, or in plugins.
+    fn set_always_on_top(&self, _always_on_top: bool) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_image() {
+        let rgba = [255u8; 4 * 4 * 4];
+        let a = CursorImage::new(4, 4, &rgba, (1, 1));
+        let b = CursorImage::new(4, 4, &rgba, (1, 1));
+        assert_eq!(a.id(), b.id());
+        assert_eq!(Cursor::Custom(a.clone()), Cursor::Custom(b));
+        assert_ne!(a.id(), CursorImage::new(4, 4, &rgba, (2, 2)).id());
+
+        assert_eq!(Cursor::Custom(a.clone()).named(), &Cursor::Arrow);
+        assert_eq!(
+            Cursor::Custom(a.fallback(Cursor::Cross)).named(),
+            &Cursor::Cross
+        );
+        assert_eq!(Cursor::Text.named(), &Cursor::Text);
+    }
 }